@@ -44,6 +44,9 @@ impl fmt::Display for Error {
 enum Value<T> {
     Field(T),
     Boolean(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
     Array(Vec<Value<T>>),
     Struct(Map<String, Value<T>>),
 }
@@ -52,8 +55,12 @@ enum Value<T> {
 enum CheckedValue<T> {
     Field(T),
     Boolean(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
     Array(Vec<CheckedValue<T>>),
     Struct(Vec<(String, CheckedValue<T>)>),
+    Tuple(Vec<CheckedValue<T>>),
 }
 
 #[derive(PartialEq, Debug)]
@@ -64,6 +71,9 @@ impl<T: Field> fmt::Display for Value<T> {
         match self {
             Value::Field(v) => write!(f, "{}", v),
             Value::Boolean(v) => write!(f, "{}", v),
+            Value::U8(v) => write!(f, "{:#04x}", v),
+            Value::U16(v) => write!(f, "{:#06x}", v),
+            Value::U32(v) => write!(f, "{:#010x}", v),
             Value::Array(v) => write!(
                 f,
                 "[{}]",
@@ -89,6 +99,9 @@ impl<T: Field> Value<T> {
         match (self, ty) {
             (Value::Field(f), Type::FieldElement) => Ok(CheckedValue::Field(f)),
             (Value::Boolean(b), Type::Boolean) => Ok(CheckedValue::Boolean(b)),
+            (Value::U8(v), Type::Uint(8)) => Ok(CheckedValue::U8(v)),
+            (Value::U16(v), Type::Uint(16)) => Ok(CheckedValue::U16(v)),
+            (Value::U32(v), Type::Uint(32)) => Ok(CheckedValue::U32(v)),
             (Value::Array(a), Type::Array(box inner_ty, size)) => {
                 if a.len() != size {
                     Err(format!(
@@ -125,6 +138,23 @@ impl<T: Field> Value<T> {
                     Ok(CheckedValue::Struct(s))
                 }
             }
+            // tuples are represented as arrays whose elements have different types
+            (Value::Array(a), Type::Tuple(elements)) => {
+                if a.len() != elements.len() {
+                    Err(format!(
+                        "Expected tuple of {} elements, found array of size {}",
+                        elements.len(),
+                        a.len()
+                    ))
+                } else {
+                    let t = a
+                        .into_iter()
+                        .zip(elements.into_iter())
+                        .map(|(val, ty)| val.check(ty))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(CheckedValue::Tuple(t))
+                }
+            }
             (v, t) => Err(format!("Value `{}` doesn't match expected type `{}`", v, t)),
         }
     }
@@ -145,13 +175,17 @@ impl<T: From<usize>> Encode<T> for CheckedValue<T> {
         match self {
             CheckedValue::Field(t) => vec![t],
             CheckedValue::Boolean(b) => vec![if b { 1.into() } else { 0.into() }],
+            CheckedValue::U8(v) => vec![(v as usize).into()],
+            CheckedValue::U16(v) => vec![(v as usize).into()],
+            CheckedValue::U32(v) => vec![(v as usize).into()],
             CheckedValue::Array(a) => a.into_iter().flat_map(|v| v.encode()).collect(),
             CheckedValue::Struct(s) => s.into_iter().flat_map(|(_, v)| v.encode()).collect(),
+            CheckedValue::Tuple(t) => t.into_iter().flat_map(|v| v.encode()).collect(),
         }
     }
 }
 
-impl<T: Clone + From<usize> + PartialEq> Decode<T> for CheckedValues<T> {
+impl<T: Field> Decode<T> for CheckedValues<T> {
     type Expected = Vec<Type>;
 
     fn decode(raw: Vec<T>, expected: Self::Expected) -> Self {
@@ -169,7 +203,7 @@ impl<T: Clone + From<usize> + PartialEq> Decode<T> for CheckedValues<T> {
     }
 }
 
-impl<T: From<usize> + PartialEq + Clone> Decode<T> for CheckedValue<T> {
+impl<T: Field> Decode<T> for CheckedValue<T> {
     type Expected = Type;
 
     fn decode(raw: Vec<T>, expected: Self::Expected) -> Self {
@@ -187,6 +221,15 @@ impl<T: From<usize> + PartialEq + Clone> Decode<T> for CheckedValue<T> {
                     unreachable!()
                 })
             }
+            Type::Uint(bitwidth) => {
+                let v = raw.pop().unwrap().to_dec_string().parse::<u32>().unwrap();
+                match bitwidth {
+                    8 => CheckedValue::U8(v as u8),
+                    16 => CheckedValue::U16(v as u16),
+                    32 => CheckedValue::U32(v),
+                    _ => unreachable!(),
+                }
+            }
             Type::Array(box inner_ty, _) => CheckedValue::Array(
                 raw.chunks(inner_ty.get_primitive_count())
                     .map(|c| CheckedValue::decode(c.to_vec(), inner_ty.clone()))
@@ -203,6 +246,17 @@ impl<T: From<usize> + PartialEq + Clone> Decode<T> for CheckedValue<T> {
                     })
                     .collect(),
             ),
+            Type::Tuple(elements) => CheckedValue::Tuple(
+                elements
+                    .into_iter()
+                    .scan(0, |state, ty| {
+                        let new_state = *state + ty.get_primitive_count();
+                        let res = CheckedValue::decode(raw[*state..new_state].to_vec(), ty);
+                        *state = new_state;
+                        Some(res)
+                    })
+                    .collect(),
+            ),
         }
     }
 }
@@ -235,9 +289,22 @@ impl<T: Field> TryFrom<serde_json::Value> for Value<T> {
     type Error = String;
     fn try_from(v: serde_json::Value) -> Result<Value<T>, Self::Error> {
         match v {
-            serde_json::Value::String(s) => T::try_from_dec_str(&s)
-                .map(|v| Value::Field(v))
-                .map_err(|_| format!("Could not parse `{}` as field element", s)),
+            serde_json::Value::String(s) => {
+                if s.starts_with("0x") {
+                    let hex = &s[2..];
+                    match hex.len() {
+                        2 => u8::from_str_radix(hex, 16).map(|v| Value::U8(v)).ok(),
+                        4 => u16::from_str_radix(hex, 16).map(|v| Value::U16(v)).ok(),
+                        8 => u32::from_str_radix(hex, 16).map(|v| Value::U32(v)).ok(),
+                        _ => None,
+                    }
+                    .ok_or_else(|| format!("Could not parse `{}` as unsigned integer", s))
+                } else {
+                    T::try_from_dec_str(&s)
+                        .map(|v| Value::Field(v))
+                        .map_err(|_| format!("Could not parse `{}` as field element", s))
+                }
+            }
             serde_json::Value::Bool(b) => Ok(Value::Boolean(b)),
             serde_json::Value::Number(n) => Err(format!(
                 "Value `{}` isn't allowed, did you mean `\"{}\"`?",
@@ -263,12 +330,18 @@ impl<T: Field> Into<serde_json::Value> for CheckedValue<T> {
         match self {
             CheckedValue::Field(f) => serde_json::Value::String(f.to_dec_string()),
             CheckedValue::Boolean(b) => serde_json::Value::Bool(b),
+            CheckedValue::U8(v) => serde_json::Value::String(format!("{:#04x}", v)),
+            CheckedValue::U16(v) => serde_json::Value::String(format!("{:#06x}", v)),
+            CheckedValue::U32(v) => serde_json::Value::String(format!("{:#010x}", v)),
             CheckedValue::Array(a) => {
                 serde_json::Value::Array(a.into_iter().map(|e| e.into()).collect())
             }
             CheckedValue::Struct(s) => {
                 serde_json::Value::Object(s.into_iter().map(|(k, v)| (k, v.into())).collect())
             }
+            CheckedValue::Tuple(t) => {
+                serde_json::Value::Array(t.into_iter().map(|e| e.into()).collect())
+            }
         }
     }
 }
@@ -338,6 +411,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn uints() {
+        let s = r#"["0x01", "0x0002", "0x00000003"]"#;
+        assert_eq!(
+            parse::<FieldPrime>(s).unwrap(),
+            Values(vec![Value::U8(1), Value::U16(2), Value::U32(3)])
+        );
+
+        let s = r#"["0x001"]"#;
+        assert_eq!(
+            parse::<FieldPrime>(s).unwrap_err(),
+            Error::Conversion(String::from("Could not parse `0x001` as unsigned integer"))
+        );
+    }
+
     #[test]
     fn array() {
         let s = "[[true, false]]";
@@ -391,6 +479,26 @@ mod tests {
             );
         }
 
+        #[test]
+        fn uints() {
+            let s = r#"["0x01", "0x0002", "0x00000003"]"#;
+            assert_eq!(
+                parse_strict::<FieldPrime>(s, vec![Type::Uint(8), Type::Uint(16), Type::Uint(32)])
+                    .unwrap(),
+                CheckedValues(vec![
+                    CheckedValue::U8(1),
+                    CheckedValue::U16(2),
+                    CheckedValue::U32(3)
+                ])
+            );
+
+            let s = r#"["0x01"]"#;
+            assert_eq!(
+                parse_strict::<FieldPrime>(s, vec![Type::Uint(32)]).unwrap_err(),
+                Error::Type("Value `0x01` doesn't match expected type `u32`".into())
+            );
+        }
+
         #[test]
         fn array() {
             let s = "[[true, false]]";
@@ -449,6 +557,32 @@ mod tests {
                 Error::Type("Value `false` doesn't match expected type `field`".into())
             );
         }
+
+        #[test]
+        fn tuple() {
+            let s = r#"[["42", true]]"#;
+            assert_eq!(
+                parse_strict::<FieldPrime>(
+                    s,
+                    vec![Type::Tuple(vec![Type::FieldElement, Type::Boolean])]
+                )
+                .unwrap(),
+                CheckedValues(vec![CheckedValue::Tuple(vec![
+                    CheckedValue::Field(42.into()),
+                    CheckedValue::Boolean(true)
+                ])])
+            );
+
+            let s = r#"[["42"]]"#;
+            assert_eq!(
+                parse_strict::<FieldPrime>(
+                    s,
+                    vec![Type::Tuple(vec![Type::FieldElement, Type::Boolean])]
+                )
+                .unwrap_err(),
+                Error::Type("Expected tuple of 2 elements, found array of size 1".into())
+            );
+        }
     }
 
     mod encode {
@@ -469,6 +603,16 @@ mod tests {
             assert_eq!(v.encode(), vec![1, 0]);
         }
 
+        #[test]
+        fn uints() {
+            let v: CheckedValues<usize> = CheckedValues(vec![
+                CheckedValue::U8(1),
+                CheckedValue::U16(2),
+                CheckedValue::U32(3),
+            ]);
+            assert_eq!(v.encode(), vec![1, 2, 3]);
+        }
+
         #[test]
         fn array() {
             let v: CheckedValues<usize> = CheckedValues(vec![CheckedValue::Array(vec![
@@ -487,5 +631,39 @@ mod tests {
             )]);
             assert_eq!(v.encode(), vec![42]);
         }
+
+        #[test]
+        fn tuple() {
+            let v: CheckedValues<usize> = CheckedValues(vec![CheckedValue::Tuple(vec![
+                CheckedValue::Field(42),
+                CheckedValue::Boolean(true),
+            ])]);
+            assert_eq!(v.encode(), vec![42, 1]);
+        }
+    }
+
+    mod decode {
+        use super::*;
+
+        #[test]
+        fn tuple() {
+            let v = CheckedValues::<FieldPrime>::decode(
+                vec![42.into(), 1.into(), 3.into()],
+                vec![
+                    Type::Tuple(vec![Type::FieldElement, Type::Boolean]),
+                    Type::Uint(8),
+                ],
+            );
+            assert_eq!(
+                v,
+                CheckedValues(vec![
+                    CheckedValue::Tuple(vec![
+                        CheckedValue::Field(42.into()),
+                        CheckedValue::Boolean(true)
+                    ]),
+                    CheckedValue::U8(3)
+                ])
+            );
+        }
     }
 }
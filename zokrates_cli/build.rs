@@ -3,7 +3,7 @@ use fs_extra::dir::CopyOptions;
 use std::env;
 
 fn main() {
-    // export js test folder to OUT_DIR
+    // export js and rust test folders to OUT_DIR
     export_stdlib();
 }
 
@@ -11,5 +11,5 @@ fn export_stdlib() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let mut options = CopyOptions::new();
     options.overwrite = true;
-    copy_items(&vec!["tests/contract"], out_dir, &options).unwrap();
+    copy_items(&vec!["tests/contract", "tests/verifier"], out_dir, &options).unwrap();
 }
@@ -4,8 +4,10 @@
 // @author Dennis Kuhnert <dennis.kuhnert@campus.tu-berlin.de>
 // @date 2017
 
-use bincode::{deserialize_from, serialize_into, Infinite};
-use clap::{App, AppSettings, Arg, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use rand::OsRng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value;
 use std::fs::File;
 use std::io::{stdin, BufReader, BufWriter, Read, Write};
@@ -13,13 +15,16 @@ use std::path::{Path, PathBuf};
 use std::string::String;
 use std::{env, io};
 use zokrates_abi::Encode;
-use zokrates_core::compile::compile;
-use zokrates_core::ir;
+use zokrates_core::cache::ModuleCache;
+use zokrates_core::compile::{compile_with_cache, profile};
+use zokrates_core::ir::{self, ProgEnum};
 use zokrates_core::proof_system::*;
-use zokrates_field::field::{Field, FieldPrime};
-use zokrates_fs_resolver::resolve as fs_resolve;
+use zokrates_core::resolve::{ChainedResolver, CompositeResolver};
+use zokrates_field::field::{Bls12_381Field, Field, FieldPrime};
+use zokrates_fs_resolver::FsResolver;
 #[cfg(feature = "github")]
-use zokrates_github_resolver::{is_github_import, resolve as github_resolve};
+use zokrates_github_resolver::{GithubResolver, GITHUB_IMPORT_PREFIX};
+use zokrates_stdlib::{StdlibResolver, STDLIB_PREFIX};
 
 fn main() {
     cli().unwrap_or_else(|e| {
@@ -28,17 +33,48 @@ fn main() {
     })
 }
 
-fn resolve<'a>(
-    location: Option<String>,
-    source: &'a str,
-) -> Result<(BufReader<File>, String, &'a str), io::Error> {
+/// The resolver used to find imported modules: absolute imports are looked up in the search paths, then in
+/// `$ZOKRATES_HOME`, then in the standard library embedded in the binary
+fn resolver(sub_matches: &ArgMatches) -> Result<CompositeResolver<'static, io::Error>, String> {
+    let fs_resolver = sub_matches
+        .values_of("search-path")
+        .into_iter()
+        .flatten()
+        .fold(FsResolver::new(), |resolver, path| {
+            resolver.with_search_path(path)
+        })
+        .with_zokrates_home();
+
+    let resolver =
+        CompositeResolver::new(ChainedResolver::new(fs_resolver).then(StdlibResolver::new()))
+            .with_prefix(STDLIB_PREFIX, StdlibResolver::new());
+
     #[cfg(feature = "github")]
-    {
-        if is_github_import(source) {
-            return github_resolve(location, source);
-        };
+    let resolver = resolver.with_prefix(GITHUB_IMPORT_PREFIX, github_resolver(sub_matches)?);
+
+    Ok(resolver)
+}
+
+/// The resolver used to find modules on github, locked in the lock file and cached in the github cache directory
+#[cfg(feature = "github")]
+fn github_resolver(sub_matches: &ArgMatches) -> Result<GithubResolver, String> {
+    let mut resolver = match sub_matches.value_of("lock-file") {
+        Some(path) => GithubResolver::new()
+            .with_lock_file(path)
+            .map_err(|why| format!("couldn't read {}: {}", path, why))?,
+        None => GithubResolver::new(),
+    };
+
+    let cache_dir = sub_matches
+        .value_of("github-cache-dir")
+        .map(PathBuf::from)
+        .or_else(GithubResolver::default_cache_dir);
+
+    if let Some(cache_dir) = cache_dir {
+        resolver = resolver.with_cache_dir(cache_dir);
     }
-    fs_resolve(location, source)
+
+    Ok(resolver.offline(sub_matches.is_present("offline")))
 }
 
 fn cli() -> Result<(), String> {
@@ -46,8 +82,15 @@ fn cli() -> Result<(), String> {
     const VERIFICATION_KEY_DEFAULT_PATH: &str = "verification.key";
     const PROVING_KEY_DEFAULT_PATH: &str = "proving.key";
     const VERIFICATION_CONTRACT_DEFAULT_PATH: &str = "verifier.sol";
+    const RUST_VERIFIER_DEFAULT_PATH: &str = "verifier.rs";
     const WITNESS_DEFAULT_PATH: &str = "witness";
     const JSON_PROOF_PATH: &str = "proof.json";
+    const PROFILE_DEFAULT_PATH: &str = "profile.json";
+    const LOCK_FILE_DEFAULT_PATH: &str = "zokrates.lock";
+    const MPC_PARAMETERS_DEFAULT_PATH: &str = "mpc.params";
+    const R1CS_DEFAULT_PATH: &str = "out.r1cs";
+    const WTNS_DEFAULT_PATH: &str = "witness.wtns";
+    const CURVE_DEFAULT: &str = "bn128";
     let default_scheme = env::var("ZOKRATES_PROVING_SCHEME").unwrap_or(String::from("g16"));
     let default_solidity_abi = "v1";
 
@@ -78,6 +121,97 @@ fn cli() -> Result<(), String> {
             .long("light")
             .help("Skip logs and human readable output")
             .required(false)
+        ).arg(Arg::with_name("cache-dir")
+            .long("cache-dir")
+            .help("Path of a directory in which checked modules are cached, so that modules which did not change are not checked again")
+            .value_name("DIR")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("search-path")
+            .long("search-path")
+            .help("Path of a directory in which absolute imports are looked up before $ZOKRATES_HOME. Can be repeated")
+            .value_name("DIR")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
+        ).arg(Arg::with_name("lock-file")
+            .long("lock-file")
+            .help("Path of the lock file recording the commit and content hash of github imports")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(LOCK_FILE_DEFAULT_PATH)
+        ).arg(Arg::with_name("github-cache-dir")
+            .long("github-cache-dir")
+            .help("Path of the directory in which modules downloaded from github are cached. Defaults to $HOME/.zokrates/cache/github")
+            .value_name("DIR")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("offline")
+            .long("offline")
+            .help("Resolve github imports from the lock file and the cache only, without accessing the network")
+            .required(false)
+        ).arg(Arg::with_name("curve")
+            .long("curve")
+            .help("Curve whose scalar field the program is compiled over")
+            .value_name("CURVE")
+            .takes_value(true)
+            .possible_values(&["bn128", "bls12_381"])
+            .default_value(CURVE_DEFAULT)
+            .required(false)
+        )
+     )
+    .subcommand(SubCommand::with_name("profile")
+        .about("Compiles a program and reports which functions, call sites and lines its constraints come from. Prints a table and writes the report as JSON")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the source code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the JSON report")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(PROFILE_DEFAULT_PATH)
+        ).arg(Arg::with_name("search-path")
+            .long("search-path")
+            .help("Path of a directory in which absolute imports are looked up before $ZOKRATES_HOME. Can be repeated")
+            .value_name("DIR")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
+        ).arg(Arg::with_name("lock-file")
+            .long("lock-file")
+            .help("Path of the lock file recording the commit and content hash of github imports")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(LOCK_FILE_DEFAULT_PATH)
+        ).arg(Arg::with_name("github-cache-dir")
+            .long("github-cache-dir")
+            .help("Path of the directory in which modules downloaded from github are cached. Defaults to $HOME/.zokrates/cache/github")
+            .value_name("DIR")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("offline")
+            .long("offline")
+            .help("Resolve github imports from the lock file and the cache only, without accessing the network")
+            .required(false)
+        ).arg(Arg::with_name("curve")
+            .long("curve")
+            .help("Curve whose scalar field the program is compiled over")
+            .value_name("CURVE")
+            .takes_value(true)
+            .possible_values(&["bn128", "bls12_381"])
+            .default_value(CURVE_DEFAULT)
+            .required(false)
         )
      )
     .subcommand(SubCommand::with_name("setup")
@@ -118,10 +252,18 @@ fn cli() -> Result<(), String> {
             .long("light")
             .help("Skip logs and human readable output")
             .required(false)
+        ).arg(Arg::with_name("curve")
+            .long("curve")
+            .help("Curve the program was compiled for, on which the setup is performed")
+            .value_name("CURVE")
+            .takes_value(true)
+            .possible_values(&["bn128", "bls12_381"])
+            .default_value(CURVE_DEFAULT)
+            .required(false)
         )
     )
     .subcommand(SubCommand::with_name("export-verifier")
-        .about("Exports a verifier as Solidity smart contract")
+        .about("Exports a verifier as Solidity smart contract or as Rust module")
         .arg(Arg::with_name("input")
             .short("i")
             .long("input")
@@ -133,11 +275,19 @@ fn cli() -> Result<(), String> {
         ).arg(Arg::with_name("output")
             .short("o")
             .long("output")
-            .help("Path of the output file")
+            .help("Path of the output file, verifier.sol or verifier.rs by default depending on the target")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
-            .default_value(VERIFICATION_CONTRACT_DEFAULT_PATH)
+        ).arg(Arg::with_name("target")
+            .short("t")
+            .long("target")
+            .help("Language of the verifier. solidity-optimized exports a G16 contract with the key as constants and proofs packed as uint256[8]")
+            .value_name("TARGET")
+            .takes_value(true)
+            .possible_values(&["solidity", "solidity-optimized", "rust"])
+            .default_value("solidity")
+            .required(false)
         ).arg(Arg::with_name("proving-scheme")
             .short("s")
             .long("proving-scheme")
@@ -194,6 +344,17 @@ fn cli() -> Result<(), String> {
             .long("light")
             .help("Skip logs and human readable output")
             .required(false)
+        ).arg(Arg::with_name("threads")
+            .long("threads")
+            .help("Number of threads to solve independent directives on [default: 1]")
+            .value_name("N")
+            .takes_value(true)
+            .required(false)
+        ).arg(Arg::with_name("stream")
+            .long("stream")
+            .help("Write the witness to the output file during the execution instead of keeping it in memory")
+            .conflicts_with("threads")
+            .required(false)
         )
     )
     .subcommand(SubCommand::with_name("generate-proof")
@@ -238,6 +399,282 @@ fn cli() -> Result<(), String> {
             .takes_value(true)
             .required(false)
             .default_value(&default_scheme)
+        ).arg(Arg::with_name("curve")
+            .long("curve")
+            .help("Curve the program was compiled for, on which the proof is generated")
+            .value_name("CURVE")
+            .takes_value(true)
+            .possible_values(&["bn128", "bls12_381"])
+            .default_value(CURVE_DEFAULT)
+            .required(false)
+        )
+    )
+    .subcommand(SubCommand::with_name("verify")
+        .about("Verifies a given proof with the given verification key")
+        .arg(Arg::with_name("verification-key-path")
+            .short("v")
+            .long("verification-key-path")
+            .help("Path of the verification key file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(VERIFICATION_KEY_DEFAULT_PATH)
+        ).arg(Arg::with_name("proofpath")
+            .short("j")
+            .long("proofpath")
+            .help("Path of the JSON proof file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(JSON_PROOF_PATH)
+        ).arg(Arg::with_name("proving-scheme")
+            .short("s")
+            .long("proving-scheme")
+            .help("Proving scheme to use to verify the proof. Available options are G16 (default), PGHR13 and GM17")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(&default_scheme)
+        ).arg(Arg::with_name("curve")
+            .long("curve")
+            .help("Curve of the verification key and the proof")
+            .value_name("CURVE")
+            .takes_value(true)
+            .possible_values(&["bn128", "bls12_381"])
+            .default_value(CURVE_DEFAULT)
+            .required(false)
+        )
+    )
+    .subcommand(SubCommand::with_name("export-r1cs")
+        .about("Exports the constraint system of a compiled program in the iden3 .r1cs format")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the output file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(R1CS_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("export-wtns")
+        .about("Exports a witness of a compiled program in the iden3 .wtns format")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("witness")
+            .short("w")
+            .long("witness")
+            .help("Path of the witness file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(WITNESS_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the output file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(WTNS_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("import-r1cs")
+        .about("Imports a constraint system in the iden3 .r1cs format or in the JSON format of snarkjs as a compiled program")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the constraint system")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(R1CS_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the output file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("import-wtns")
+        .about("Imports a witness in the iden3 .wtns format or in the JSON format of snarkjs for an imported program")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("witness")
+            .short("w")
+            .long("witness")
+            .help("Path of the witness to import")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(WTNS_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the output file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(WITNESS_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc-init")
+        .about("Initializes the G16 parameters of a multi-party setup from the result of a powers of tau ceremony")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of compiled code")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("powers-of-tau")
+            .short("t")
+            .long("powers-of-tau")
+            .help("Path of the powers of tau, in the uncompressed format of a ceremony challenge")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the initial parameters")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc-contribute")
+        .about("Contributes fresh randomness to the parameters of a multi-party setup")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the parameters to contribute to")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the parameters including the contribution")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc-beacon")
+        .about("Applies a last contribution derived from a random beacon to the parameters of a multi-party setup")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the parameters to contribute to")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        ).arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .help("Path of the parameters including the contribution")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        ).arg(Arg::with_name("hash")
+            .long("hash")
+            .help("Value of the beacon in hex, for example a block hash announced before the ceremony")
+            .value_name("HEX")
+            .takes_value(true)
+            .required(true)
+        ).arg(Arg::with_name("iterations")
+            .short("n")
+            .long("iterations")
+            .help("Base 2 logarithm of the number of times the beacon is hashed, between 10 and 63")
+            .value_name("EXPONENT")
+            .takes_value(true)
+            .required(false)
+            .default_value("10")
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc-verify")
+        .about("Verifies the contributions to the parameters of a multi-party setup and prints their hashes")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the parameters to verify")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        ).arg(Arg::with_name("circuit")
+            .short("c")
+            .long("circuit")
+            .help("Path of the compiled code the parameters were initialized with")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(FLATTENED_CODE_DEFAULT_PATH)
+        ).arg(Arg::with_name("powers-of-tau")
+            .short("t")
+            .long("powers-of-tau")
+            .help("Path of the powers of tau the parameters were initialized with")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(true)
+        )
+    )
+    .subcommand(SubCommand::with_name("mpc-export")
+        .about("Exports the proving and verification keys of the parameters of a multi-party setup")
+        .arg(Arg::with_name("input")
+            .short("i")
+            .long("input")
+            .help("Path of the parameters")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(MPC_PARAMETERS_DEFAULT_PATH)
+        ).arg(Arg::with_name("proving-key-path")
+            .short("p")
+            .long("proving-key-path")
+            .help("Path of the generated proving key file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(PROVING_KEY_DEFAULT_PATH)
+        ).arg(Arg::with_name("verification-key-path")
+            .short("v")
+            .long("verification-key-path")
+            .help("Path of the generated verification key file")
+            .value_name("FILE")
+            .takes_value(true)
+            .required(false)
+            .default_value(VERIFICATION_KEY_DEFAULT_PATH)
         )
     )
      .subcommand(SubCommand::with_name("print-proof")
@@ -263,261 +700,313 @@ fn cli() -> Result<(), String> {
     .get_matches();
 
     match matches.subcommand() {
-        ("compile", Some(sub_matches)) => {
-            println!("Compiling {}\n", sub_matches.value_of("input").unwrap());
-
-            let path = PathBuf::from(sub_matches.value_of("input").unwrap());
-
-            let location = path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .into_os_string()
-                .into_string()
-                .unwrap();
-
-            let light = sub_matches.occurrences_of("light") > 0;
-
-            let bin_output_path = Path::new(sub_matches.value_of("output").unwrap());
+        ("compile", Some(sub_matches)) => match sub_matches.value_of("curve").unwrap() {
+            "bn128" => cli_compile::<FieldPrime>(sub_matches)?,
+            "bls12_381" => cli_compile::<Bls12_381Field>(sub_matches)?,
+            _ => unreachable!(),
+        },
+        ("profile", Some(sub_matches)) => match sub_matches.value_of("curve").unwrap() {
+            "bn128" => cli_profile::<FieldPrime>(sub_matches)?,
+            "bls12_381" => cli_profile::<Bls12_381Field>(sub_matches)?,
+            _ => unreachable!(),
+        },
+        ("compute-witness", Some(sub_matches)) => {
+            println!("Computing witness...");
 
-            let hr_output_path = bin_output_path.to_path_buf().with_extension("ztf");
+            // read compiled program
+            match read_program(Path::new(sub_matches.value_of("input").unwrap()))? {
+                ProgEnum::Bn128Program(p) => cli_compute_witness(p, sub_matches)?,
+                ProgEnum::Bls12_381Program(p) => cli_compute_witness(p, sub_matches)?,
+            }
+        }
+        ("setup", Some(sub_matches)) => {
+            let scheme = sub_matches.value_of("proving-scheme").unwrap();
 
-            let file = File::open(path.clone()).unwrap();
+            println!("Performing setup...");
 
-            let mut reader = BufReader::new(file);
+            match read_program_for(
+                Path::new(sub_matches.value_of("input").unwrap()),
+                sub_matches.value_of("curve").unwrap(),
+            )? {
+                ProgEnum::Bn128Program(p) => cli_setup(p, get_scheme(scheme)?, sub_matches)?,
+                ProgEnum::Bls12_381Program(p) => {
+                    cli_setup(p, get_bls12_381_scheme(scheme)?, sub_matches)?
+                }
+            }
+        }
+        ("export-verifier", Some(sub_matches)) => {
+            {
+                let scheme = get_scheme(sub_matches.value_of("proving-scheme").unwrap())?;
+                let is_abiv2 = sub_matches.value_of("abi").unwrap() == "v2";
+                println!("Exporting verifier...");
 
-            let program_flattened: ir::Prog<FieldPrime> =
-                compile(&mut reader, Some(location), Some(resolve))
-                    .map_err(|e| format!("Compilation failed:\n\n {}", e))?;
+                // read vk file
+                let input_path = Path::new(sub_matches.value_of("input").unwrap());
+                let input_file = File::open(&input_path)
+                    .map_err(|why| format!("couldn't open {}: {}", input_path.display(), why))?;
+                let vk = VerificationKey::read(BufReader::new(input_file))
+                    .map_err(|why| format!("couldn't read {}: {}", input_path.display(), why))?;
+
+                let (verifier, default_output_path) = match sub_matches.value_of("target").unwrap()
+                {
+                    "rust" => (scheme.export_rust_verifier(&vk), RUST_VERIFIER_DEFAULT_PATH),
+                    "solidity-optimized" => (
+                        scheme.export_optimized_solidity_verifier(&vk),
+                        VERIFICATION_CONTRACT_DEFAULT_PATH,
+                    ),
+                    _ => (
+                        scheme.export_solidity_verifier(&vk, is_abiv2),
+                        VERIFICATION_CONTRACT_DEFAULT_PATH,
+                    ),
+                };
+                let verifier = verifier.map_err(|e| format!("Export failed: {}", e))?;
 
-            // number of constraints the flattened program will translate to.
-            let num_constraints = program_flattened.constraint_count();
+                //write output file
+                let output_path = Path::new(
+                    sub_matches
+                        .value_of("output")
+                        .unwrap_or(default_output_path),
+                );
+                let output_file = File::create(&output_path)
+                    .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
 
-            // serialize flattened program and write to binary file
-            let bin_output_file = File::create(&bin_output_path)
-                .map_err(|why| format!("couldn't create {}: {}", bin_output_path.display(), why))?;
+                let mut writer = BufWriter::new(output_file);
 
-            let mut writer = BufWriter::new(bin_output_file);
+                writer
+                    .write_all(&verifier.as_bytes())
+                    .map_err(|_| "Failed writing output to file.".to_string())?;
+                println!("Finished exporting verifier.");
+            }
+        }
+        ("generate-proof", Some(sub_matches)) => {
+            println!("Generating proof...");
 
-            serialize_into(&mut writer, &program_flattened, Infinite)
-                .map_err(|_| "Unable to write data to file.".to_string())?;
+            let scheme = sub_matches.value_of("proving-scheme").unwrap();
 
-            if !light {
-                // write human-readable output file
-                let hr_output_file = File::create(&hr_output_path).map_err(|why| {
-                    format!("couldn't create {}: {}", hr_output_path.display(), why)
-                })?;
-
-                let mut hrofb = BufWriter::new(hr_output_file);
-                write!(&mut hrofb, "{}\n", program_flattened)
-                    .map_err(|_| "Unable to write data to file.".to_string())?;
-                hrofb
-                    .flush()
-                    .map_err(|_| "Unable to flush buffer.".to_string())?;
+            match read_program_for(
+                Path::new(sub_matches.value_of("input").unwrap()),
+                sub_matches.value_of("curve").unwrap(),
+            )? {
+                ProgEnum::Bn128Program(p) => {
+                    cli_generate_proof(p, get_scheme(scheme)?, sub_matches)?
+                }
+                ProgEnum::Bls12_381Program(p) => {
+                    cli_generate_proof(p, get_bls12_381_scheme(scheme)?, sub_matches)?
+                }
             }
+        }
+        ("verify", Some(sub_matches)) => {
+            let scheme = sub_matches.value_of("proving-scheme").unwrap();
+
+            println!("Verifying proof...");
+
+            let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
+            let vk_file = File::open(&vk_path)
+                .map_err(|why| format!("couldn't open {}: {}", vk_path.display(), why))?;
+            let vk = VerificationKey::read(BufReader::new(vk_file))
+                .map_err(|why| format!("couldn't read {}: {}", vk_path.display(), why))?;
+
+            let proof_path = Path::new(sub_matches.value_of("proofpath").unwrap());
+            let proof_file = File::open(&proof_path)
+                .map_err(|why| format!("couldn't open {}: {}", proof_path.display(), why))?;
+            let proof = Proof::read(BufReader::new(proof_file))
+                .map_err(|why| format!("couldn't read {}: {}", proof_path.display(), why))?;
+
+            let verified = match sub_matches.value_of("curve").unwrap() {
+                "bn128" => get_scheme(scheme)?.verify(&vk, &proof),
+                "bls12_381" => get_bls12_381_scheme(scheme)?.verify(&vk, &proof),
+                _ => unreachable!(),
+            }
+            .map_err(|e| format!("Verification failed: {}", e))?;
 
-            if !light {
-                // debugging output
-                println!("Compiled program:\n{}", program_flattened);
+            if !verified {
+                return Err(String::from("Proof is invalid"));
             }
 
-            println!("Compiled code written to '{}'", bin_output_path.display());
+            println!("Proof is valid");
+        }
+        ("export-r1cs", Some(sub_matches)) => {
+            println!("Exporting constraint system...");
 
-            if !light {
-                println!("Human readable code to '{}'", hr_output_path.display());
-            }
+            let program = read_bn128_program(Path::new(sub_matches.value_of("input").unwrap()))?;
 
-            println!("Number of constraints: {}", num_constraints);
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            let output_file = File::create(&output_path)
+                .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+
+            r1cs::write_r1cs(program, BufWriter::new(output_file))
+                .map_err(|e| format!("Export failed: {}", e))?;
+
+            println!("Constraint system written to {}", output_path.display());
         }
-        ("compute-witness", Some(sub_matches)) => {
-            println!("Computing witness...");
+        ("export-wtns", Some(sub_matches)) => {
+            println!("Exporting witness...");
 
-            // read compiled program
-            let path = Path::new(sub_matches.value_of("input").unwrap());
-            let file = File::open(&path)
-                .map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+            let program = read_bn128_program(Path::new(sub_matches.value_of("input").unwrap()))?;
 
-            let mut reader = BufReader::new(file);
+            let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
+            let witness_file = File::open(&witness_path)
+                .map_err(|why| format!("couldn't open {}: {}", witness_path.display(), why))?;
+            let witness = ir::Witness::read(witness_file)
+                .map_err(|why| format!("could not load witness: {:?}", why))?;
 
-            let ir_prog: ir::Prog<FieldPrime> =
-                deserialize_from(&mut reader, Infinite).map_err(|why| why.to_string())?;
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            let output_file = File::create(&output_path)
+                .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
 
-            // print deserialized flattened program
-            if !sub_matches.is_present("light") {
-                println!("{}", ir_prog);
-            }
+            r1cs::write_wtns(program, witness, BufWriter::new(output_file))
+                .map_err(|e| format!("Export failed: {}", e))?;
 
-            let signature = ir_prog.signature.clone();
+            println!("Witness written to {}", output_path.display());
+        }
+        ("import-r1cs", Some(sub_matches)) => {
+            println!("Importing constraint system...");
 
-            let is_stdin = sub_matches.is_present("stdin");
-            let is_abi = sub_matches.is_present("abi");
+            let input_path = Path::new(sub_matches.value_of("input").unwrap());
+            let input_file = File::open(&input_path)
+                .map_err(|why| format!("couldn't open {}: {}", input_path.display(), why))?;
 
-            if !is_stdin && is_abi {
-                return Err(
-                    "ABI input as inline argument is not supported. Please use `--stdin`.".into(),
-                );
-            }
+            let program = r1cs::read_r1cs(BufReader::new(input_file))
+                .map_err(|e| format!("Import failed: {}", e))?;
 
-            use zokrates_abi::Inputs;
+            let num_constraints = program.constraint_count();
 
-            // get arguments
-            let arguments = match is_stdin {
-                // take inline arguments
-                false => {
-                    let arguments = sub_matches.values_of("arguments");
-                    arguments
-                        .map(|a| {
-                            a.map(|x| FieldPrime::try_from_dec_str(x).map_err(|_| x.to_string()))
-                                .collect::<Result<Vec<_>, _>>()
-                        })
-                        .unwrap_or(Ok(vec![]))
-                        .map(|v| Inputs::Raw(v))
-                }
-                // take stdin arguments
-                true => {
-                    let mut stdin = stdin();
-                    let mut input = String::new();
-
-                    match is_abi {
-                        true => match stdin.read_to_string(&mut input) {
-                            Ok(_) => {
-                                use zokrates_abi::parse_strict;
-
-                                parse_strict(&input, signature.inputs)
-                                    .map(|parsed| Inputs::Abi(parsed))
-                                    .map_err(|why| why.to_string())
-                            }
-                            Err(_) => Err(String::from("???")),
-                        },
-                        false => match ir_prog.arguments_count() {
-                            0 => Ok(Inputs::Raw(vec![])),
-                            _ => match stdin.read_to_string(&mut input) {
-                                Ok(_) => {
-                                    input.retain(|x| x != '\n');
-                                    input
-                                        .split(" ")
-                                        .map(|x| {
-                                            FieldPrime::try_from_dec_str(x)
-                                                .map_err(|_| x.to_string())
-                                        })
-                                        .collect::<Result<Vec<_>, _>>()
-                                        .map(|v| Inputs::Raw(v))
-                                }
-                                Err(_) => Err(String::from("???")),
-                            },
-                        },
-                    }
-                }
-            }
-            .map_err(|e| format!("Could not parse argument: {}", e))?;
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            let output_file = File::create(&output_path)
+                .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+
+            program
+                .serialize(BufWriter::new(output_file))
+                .map_err(|_| "Unable to write data to file.".to_string())?;
 
-            let witness = ir_prog
-                .execute(&arguments.encode())
-                .map_err(|e| format!("Execution failed: {}", e))?;
+            println!("Compiled code written to '{}'", output_path.display());
+            println!("Number of constraints: {}", num_constraints);
+        }
+        ("import-wtns", Some(sub_matches)) => {
+            println!("Importing witness...");
 
-            use zokrates_abi::Decode;
+            let program = read_bn128_program(Path::new(sub_matches.value_of("input").unwrap()))?;
 
-            let results_json_value: serde_json::Value =
-                zokrates_abi::CheckedValues::decode(witness.return_values(), signature.outputs)
-                    .into();
+            let wtns_path = Path::new(sub_matches.value_of("witness").unwrap());
+            let wtns_file = File::open(&wtns_path)
+                .map_err(|why| format!("couldn't open {}: {}", wtns_path.display(), why))?;
 
-            println!("\nWitness: \n\n{}", results_json_value.to_string());
+            let witness = r1cs::read_wtns(&program, BufReader::new(wtns_file))
+                .map_err(|e| format!("Import failed: {}", e))?;
 
-            // write witness to file
             let output_path = Path::new(sub_matches.value_of("output").unwrap());
             let output_file = File::create(&output_path)
                 .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
 
-            let writer = BufWriter::new(output_file);
-
             witness
-                .write(writer)
+                .write(BufWriter::new(output_file))
                 .map_err(|why| format!("could not save witness: {:?}", why))?;
+
+            println!("Witness file written to {}", output_path.display());
         }
-        ("setup", Some(sub_matches)) => {
-            let scheme = get_scheme(sub_matches.value_of("proving-scheme").unwrap())?;
+        ("mpc-init", Some(sub_matches)) => {
+            println!("Initializing parameters...");
 
-            println!("Performing setup...");
+            let program = read_bn128_program(Path::new(sub_matches.value_of("input").unwrap()))?;
 
-            let path = Path::new(sub_matches.value_of("input").unwrap());
-            let file = File::open(&path)
-                .map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
-
-            let mut reader = BufReader::new(file);
+            let phase1_path = Path::new(sub_matches.value_of("powers-of-tau").unwrap());
+            let phase1_file = File::open(&phase1_path)
+                .map_err(|why| format!("couldn't open {}: {}", phase1_path.display(), why))?;
 
-            let program: ir::Prog<FieldPrime> =
-                deserialize_from(&mut reader, Infinite).map_err(|why| format!("{:?}", why))?;
+            let params = mpc::MPCParameters::new(program, BufReader::new(phase1_file))
+                .map_err(|e| format!("Initialization failed: {}", e))?;
 
-            // print deserialized flattened program
-            if !sub_matches.is_present("light") {
-                println!("{}", program);
-            }
-
-            // get paths for proving and verification keys
-            let pk_path = sub_matches.value_of("proving-key-path").unwrap();
-            let vk_path = sub_matches.value_of("verification-key-path").unwrap();
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            write_mpc_parameters(&params, output_path)?;
 
-            // run setup phase
-            scheme.setup(program, pk_path, vk_path);
+            println!("Parameters written to {}", output_path.display());
         }
-        ("export-verifier", Some(sub_matches)) => {
-            {
-                let scheme = get_scheme(sub_matches.value_of("proving-scheme").unwrap())?;
-                let is_abiv2 = sub_matches.value_of("abi").unwrap() == "v2";
-                println!("Exporting verifier...");
+        ("mpc-contribute", Some(sub_matches)) => {
+            let input_path = Path::new(sub_matches.value_of("input").unwrap());
+            let mut params = read_mpc_parameters(input_path)?;
 
-                // read vk file
-                let input_path = Path::new(sub_matches.value_of("input").unwrap());
-                let input_file = File::open(&input_path)
-                    .map_err(|why| format!("couldn't open {}: {}", input_path.display(), why))?;
-                let reader = BufReader::new(input_file);
+            println!("Contributing to parameters...");
 
-                let verifier = scheme.export_solidity_verifier(reader, is_abiv2);
-
-                //write output file
-                let output_path = Path::new(sub_matches.value_of("output").unwrap());
-                let output_file = File::create(&output_path)
-                    .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+            let mut rng = OsRng::new()
+                .map_err(|why| format!("couldn't access system randomness: {}", why))?;
+            let hash = params.contribute(&mut rng);
 
-                let mut writer = BufWriter::new(output_file);
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            write_mpc_parameters(&params, output_path)?;
 
-                writer
-                    .write_all(&verifier.as_bytes())
-                    .map_err(|_| "Failed writing output to file.".to_string())?;
-                println!("Finished exporting verifier.");
-            }
+            println!("Parameters written to {}", output_path.display());
+            println!("Contribution hash: {}", to_hex(&hash));
         }
-        ("generate-proof", Some(sub_matches)) => {
-            println!("Generating proof...");
+        ("mpc-beacon", Some(sub_matches)) => {
+            let beacon = from_hex(sub_matches.value_of("hash").unwrap())?;
+            let iterations = sub_matches.value_of("iterations").unwrap();
+            let iterations: u8 = iterations
+                .parse()
+                .map_err(|_| format!("Invalid number of iterations: {}", iterations))?;
 
-            let scheme = get_scheme(sub_matches.value_of("proving-scheme").unwrap())?;
+            let input_path = Path::new(sub_matches.value_of("input").unwrap());
+            let mut params = read_mpc_parameters(input_path)?;
 
-            // deserialize witness
-            let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
-            let witness_file = match File::open(&witness_path) {
-                Ok(file) => file,
-                Err(why) => panic!("couldn't open {}: {}", witness_path.display(), why),
-            };
+            println!("Applying beacon...");
 
-            let witness = ir::Witness::read(witness_file)
-                .map_err(|why| format!("could not load witness: {:?}", why))?;
+            let hash = params
+                .beacon(&beacon, iterations)
+                .map_err(|e| format!("Beacon failed: {}", e))?;
 
-            let pk_path = sub_matches.value_of("provingkey").unwrap();
-            let proof_path = sub_matches.value_of("proofpath").unwrap();
+            let output_path = Path::new(sub_matches.value_of("output").unwrap());
+            write_mpc_parameters(&params, output_path)?;
 
-            let program_path = Path::new(sub_matches.value_of("input").unwrap());
-            let program_file = File::open(&program_path)
-                .map_err(|why| format!("couldn't open {}: {}", program_path.display(), why))?;
+            println!("Parameters written to {}", output_path.display());
+            println!("Contribution hash: {}", to_hex(&hash));
+        }
+        ("mpc-verify", Some(sub_matches)) => {
+            let input_path = Path::new(sub_matches.value_of("input").unwrap());
+            let params = read_mpc_parameters(input_path)?;
+
+            let program = read_bn128_program(Path::new(sub_matches.value_of("circuit").unwrap()))?;
 
-            let mut reader = BufReader::new(program_file);
+            let phase1_path = Path::new(sub_matches.value_of("powers-of-tau").unwrap());
+            let phase1_file = File::open(&phase1_path)
+                .map_err(|why| format!("couldn't open {}: {}", phase1_path.display(), why))?;
 
-            let program: ir::Prog<FieldPrime> =
-                deserialize_from(&mut reader, Infinite).map_err(|why| format!("{:?}", why))?;
+            println!("Verifying contributions...");
 
-            println!(
-                "generate-proof successful: {:?}",
-                scheme.generate_proof(program, witness, pk_path, proof_path)
-            );
+            let hashes = params
+                .verify(program, BufReader::new(phase1_file))
+                .map_err(|e| format!("Verification failed: {}", e))?;
+
+            println!("Parameters are valid, with {} contributions:", hashes.len());
+            for (i, hash) in hashes.iter().enumerate() {
+                println!("{}: {}", i, to_hex(hash));
+            }
+        }
+        ("mpc-export", Some(sub_matches)) => {
+            let input_path = Path::new(sub_matches.value_of("input").unwrap());
+            let params = read_mpc_parameters(input_path)?;
+
+            println!("Exporting keys...");
+
+            let keypair = params
+                .keypair()
+                .map_err(|e| format!("Export failed: {}", e))?;
+
+            let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
+            let pk_file = File::create(&pk_path)
+                .map_err(|why| format!("couldn't create {}: {}", pk_path.display(), why))?;
+            keypair
+                .pk
+                .write(BufWriter::new(pk_file))
+                .map_err(|why| format!("couldn't write {}: {}", pk_path.display(), why))?;
+
+            let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
+            let vk_file = File::create(&vk_path)
+                .map_err(|why| format!("couldn't create {}: {}", vk_path.display(), why))?;
+            keypair
+                .vk
+                .write(BufWriter::new(vk_file))
+                .map_err(|why| format!("couldn't write {}: {}", vk_path.display(), why))?;
+
+            println!("Keys exported.");
         }
         ("print-proof", Some(sub_matches)) => {
             let format = sub_matches.value_of("format").unwrap();
@@ -572,11 +1061,383 @@ fn get_scheme(scheme_str: &str) -> Result<&'static dyn ProofSystem, String> {
     }
 }
 
+fn get_bls12_381_scheme(
+    scheme_str: &str,
+) -> Result<&'static dyn ProofSystem<Bls12_381Field>, String> {
+    match scheme_str.to_lowercase().as_ref() {
+        "g16" => Ok(&bls12_381::G16 {}),
+        s => Err(format!("Backend \"{}\" not supported on bls12_381", s)),
+    }
+}
+
+fn read_program(path: &Path) -> Result<ProgEnum, String> {
+    let file =
+        File::open(&path).map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+
+    ProgEnum::deserialize(BufReader::new(file))
+}
+
+/// Reads a program, checking that it was compiled for `curve`
+fn read_program_for(path: &Path, curve: &str) -> Result<ProgEnum, String> {
+    let program = read_program(path)?;
+
+    if program.curve() != curve {
+        return Err(format!(
+            "The program was compiled for {}, not for {}",
+            program.curve(),
+            curve
+        ));
+    }
+
+    Ok(program)
+}
+
+/// Reads a program for commands which are only available on bn128
+fn read_bn128_program(path: &Path) -> Result<ir::Prog<FieldPrime>, String> {
+    match read_program(path)? {
+        ProgEnum::Bn128Program(p) => Ok(p),
+        p => Err(format!(
+            "The program was compiled for {}, this command is only available on bn128",
+            p.curve()
+        )),
+    }
+}
+
+fn cli_compile<T: Field + Serialize + DeserializeOwned>(
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Compiling {}\n", sub_matches.value_of("input").unwrap());
+
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path.to_str().unwrap().to_string();
+
+    let light = sub_matches.occurrences_of("light") > 0;
+
+    let bin_output_path = Path::new(sub_matches.value_of("output").unwrap());
+
+    let hr_output_path = bin_output_path.to_path_buf().with_extension("ztf");
+
+    let file = File::open(path.clone()).unwrap();
+
+    let mut reader = BufReader::new(file);
+
+    let resolver = resolver(sub_matches)?;
+
+    let cache = sub_matches.value_of("cache-dir").map(ModuleCache::new);
+
+    let program_flattened: ir::Prog<T> =
+        compile_with_cache(&mut reader, Some(location), Some(&resolver), cache)
+            .map_err(|e| format!("Compilation failed:\n\n {}", e))?;
+
+    // number of constraints the flattened program will translate to.
+    let num_constraints = program_flattened.constraint_count();
+
+    // serialize flattened program and write to binary file
+    let bin_output_file = File::create(&bin_output_path)
+        .map_err(|why| format!("couldn't create {}: {}", bin_output_path.display(), why))?;
+
+    let mut writer = BufWriter::new(bin_output_file);
+
+    program_flattened
+        .serialize(&mut writer)
+        .map_err(|_| "Unable to write data to file.".to_string())?;
+
+    if !light {
+        // write human-readable output file
+        let hr_output_file = File::create(&hr_output_path)
+            .map_err(|why| format!("couldn't create {}: {}", hr_output_path.display(), why))?;
+
+        let mut hrofb = BufWriter::new(hr_output_file);
+        write!(&mut hrofb, "{}\n", program_flattened)
+            .map_err(|_| "Unable to write data to file.".to_string())?;
+        hrofb
+            .flush()
+            .map_err(|_| "Unable to flush buffer.".to_string())?;
+    }
+
+    if !light {
+        // debugging output
+        println!("Compiled program:\n{}", program_flattened);
+    }
+
+    println!("Compiled code written to '{}'", bin_output_path.display());
+
+    if !light {
+        println!("Human readable code to '{}'", hr_output_path.display());
+    }
+
+    println!("Number of constraints: {}", num_constraints);
+
+    Ok(())
+}
+
+fn cli_profile<T: Field + Serialize + DeserializeOwned>(
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Profiling {}\n", sub_matches.value_of("input").unwrap());
+
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let location = path.to_str().unwrap().to_string();
+
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+
+    let file = File::open(path.clone()).unwrap();
+
+    let mut reader = BufReader::new(file);
+
+    let resolver = resolver(sub_matches)?;
+
+    let profile = profile::<T, _, _>(&mut reader, Some(location), Some(&resolver))
+        .map_err(|e| format!("Compilation failed:\n\n {}", e))?;
+
+    println!("{}\n", profile);
+
+    // write the report to a JSON file
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))?;
+
+    let mut writer = BufWriter::new(output_file);
+
+    serde_json::to_writer_pretty(&mut writer, &profile)
+        .map_err(|_| "Unable to write data to file.".to_string())?;
+
+    println!("Profile written to '{}'", output_path.display());
+
+    Ok(())
+}
+
+fn cli_compute_witness<T: Field + Send + Sync>(
+    ir_prog: ir::Prog<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    // print deserialized flattened program
+    if !sub_matches.is_present("light") {
+        println!("{}", ir_prog);
+    }
+
+    let signature = ir_prog.signature.clone();
+
+    let is_stdin = sub_matches.is_present("stdin");
+    let is_abi = sub_matches.is_present("abi");
+
+    if !is_stdin && is_abi {
+        return Err("ABI input as inline argument is not supported. Please use `--stdin`.".into());
+    }
+
+    use zokrates_abi::Inputs;
+
+    // get arguments
+    let arguments = match is_stdin {
+        // take inline arguments
+        false => {
+            let arguments = sub_matches.values_of("arguments");
+            arguments
+                .map(|a| {
+                    a.map(|x| T::try_from_dec_str(x).map_err(|_| x.to_string()))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .unwrap_or(Ok(vec![]))
+                .map(|v| Inputs::Raw(v))
+        }
+        // take stdin arguments
+        true => {
+            let mut stdin = stdin();
+            let mut input = String::new();
+
+            match is_abi {
+                true => match stdin.read_to_string(&mut input) {
+                    Ok(_) => {
+                        use zokrates_abi::parse_strict;
+
+                        parse_strict(&input, signature.inputs)
+                            .map(|parsed| Inputs::Abi(parsed))
+                            .map_err(|why| why.to_string())
+                    }
+                    Err(_) => Err(String::from("???")),
+                },
+                false => match ir_prog.arguments_count() {
+                    0 => Ok(Inputs::Raw(vec![])),
+                    _ => match stdin.read_to_string(&mut input) {
+                        Ok(_) => {
+                            input.retain(|x| x != '\n');
+                            input
+                                .split(" ")
+                                .map(|x| T::try_from_dec_str(x).map_err(|_| x.to_string()))
+                                .collect::<Result<Vec<_>, _>>()
+                                .map(|v| Inputs::Raw(v))
+                        }
+                        Err(_) => Err(String::from("???")),
+                    },
+                },
+            }
+        }
+    }
+    .map_err(|e| format!("Could not parse argument: {}", e))?;
+
+    let inputs = arguments.encode();
+
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    let create_output = || {
+        File::create(&output_path)
+            .map(BufWriter::new)
+            .map_err(|why| format!("couldn't create {}: {}", output_path.display(), why))
+    };
+
+    let return_values = match sub_matches.is_present("stream") {
+        // write the witness to file as it gets computed
+        true => ir_prog
+            .execute_streaming(&inputs, create_output()?)
+            .map_err(|e| format!("Execution failed: {}", e))?,
+        false => {
+            let threads = match sub_matches.value_of("threads") {
+                Some(threads) => threads
+                    .parse()
+                    .ok()
+                    .filter(|threads| *threads > 0)
+                    .ok_or_else(|| format!("Invalid number of threads: {}", threads))?,
+                None => 1,
+            };
+
+            let witness = match threads {
+                1 => ir_prog.execute(&inputs),
+                threads => ir_prog.execute_parallel(&inputs, threads),
+            }
+            .map_err(|e| format!("Execution failed: {}", e))?;
+
+            // write witness to file
+            witness
+                .write(create_output()?)
+                .map_err(|why| format!("could not save witness: {:?}", why))?;
+
+            witness.return_values()
+        }
+    };
+
+    use zokrates_abi::Decode;
+
+    let results_json_value: serde_json::Value =
+        zokrates_abi::CheckedValues::decode(return_values, signature.outputs).into();
+
+    println!("\nWitness: \n\n{}", results_json_value.to_string());
+
+    Ok(())
+}
+
+fn cli_setup<T: Field>(
+    program: ir::Prog<T>,
+    scheme: &dyn ProofSystem<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    // print deserialized flattened program
+    if !sub_matches.is_present("light") {
+        println!("{}", program);
+    }
+
+    // run setup phase
+    let keypair = scheme
+        .setup(program)
+        .map_err(|e| format!("Setup failed: {}", e))?;
+
+    // write proving and verification keys
+    let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
+    let pk_file = File::create(&pk_path)
+        .map_err(|why| format!("couldn't create {}: {}", pk_path.display(), why))?;
+    keypair
+        .pk
+        .write(BufWriter::new(pk_file))
+        .map_err(|why| format!("couldn't write {}: {}", pk_path.display(), why))?;
+
+    let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
+    let vk_file = File::create(&vk_path)
+        .map_err(|why| format!("couldn't create {}: {}", vk_path.display(), why))?;
+    keypair
+        .vk
+        .write(BufWriter::new(vk_file))
+        .map_err(|why| format!("couldn't write {}: {}", vk_path.display(), why))?;
+
+    println!("Setup completed.");
+
+    Ok(())
+}
+
+fn cli_generate_proof<T: Field>(
+    program: ir::Prog<T>,
+    scheme: &dyn ProofSystem<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    // deserialize witness
+    let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
+    let witness_file = File::open(&witness_path)
+        .map_err(|why| format!("couldn't open {}: {}", witness_path.display(), why))?;
+
+    let witness = ir::Witness::read(witness_file)
+        .map_err(|why| format!("could not load witness: {:?}", why))?;
+
+    let pk_path = Path::new(sub_matches.value_of("provingkey").unwrap());
+    let pk_file = File::open(&pk_path)
+        .map_err(|why| format!("couldn't open {}: {}", pk_path.display(), why))?;
+
+    let proving_key = ProvingKey::read(BufReader::new(pk_file))
+        .map_err(|why| format!("couldn't read {}: {}", pk_path.display(), why))?;
+
+    let proof = scheme
+        .generate_proof(program, witness, &proving_key)
+        .map_err(|e| format!("Proof generation failed: {}", e))?;
+
+    let proof_path = Path::new(sub_matches.value_of("proofpath").unwrap());
+    let proof_file = File::create(&proof_path)
+        .map_err(|why| format!("couldn't create {}: {}", proof_path.display(), why))?;
+    proof
+        .write(BufWriter::new(proof_file))
+        .map_err(|why| format!("couldn't write {}: {}", proof_path.display(), why))?;
+
+    println!("Proof written to {}", proof_path.display());
+
+    Ok(())
+}
+
+fn read_mpc_parameters(path: &Path) -> Result<mpc::MPCParameters, String> {
+    let file =
+        File::open(path).map_err(|why| format!("couldn't open {}: {}", path.display(), why))?;
+    mpc::MPCParameters::read(BufReader::new(file))
+        .map_err(|why| format!("couldn't read {}: {}", path.display(), why))
+}
+
+fn write_mpc_parameters(params: &mpc::MPCParameters, path: &Path) -> Result<(), String> {
+    let file =
+        File::create(path).map_err(|why| format!("couldn't create {}: {}", path.display(), why))?;
+    params
+        .write(BufWriter::new(file))
+        .map_err(|why| format!("couldn't write {}: {}", path.display(), why))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits = hex.trim_start_matches("0x");
+
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return Err(format!("Invalid hex value: {}", hex));
+    }
+
+    (0..digits.len() / 2)
+        .map(|i| {
+            u8::from_str_radix(&digits[2 * i..2 * i + 2], 16)
+                .map_err(|_| format!("Invalid hex value: {}", hex))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     extern crate glob;
     use self::glob::glob;
     use super::*;
+    use zokrates_core::compile::compile;
 
     #[test]
     fn examples() {
@@ -595,16 +1456,14 @@ mod tests {
             let file = File::open(path.clone()).unwrap();
 
             let mut reader = BufReader::new(file);
-            let location = path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .into_os_string()
-                .into_string()
-                .unwrap();
-
-            let _: ir::Prog<FieldPrime> =
-                compile(&mut reader, Some(location), Some(resolve)).unwrap();
+            let location = path.to_str().unwrap().to_string();
+
+            let _: ir::Prog<FieldPrime> = compile(
+                &mut reader,
+                Some(location),
+                Some(&resolver(&ArgMatches::default()).unwrap()),
+            )
+            .unwrap();
         }
     }
 
@@ -620,18 +1479,16 @@ mod tests {
 
             let file = File::open(path.clone()).unwrap();
 
-            let location = path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .into_os_string()
-                .into_string()
-                .unwrap();
+            let location = path.to_str().unwrap().to_string();
 
             let mut reader = BufReader::new(file);
 
-            let program_flattened: ir::Prog<FieldPrime> =
-                compile(&mut reader, Some(location), Some(resolve)).unwrap();
+            let program_flattened: ir::Prog<FieldPrime> = compile(
+                &mut reader,
+                Some(location),
+                Some(&resolver(&ArgMatches::default()).unwrap()),
+            )
+            .unwrap();
 
             let _ = program_flattened
                 .execute(&vec![FieldPrime::from(0)])
@@ -652,18 +1509,16 @@ mod tests {
 
             let file = File::open(path.clone()).unwrap();
 
-            let location = path
-                .parent()
-                .unwrap()
-                .to_path_buf()
-                .into_os_string()
-                .into_string()
-                .unwrap();
+            let location = path.to_str().unwrap().to_string();
 
             let mut reader = BufReader::new(file);
 
-            let program_flattened: ir::Prog<FieldPrime> =
-                compile(&mut reader, Some(location), Some(resolve)).unwrap();
+            let program_flattened: ir::Prog<FieldPrime> = compile(
+                &mut reader,
+                Some(location),
+                Some(&resolver(&ArgMatches::default()).unwrap()),
+            )
+            .unwrap();
 
             let _ = program_flattened
                 .execute(&vec![FieldPrime::from(0)])
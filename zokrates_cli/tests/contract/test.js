@@ -93,12 +93,22 @@ let jsonInterface = JSON.parse(solc.compile(jsonContractSource));
                 return verifyTxPGHR13_ABIV1(proof, account, correct).on('receipt', handleReceipt)
                     .catch(handleError);
             }
+        } else if (abiVersion == "packed") {
+            return verifyTxPacked(proof, account, correct).on('receipt', handleReceipt)
+                .catch(handleError);
         } else {
             return verifyTxABIV2(proof, account, correct).on('receipt', handleReceipt)
                 .catch(handleError);
         }
     }
 
+    function verifyTxPacked(proof, account, correct) {
+        return contract.methods.verifyTx(proof[0], proof[1]).send({
+            from: account,
+            gas: 5000000
+        })
+    }
+
     function verifyTxABIV2(proof, account, correct) {
         contract.methods.verifyTx(proof[0], proof[1]).send({
             from: account,
@@ -147,6 +157,9 @@ let jsonInterface = JSON.parse(solc.compile(jsonContractSource));
             return [Object.values(proof), Object.values(inputs)];
         } else if (abiVersion == "v2") {
             return [proof, inputs]
+        } else if (abiVersion == "packed") {
+            //a, b and c flattened into a uint256[8]
+            return [[].concat(proof["a"], proof["b"][0], proof["b"][1], proof["c"]), inputs]
         }
     }
 
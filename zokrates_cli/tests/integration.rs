@@ -4,7 +4,6 @@ extern crate serde_json;
 #[cfg(test)]
 mod integration {
     use assert_cli;
-    use bincode::{deserialize_from, Infinite};
     use std::fs;
     use std::fs::File;
     use std::io::{BufReader, Read};
@@ -13,7 +12,6 @@ mod integration {
     use tempdir::TempDir;
     use zokrates_abi::{parse_strict, Encode};
     use zokrates_core::ir;
-    use zokrates_field::field::FieldPrime;
 
     #[test]
     #[ignore]
@@ -64,6 +62,12 @@ mod integration {
         let witness_path = tmp_base.join(program_name).join("witness");
         let inline_witness_path = tmp_base.join(program_name).join("inline_witness");
         let proof_path = tmp_base.join(program_name).join("proof.json");
+        let r1cs_path = tmp_base.join(program_name).join("out.r1cs");
+        let wtns_path = tmp_base.join(program_name).join("witness.wtns");
+        let imported_path = tmp_base.join(program_name).join("imported");
+        let imported_witness_path = tmp_base.join(program_name).join("imported_witness");
+        let profile_path = tmp_base.join(program_name).join("profile.json");
+        let bls12_381_profile_path = tmp_base.join(program_name).join("profile_bls12_381.json");
         let verification_key_path = tmp_base
             .join(program_name)
             .join("verification")
@@ -76,6 +80,14 @@ mod integration {
             .join(program_name)
             .join("verifier")
             .with_extension("sol");
+        let optimized_verification_contract_path = tmp_base
+            .join(program_name)
+            .join("verifier_optimized")
+            .with_extension("sol");
+        let rust_verifier_path = tmp_base
+            .join(program_name)
+            .join("verifier")
+            .with_extension("rs");
 
         // create a tmp folder to store artifacts
         fs::create_dir(test_case_path).unwrap();
@@ -101,13 +113,49 @@ mod integration {
             .map_err(|why| format!("couldn't open {}: {}", flattened_path.display(), why))
             .unwrap();
 
-        let mut reader = BufReader::new(file);
+        let ir_prog = match ir::ProgEnum::deserialize(BufReader::new(file)).unwrap() {
+            ir::ProgEnum::Bn128Program(p) => p,
+            p => panic!("Expected a program compiled for bn128, found {}", p.curve()),
+        };
+
+        let signature = ir_prog.signature.clone();
+
+        // PROFILE
 
-        let ir_prog: ir::Prog<FieldPrime> = deserialize_from(&mut reader, Infinite)
-            .map_err(|why| why.to_string())
+        let profile = vec![
+            "../target/release/zokrates",
+            "profile",
+            "-i",
+            program_path.to_str().unwrap(),
+            "-o",
+            profile_path.to_str().unwrap(),
+        ];
+
+        assert_cli::Assert::command(&profile).succeeds().unwrap();
+
+        let bls12_381_profile = vec![
+            "../target/release/zokrates",
+            "profile",
+            "-i",
+            program_path.to_str().unwrap(),
+            "-o",
+            bls12_381_profile_path.to_str().unwrap(),
+            "--curve",
+            "bls12_381",
+        ];
+
+        assert_cli::Assert::command(&bls12_381_profile)
+            .succeeds()
             .unwrap();
 
-        let signature = ir_prog.signature.clone();
+        // the profile accounts for every constraint of the compiled program
+        let profile: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&profile_path).unwrap()).unwrap();
+
+        assert_eq!(
+            profile["constraints"],
+            serde_json::Value::from(ir_prog.constraint_count())
+        );
 
         // run witness-computation for ABI-encoded inputs through stdin
         let json_input_str = fs::read_to_string(inputs_path).unwrap();
@@ -188,6 +236,74 @@ mod integration {
             );
         }
 
+        // EXPORT-R1CS
+        assert_cli::Assert::command(&[
+            "../target/release/zokrates",
+            "export-r1cs",
+            "-i",
+            flattened_path.to_str().unwrap(),
+            "-o",
+            r1cs_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        // EXPORT-WTNS, which checks that the witness satisfies the constraints
+        assert_cli::Assert::command(&[
+            "../target/release/zokrates",
+            "export-wtns",
+            "-i",
+            flattened_path.to_str().unwrap(),
+            "-w",
+            witness_path.to_str().unwrap(),
+            "-o",
+            wtns_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        // IMPORT-R1CS
+        assert_cli::Assert::command(&[
+            "../target/release/zokrates",
+            "import-r1cs",
+            "-i",
+            r1cs_path.to_str().unwrap(),
+            "-o",
+            imported_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        // IMPORT-WTNS, which checks that the witness satisfies the imported constraints
+        assert_cli::Assert::command(&[
+            "../target/release/zokrates",
+            "import-wtns",
+            "-i",
+            imported_path.to_str().unwrap(),
+            "-w",
+            wtns_path.to_str().unwrap(),
+            "-o",
+            imported_witness_path.to_str().unwrap(),
+        ])
+        .succeeds()
+        .unwrap();
+
+        // the imported program returns the same values
+        let mut imported_witness_file = File::open(&imported_witness_path).unwrap();
+        let mut imported_witness = String::new();
+        imported_witness_file
+            .read_to_string(&mut imported_witness)
+            .unwrap();
+
+        for line in witness.lines().filter(|line| line.starts_with("~out_")) {
+            assert!(
+                imported_witness.contains(line),
+                "Witness import failed for {}\n\nLine \"{}\" not found in witness",
+                program_path.to_str().unwrap(),
+                line
+            );
+        }
+
         #[cfg(feature = "libsnark")]
         let schemes = ["pghr13", "gm17", "g16"];
         #[cfg(not(feature = "libsnark"))]
@@ -242,6 +358,20 @@ mod integration {
             .succeeds()
             .unwrap();
 
+            // VERIFY
+            assert_cli::Assert::command(&[
+                "../target/release/zokrates",
+                "verify",
+                "-v",
+                verification_key_path.to_str().unwrap(),
+                "-j",
+                proof_path.to_str().unwrap(),
+                "--proving-scheme",
+                scheme,
+            ])
+            .succeeds()
+            .unwrap();
+
             // TEST VERIFIER
 
             assert_cli::Assert::command(&[
@@ -255,6 +385,73 @@ mod integration {
             .current_dir(concat!(env!("OUT_DIR"), "/contract"))
             .succeeds()
             .unwrap();
+
+            // TEST OPTIMIZED VERIFIER AGAINST THE SAME PROOFS
+
+            if *scheme == "g16" {
+                assert_cli::Assert::command(&[
+                    "../target/release/zokrates",
+                    "export-verifier",
+                    "-i",
+                    verification_key_path.to_str().unwrap(),
+                    "-o",
+                    optimized_verification_contract_path.to_str().unwrap(),
+                    "--proving-scheme",
+                    scheme,
+                    "--target",
+                    "solidity-optimized",
+                ])
+                .succeeds()
+                .unwrap();
+
+                assert_cli::Assert::command(&[
+                    "node",
+                    "test.js",
+                    optimized_verification_contract_path.to_str().unwrap(),
+                    proof_path.to_str().unwrap(),
+                    scheme,
+                    "packed",
+                ])
+                .current_dir(concat!(env!("OUT_DIR"), "/contract"))
+                .succeeds()
+                .unwrap();
+            }
+
+            // TEST RUST VERIFIER
+
+            if *scheme == "g16" {
+                assert_cli::Assert::command(&[
+                    "../target/release/zokrates",
+                    "export-verifier",
+                    "-i",
+                    verification_key_path.to_str().unwrap(),
+                    "-o",
+                    rust_verifier_path.to_str().unwrap(),
+                    "--proving-scheme",
+                    scheme,
+                    "--target",
+                    "rust",
+                ])
+                .succeeds()
+                .unwrap();
+
+                fs::copy(
+                    &rust_verifier_path,
+                    concat!(env!("OUT_DIR"), "/verifier/src/verifier.rs"),
+                )
+                .unwrap();
+
+                assert_cli::Assert::command(&[
+                    "cargo",
+                    "run",
+                    "--quiet",
+                    "--",
+                    proof_path.to_str().unwrap(),
+                ])
+                .current_dir(concat!(env!("OUT_DIR"), "/verifier"))
+                .succeeds()
+                .unwrap();
+            }
         }
     }
 }
@@ -0,0 +1,48 @@
+//! Checks a G16 proof with the verifier exported to `src/verifier.rs` by `zokrates export-verifier --target rust`
+
+mod verifier;
+
+use pairing_ce::bn256::Fr;
+use pairing_ce::ff::Field;
+use serde_json::Value;
+use std::{env, fs, process};
+
+fn point(value: &Value) -> [&str; 2] {
+    [value[0].as_str().unwrap(), value[1].as_str().unwrap()]
+}
+
+fn main() {
+    let path = env::args().nth(1).expect("Usage: verifier <proof.json>");
+    let proof: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+
+    let points = &proof["proof"];
+    let proof_points = verifier::Proof::from_hex(
+        point(&points["a"]),
+        [point(&points["b"][0]), point(&points["b"][1])],
+        point(&points["c"]),
+    )
+    .expect("Invalid proof");
+
+    let mut inputs: Vec<Fr> = proof["inputs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|input| verifier::input(input.as_str().unwrap()).expect("Invalid input"))
+        .collect();
+
+    if !verifier::verify(&proof_points, &inputs) {
+        eprintln!("Proof is invalid");
+        process::exit(1);
+    }
+
+    if let Some(input) = inputs.first_mut() {
+        input.add_assign(&Fr::one());
+
+        if verifier::verify(&proof_points, &inputs) {
+            eprintln!("Proof is valid for other inputs");
+            process::exit(1);
+        }
+    }
+
+    println!("Proof is valid");
+}
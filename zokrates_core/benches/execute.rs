@@ -0,0 +1,79 @@
+//! Run with `cargo +nightly bench` from `zokrates_core`
+
+#![feature(test)]
+
+extern crate test;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use test::Bencher;
+use zokrates_core::compile::compile;
+use zokrates_core::ir::Prog;
+use zokrates_core::resolve::Resolver;
+use zokrates_field::field::{Field, FieldPrime, Pow};
+
+fn program(source: &str) -> Prog<FieldPrime> {
+    compile(
+        &mut source.as_bytes(),
+        None,
+        None::<&dyn Resolver<io::Error>>,
+    )
+    .unwrap()
+}
+
+#[bench]
+fn execute_sha256round(b: &mut Bencher) {
+    let program = program(
+        r#"
+import "EMBED/sha256round" as sha256round
+def main(private field[512] a, field[256] b) -> (field[256]):
+    return sha256round(a, b)
+"#,
+    );
+
+    let arguments: Vec<_> = (0..768).map(|i| FieldPrime::from(i % 3 % 2)).collect();
+
+    b.iter(|| program.clone().execute(&arguments).unwrap());
+}
+
+#[bench]
+fn execute_arithmetic(b: &mut Bencher) {
+    let program = program(
+        r#"
+def main(private field a) -> (field):
+    field x = a
+    for field i in 0..200 do
+        x = x * x / a + i
+    endfor
+    return x
+"#,
+    );
+
+    let arguments = vec![FieldPrime::from(42)];
+
+    b.iter(|| program.clone().execute(&arguments).unwrap());
+}
+
+#[bench]
+fn mul(b: &mut Bencher) {
+    let x = FieldPrime::from(-42);
+    let y = FieldPrime::from(-1337);
+
+    b.iter(|| x.clone() * &y);
+}
+
+#[bench]
+fn inverse_mul(b: &mut Bencher) {
+    let x = FieldPrime::from(-42);
+
+    b.iter(|| x.inverse_mul());
+}
+
+#[bench]
+fn pow(b: &mut Bencher) {
+    let x = FieldPrime::from(-42);
+    let exp = FieldPrime::from(1 << 16);
+
+    b.iter(|| x.clone().pow(&exp));
+}
@@ -84,40 +84,34 @@ r1cs_se_ppzksnark_keypair<libff::alt_bn128_pp> generateKeypair(const r1cs_se_ppz
   return r1cs_se_ppzksnark_generator<libff::alt_bn128_pp>(cs);
 }
 
-void serializeProvingKeyToFile(r1cs_se_ppzksnark_proving_key<libff::alt_bn128_pp> pk, const char* pk_path){
-  writeToFile(pk_path, pk);
+r1cs_se_ppzksnark_proving_key<libff::alt_bn128_pp> deserializeProvingKey(const uint8_t* pk, int pk_length){
+  return deserializeFromBuffer<r1cs_se_ppzksnark_proving_key<libff::alt_bn128_pp>>(pk, pk_length);
 }
 
-r1cs_se_ppzksnark_proving_key<libff::alt_bn128_pp> deserializeProvingKeyFromFile(const char* pk_path){
-  return loadFromFile<r1cs_se_ppzksnark_proving_key<libff::alt_bn128_pp>>(pk_path);
-}
-
-void serializeVerificationKeyToFile(r1cs_se_ppzksnark_verification_key<libff::alt_bn128_pp> vk, const char* vk_path){
+buffer_t serializeVerificationKey(r1cs_se_ppzksnark_verification_key<libff::alt_bn128_pp> vk){
   std::stringstream ss;
 
   unsigned queryLength = vk.query.size();
 
-  ss << "\t\tvk.h = " << outputPointG2AffineAsHex(vk.H) << endl;
-  ss << "\t\tvk.g_alpha = " << outputPointG1AffineAsHex(vk.G_alpha) << endl;
-  ss << "\t\tvk.h_beta = " << outputPointG2AffineAsHex(vk.H_beta) << endl;
-  ss << "\t\tvk.g_gamma = " << outputPointG1AffineAsHex(vk.G_gamma) << endl;
-  ss << "\t\tvk.h_gamma = " << outputPointG2AffineAsHex(vk.H_gamma) << endl;
-  ss << "\t\tvk.query.len() = " << queryLength << endl;
+  ss << "{" << "\n";
+  ss << "\t\"h\": " << outputPointG2AffineAsHexJson(vk.H) << ",\n";
+  ss << "\t\"g_alpha\": " << outputPointG1AffineAsHexJson(vk.G_alpha) << ",\n";
+  ss << "\t\"h_beta\": " << outputPointG2AffineAsHexJson(vk.H_beta) << ",\n";
+  ss << "\t\"g_gamma\": " << outputPointG1AffineAsHexJson(vk.G_gamma) << ",\n";
+  ss << "\t\"h_gamma\": " << outputPointG2AffineAsHexJson(vk.H_gamma) << ",\n";
+  ss << "\t\"query\": [";
   for (size_t i = 0; i < queryLength; ++i)
   {
-      auto vkqueryi = outputPointG1AffineAsHex(vk.query[i]);
-      ss << "\t\tvk.query[" << i << "] = " << vkqueryi << endl;
+      if (i != 0) ss << ", ";
+      ss << outputPointG1AffineAsHexJson(vk.query[i]);
   }
+  ss << "]\n";
+  ss << "}" << "\n";
 
-  std::ofstream fh;
-  fh.open(vk_path, std::ios::binary);
-  ss.rdbuf()->pubseekpos(0, std::ios_base::out);
-  fh << ss.rdbuf();
-  fh.flush();
-  fh.close();
+  return createBuffer(ss);
 }
 
-void exportProof(r1cs_se_ppzksnark_proof<libff::alt_bn128_pp> proof, const char* proof_path, const uint8_t* public_inputs,
+buffer_t exportProof(r1cs_se_ppzksnark_proof<libff::alt_bn128_pp> proof, const uint8_t* public_inputs,
             int public_inputs_length){
     //create JSON file
     std::stringstream ss;
@@ -141,14 +135,12 @@ void exportProof(r1cs_se_ppzksnark_proof<libff::alt_bn128_pp> proof, const char*
       ss << "]" << "\n";
     ss << "}" << "\n";
 
-    std::string s = ss.str();
-    //write json string to proof_path
-    writeToFile(proof_path, s);
+    return createBuffer(ss);
 }
 
 }
 
-bool _gm17_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_len, int B_len, int C_len, int constraints, int variables, int inputs, const char* pk_path, const char* vk_path)
+setup_result_t _gm17_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_len, int B_len, int C_len, int constraints, int variables, int inputs)
 {
   libff::inhibit_profiling_info = true;
   libff::inhibit_profiling_counters = true;
@@ -165,21 +157,22 @@ bool _gm17_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_len
   // create keypair
   auto keypair = r1cs_se_ppzksnark_generator<libff::alt_bn128_pp>(cs);
 
-  // Export vk and pk to files
-  gm17::serializeProvingKeyToFile(keypair.pk, pk_path);
-  gm17::serializeVerificationKeyToFile(keypair.vk, vk_path);
+  // Export vk and pk to buffers
+  setup_result_t result;
+  result.vk = gm17::serializeVerificationKey(keypair.vk);
+  result.pk = serializeToBuffer(keypair.pk);
 
-  return true;
+  return result;
 }
 
-bool _gm17_generate_proof(const char* pk_path, const char* proof_path, const uint8_t* public_inputs, int public_inputs_length, const uint8_t* private_inputs, int private_inputs_length)
+buffer_t _gm17_generate_proof(const uint8_t* pk_buf, int pk_length, const uint8_t* public_inputs, int public_inputs_length, const uint8_t* private_inputs, int private_inputs_length)
 {
   libff::inhibit_profiling_info = true;
   libff::inhibit_profiling_counters = true;
 
   //initialize curve parameters
   libff::alt_bn128_pp::init_public_params();
-  auto pk = gm17::deserializeProvingKeyFromFile(pk_path);
+  auto pk = gm17::deserializeProvingKey(pk_buf, pk_length);
 
   // assign variables based on witness values, excludes ~one
   r1cs_variable_assignment<libff::Fr<libff::alt_bn128_pp> > full_variable_assignment;
@@ -203,8 +196,6 @@ bool _gm17_generate_proof(const char* pk_path, const char* proof_path, const uin
   // Proof Generation
   auto proof = r1cs_se_ppzksnark_prover<libff::alt_bn128_pp>(pk, primary_input, auxiliary_input);
 
-  gm17::exportProof(proof, proof_path, public_inputs, public_inputs_length);
-
-  return true;
+  return gm17::exportProof(proof, public_inputs, public_inputs_length);
 }
 
@@ -7,6 +7,8 @@
 
 #pragma once
 
+#include "util.hpp"
+
 #ifdef __cplusplus
 extern "C" {
 #endif
@@ -14,7 +16,7 @@ extern "C" {
 #include <stdbool.h>
 #include <stdint.h>
 
-bool _gm17_setup(const uint8_t* A,
+setup_result_t _gm17_setup(const uint8_t* A,
             const uint8_t* B,
             const uint8_t* C,
             int A_len,
@@ -22,13 +24,11 @@ bool _gm17_setup(const uint8_t* A,
             int C_len,
             int constraints,
             int variables,
-            int inputs,
-            const char* pk_path,
-            const char* vk_path
+            int inputs
           );
 
-bool _gm17_generate_proof(const char* pk_path,
-            const char* proof_path,
+buffer_t _gm17_generate_proof(const uint8_t* pk_buf,
+            int pk_length,
             const uint8_t* public_inputs,
             int public_inputs_length,
             const uint8_t* private_inputs,
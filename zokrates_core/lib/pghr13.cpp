@@ -84,43 +84,35 @@ r1cs_ppzksnark_keypair<libff::alt_bn128_pp> generateKeypair(const r1cs_ppzksnark
   return r1cs_ppzksnark_generator<libff::alt_bn128_pp>(cs);
 }
 
-void serializeProvingKeyToFile(r1cs_ppzksnark_proving_key<libff::alt_bn128_pp> pk, const char* pk_path){
-  writeToFile(pk_path, pk);
+r1cs_ppzksnark_proving_key<libff::alt_bn128_pp> deserializeProvingKey(const uint8_t* pk, int pk_length){
+  return deserializeFromBuffer<r1cs_ppzksnark_proving_key<libff::alt_bn128_pp>>(pk, pk_length);
 }
 
-r1cs_ppzksnark_proving_key<libff::alt_bn128_pp> deserializeProvingKeyFromFile(const char* pk_path){
-  return loadFromFile<r1cs_ppzksnark_proving_key<libff::alt_bn128_pp>>(pk_path);
-}
-
-void serializeVerificationKeyToFile(r1cs_ppzksnark_verification_key<libff::alt_bn128_pp> vk, const char* vk_path){
+buffer_t serializeVerificationKey(r1cs_ppzksnark_verification_key<libff::alt_bn128_pp> vk){
   std::stringstream ss;
 
   unsigned icLength = vk.encoded_IC_query.rest.indices.size() + 1;
 
-  ss << "\t\tvk.a = " << outputPointG2AffineAsHex(vk.alphaA_g2) << endl;
-  ss << "\t\tvk.b = " << outputPointG1AffineAsHex(vk.alphaB_g1) << endl;
-  ss << "\t\tvk.c = " << outputPointG2AffineAsHex(vk.alphaC_g2) << endl;
-  ss << "\t\tvk.gamma = " << outputPointG2AffineAsHex(vk.gamma_g2) << endl;
-  ss << "\t\tvk.gamma_beta_1 = " << outputPointG1AffineAsHex(vk.gamma_beta_g1) << endl;
-  ss << "\t\tvk.gamma_beta_2 = " << outputPointG2AffineAsHex(vk.gamma_beta_g2) << endl;
-  ss << "\t\tvk.z = " << outputPointG2AffineAsHex(vk.rC_Z_g2) << endl;
-  ss << "\t\tvk.ic.len() = " << icLength << endl;
-  ss << "\t\tvk.ic[0] = " << outputPointG1AffineAsHex(vk.encoded_IC_query.first) << endl;
+  ss << "{" << "\n";
+  ss << "\t\"a\": " << outputPointG2AffineAsHexJson(vk.alphaA_g2) << ",\n";
+  ss << "\t\"b\": " << outputPointG1AffineAsHexJson(vk.alphaB_g1) << ",\n";
+  ss << "\t\"c\": " << outputPointG2AffineAsHexJson(vk.alphaC_g2) << ",\n";
+  ss << "\t\"gamma\": " << outputPointG2AffineAsHexJson(vk.gamma_g2) << ",\n";
+  ss << "\t\"gamma_beta_1\": " << outputPointG1AffineAsHexJson(vk.gamma_beta_g1) << ",\n";
+  ss << "\t\"gamma_beta_2\": " << outputPointG2AffineAsHexJson(vk.gamma_beta_g2) << ",\n";
+  ss << "\t\"z\": " << outputPointG2AffineAsHexJson(vk.rC_Z_g2) << ",\n";
+  ss << "\t\"ic\": [" << outputPointG1AffineAsHexJson(vk.encoded_IC_query.first);
   for (size_t i = 1; i < icLength; ++i)
   {
-                  auto vkICi = outputPointG1AffineAsHex(vk.encoded_IC_query.rest.values[i - 1]);
-                  ss << "\t\tvk.IC[" << i << "] = " << vkICi << endl;
+      ss << ", " << outputPointG1AffineAsHexJson(vk.encoded_IC_query.rest.values[i - 1]);
   }
+  ss << "]\n";
+  ss << "}" << "\n";
 
-  std::ofstream fh;
-  fh.open(vk_path, std::ios::binary);
-  ss.rdbuf()->pubseekpos(0, std::ios_base::out);
-  fh << ss.rdbuf();
-  fh.flush();
-  fh.close();
+  return createBuffer(ss);
 }
 
-void exportProof(r1cs_ppzksnark_proof<libff::alt_bn128_pp> proof, const char* proof_path, const uint8_t* public_inputs,
+buffer_t exportProof(r1cs_ppzksnark_proof<libff::alt_bn128_pp> proof, const uint8_t* public_inputs,
             int public_inputs_length){
                 //create JSON file
                 std::stringstream ss;
@@ -149,14 +141,12 @@ void exportProof(r1cs_ppzksnark_proof<libff::alt_bn128_pp> proof, const char* pr
                   ss << "]" << "\n";
                 ss << "}" << "\n";
 
-                std::string s = ss.str();
-                //write json string to proof_path
-                writeToFile(proof_path, s);
+                return createBuffer(ss);
 }
 
 }
 
-bool _pghr13_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_len, int B_len, int C_len, int constraints, int variables, int inputs, const char* pk_path, const char* vk_path)
+setup_result_t _pghr13_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_len, int B_len, int C_len, int constraints, int variables, int inputs)
 {
   libff::inhibit_profiling_info = true;
   libff::inhibit_profiling_counters = true;
@@ -173,21 +163,22 @@ bool _pghr13_setup(const uint8_t* A, const uint8_t* B, const uint8_t* C, int A_l
   // create keypair
   auto keypair = r1cs_ppzksnark_generator<libff::alt_bn128_pp>(cs);
 
-  // Export vk and pk to files
-  pghr13::serializeProvingKeyToFile(keypair.pk, pk_path);
-  pghr13::serializeVerificationKeyToFile(keypair.vk, vk_path);
+  // Export vk and pk to buffers
+  setup_result_t result;
+  result.vk = pghr13::serializeVerificationKey(keypair.vk);
+  result.pk = serializeToBuffer(keypair.pk);
 
-  return true;
+  return result;
 }
 
-bool _pghr13_generate_proof(const char* pk_path, const char* proof_path, const uint8_t* public_inputs, int public_inputs_length, const uint8_t* private_inputs, int private_inputs_length)
+buffer_t _pghr13_generate_proof(const uint8_t* pk_buf, int pk_length, const uint8_t* public_inputs, int public_inputs_length, const uint8_t* private_inputs, int private_inputs_length)
 {
   libff::inhibit_profiling_info = true;
   libff::inhibit_profiling_counters = true;
 
   //initialize curve parameters
   libff::alt_bn128_pp::init_public_params();
-  auto pk = pghr13::deserializeProvingKeyFromFile(pk_path);
+  auto pk = pghr13::deserializeProvingKey(pk_buf, pk_length);
 
   // assign variables based on witness values, excludes ~one
   r1cs_variable_assignment<libff::Fr<libff::alt_bn128_pp> > full_variable_assignment;
@@ -211,7 +202,5 @@ bool _pghr13_generate_proof(const char* pk_path, const char* proof_path, const u
   // Proof Generation
   auto proof = r1cs_ppzksnark_prover<libff::alt_bn128_pp>(pk, primary_input, auxiliary_input);
 
-  pghr13::exportProof(proof, proof_path, public_inputs, public_inputs_length);
-
-  return true;
+  return pghr13::exportProof(proof, public_inputs, public_inputs_length);
 }
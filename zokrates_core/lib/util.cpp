@@ -6,9 +6,27 @@
  */
 
 #include "util.hpp"
+#include <cstring>
 
 using namespace std;
 
+buffer_t createBuffer(std::stringstream& ss)
+{
+  std::string s = ss.str();
+
+  buffer_t buffer;
+  buffer.length = s.size();
+  buffer.data = (uint8_t*) malloc(s.size());
+  memcpy(buffer.data, s.data(), s.size());
+
+  return buffer;
+}
+
+void __free(uint8_t* ptr)
+{
+  free(ptr);
+}
+
 // conversion byte[32] <-> libsnark bigint.
 libff::bigint<libff::alt_bn128_r_limbs> libsnarkBigintFromBytes(const uint8_t* _x)
 {
@@ -42,17 +60,6 @@ std::string outputInputAsHex(libff::bigint<libff::alt_bn128_r_limbs> _x){
         return "\"0x" + HexStringFromLibsnarkBigint(_x) + "\"";
 }
 
-std::string outputPointG1AffineAsHex(libff::alt_bn128_G1 _p)
-{
-        libff::alt_bn128_G1 aff = _p;
-        aff.to_affine_coordinates();
-        return
-                "0x" +
-                HexStringFromLibsnarkBigint(aff.X.as_bigint()) +
-                ", 0x" +
-                HexStringFromLibsnarkBigint(aff.Y.as_bigint());
-}
-
 std::string outputPointG1AffineAsHexJson(libff::alt_bn128_G1 _p)
 {
         libff::alt_bn128_G1 aff = _p;
@@ -64,18 +71,6 @@ std::string outputPointG1AffineAsHexJson(libff::alt_bn128_G1 _p)
                 HexStringFromLibsnarkBigint(aff.Y.as_bigint())+"\"]";
 }
 
-std::string outputPointG2AffineAsHex(libff::alt_bn128_G2 _p)
-{
-        libff::alt_bn128_G2 aff = _p;
-        aff.to_affine_coordinates();
-        return
-                "[0x" +
-                HexStringFromLibsnarkBigint(aff.X.c1.as_bigint()) + ", 0x" +
-                HexStringFromLibsnarkBigint(aff.X.c0.as_bigint()) + "], [0x" +
-                HexStringFromLibsnarkBigint(aff.Y.c1.as_bigint()) + ", 0x" +
-                HexStringFromLibsnarkBigint(aff.Y.c0.as_bigint()) + "]";
-}
-
 std::string outputPointG2AffineAsHexJson(libff::alt_bn128_G2 _p)
 {
         libff::alt_bn128_G2 aff = _p;
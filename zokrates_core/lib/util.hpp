@@ -6,6 +6,9 @@
 #include <iostream>
 #include <cassert>
 #include <iomanip>
+#include <sstream>
+#include <cstdint>
+#include <cstdlib>
 
 libff::bigint<libff::alt_bn128_r_limbs> libsnarkBigintFromBytes(const uint8_t* _x);
 
@@ -13,35 +16,36 @@ std::string HexStringFromLibsnarkBigint(libff::bigint<libff::alt_bn128_r_limbs>
 
 std::string outputInputAsHex(libff::bigint<libff::alt_bn128_r_limbs> _x);
 
-std::string outputPointG1AffineAsHex(libff::alt_bn128_G1 _p);
-
 std::string outputPointG1AffineAsHexJson(libff::alt_bn128_G1 _p);
 
-std::string outputPointG2AffineAsHex(libff::alt_bn128_G2 _p);
-
 std::string outputPointG2AffineAsHexJson(libff::alt_bn128_G2 _p);
 
+struct buffer_t {
+    uint8_t* data;
+    int32_t length;
+};
+
+struct setup_result_t {
+    buffer_t vk;
+    buffer_t pk;
+};
+
+extern "C" void __free(uint8_t* ptr);
+
+// copies the contents of `ss` to a buffer, which is released by the caller with `__free`
+buffer_t createBuffer(std::stringstream& ss);
+
 template<typename T>
-void writeToFile(std::string path, T& obj) {
+buffer_t serializeToBuffer(T& obj) {
     std::stringstream ss;
     ss << obj;
-    std::ofstream fh;
-    fh.open(path, std::ios::binary);
-    ss.rdbuf()->pubseekpos(0, std::ios_base::out);
-    fh << ss.rdbuf();
-    fh.flush();
-    fh.close();
+    return createBuffer(ss);
 }
 
 template<typename T>
-T loadFromFile(std::string path) {
+T deserializeFromBuffer(const uint8_t* data, int length) {
     std::stringstream ss;
-    std::ifstream fh(path, std::ios::binary);
-
-    assert(fh.is_open());
-
-    ss << fh.rdbuf();
-    fh.close();
+    ss.write((const char*) data, length);
 
     ss.rdbuf()->pubseekpos(0, std::ios_base::in);
 
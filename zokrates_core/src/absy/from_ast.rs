@@ -6,9 +6,14 @@ use zokrates_pest_ast as pest;
 impl<'ast, T: Field> From<pest::File<'ast>> for absy::Module<'ast, T> {
     fn from(prog: pest::File<'ast>) -> absy::Module<T> {
         absy::Module::with_symbols(
-            prog.structs
+            prog.constants
                 .into_iter()
-                .map(|t| absy::SymbolDeclarationNode::from(t))
+                .map(|c| absy::SymbolDeclarationNode::from(c))
+                .chain(
+                    prog.structs
+                        .into_iter()
+                        .map(|t| absy::SymbolDeclarationNode::from(t)),
+                )
                 .chain(
                     prog.functions
                         .into_iter()
@@ -39,6 +44,28 @@ impl<'ast> From<pest::ImportDirective<'ast>> for absy::ImportNode<'ast> {
     }
 }
 
+impl<'ast, T: Field> From<pest::ConstantDefinition<'ast>> for absy::SymbolDeclarationNode<'ast, T> {
+    fn from(definition: pest::ConstantDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast, T> {
+        use absy::NodeValue;
+
+        let span = definition.span;
+
+        let id = definition.id.span.as_str();
+
+        let constant = absy::Constant {
+            ty: absy::UnresolvedTypeNode::from(definition.ty),
+            expression: absy::ExpressionNode::from(definition.expression),
+        }
+        .span(span.clone());
+
+        absy::SymbolDeclaration {
+            id,
+            symbol: absy::Symbol::HereConstant(constant),
+        }
+        .span(span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::StructDefinition<'ast>> for absy::SymbolDeclarationNode<'ast, T> {
     fn from(definition: pest::StructDefinition<'ast>) -> absy::SymbolDeclarationNode<'ast, T> {
         use absy::NodeValue;
@@ -105,6 +132,7 @@ impl<'ast, T: Field> From<pest::Function<'ast>> for absy::SymbolDeclarationNode<
         let id = function.id.span.as_str();
 
         let function = absy::Function::<T> {
+            generics: function.generics.iter().map(|g| g.span.as_str()).collect(),
             arguments: function
                 .parameters
                 .into_iter()
@@ -155,40 +183,56 @@ fn statements_from_statement<'ast, T: Field>(
     match statement {
         pest::Statement::Definition(s) => statements_from_definition(s),
         pest::Statement::Iteration(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::IfElse(s) => vec![absy::StatementNode::from(s)],
+        pest::Statement::Expression(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Assertion(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Assignment(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::Return(s) => vec![absy::StatementNode::from(s)],
         pest::Statement::MultiAssignment(s) => statements_from_multi_assignment(s),
+        pest::Statement::Destructuring(s) => statements_from_destructuring(s),
     }
 }
 
-fn statements_from_multi_assignment<'ast, T: Field>(
-    assignment: pest::MultiAssignmentStatement<'ast>,
-) -> Vec<absy::StatementNode<T>> {
+/// Declare the typed identifiers of a list of assignees, and return these declarations along with the assignees
+fn declarations_and_assignees<'ast, T: Field>(
+    lhs: Vec<pest::OptionallyTypedAssignee<'ast>>,
+) -> (Vec<absy::StatementNode<T>>, Vec<absy::AssigneeNode<T>>) {
     use absy::NodeValue;
 
-    let declarations = assignment
-        .lhs
-        .clone()
-        .into_iter()
-        .filter(|i| i.ty.is_some())
-        .map(|i| {
-            absy::Statement::Declaration(
-                absy::Variable::new(
-                    i.id.span.as_str(),
-                    absy::UnresolvedTypeNode::from(i.ty.unwrap()),
+    let declarations = lhs
+        .iter()
+        .filter_map(|a| match a {
+            pest::OptionallyTypedAssignee::Identifier(i) => i.ty.clone().map(|ty| {
+                absy::Statement::Declaration(
+                    absy::Variable::new(i.id.span.as_str(), absy::UnresolvedTypeNode::from(ty))
+                        .span(i.id.span.clone()),
                 )
-                .span(i.id.span),
-            )
-            .span(i.span)
-        });
+                .span(i.span.clone())
+            }),
+            pest::OptionallyTypedAssignee::Wildcard(_) => None,
+        })
+        .collect();
 
-    let lhs = assignment
-        .lhs
+    let assignees = lhs
         .into_iter()
-        .map(|i| absy::Assignee::Identifier(i.id.span.as_str()).span(i.id.span))
+        .map(|a| match a {
+            pest::OptionallyTypedAssignee::Identifier(i) => {
+                absy::Assignee::Identifier(i.id.span.as_str()).span(i.id.span)
+            }
+            pest::OptionallyTypedAssignee::Wildcard(w) => absy::Assignee::Wildcard.span(w.span),
+        })
         .collect();
 
+    (declarations, assignees)
+}
+
+fn statements_from_multi_assignment<'ast, T: Field>(
+    assignment: pest::MultiAssignmentStatement<'ast>,
+) -> Vec<absy::StatementNode<T>> {
+    use absy::NodeValue;
+
+    let (declarations, lhs) = declarations_and_assignees(assignment.lhs);
+
     let multi_def = absy::Statement::MultipleDefinition(
         lhs,
         absy::Expression::FunctionCall(
@@ -203,7 +247,29 @@ fn statements_from_multi_assignment<'ast, T: Field>(
     )
     .span(assignment.span);
 
-    declarations.chain(std::iter::once(multi_def)).collect()
+    declarations
+        .into_iter()
+        .chain(std::iter::once(multi_def))
+        .collect()
+}
+
+fn statements_from_destructuring<'ast, T: Field>(
+    destructuring: pest::DestructuringStatement<'ast>,
+) -> Vec<absy::StatementNode<T>> {
+    use absy::NodeValue;
+
+    let (declarations, lhs) = declarations_and_assignees(destructuring.lhs);
+
+    let multi_def = absy::Statement::MultipleDefinition(
+        lhs,
+        absy::ExpressionNode::from(destructuring.expression),
+    )
+    .span(destructuring.span);
+
+    declarations
+        .into_iter()
+        .chain(std::iter::once(multi_def))
+        .collect()
 }
 
 fn statements_from_definition<'ast, T: Field>(
@@ -246,8 +312,8 @@ impl<'ast, T: Field> From<pest::ReturnStatement<'ast>> for absy::StatementNode<'
     }
 }
 
-impl<'ast, T: Field> From<pest::AssertionStatement<'ast>> for absy::StatementNode<'ast, T> {
-    fn from(statement: pest::AssertionStatement<'ast>) -> absy::StatementNode<T> {
+impl<'ast, T: Field> From<pest::ExpressionStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::ExpressionStatement<'ast>) -> absy::StatementNode<T> {
         use absy::NodeValue;
 
         match statement.expression {
@@ -270,6 +336,18 @@ impl<'ast, T: Field> From<pest::AssertionStatement<'ast>> for absy::StatementNod
     }
 }
 
+impl<'ast, T: Field> From<pest::AssertionStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::AssertionStatement<'ast>) -> absy::StatementNode<T> {
+        use absy::NodeValue;
+
+        absy::Statement::Assertion(
+            absy::ExpressionNode::from(statement.expression),
+            statement.message.map(|m| m.value),
+        )
+        .span(statement.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::IterationStatement<'ast>> for absy::StatementNode<'ast, T> {
     fn from(statement: pest::IterationStatement<'ast>) -> absy::StatementNode<T> {
         use absy::NodeValue;
@@ -283,19 +361,39 @@ impl<'ast, T: Field> From<pest::IterationStatement<'ast>> for absy::StatementNod
             .flat_map(|s| statements_from_statement(s))
             .collect();
 
-        let from = match from.value {
-            absy::Expression::FieldConstant(n) => n,
-            e => unimplemented!("For loop bounds should be constants, found {}", e),
-        };
+        let var = absy::Variable::new(index, ty).span(statement.index.span);
 
-        let to = match to.value {
-            absy::Expression::FieldConstant(n) => n,
-            e => unimplemented!("For loop bounds should be constants, found {}", e),
-        };
+        match statement.max {
+            Some(max) => {
+                let max = absy::ExpressionNode::from(max.expression);
+                absy::Statement::BoundedFor(var, from, to, max, statements).span(statement.span)
+            }
+            None => absy::Statement::For(var, from, to, statements).span(statement.span),
+        }
+    }
+}
 
-        let var = absy::Variable::new(index, ty).span(statement.index.span);
+impl<'ast, T: Field> From<pest::IfElseStatement<'ast>> for absy::StatementNode<'ast, T> {
+    fn from(statement: pest::IfElseStatement<'ast>) -> absy::StatementNode<T> {
+        use absy::NodeValue;
+        let condition = absy::ExpressionNode::from(statement.condition);
+        let consequence = statement
+            .consequence
+            .statements
+            .into_iter()
+            .flat_map(|s| statements_from_statement(s))
+            .collect();
+        let alternative = statement
+            .alternative
+            .map(|a| {
+                a.statements
+                    .into_iter()
+                    .flat_map(|s| statements_from_statement(s))
+                    .collect()
+            })
+            .unwrap_or(vec![]);
 
-        absy::Statement::For(var, from, to, statements).span(statement.span)
+        absy::Statement::IfElse(condition, consequence, alternative).span(statement.span)
     }
 }
 
@@ -316,11 +414,13 @@ impl<'ast, T: Field> From<pest::Expression<'ast>> for absy::ExpressionNode<'ast,
         match expression {
             pest::Expression::Binary(e) => absy::ExpressionNode::from(e),
             pest::Expression::Ternary(e) => absy::ExpressionNode::from(e),
+            pest::Expression::Cast(e) => absy::ExpressionNode::from(e),
             pest::Expression::Constant(e) => absy::ExpressionNode::from(e),
             pest::Expression::Identifier(e) => absy::ExpressionNode::from(e),
             pest::Expression::Postfix(e) => absy::ExpressionNode::from(e),
             pest::Expression::InlineArray(e) => absy::ExpressionNode::from(e),
             pest::Expression::InlineStruct(e) => absy::ExpressionNode::from(e),
+            pest::Expression::InlineTuple(e) => absy::ExpressionNode::from(e),
             pest::Expression::ArrayInitializer(e) => absy::ExpressionNode::from(e),
             pest::Expression::Unary(e) => absy::ExpressionNode::from(e),
         }
@@ -379,6 +479,26 @@ impl<'ast, T: Field> From<pest::BinaryExpression<'ast>> for absy::ExpressionNode
                 box absy::ExpressionNode::from(*expression.left),
                 box absy::ExpressionNode::from(*expression.right),
             ),
+            pest::BinaryOperator::BitAnd => absy::Expression::BitAnd(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
+            pest::BinaryOperator::BitOr => absy::Expression::BitOr(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
+            pest::BinaryOperator::Xor => absy::Expression::BitXor(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
+            pest::BinaryOperator::LeftShift => absy::Expression::LeftShift(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
+            pest::BinaryOperator::RightShift => absy::Expression::RightShift(
+                box absy::ExpressionNode::from(*expression.left),
+                box absy::ExpressionNode::from(*expression.right),
+            ),
             o => unimplemented!("Operator {:?} not implemented", o),
         }
         .span(expression.span)
@@ -397,6 +517,17 @@ impl<'ast, T: Field> From<pest::TernaryExpression<'ast>> for absy::ExpressionNod
     }
 }
 
+impl<'ast, T: Field> From<pest::CastExpression<'ast>> for absy::ExpressionNode<'ast, T> {
+    fn from(expression: pest::CastExpression<'ast>) -> absy::ExpressionNode<'ast, T> {
+        use absy::NodeValue;
+        absy::Expression::Cast(
+            absy::UnresolvedTypeNode::from(expression.ty),
+            box absy::ExpressionNode::from(*expression.expression),
+        )
+        .span(expression.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::Spread<'ast>> for absy::SpreadNode<'ast, T> {
     fn from(spread: pest::Spread<'ast>) -> absy::SpreadNode<'ast, T> {
         use absy::NodeValue;
@@ -473,6 +604,19 @@ impl<'ast, T: Field> From<pest::InlineArrayExpression<'ast>> for absy::Expressio
     }
 }
 
+impl<'ast, T: Field> From<pest::InlineTupleExpression<'ast>> for absy::ExpressionNode<'ast, T> {
+    fn from(t: pest::InlineTupleExpression<'ast>) -> absy::ExpressionNode<'ast, T> {
+        use absy::NodeValue;
+        absy::Expression::InlineTuple(
+            t.elements
+                .into_iter()
+                .map(|e| absy::ExpressionNode::from(e))
+                .collect(),
+        )
+        .span(t.span)
+    }
+}
+
 impl<'ast, T: Field> From<pest::InlineStructExpression<'ast>> for absy::ExpressionNode<'ast, T> {
     fn from(s: pest::InlineStructExpression<'ast>) -> absy::ExpressionNode<'ast, T> {
         use absy::NodeValue;
@@ -552,6 +696,9 @@ impl<'ast, T: Field> From<pest::PostfixExpression<'ast>> for absy::ExpressionNod
             pest::Access::Member(m) => {
                 absy::Expression::Member(box acc, box m.id.span.as_str()).span(m.span)
             }
+            pest::Access::Element(e) => {
+                absy::Expression::Element(box acc, e.index.value.parse().unwrap()).span(e.span)
+            }
         })
     }
 }
@@ -566,6 +713,17 @@ impl<'ast, T: Field> From<pest::ConstantExpression<'ast>> for absy::ExpressionNo
             pest::ConstantExpression::DecimalNumber(n) => {
                 absy::Expression::FieldConstant(T::try_from_dec_str(&n.value).unwrap()).span(n.span)
             }
+            pest::ConstantExpression::HexNumber(n) => {
+                // the grammar only accepts 2, 4 or 8 hex digits after the `0x` prefix
+                let digits = &n.value[2..];
+                match digits.len() {
+                    2 => absy::Expression::U8Constant(u8::from_str_radix(digits, 16).unwrap()),
+                    4 => absy::Expression::U16Constant(u16::from_str_radix(digits, 16).unwrap()),
+                    8 => absy::Expression::U32Constant(u32::from_str_radix(digits, 16).unwrap()),
+                    _ => unreachable!(),
+                }
+                .span(n.span)
+            }
         }
     }
 }
@@ -606,26 +764,33 @@ impl<'ast, T: Field> From<pest::Assignee<'ast>> for absy::AssigneeNode<'ast, T>
     }
 }
 
+impl<'ast> From<pest::BasicType<'ast>> for absy::UnresolvedTypeNode {
+    fn from(t: pest::BasicType<'ast>) -> absy::UnresolvedTypeNode {
+        use absy::NodeValue;
+
+        match t {
+            pest::BasicType::Field(t) => absy::UnresolvedType::FieldElement.span(t.span),
+            pest::BasicType::Boolean(t) => absy::UnresolvedType::Boolean.span(t.span),
+            pest::BasicType::U8(t) => absy::UnresolvedType::Uint(8).span(t.span),
+            pest::BasicType::U16(t) => absy::UnresolvedType::Uint(16).span(t.span),
+            pest::BasicType::U32(t) => absy::UnresolvedType::Uint(32).span(t.span),
+        }
+    }
+}
+
 impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
     fn from(t: pest::Type<'ast>) -> absy::UnresolvedTypeNode {
         use absy::NodeValue;
 
         match t {
-            pest::Type::Basic(t) => match t {
-                pest::BasicType::Field(t) => absy::UnresolvedType::FieldElement.span(t.span),
-                pest::BasicType::Boolean(t) => absy::UnresolvedType::Boolean.span(t.span),
-            },
+            pest::Type::Basic(t) => absy::UnresolvedTypeNode::from(t),
             pest::Type::Array(t) => {
                 let inner_type = match t.ty {
-                    pest::BasicOrStructType::Basic(t) => match t {
-                        pest::BasicType::Field(t) => {
-                            absy::UnresolvedType::FieldElement.span(t.span)
-                        }
-                        pest::BasicType::Boolean(t) => absy::UnresolvedType::Boolean.span(t.span),
-                    },
+                    pest::BasicOrStructType::Basic(t) => absy::UnresolvedTypeNode::from(t),
                     pest::BasicOrStructType::Struct(t) => {
                         absy::UnresolvedType::User(t.span.as_str().to_string()).span(t.span)
                     }
+                    pest::BasicOrStructType::Tuple(t) => absy::UnresolvedTypeNode::from(t),
                 };
 
                 let span = t.span;
@@ -635,13 +800,18 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
                     .map(|s| match s {
                         pest::Expression::Constant(c) => match c {
                             pest::ConstantExpression::DecimalNumber(n) => {
-                                str::parse::<usize>(&n.value).unwrap()
+                                absy::types::ArraySize::Number(
+                                    str::parse::<usize>(&n.value).unwrap(),
+                                )
                             }
                             _ => unimplemented!(
                                 "Array size should be a decimal number, found {}",
                                 c.span().as_str()
                             ),
                         },
+                        pest::Expression::Identifier(id) => {
+                            absy::types::ArraySize::Identifier(id.span.as_str().to_string())
+                        }
                         e => unimplemented!(
                             "Array size should be constant, found {}",
                             e.span().as_str()
@@ -658,10 +828,25 @@ impl<'ast> From<pest::Type<'ast>> for absy::UnresolvedTypeNode {
             pest::Type::Struct(s) => {
                 absy::UnresolvedType::User(s.id.span.as_str().to_string()).span(s.span)
             }
+            pest::Type::Tuple(t) => absy::UnresolvedTypeNode::from(t),
         }
     }
 }
 
+impl<'ast> From<pest::TupleType<'ast>> for absy::UnresolvedTypeNode {
+    fn from(t: pest::TupleType<'ast>) -> absy::UnresolvedTypeNode {
+        use absy::NodeValue;
+
+        absy::UnresolvedType::Tuple(
+            t.elements
+                .into_iter()
+                .map(absy::UnresolvedTypeNode::from)
+                .collect(),
+        )
+        .span(t.span)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -677,6 +862,7 @@ mod tests {
                 id: &source[4..8],
                 symbol: absy::Symbol::HereFunction(
                     absy::Function {
+                        generics: vec![],
                         arguments: vec![],
                         statements: vec![absy::Statement::Return(
                             absy::ExpressionList {
@@ -710,6 +896,7 @@ mod tests {
                 id: &source[4..8],
                 symbol: absy::Symbol::HereFunction(
                     absy::Function {
+                        generics: vec![],
                         arguments: vec![],
                         statements: vec![absy::Statement::Return(
                             absy::ExpressionList {
@@ -741,6 +928,7 @@ mod tests {
                 id: &source[4..8],
                 symbol: absy::Symbol::HereFunction(
                     absy::Function {
+                        generics: vec![],
                         arguments: vec![
                             absy::Parameter::private(
                                 absy::Variable::new(
@@ -796,6 +984,7 @@ mod tests {
                     id: "main",
                     symbol: absy::Symbol::HereFunction(
                         absy::Function {
+                            generics: vec![],
                             arguments: vec![absy::Parameter::private(
                                 absy::Variable::new("a", ty.clone().mock()).into(),
                             )
@@ -824,17 +1013,20 @@ mod tests {
                 ("bool", absy::UnresolvedType::Boolean),
                 (
                     "field[2]",
-                    absy::UnresolvedType::Array(box absy::UnresolvedType::FieldElement.mock(), 2),
+                    absy::UnresolvedType::Array(
+                        box absy::UnresolvedType::FieldElement.mock(),
+                        2.into(),
+                    ),
                 ),
                 (
                     "field[2][3]",
                     absy::UnresolvedType::Array(
                         box absy::UnresolvedType::Array(
                             box absy::UnresolvedType::FieldElement.mock(),
-                            3,
+                            3.into(),
                         )
                         .mock(),
-                        2,
+                        2.into(),
                     ),
                 ),
                 (
@@ -842,10 +1034,17 @@ mod tests {
                     absy::UnresolvedType::Array(
                         box absy::UnresolvedType::Array(
                             box absy::UnresolvedType::Boolean.mock(),
-                            3,
+                            3.into(),
                         )
                         .mock(),
-                        2,
+                        2.into(),
+                    ),
+                ),
+                (
+                    "field[N]",
+                    absy::UnresolvedType::Array(
+                        box absy::UnresolvedType::FieldElement.mock(),
+                        absy::types::ArraySize::Identifier(String::from("N")),
                     ),
                 ),
             ];
@@ -867,6 +1066,7 @@ mod tests {
                     id: "main",
                     symbol: absy::Symbol::HereFunction(
                         absy::Function {
+                            generics: vec![],
                             arguments: vec![],
                             statements: vec![absy::Statement::Return(
                                 absy::ExpressionList {
@@ -50,6 +50,7 @@ pub struct SymbolDeclaration<'ast, T: Field> {
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum Symbol<'ast, T: Field> {
+    HereConstant(ConstantNode<'ast, T>),
     HereType(StructTypeNode<'ast>),
     HereFunction(FunctionNode<'ast, T>),
     There(SymbolImportNode<'ast>),
@@ -59,6 +60,11 @@ pub enum Symbol<'ast, T: Field> {
 impl<'ast, T: Field> fmt::Display for SymbolDeclaration<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.symbol {
+            Symbol::HereConstant(ref c) => write!(
+                f,
+                "const {} {} = {}",
+                c.value.ty, self.id, c.value.expression
+            ),
             Symbol::HereType(ref t) => write!(f, "struct {} {}", self.id, t),
             Symbol::HereFunction(ref fun) => write!(f, "def {}{}", self.id, fun),
             Symbol::There(ref import) => write!(f, "import {} as {}", import, self.id),
@@ -98,6 +104,21 @@ impl<'ast, T: Field> Module<'ast, T> {
 
 pub type UnresolvedTypeNode = Node<UnresolvedType>;
 
+/// A constant definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constant<'ast, T: Field> {
+    pub ty: UnresolvedTypeNode,
+    pub expression: ExpressionNode<'ast, T>,
+}
+
+impl<'ast, T: Field> fmt::Display for Constant<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.ty, self.expression)
+    }
+}
+
+pub type ConstantNode<'ast, T> = Node<Constant<'ast, T>>;
+
 /// A struct type definition
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructType<'ast> {
@@ -205,6 +226,8 @@ impl<'ast, T: Field> fmt::Debug for Module<'ast, T> {
 /// A function defined locally
 #[derive(Clone, PartialEq)]
 pub struct Function<'ast, T: Field> {
+    /// Generic parameters of the function, which stand for array sizes
+    pub generics: Vec<Identifier<'ast>>,
     /// Arguments of the function
     pub arguments: Vec<ParameterNode<'ast>>,
     /// Vector of statements that are executed when running the function
@@ -217,6 +240,9 @@ pub type FunctionNode<'ast, T> = Node<Function<'ast, T>>;
 
 impl<'ast, T: Field> fmt::Display for Function<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.generics.is_empty() {
+            write!(f, "<{}>", self.generics.join(", "))?;
+        }
         write!(
             f,
             "({}):\n{}",
@@ -255,6 +281,8 @@ pub enum Assignee<'ast, T: Field> {
     Identifier(Identifier<'ast>),
     Select(Box<AssigneeNode<'ast, T>>, Box<RangeOrExpression<'ast, T>>),
     Member(Box<AssigneeNode<'ast, T>>, Box<Identifier<'ast>>),
+    /// `_` in a destructuring pattern, which discards the corresponding value
+    Wildcard,
 }
 
 pub type AssigneeNode<'ast, T> = Node<Assignee<'ast, T>>;
@@ -265,6 +293,7 @@ impl<'ast, T: Field> fmt::Debug for Assignee<'ast, T> {
             Assignee::Identifier(ref s) => write!(f, "Identifier({:?})", s),
             Assignee::Select(ref a, ref e) => write!(f, "Select({:?}[{:?}])", a, e),
             Assignee::Member(ref s, ref m) => write!(f, "Member({:?}.{:?})", s, m),
+            Assignee::Wildcard => write!(f, "_"),
         }
     }
 }
@@ -282,7 +311,25 @@ pub enum Statement<'ast, T: Field> {
     Declaration(VariableNode<'ast>),
     Definition(AssigneeNode<'ast, T>, ExpressionNode<'ast, T>),
     Condition(ExpressionNode<'ast, T>, ExpressionNode<'ast, T>),
-    For(VariableNode<'ast>, T, T, Vec<StatementNode<'ast, T>>),
+    Assertion(ExpressionNode<'ast, T>, Option<String>),
+    For(
+        VariableNode<'ast>,
+        ExpressionNode<'ast, T>,
+        ExpressionNode<'ast, T>,
+        Vec<StatementNode<'ast, T>>,
+    ),
+    BoundedFor(
+        VariableNode<'ast>,
+        ExpressionNode<'ast, T>,
+        ExpressionNode<'ast, T>,
+        ExpressionNode<'ast, T>,
+        Vec<StatementNode<'ast, T>>,
+    ),
+    IfElse(
+        ExpressionNode<'ast, T>,
+        Vec<StatementNode<'ast, T>>,
+        Vec<StatementNode<'ast, T>>,
+    ),
     MultipleDefinition(Vec<AssigneeNode<'ast, T>>, ExpressionNode<'ast, T>),
 }
 
@@ -295,6 +342,10 @@ impl<'ast, T: Field> fmt::Display for Statement<'ast, T> {
             Statement::Declaration(ref var) => write!(f, "{}", var),
             Statement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
             Statement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            Statement::Assertion(ref e, ref message) => match message {
+                Some(message) => write!(f, "assert({}, \"{}\")", e, message),
+                None => write!(f, "assert({})", e),
+            },
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {} in {}..{} do\n", var, start, stop)?;
                 for l in list {
@@ -302,6 +353,24 @@ impl<'ast, T: Field> fmt::Display for Statement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            Statement::BoundedFor(ref var, ref start, ref stop, ref max, ref list) => {
+                write!(f, "for {} in {}..{} max {} do\n", var, start, stop, max)?;
+                for l in list {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tendfor")
+            }
+            Statement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
             Statement::MultipleDefinition(ref ids, ref rhs) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
@@ -324,6 +393,9 @@ impl<'ast, T: Field> fmt::Debug for Statement<'ast, T> {
                 write!(f, "Definition({:?}, {:?})", lhs, rhs)
             }
             Statement::Condition(ref lhs, ref rhs) => write!(f, "Condition({:?}, {:?})", lhs, rhs),
+            Statement::Assertion(ref e, ref message) => {
+                write!(f, "Assertion({:?}, {:?})", e, message)
+            }
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {:?} in {:?}..{:?} do\n", var, start, stop)?;
                 for l in list {
@@ -331,6 +403,24 @@ impl<'ast, T: Field> fmt::Debug for Statement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
+            Statement::BoundedFor(ref var, ref start, ref stop, ref max, ref list) => {
+                write!(f, "for {:?} in {:?}..{:?} max {:?} do\n", var, start, stop, max)?;
+                for l in list {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\tendfor")
+            }
+            Statement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {:?} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
             Statement::MultipleDefinition(ref lhs, ref rhs) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
@@ -451,6 +541,9 @@ impl<'ast, T: Field> fmt::Debug for Range<T> {
 pub enum Expression<'ast, T: Field> {
     FieldConstant(T),
     BooleanConstant(bool),
+    U8Constant(u8),
+    U16Constant(u16),
+    U32Constant(u32),
     Identifier(Identifier<'ast>),
     Add(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
     Sub(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
@@ -472,12 +565,20 @@ pub enum Expression<'ast, T: Field> {
     Not(Box<ExpressionNode<'ast, T>>),
     InlineArray(Vec<SpreadOrExpression<'ast, T>>),
     InlineStruct(UserTypeId, Vec<(Identifier<'ast>, ExpressionNode<'ast, T>)>),
+    InlineTuple(Vec<ExpressionNode<'ast, T>>),
     Select(
         Box<ExpressionNode<'ast, T>>,
         Box<RangeOrExpression<'ast, T>>,
     ),
     Member(Box<ExpressionNode<'ast, T>>, Box<Identifier<'ast>>),
+    Element(Box<ExpressionNode<'ast, T>>, usize),
     Or(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    BitAnd(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    BitOr(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    BitXor(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    LeftShift(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    RightShift(Box<ExpressionNode<'ast, T>>, Box<ExpressionNode<'ast, T>>),
+    Cast(UnresolvedTypeNode, Box<ExpressionNode<'ast, T>>),
 }
 
 pub type ExpressionNode<'ast, T> = Node<Expression<'ast, T>>;
@@ -486,6 +587,9 @@ impl<'ast, T: Field> fmt::Display for Expression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Expression::FieldConstant(ref i) => write!(f, "{}", i),
+            Expression::U8Constant(ref i) => write!(f, "{:#04x}", i),
+            Expression::U16Constant(ref i) => write!(f, "{:#06x}", i),
+            Expression::U32Constant(ref i) => write!(f, "{:#010x}", i),
             Expression::Identifier(ref var) => write!(f, "{}", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
@@ -535,9 +639,26 @@ impl<'ast, T: Field> fmt::Display for Expression<'ast, T> {
                 }
                 write!(f, "}}")
             }
+            Expression::InlineTuple(ref exprs) => {
+                write!(f, "(")?;
+                for (i, e) in exprs.iter().enumerate() {
+                    write!(f, "{}", e)?;
+                    if i < exprs.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, ")")
+            }
             Expression::Select(ref array, ref index) => write!(f, "{}[{}]", array, index),
             Expression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            Expression::Element(ref tuple, ref index) => write!(f, "{}.{}", tuple, index),
             Expression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
+            Expression::BitAnd(ref lhs, ref rhs) => write!(f, "{} & {}", lhs, rhs),
+            Expression::BitOr(ref lhs, ref rhs) => write!(f, "{} | {}", lhs, rhs),
+            Expression::BitXor(ref lhs, ref rhs) => write!(f, "{} ^ {}", lhs, rhs),
+            Expression::LeftShift(ref lhs, ref rhs) => write!(f, "{} << {}", lhs, rhs),
+            Expression::RightShift(ref lhs, ref rhs) => write!(f, "{} >> {}", lhs, rhs),
+            Expression::Cast(ref ty, ref e) => write!(f, "{}({})", ty, e),
         }
     }
 }
@@ -546,6 +667,9 @@ impl<'ast, T: Field> fmt::Debug for Expression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Expression::FieldConstant(ref i) => write!(f, "Num({})", i),
+            Expression::U8Constant(ref i) => write!(f, "U8({:#04x})", i),
+            Expression::U16Constant(ref i) => write!(f, "U16({:#06x})", i),
+            Expression::U32Constant(ref i) => write!(f, "U32({:#010x})", i),
             Expression::Identifier(ref var) => write!(f, "Ide({})", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "Add({:?}, {:?})", lhs, rhs),
             Expression::Sub(ref lhs, ref rhs) => write!(f, "Sub({:?}, {:?})", lhs, rhs),
@@ -580,11 +704,25 @@ impl<'ast, T: Field> fmt::Debug for Expression<'ast, T> {
                 f.debug_list().entries(members.iter()).finish()?;
                 write!(f, "]")
             }
+            Expression::InlineTuple(ref exprs) => {
+                write!(f, "InlineTuple(")?;
+                f.debug_list().entries(exprs.iter()).finish()?;
+                write!(f, ")")
+            }
             Expression::Select(ref array, ref index) => {
                 write!(f, "Select({:?}, {:?})", array, index)
             }
             Expression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            Expression::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {})", tuple, index)
+            }
             Expression::Or(ref lhs, ref rhs) => write!(f, "{} || {}", lhs, rhs),
+            Expression::BitAnd(ref lhs, ref rhs) => write!(f, "{} & {}", lhs, rhs),
+            Expression::BitOr(ref lhs, ref rhs) => write!(f, "{} | {}", lhs, rhs),
+            Expression::BitXor(ref lhs, ref rhs) => write!(f, "{} ^ {}", lhs, rhs),
+            Expression::LeftShift(ref lhs, ref rhs) => write!(f, "{} << {}", lhs, rhs),
+            Expression::RightShift(ref lhs, ref rhs) => write!(f, "{} >> {}", lhs, rhs),
+            Expression::Cast(ref ty, ref e) => write!(f, "{}({})", ty, e),
         }
     }
 }
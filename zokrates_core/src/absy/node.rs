@@ -76,6 +76,7 @@ impl<'ast, T: Field> NodeValue for Assignee<'ast, T> {}
 impl<'ast, T: Field> NodeValue for Statement<'ast, T> {}
 impl<'ast, T: Field> NodeValue for SymbolDeclaration<'ast, T> {}
 impl NodeValue for UnresolvedType {}
+impl<'ast, T: Field> NodeValue for Constant<'ast, T> {}
 impl<'ast> NodeValue for StructType<'ast> {}
 impl<'ast> NodeValue for StructField<'ast> {}
 impl<'ast, T: Field> NodeValue for Function<'ast, T> {}
@@ -11,8 +11,32 @@ pub type UserTypeId = String;
 pub enum UnresolvedType {
     FieldElement,
     Boolean,
-    Array(Box<UnresolvedTypeNode>, usize),
+    Uint(usize),
+    Array(Box<UnresolvedTypeNode>, ArraySize),
     User(UserTypeId),
+    Tuple(Vec<UnresolvedTypeNode>),
+}
+
+/// The size of an array type: a number, or a generic parameter of the function the type appears in
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub enum ArraySize {
+    Number(usize),
+    Identifier(String),
+}
+
+impl fmt::Display for ArraySize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArraySize::Number(n) => write!(f, "{}", n),
+            ArraySize::Identifier(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<usize> for ArraySize {
+    fn from(n: usize) -> Self {
+        ArraySize::Number(n)
+    }
 }
 
 impl fmt::Display for UnresolvedType {
@@ -20,15 +44,25 @@ impl fmt::Display for UnresolvedType {
         match self {
             UnresolvedType::FieldElement => write!(f, "field"),
             UnresolvedType::Boolean => write!(f, "bool"),
+            UnresolvedType::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
             UnresolvedType::Array(ref ty, ref size) => write!(f, "{}[{}]", ty, size),
             UnresolvedType::User(i) => write!(f, "{}", i),
+            UnresolvedType::Tuple(elements) => write!(
+                f,
+                "({})",
+                elements
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
 
 impl UnresolvedType {
-    pub fn array(ty: UnresolvedTypeNode, size: usize) -> Self {
-        UnresolvedType::Array(box ty, size)
+    pub fn array<S: Into<ArraySize>>(ty: UnresolvedTypeNode, size: S) -> Self {
+        UnresolvedType::Array(box ty, size.into())
     }
 }
 
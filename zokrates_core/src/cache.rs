@@ -0,0 +1,214 @@
+//! Module containing an on-disk cache of checked modules, so that modules which did not change are not parsed and checked again
+//!
+//! @file cache.rs
+//! @date 2020
+
+use absy::types::UserTypeId;
+use absy::ModuleId;
+use semantics::TypeMap;
+use serde::de::DeserializeOwned;
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use typed_absy::types::{FunctionKey, Type};
+use typed_absy::{TypedFunctionSymbol, TypedModule, TypedModules, TypedProgram};
+use typed_arena::Arena;
+use zokrates_field::field::Field;
+
+/// A directory in which checked modules are stored, keyed by the hash of their location and content
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    path: PathBuf,
+}
+
+impl ModuleCache {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        ModuleCache { path: path.into() }
+    }
+
+    fn entry(&self, hash: &str) -> PathBuf {
+        self.path.join(format!("{}.json", hash))
+    }
+
+    fn get<'ast, T: Field + DeserializeOwned>(
+        &self,
+        hash: &str,
+        arena: &'ast Arena<String>,
+    ) -> Option<CachedModule<'ast, T>> {
+        let content = fs::read_to_string(self.entry(hash)).ok()?;
+        // identifiers in the cached module borrow from its content, so it has to live as long as the program
+        let content = arena.alloc(content);
+        // entries we cannot read, for example because they were written by another version, are ignored
+        serde_json::from_str(content).ok()
+    }
+
+    fn insert<T: Field + Serialize>(&self, hash: &str, module: &CachedModule<T>) -> io::Result<()> {
+        fs::create_dir_all(&self.path)?;
+        let file = File::create(self.entry(hash))?;
+        serde_json::to_writer(BufWriter::new(file), module).map_err(io::Error::from)
+    }
+}
+
+/// A checked module as stored in the cache
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
+pub struct CachedModule<'ast, T: Field> {
+    /// the source, location and key of each module imported when this module was checked
+    pub imports: Vec<(String, ModuleId, String)>,
+    /// the functions of the module
+    functions: Vec<(FunctionKey<'ast>, TypedFunctionSymbol<'ast, T>)>,
+    /// the user-defined types of the module
+    types: HashMap<UserTypeId, Type>,
+}
+
+/// The state of the cache during a compilation
+pub struct CacheState<'ast, T: Field> {
+    /// the cache to read from and write to, if any
+    cache: Option<ModuleCache>,
+    /// the modules found in the cache
+    checked: TypedModules<'ast, T>,
+    /// the user-defined types of the modules found in the cache
+    types: TypeMap,
+    /// the key of each module compiled so far, which changes with its content and the keys of the modules it imports
+    keys: HashMap<ModuleId, String>,
+    /// the modules which were not found in the cache, along with their content hash and imports
+    misses: Vec<(ModuleId, String, Vec<(String, ModuleId)>)>,
+}
+
+impl<'ast, T: Field + Serialize + DeserializeOwned> CacheState<'ast, T> {
+    pub fn new(cache: Option<ModuleCache>) -> Self {
+        CacheState {
+            cache,
+            checked: HashMap::new(),
+            types: HashMap::new(),
+            keys: HashMap::new(),
+            misses: vec![],
+        }
+    }
+
+    /// Hash the location and the content of a module
+    pub fn hash(location: &str, source: &str) -> String {
+        // entries written by another version of the compiler or for another field are not compatible
+        sha256(&[env!("CARGO_PKG_VERSION"), T::name(), location, source])
+    }
+
+    /// Take the modules found in the cache and their user-defined types, to check the rest of the program against them
+    pub fn take_checked(&mut self) -> (TypedModules<'ast, T>, TypeMap) {
+        (
+            std::mem::replace(&mut self.checked, HashMap::new()),
+            std::mem::replace(&mut self.types, HashMap::new()),
+        )
+    }
+
+    /// Get the module with hash `hash` from the cache, if any. It is only valid if the keys of its imports did not change.
+    pub fn get(&self, hash: &str, arena: &'ast Arena<String>) -> Option<CachedModule<'ast, T>> {
+        self.cache.as_ref().and_then(|cache| cache.get(hash, arena))
+    }
+
+    /// The key of a module compiled so far
+    pub fn key(&self, location: &str) -> Option<&String> {
+        self.keys.get(location)
+    }
+
+    /// Whether a module was already compiled or found in the cache
+    pub fn contains(&self, location: &str) -> bool {
+        self.keys.contains_key(location)
+    }
+
+    fn set_key(&mut self, location: ModuleId, hash: &str, imports: Vec<String>) {
+        let parts: Vec<_> = std::iter::once(hash)
+            .chain(imports.iter().map(|key| key.as_str()))
+            .collect();
+        self.keys.insert(location, sha256(&parts));
+    }
+
+    /// Use a module found in the cache, whose imports were checked to be up to date
+    pub fn hit(&mut self, location: ModuleId, hash: String, module: CachedModule<'ast, T>) {
+        self.set_key(
+            location.clone(),
+            &hash,
+            module
+                .imports
+                .iter()
+                .map(|(_, _, key)| key.clone())
+                .collect(),
+        );
+        self.checked.insert(
+            location.clone(),
+            TypedModule {
+                functions: module.functions.into_iter().collect(),
+            },
+        );
+        self.types.insert(location, module.types);
+    }
+
+    /// Register a module which was not found in the cache, once its imports were compiled
+    pub fn miss(&mut self, location: ModuleId, hash: String, imports: Vec<(String, ModuleId)>) {
+        // imported modules are compiled first, as import cycles are rejected
+        let keys: Vec<_> = imports
+            .iter()
+            .map(|(_, location)| self.key(location).unwrap().clone())
+            .collect();
+        self.set_key(location.clone(), &hash, keys);
+        if self.cache.is_some() {
+            self.misses.push((location, hash, imports));
+        }
+    }
+
+    /// Write the modules which were not found in the cache, now that they were checked, apart from the ones in
+    /// `uncached_modules`.
+    pub fn store(
+        &self,
+        program: &TypedProgram<'ast, T>,
+        types: &TypeMap,
+        uncached_modules: &HashSet<ModuleId>,
+    ) -> io::Result<()> {
+        let cache = match self.cache {
+            Some(ref cache) => cache,
+            None => return Ok(()),
+        };
+
+        for (location, hash, imports) in self
+            .misses
+            .iter()
+            .filter(|(location, ..)| !uncached_modules.contains(location))
+        {
+            let module = CachedModule {
+                imports: imports
+                    .iter()
+                    .map(|(source, location)| {
+                        (
+                            source.clone(),
+                            location.clone(),
+                            self.key(location).unwrap().clone(),
+                        )
+                    })
+                    .collect(),
+                functions: program.modules[location]
+                    .functions
+                    .iter()
+                    .map(|(key, symbol)| (key.clone(), symbol.clone()))
+                    .collect(),
+                types: types.get(location).cloned().unwrap_or_default(),
+            };
+
+            cache.insert(hash, &module)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Hash `parts` with SHA-256, each prefixed with its length so that different parts cannot be confused
+fn sha256(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.input(&(part.len() as u64).to_be_bytes());
+        hasher.input_str(part);
+    }
+    hasher.result_str()
+}
@@ -4,12 +4,16 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 use absy::{Module, ModuleId, Program};
+use cache::{CacheState, CachedModule, ModuleCache};
 use flatten::Flattener;
-use imports::{self, Importer};
+use imports::{self, ImportStack, Importer};
 use ir;
 use optimizer::Optimize;
+use resolve::Resolver;
 use semantics::{self, Checker};
-use static_analysis::Analyse;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use static_analysis::{self, Analyse, ShiftChecker};
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
@@ -47,6 +51,7 @@ pub enum CompileErrorInner {
     ImportError(imports::Error),
     SemanticError(semantics::Error),
     ReadError(io::Error),
+    AnalysisError(static_analysis::Error),
 }
 
 impl CompileErrorInner {
@@ -112,6 +117,12 @@ impl From<semantics::Error> for CompileErrorInner {
     }
 }
 
+impl From<static_analysis::Error> for CompileErrorInner {
+    fn from(error: static_analysis::Error) -> Self {
+        CompileErrorInner::AnalysisError(error)
+    }
+}
+
 impl fmt::Display for CompileErrorInner {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let res = match *self {
@@ -119,17 +130,30 @@ impl fmt::Display for CompileErrorInner {
             CompileErrorInner::SemanticError(ref e) => format!("{}", e),
             CompileErrorInner::ReadError(ref e) => format!("{}", e),
             CompileErrorInner::ImportError(ref e) => format!("{}", e),
+            CompileErrorInner::AnalysisError(ref e) => format!("{}", e),
         };
         write!(f, "{}", res)
     }
 }
 
-pub type Resolve<S, E> = fn(Option<String>, &str) -> Result<(S, String, &str), E>;
+pub fn compile<T: Field + Serialize + DeserializeOwned, R: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolver: Option<&dyn Resolver<E>>,
+) -> Result<ir::Prog<T>, CompileErrors> {
+    compile_with_cache(reader, location, resolver, None)
+}
 
-pub fn compile<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
+/// Compile a program, reusing the checked modules found in `cache` and storing the ones which had to be checked
+pub fn compile_with_cache<
+    T: Field + Serialize + DeserializeOwned,
+    R: BufRead,
+    E: Into<imports::Error>,
+>(
     reader: &mut R,
     location: Option<String>,
-    resolve_option: Option<Resolve<S, E>>,
+    resolver: Option<&dyn Resolver<E>>,
+    cache: Option<ModuleCache>,
 ) -> Result<ir::Prog<T>, CompileErrors> {
     let arena = Arena::new();
 
@@ -138,10 +162,33 @@ pub fn compile<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
 
     let source = arena.alloc(source);
 
-    let compiled = compile_program(source, location.clone(), resolve_option, &arena)?;
+    let mut cache = CacheState::new(cache);
+
+    let compiled = compile_program(source, location.clone(), resolver, &mut cache, &arena)?;
+
+    let (checked_modules, checked_types) = cache.take_checked();
 
     // check semantics
-    let typed_ast = Checker::check(compiled).map_err(|errors| {
+    let (typed_ast, types, uncached_modules) =
+        Checker::check_with_checked_modules(compiled, checked_modules, checked_types).map_err(
+            |errors| {
+                CompileErrors(
+                    errors
+                        .into_iter()
+                        .map(|e| CompileErrorInner::from(e).with_context(&location))
+                        .collect(),
+                )
+            },
+        )?;
+
+    // store the modules we checked. The cache is only an optimisation, so failing to write to it is not an error
+    cache.store(&typed_ast, &types, &uncached_modules).ok();
+
+    // analyse (unroll and constant propagation)
+    let typed_ast = typed_ast.analyse();
+
+    // check shift amounts, which are only constant once loops are unrolled
+    let typed_ast = ShiftChecker::check(typed_ast).map_err(|errors| {
         CompileErrors(
             errors
                 .into_iter()
@@ -150,9 +197,6 @@ pub fn compile<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
         )
     })?;
 
-    // analyse (unroll and constant propagation)
-    let typed_ast = typed_ast.analyse();
-
     // flatten input program
     let program_flattened = Flattener::flatten(typed_ast);
 
@@ -168,10 +212,20 @@ pub fn compile<T: Field, R: BufRead, S: BufRead, E: Into<imports::Error>>(
     Ok(optimized_ir_prog)
 }
 
-pub fn compile_program<'ast, T: Field, S: BufRead, E: Into<imports::Error>>(
+/// Compile a program and attribute its constraints and directives to the source they come from
+pub fn profile<T: Field + Serialize + DeserializeOwned, R: BufRead, E: Into<imports::Error>>(
+    reader: &mut R,
+    location: Option<String>,
+    resolver: Option<&dyn Resolver<E>>,
+) -> Result<ir::Profile, CompileErrors> {
+    compile::<T, R, E>(reader, location, resolver).map(|program| program.profile())
+}
+
+pub fn compile_program<'ast, T: Field + Serialize + DeserializeOwned, E: Into<imports::Error>>(
     source: &'ast str,
     location: Option<String>,
-    resolve_option: Option<Resolve<S, E>>,
+    resolver: Option<&dyn Resolver<E>>,
+    cache: &mut CacheState<'ast, T>,
     arena: &'ast Arena<String>,
 ) -> Result<Program<'ast, T>, CompileErrors> {
     let mut modules = HashMap::new();
@@ -179,14 +233,19 @@ pub fn compile_program<'ast, T: Field, S: BufRead, E: Into<imports::Error>>(
     let main = compile_module(
         &source,
         location.clone(),
-        resolve_option,
+        resolver,
         &mut modules,
+        cache,
+        &mut ImportStack::default(),
         &arena,
     )?;
 
     let location = location.unwrap_or("???".to_string());
 
-    modules.insert(location.clone(), main);
+    // the main module is not in `modules` if it was found in the cache
+    if let Some(main) = main {
+        modules.insert(location.clone(), main);
+    }
 
     Ok(Program {
         main: location,
@@ -194,30 +253,164 @@ pub fn compile_program<'ast, T: Field, S: BufRead, E: Into<imports::Error>>(
     })
 }
 
-pub fn compile_module<'ast, T: Field, S: BufRead, E: Into<imports::Error>>(
+/// Compile a module and the modules it imports, unless it can be found checked in the cache
+///
+/// Returns the module if it had to be compiled, and `None` if it was found in the cache
+pub fn compile_module<'ast, T: Field + Serialize + DeserializeOwned, E: Into<imports::Error>>(
     source: &'ast str,
     location: Option<String>,
-    resolve_option: Option<Resolve<S, E>>,
+    resolver: Option<&dyn Resolver<E>>,
     modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+    cache: &mut CacheState<'ast, T>,
+    stack: &mut ImportStack,
     arena: &'ast Arena<String>,
-) -> Result<Module<'ast, T>, CompileErrors> {
+) -> Result<Option<Module<'ast, T>>, CompileErrors> {
+    let module_id = location.clone().unwrap_or("???".to_string());
+
+    // keep track of the modules being compiled to detect import cycles
+    stack.push(module_id.clone());
+
+    let res = compile_module_inner(
+        source, module_id, location, resolver, modules, cache, stack, arena,
+    );
+
+    stack.pop();
+
+    res
+}
+
+fn compile_module_inner<'ast, T: Field + Serialize + DeserializeOwned, E: Into<imports::Error>>(
+    source: &'ast str,
+    module_id: ModuleId,
+    location: Option<String>,
+    resolver: Option<&dyn Resolver<E>>,
+    modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+    cache: &mut CacheState<'ast, T>,
+    stack: &mut ImportStack,
+    arena: &'ast Arena<String>,
+) -> Result<Option<Module<'ast, T>>, CompileErrors> {
+    let hash = CacheState::<T>::hash(&module_id, source);
+
+    if let Some(cached) = cache.get(&hash, arena) {
+        if is_up_to_date(
+            &cached,
+            location.clone(),
+            resolver,
+            modules,
+            cache,
+            stack,
+            arena,
+        )? {
+            cache.hit(module_id, hash, cached);
+            return Ok(None);
+        }
+    }
+
     let ast = pest::generate_ast(&source)
         .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).with_context(&location)))?;
     let module_without_imports: Module<T> = Module::from(ast);
 
-    Importer::new().apply_imports(
+    let (module, imports) = Importer::new().apply_imports(
         module_without_imports,
         location.clone(),
-        resolve_option,
+        resolver,
+        modules,
+        cache,
+        stack,
+        &arena,
+    )?;
+
+    cache.miss(module_id, hash, imports);
+
+    Ok(Some(module))
+}
+
+/// Check that the modules imported by a cached module did not change since it was checked, compiling them if necessary
+fn is_up_to_date<'ast, T: Field + Serialize + DeserializeOwned, E: Into<imports::Error>>(
+    cached: &CachedModule<'ast, T>,
+    location: Option<String>,
+    resolver: Option<&dyn Resolver<E>>,
+    modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+    cache: &mut CacheState<'ast, T>,
+    stack: &mut ImportStack,
+    arena: &'ast Arena<String>,
+) -> Result<bool, CompileErrors> {
+    for (source, import_location, key) in &cached.imports {
+        // the module now takes part in an import cycle, which is reported when compiling it
+        if stack.contains(import_location) {
+            return Ok(false);
+        }
+
+        let resolved = match resolver {
+            Some(r) => r.resolve(location.clone(), source),
+            None => return Ok(false),
+        };
+
+        match resolved {
+            // if the import still resolves to the same module, compile that module
+            Ok((code, resolved_location)) if resolved_location == *import_location => {
+                load_module(
+                    code,
+                    resolved_location,
+                    resolver,
+                    modules,
+                    cache,
+                    stack,
+                    arena,
+                )
+                .map_err(|e| e.with_context(Some(source.clone())))?;
+            }
+            // otherwise the cached module is stale, and errors are reported when compiling it
+            _ => return Ok(false),
+        }
+
+        if cache.key(import_location) != Some(key) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Compile the module at `location` with source code `source`, unless it was already compiled
+pub fn load_module<'ast, T: Field + Serialize + DeserializeOwned, E: Into<imports::Error>>(
+    source: String,
+    location: ModuleId,
+    resolver: Option<&dyn Resolver<E>>,
+    modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+    cache: &mut CacheState<'ast, T>,
+    stack: &mut ImportStack,
+    arena: &'ast Arena<String>,
+) -> Result<(), CompileErrors> {
+    // modules imported many times are only compiled once
+    if cache.contains(&location) {
+        return Ok(());
+    }
+
+    let source = arena.alloc(source);
+
+    let compiled = compile_module(
+        source,
+        Some(location.clone()),
+        resolver,
         modules,
+        cache,
+        stack,
         &arena,
-    )
+    )?;
+
+    if let Some(compiled) = compiled {
+        modules.insert(location, compiled);
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::io::{BufReader, Empty};
+    use resolve::MemoryResolver;
+    use std::io::BufReader;
     use zokrates_field::field::FieldPrime;
 
     #[test]
@@ -233,7 +426,7 @@ mod test {
         let res: Result<ir::Prog<FieldPrime>, CompileErrors> = compile(
             &mut r,
             Some(String::from("./path/to/file")),
-            None::<Resolve<BufReader<Empty>, io::Error>>,
+            None::<&dyn Resolver<io::Error>>,
         );
 
         assert!(res
@@ -254,8 +447,737 @@ mod test {
         let res: Result<ir::Prog<FieldPrime>, CompileErrors> = compile(
             &mut r,
             Some(String::from("./path/to/file")),
-            None::<Resolve<BufReader<Empty>, io::Error>>,
+            None::<&dyn Resolver<io::Error>>,
         );
         assert!(res.is_ok());
     }
+
+    const MAIN: &str = r#"
+import "./foo" as foo
+import "./bar/../foo" as bar
+def main() -> (field):
+    return foo() + bar()
+"#;
+
+    // both imports of `MAIN` resolve to the same module
+    fn resolver() -> MemoryResolver {
+        MemoryResolver::new().with_module("foo.zok", "def main() -> (field):\n    return 1")
+    }
+
+    fn resolver_changed() -> MemoryResolver {
+        MemoryResolver::new().with_module("foo.zok", "def main() -> (field):\n    return 2")
+    }
+
+    // the ids of the modules which are compiled, as opposed to found in the cache
+    fn compiled_modules(cache: Option<ModuleCache>, resolver: MemoryResolver) -> Vec<ModuleId> {
+        let arena = Arena::new();
+        let mut cache = CacheState::<FieldPrime>::new(cache);
+        let program = compile_program(
+            MAIN,
+            Some(String::from("main.zok")),
+            Some(&resolver),
+            &mut cache,
+            &arena,
+        )
+        .unwrap();
+        let mut modules: Vec<_> = program.modules.keys().cloned().collect();
+        modules.sort();
+        modules
+    }
+
+    #[test]
+    fn import_module_once() {
+        assert_eq!(
+            compiled_modules(None, resolver()),
+            vec![String::from("foo.zok"), String::from("main.zok")]
+        );
+
+        let program: ir::Prog<FieldPrime> = compile(
+            &mut MAIN.as_bytes(),
+            Some(String::from("main.zok")),
+            Some(&resolver()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            program.execute(&vec![]).unwrap().return_values(),
+            vec![FieldPrime::from(2)]
+        );
+    }
+
+    #[test]
+    fn reuse_cached_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ModuleCache::new(dir.path());
+
+        let compile = |resolver: MemoryResolver| {
+            compile_with_cache::<FieldPrime, _, _>(
+                &mut MAIN.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver),
+                Some(cache.clone()),
+            )
+            .unwrap()
+        };
+
+        let program = compile(resolver());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        // nothing changed, so all modules are found in the cache
+        assert!(compiled_modules(Some(cache.clone()), resolver()).is_empty());
+        assert_eq!(compile(resolver()), program);
+
+        // `foo` changed, so it has to be compiled again along with `main` which imports it
+        assert_eq!(
+            compiled_modules(Some(cache.clone()), resolver_changed()),
+            vec![String::from("foo.zok"), String::from("main.zok")]
+        );
+        assert_eq!(
+            compile(resolver_changed())
+                .execute(&vec![])
+                .unwrap()
+                .return_values(),
+            vec![FieldPrime::from(4)]
+        );
+    }
+
+    mod shifts {
+        use super::*;
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        #[test]
+        fn shift_by_loop_index() {
+            let source = r#"
+def main(u32 a) -> (u32):
+    u32 b = 0x00000000
+    for field i in 0..4 do
+        b = b | (a << i)
+    endfor
+    return b
+"#;
+            let program: ir::Prog<FieldPrime> = compile(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap();
+
+            assert_eq!(
+                program
+                    .execute(&vec![FieldPrime::from(1)])
+                    .unwrap()
+                    .return_values(),
+                vec![FieldPrime::from(15)]
+            );
+        }
+
+        #[test]
+        fn shift_by_variable() {
+            let error = compile_error(
+                r#"
+def main(u32 a, field b) -> (u32):
+    return a << b
+"#,
+            );
+            assert!(error.contains("Shift amounts should be constants, found b"));
+        }
+    }
+
+    mod generics {
+        use super::*;
+
+        const LIB: &str = r#"
+def sum<N>(field[N] a) -> (field):
+    field res = 0
+    for field i in 0..N do
+        res = res + a[i]
+    endfor
+    return res
+
+def reverse<N>(field[N] a) -> (field[N]):
+    field[N] res = a
+    for field i in 0..N do
+        res[i] = a[N - 1 - i]
+    endfor
+    return res
+"#;
+
+        const MAIN: &str = r#"
+from "./lib" import sum as sum
+from "./lib" import reverse as reverse
+
+def total<N, M>(field[N] a, field[M] b) -> (field):
+    return sum(a) + sum(b)
+
+def main(field[3] a, field[2] b) -> (field, field, field[3]):
+    return sum(a), total(a, b), reverse(a)
+"#;
+
+        fn resolver() -> MemoryResolver {
+            MemoryResolver::new().with_module("lib.zok", LIB)
+        }
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver()),
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        #[test]
+        fn instantiate_per_size() {
+            let program: ir::Prog<FieldPrime> = compile(
+                &mut MAIN.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver()),
+            )
+            .unwrap();
+
+            let inputs: Vec<_> = (1..6).map(FieldPrime::from).collect();
+            assert_eq!(
+                program.execute(&inputs).unwrap().return_values(),
+                vec![6, 15, 3, 2, 1]
+                    .into_iter()
+                    .map(FieldPrime::from)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn modules_with_generics_are_not_cached() {
+            let dir = tempfile::tempdir().unwrap();
+
+            compile_with_cache::<FieldPrime, _, _>(
+                &mut MAIN.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver()),
+                Some(ModuleCache::new(dir.path())),
+            )
+            .unwrap();
+
+            assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        }
+
+        #[test]
+        fn size_mismatch() {
+            let error = compile_error(
+                r#"
+def dot<N>(field[N] a, field[N] b) -> (field):
+    return 0
+
+def main(field[3] a, field[2] b) -> (field):
+    return dot(a, b)
+"#,
+            );
+            assert!(error.contains(
+                "Function definition for function dot with signature (field[3], field[2]) -> (_) not found"
+            ));
+        }
+
+        #[test]
+        fn generic_main() {
+            let error = compile_error(
+                r#"
+def main<N>(field[N] a) -> (field):
+    return 1
+"#,
+            );
+            assert!(error.contains("Function main cannot be generic"));
+        }
+
+        #[test]
+        fn undefined_generic() {
+            let error = compile_error(
+                r#"
+def first<N>(field[N] a) -> (field[M]):
+    return a
+
+def main() -> (field):
+    return 1
+"#,
+            );
+            assert!(error.contains("Undefined generic parameter M"));
+        }
+
+        #[test]
+        fn generic_not_inferred() {
+            let error = compile_error(
+                r#"
+def zeros<N>() -> (field[N]):
+    return [0]
+
+def main() -> (field):
+    return 1
+"#,
+            );
+            assert!(error.contains("Generic parameter N of zeros is not the size of an argument"));
+        }
+    }
+
+    mod constants {
+        use super::*;
+
+        const LIB: &str = r#"
+const field DEPTH = 3
+const field[DEPTH] WEIGHTS = [1, 2, DEPTH]
+
+def weigh(field[DEPTH] a) -> (field[DEPTH]):
+    field[DEPTH] res = a
+    for field i in 0..DEPTH do
+        res[i] = a[i] * WEIGHTS[i]
+    endfor
+    return res
+"#;
+
+        const MAIN: &str = r#"
+from "./lib" import DEPTH
+from "./lib" import weigh
+
+const field TWICE = 2 * DEPTH
+
+struct Path {
+    field[DEPTH] nodes
+}
+
+def main(Path p) -> (field, field[DEPTH]):
+    field res = 0
+    for field i in 0..TWICE - DEPTH do
+        res = res + p.nodes[i]
+    endfor
+    return res + TWICE, weigh(p.nodes)
+"#;
+
+        fn resolver() -> MemoryResolver {
+            MemoryResolver::new().with_module("lib.zok", LIB)
+        }
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver()),
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        #[test]
+        fn evaluate_at_compile_time() {
+            let dir = tempfile::tempdir().unwrap();
+
+            let program: ir::Prog<FieldPrime> = compile_with_cache(
+                &mut MAIN.as_bytes(),
+                Some(String::from("main.zok")),
+                Some(&resolver()),
+                Some(ModuleCache::new(dir.path())),
+            )
+            .unwrap();
+
+            let inputs: Vec<_> = (1..4).map(FieldPrime::from).collect();
+            assert_eq!(
+                program.execute(&inputs).unwrap().return_values(),
+                vec![12, 1, 4, 9]
+                    .into_iter()
+                    .map(FieldPrime::from)
+                    .collect::<Vec<_>>()
+            );
+
+            // constants are only known while checking, so modules with constants are not cached
+            assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        }
+
+        #[test]
+        fn not_constant() {
+            let error = compile_error(
+                r#"
+from "./lib" import weigh
+
+const field[3] A = weigh([1, 2, 3])
+
+def main() -> (field):
+    return 1
+"#,
+            );
+            assert!(
+                error.contains("Expression weigh([1, 2, 3]) cannot be evaluated at compile time")
+            );
+        }
+
+        #[test]
+        fn type_mismatch() {
+            let error = compile_error(
+                r#"
+const field A = true
+
+def main() -> (field):
+    return 1
+"#,
+            );
+            assert!(error.contains(
+                "Expression true of type bool cannot be assigned to constant of type field"
+            ));
+        }
+
+        #[test]
+        fn invalid_array_size() {
+            let error = compile_error(
+                r#"
+const bool B = true
+
+def main(field[B] a) -> (field):
+    return 1
+"#,
+            );
+            assert!(error.contains("Constant B with value true is not a valid array size"));
+        }
+    }
+
+    mod tuples {
+        use super::*;
+
+        const MAIN: &str = r#"
+def swap((field, bool) t) -> ((bool, field)):
+    return (t.1, t.0)
+
+def square(field a) -> (field, field):
+    return a, a * a
+
+def main((field, field) ab) -> (field, (field, bool)):
+    (field, bool)[2] pairs = [(ab.0, true), (ab.1, false)]
+    bool c, field d = swap(pairs[1])
+    field e, _ = square(ab.0)
+    (field, field) p = (d, e)
+    return p.0 + p.1, (d * ab.0, c)
+"#;
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        #[test]
+        fn pass_store_and_destructure() {
+            let program: ir::Prog<FieldPrime> = compile(
+                &mut MAIN.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap();
+
+            let inputs: Vec<_> = (2..4).map(FieldPrime::from).collect();
+            assert_eq!(
+                program.execute(&inputs).unwrap().return_values(),
+                vec![5, 6, 0]
+                    .into_iter()
+                    .map(FieldPrime::from)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn element_out_of_range() {
+            let error = compile_error(
+                r#"
+def main((field, bool) t) -> (field):
+    return t.2
+"#,
+            );
+            assert!(error.contains("(field, bool) doesn't have element 2"));
+        }
+
+        #[test]
+        fn arity_mismatch() {
+            let error = compile_error(
+                r#"
+def main() -> (field):
+    field a, field b, _ = (1, 2)
+    return a
+"#,
+            );
+            assert!(error.contains(
+                "Expected a tuple of 3 elements, found (1, 2) of type (field, field)"
+            ));
+        }
+
+        #[test]
+        fn destructure_non_tuple() {
+            let error = compile_error(
+                r#"
+def main() -> (field):
+    field a, _ = [1, 2]
+    return a
+"#,
+            );
+            assert!(error.contains("Expected a tuple to destructure, found"));
+        }
+    }
+
+    mod if_else {
+        use super::*;
+
+        const MAIN: &str = r#"
+def main(field a, field b) -> (field, field[2]):
+    field c = 0
+    field[2] d = [0, 0]
+    if a == b then
+        c = a + b
+        d[0] = 1
+    else
+        field e = a * b
+        c = e
+        if a == 0 then
+            d[1] = 1
+        fi
+    fi
+    return c, d
+"#;
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        fn run(source: &str, inputs: Vec<u32>) -> Result<Vec<u32>, String> {
+            let program: ir::Prog<FieldPrime> = compile(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap();
+
+            let inputs: Vec<_> = inputs.into_iter().map(FieldPrime::from).collect();
+            program
+                .execute(&inputs)
+                .map(|witness| {
+                    witness
+                        .return_values()
+                        .into_iter()
+                        .map(|v| v.to_dec_string().parse().unwrap())
+                        .collect()
+                })
+                .map_err(|e| e.to_string())
+        }
+
+        #[test]
+        fn consequence() {
+            assert_eq!(run(MAIN, vec![2, 2]), Ok(vec![4, 1, 0]));
+        }
+
+        #[test]
+        fn alternative() {
+            assert_eq!(run(MAIN, vec![2, 3]), Ok(vec![6, 0, 0]));
+            assert_eq!(run(MAIN, vec![0, 3]), Ok(vec![0, 0, 1]));
+        }
+
+        #[test]
+        fn assertion_only_in_taken_branch() {
+            let source = r#"
+def main(field a) -> (field):
+    if a == 0 then
+        assert(a == 0)
+    else
+        assert(a == 5)
+    fi
+    return a
+"#;
+            assert_eq!(run(source, vec![0]), Ok(vec![0]));
+            assert_eq!(run(source, vec![5]), Ok(vec![5]));
+            assert!(run(source, vec![3]).is_err());
+        }
+
+        #[test]
+        fn assertion_in_function_called_from_untaken_branch() {
+            let source = r#"
+def check(field a) -> (field):
+    assert(a == 5)
+    return a
+
+def main(field a) -> (field):
+    field b = 0
+    if a == 5 then
+        b = check(a)
+    fi
+    return b
+"#;
+            assert_eq!(run(source, vec![5]), Ok(vec![5]));
+            assert_eq!(run(source, vec![3]), Ok(vec![0]));
+        }
+
+        #[test]
+        fn division_in_untaken_branch() {
+            let source = r#"
+def inverse(field a) -> (field):
+    return 1 / a
+
+def main(field a) -> (field):
+    field b = 0
+    if a != 0 then
+        b = inverse(a) * a
+    fi
+    return b
+"#;
+            assert_eq!(run(source, vec![2]), Ok(vec![1]));
+            assert_eq!(run(source, vec![0]), Ok(vec![0]));
+        }
+
+        #[test]
+        fn call_in_condition() {
+            let source = r#"
+def double(field a) -> (field):
+    return 2 * a
+
+def main(field a) -> (field):
+    field b = 0
+    if double(a) == 4 then
+        b = 1
+    else
+        b = 2
+    fi
+    return b
+"#;
+            assert_eq!(run(source, vec![2]), Ok(vec![1]));
+            assert_eq!(run(source, vec![3]), Ok(vec![2]));
+        }
+
+        #[test]
+        fn return_in_branch() {
+            let error = compile_error(
+                r#"
+def main(field a) -> (field):
+    if a == 0 then
+        return 1
+    fi
+    return a
+"#,
+            );
+            assert!(error.contains("Return statements are not allowed in `if/else` blocks"));
+        }
+
+        #[test]
+        fn local_not_visible_after_branch() {
+            let error = compile_error(
+                r#"
+def main(field a) -> (field):
+    if a == 0 then
+        field b = 1
+    fi
+    return b
+"#,
+            );
+            assert!(error.contains("Identifier \"b\" is undefined"));
+        }
+    }
+
+    mod bounded_for {
+        use super::*;
+
+        const MAIN: &str = r#"
+def main(field[4] a, field n) -> (field):
+    field sum = 0
+    for field i in 0..n max 4 do
+        sum = sum + a[i]
+    endfor
+    return sum
+"#;
+
+        fn compile_error(source: &str) -> String {
+            compile::<FieldPrime, _, _>(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap_err()
+            .to_string()
+        }
+
+        fn run(n: u32) -> Result<Vec<FieldPrime>, ir::Error> {
+            execute(MAIN, vec![1, 2, 3, 4, n])
+        }
+
+        fn execute(source: &str, inputs: Vec<u32>) -> Result<Vec<FieldPrime>, ir::Error> {
+            let program: ir::Prog<FieldPrime> = compile(
+                &mut source.as_bytes(),
+                Some(String::from("main.zok")),
+                None::<&dyn Resolver<io::Error>>,
+            )
+            .unwrap();
+
+            let inputs: Vec<_> = inputs.into_iter().map(FieldPrime::from).collect();
+            program
+                .execute(&inputs)
+                .map(|witness| witness.return_values())
+        }
+
+        #[test]
+        fn below_maximum() {
+            assert_eq!(run(0).unwrap(), vec![FieldPrime::from(0)]);
+            assert_eq!(run(3).unwrap(), vec![FieldPrime::from(6)]);
+        }
+
+        #[test]
+        fn at_maximum() {
+            assert_eq!(run(4).unwrap(), vec![FieldPrime::from(10)]);
+        }
+
+        #[test]
+        fn above_maximum() {
+            assert!(run(5).is_err());
+        }
+
+        #[test]
+        fn assertion_in_function_called_past_bound() {
+            let source = r#"
+def step(field count, field x) -> (field):
+    assert(x == 1)
+    return count + x
+
+def main(field[4] a, field n) -> (field):
+    field count = 0
+    for field i in 0..n max 4 do
+        count = step(count, a[i])
+    endfor
+    return count
+"#;
+            assert_eq!(
+                execute(source, vec![1, 1, 0, 0, 2]).unwrap(),
+                vec![FieldPrime::from(2)]
+            );
+            assert!(execute(source, vec![1, 1, 0, 0, 3]).is_err());
+        }
+
+        #[test]
+        fn variable_maximum() {
+            let error = compile_error(
+                r#"
+def main(field n) -> (field):
+    for field i in 0..n max n do
+    endfor
+    return n
+"#,
+            );
+            assert!(error.contains("For loop bounds should be constants, found n"));
+        }
+    }
 }
@@ -11,7 +11,7 @@ use zokrates_field::field::Field;
 
 /// A low level function that contains non-deterministic introduction of variables. It is carried as is until
 /// the flattening step when it can be inlined.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FlatEmbed {
     Sha256Round,
     Unpack,
@@ -83,7 +83,7 @@ impl<T: Field> From<BellmanConstraint<T::BellmanEngine>> for FlatStatement<T> {
         let rhs_b = flat_expression_from_vec(c.b);
         let lhs = flat_expression_from_vec(c.c);
 
-        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b))
+        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b), None, None)
     }
 }
 
@@ -146,6 +146,8 @@ pub fn sha256_round<T: Field>() -> FlatFunction<T> {
     let one_binding_statement = FlatStatement::Condition(
         FlatVariable::new(0).into(),
         FlatExpression::Number(T::from(1)),
+        None,
+        None,
     );
 
     let input_binding_statements =
@@ -154,6 +156,8 @@ pub fn sha256_round<T: Field>() -> FlatFunction<T> {
         FlatStatement::Condition(
             FlatVariable::new(cs_index).into(),
             FlatVariable::new(argument_index).into(),
+            None,
+            None,
         )
     });
 
@@ -166,19 +170,25 @@ pub fn sha256_round<T: Field>() -> FlatFunction<T> {
         .collect();
 
     // insert a directive to set the witness based on the bellman gadget and  inputs
-    let directive_statement = FlatStatement::Directive(DirectiveStatement {
-        outputs: cs_indices.map(|i| FlatVariable::new(i)).collect(),
-        inputs: input_argument_indices
-            .chain(current_hash_argument_indices)
-            .map(|i| FlatVariable::new(i).into())
-            .collect(),
-        helper: Helper::Rust(RustHelper::Sha256Round),
-    });
+    let directive_statement = FlatStatement::Directive(
+        DirectiveStatement {
+            outputs: cs_indices.map(|i| FlatVariable::new(i)).collect(),
+            inputs: input_argument_indices
+                .chain(current_hash_argument_indices)
+                .map(|i| FlatVariable::new(i).into())
+                .collect(),
+            helper: Helper::Rust(RustHelper::Sha256Round),
+        },
+        None,
+    );
 
     // insert a statement to return the subset of the witness
-    let return_statement = FlatStatement::Return(FlatExpressionList {
-        expressions: outputs,
-    });
+    let return_statement = FlatStatement::Return(
+        FlatExpressionList {
+            expressions: outputs,
+        },
+        None,
+    );
 
     let statements = std::iter::once(directive_statement)
         .chain(std::iter::once(one_binding_statement))
@@ -254,6 +264,8 @@ pub fn unpack<T: Field>() -> FlatFunction<T> {
             FlatStatement::Condition(
                 bit.clone(),
                 FlatExpression::Mult(box bit.clone(), box bit.clone()),
+                None,
+                None,
             )
         })
         .collect();
@@ -277,20 +289,28 @@ pub fn unpack<T: Field>() -> FlatFunction<T> {
             box FlatExpression::Identifier(FlatVariable::new(0)),
             box FlatExpression::Number(T::from(1)),
         ),
+        None,
+        None,
     ));
 
     statements.insert(
         0,
-        FlatStatement::Directive(DirectiveStatement {
-            inputs: directive_inputs,
-            outputs: directive_outputs,
-            helper: helper,
-        }),
+        FlatStatement::Directive(
+            DirectiveStatement {
+                inputs: directive_inputs,
+                outputs: directive_outputs,
+                helper: helper,
+            },
+            None,
+        ),
     );
 
-    statements.push(FlatStatement::Return(FlatExpressionList {
-        expressions: outputs,
-    }));
+    statements.push(FlatStatement::Return(
+        FlatExpressionList {
+            expressions: outputs,
+        },
+        None,
+    ));
 
     FlatFunction {
         arguments,
@@ -322,21 +342,27 @@ mod tests {
             ); // 128 bit checks, 1 directive, 1 sum check, 1 return
             assert_eq!(
                 unpack.statements[0],
-                FlatStatement::Directive(DirectiveStatement::new(
-                    (0..FieldPrime::get_required_bits())
-                        .map(|i| FlatVariable::new(i + 1))
-                        .collect(),
-                    Helper::bits(),
-                    vec![FlatVariable::new(0)]
-                ))
+                FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        (0..FieldPrime::get_required_bits())
+                            .map(|i| FlatVariable::new(i + 1))
+                            .collect(),
+                        Helper::bits(),
+                        vec![FlatVariable::new(0)]
+                    ),
+                    None,
+                )
             );
             assert_eq!(
                 *unpack.statements.last().unwrap(),
-                FlatStatement::Return(FlatExpressionList {
-                    expressions: (0..FieldPrime::get_required_bits())
-                        .map(|i| FlatExpression::Identifier(FlatVariable::new(i + 1)))
-                        .collect()
-                })
+                FlatStatement::Return(
+                    FlatExpressionList {
+                        expressions: (0..FieldPrime::get_required_bits())
+                            .map(|i| FlatExpression::Identifier(FlatVariable::new(i + 1)))
+                            .collect()
+                    },
+                    None,
+                )
             );
         }
     }
@@ -369,7 +395,7 @@ mod tests {
                     .statements
                     .iter()
                     .filter_map(|s| match s {
-                        FlatStatement::Return(v) => Some(v),
+                        FlatStatement::Return(v, _) => Some(v),
                         _ => None,
                     })
                     .next()
@@ -384,7 +410,7 @@ mod tests {
                 .statements
                 .iter()
                 .filter_map(|s| match s {
-                    FlatStatement::Directive(d) => Some(d.clone()),
+                    FlatStatement::Directive(d, _) => Some(d.clone()),
                     _ => None,
                 })
                 .next()
@@ -402,7 +428,9 @@ mod tests {
                 compiled.statements[1],
                 FlatStatement::Condition(
                     FlatVariable::new(0).into(),
-                    FlatExpression::Number(FieldPrime::from(1))
+                    FlatExpression::Number(FieldPrime::from(1)),
+                    None,
+                    None,
                 )
             );
 
@@ -411,7 +439,9 @@ mod tests {
                 compiled.statements[2],
                 FlatStatement::Condition(
                     FlatVariable::new(1).into(),
-                    FlatVariable::new(26936).into()
+                    FlatVariable::new(26936).into(),
+                    None,
+                    None,
                 )
             );
 
@@ -32,6 +32,15 @@ impl FlatVariable {
         (self.id as usize) - 1
     }
 
+    /// The position of the variable among the outputs if it is one, or among `~one` and the other variables
+    pub fn index(&self) -> usize {
+        if self.id < 0 {
+            (-self.id - 1) as usize
+        } else {
+            self.id as usize
+        }
+    }
+
     pub fn try_from_human_readable(s: &str) -> Result<Self, &str> {
         if s == "~one" {
             return Ok(FlatVariable::one());
@@ -13,6 +13,7 @@ pub use self::flat_variable::FlatVariable;
 
 use crate::helpers::DirectiveStatement;
 use crate::typed_absy::types::Signature;
+use crate::typed_absy::{AssertionMetadata, SourceSpan};
 use std::collections::HashMap;
 use std::fmt;
 use zokrates_field::field::Field;
@@ -92,19 +93,24 @@ impl<T: Field> fmt::Debug for FlatFunction<T> {
 
 #[derive(Clone, PartialEq)]
 pub enum FlatStatement<T: Field> {
-    Return(FlatExpressionList<T>),
-    Condition(FlatExpression<T>, FlatExpression<T>),
-    Definition(FlatVariable, FlatExpression<T>),
-    Directive(DirectiveStatement<T>),
+    Return(FlatExpressionList<T>, Option<SourceSpan>),
+    Condition(
+        FlatExpression<T>,
+        FlatExpression<T>,
+        Option<AssertionMetadata>,
+        Option<SourceSpan>,
+    ),
+    Definition(FlatVariable, FlatExpression<T>, Option<SourceSpan>),
+    Directive(DirectiveStatement<T>, Option<SourceSpan>),
 }
 
 impl<T: Field> fmt::Display for FlatStatement<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            FlatStatement::Return(ref expr) => write!(f, "return {}", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
-            FlatStatement::Directive(ref d) => write!(f, "{}", d),
+            FlatStatement::Definition(ref lhs, ref rhs, _) => write!(f, "{} = {}", lhs, rhs),
+            FlatStatement::Return(ref expr, _) => write!(f, "return {}", expr),
+            FlatStatement::Condition(ref lhs, ref rhs, ..) => write!(f, "{} == {}", lhs, rhs),
+            FlatStatement::Directive(ref d, _) => write!(f, "{}", d),
         }
     }
 }
@@ -112,32 +118,47 @@ impl<T: Field> fmt::Display for FlatStatement<T> {
 impl<T: Field> fmt::Debug for FlatStatement<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            FlatStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            FlatStatement::Return(ref expr) => write!(f, "FlatReturn({:?})", expr),
-            FlatStatement::Condition(ref lhs, ref rhs) => {
-                write!(f, "FlatCondition({:?}, {:?})", lhs, rhs)
+            FlatStatement::Definition(ref lhs, ref rhs, _) => write!(f, "{} = {}", lhs, rhs),
+            FlatStatement::Return(ref expr, _) => write!(f, "FlatReturn({:?})", expr),
+            FlatStatement::Condition(ref lhs, ref rhs, ref metadata, _) => {
+                write!(f, "FlatCondition({:?}, {:?}, {:?})", lhs, rhs, metadata)
             }
-            FlatStatement::Directive(ref d) => write!(f, "{:?}", d),
+            FlatStatement::Directive(ref d, _) => write!(f, "{:?}", d),
         }
     }
 }
 
 impl<T: Field> FlatStatement<T> {
+    /// The location of the source this statement was compiled from, if known
+    pub fn span_mut(&mut self) -> &mut Option<SourceSpan> {
+        match *self {
+            FlatStatement::Return(_, ref mut span)
+            | FlatStatement::Condition(_, _, _, ref mut span)
+            | FlatStatement::Definition(_, _, ref mut span)
+            | FlatStatement::Directive(_, ref mut span) => span,
+        }
+    }
+
     pub fn apply_substitution(
         self,
         substitution: &HashMap<FlatVariable, FlatVariable>,
     ) -> FlatStatement<T> {
         match self {
-            FlatStatement::Definition(id, x) => FlatStatement::Definition(
+            FlatStatement::Definition(id, x, span) => FlatStatement::Definition(
                 *id.apply_substitution(substitution),
                 x.apply_substitution(substitution),
+                span,
             ),
-            FlatStatement::Return(x) => FlatStatement::Return(x.apply_substitution(substitution)),
-            FlatStatement::Condition(x, y) => FlatStatement::Condition(
+            FlatStatement::Return(x, span) => {
+                FlatStatement::Return(x.apply_substitution(substitution), span)
+            }
+            FlatStatement::Condition(x, y, metadata, span) => FlatStatement::Condition(
                 x.apply_substitution(substitution),
                 y.apply_substitution(substitution),
+                metadata,
+                span,
             ),
-            FlatStatement::Directive(d) => {
+            FlatStatement::Directive(d, span) => {
                 let outputs = d
                     .outputs
                     .into_iter()
@@ -149,11 +170,14 @@ impl<T: Field> FlatStatement<T> {
                     .map(|i| i.apply_substitution(substitution))
                     .collect();
 
-                FlatStatement::Directive(DirectiveStatement {
-                    outputs,
-                    inputs,
-                    ..d
-                })
+                FlatStatement::Directive(
+                    DirectiveStatement {
+                        outputs,
+                        inputs,
+                        ..d
+                    },
+                    span,
+                )
             }
         }
     }
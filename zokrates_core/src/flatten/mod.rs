@@ -22,6 +22,8 @@ pub struct Flattener<'ast, T: Field> {
     layout: HashMap<Identifier<'ast>, Vec<FlatVariable>>,
     /// Cached `FlatFunction`s to avoid re-flattening them
     flat_cache: HashMap<FunctionKey<'ast>, FlatFunction<T>>,
+    /// Location of the statement being flattened
+    span: Option<SourceSpan>,
 }
 
 // We introduce a trait in order to make it possible to make flattening `e` generic over the type of `e`
@@ -59,6 +61,17 @@ impl<'ast, T: Field> Flatten<'ast, T> for BooleanExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Flatten<'ast, T> for UExpression<'ast, T> {
+    fn flatten(
+        self,
+        flattener: &mut Flattener<'ast, T>,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+    ) -> Vec<FlatExpression<T>> {
+        vec![flattener.flatten_uint_expression(symbols, statements_flattened, self)]
+    }
+}
+
 impl<'ast, T: Field> Flatten<'ast, T> for StructExpression<'ast, T> {
     fn flatten(
         self,
@@ -70,6 +83,17 @@ impl<'ast, T: Field> Flatten<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Flatten<'ast, T> for TupleExpression<'ast, T> {
+    fn flatten(
+        self,
+        flattener: &mut Flattener<'ast, T>,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+    ) -> Vec<FlatExpression<T>> {
+        flattener.flatten_tuple_expression(symbols, statements_flattened, self)
+    }
+}
+
 impl<'ast, T: Field> Flatten<'ast, T> for ArrayExpression<'ast, T> {
     fn flatten(
         self,
@@ -89,6 +113,11 @@ impl<'ast, T: Field> Flatten<'ast, T> for ArrayExpression<'ast, T> {
                 statements_flattened,
                 self,
             ),
+            Type::Uint(..) => flattener.flatten_array_expression::<UExpression<'ast, T>>(
+                symbols,
+                statements_flattened,
+                self,
+            ),
             Type::Array(..) => flattener.flatten_array_expression::<ArrayExpression<'ast, T>>(
                 symbols,
                 statements_flattened,
@@ -99,6 +128,11 @@ impl<'ast, T: Field> Flatten<'ast, T> for ArrayExpression<'ast, T> {
                 statements_flattened,
                 self,
             ),
+            Type::Tuple(..) => flattener.flatten_array_expression::<TupleExpression<'ast, T>>(
+                symbols,
+                statements_flattened,
+                self,
+            ),
         }
     }
 }
@@ -115,6 +149,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             next_var_idx: 0,
             layout: HashMap::new(),
             flat_cache: HashMap::new(),
+            span: None,
         }
     }
 
@@ -146,14 +181,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         let size = consequence.len();
 
         let condition_id = self.use_sym();
-        statements_flattened.push(FlatStatement::Definition(condition_id, condition));
+        statements_flattened.push(FlatStatement::Definition(condition_id, condition, None));
 
         let consequence_ids: Vec<_> = (0..size).map(|_| self.use_sym()).collect();
         statements_flattened.extend(
             consequence
                 .into_iter()
                 .zip(consequence_ids.iter())
-                .map(|(c, c_id)| FlatStatement::Definition(*c_id, c)),
+                .map(|(c, c_id)| FlatStatement::Definition(*c_id, c, None)),
         );
 
         let alternative_ids: Vec<_> = (0..size).map(|_| self.use_sym()).collect();
@@ -161,7 +196,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             alternative
                 .into_iter()
                 .zip(alternative_ids.iter())
-                .map(|(a, a_id)| FlatStatement::Definition(*a_id, a)),
+                .map(|(a, a_id)| FlatStatement::Definition(*a_id, a, None)),
         );
 
         let term0_ids: Vec<_> = (0..size).map(|_| self.use_sym()).collect();
@@ -173,6 +208,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         box condition_id.clone().into(),
                         box FlatExpression::from(*c_id),
                     ),
+                    None,
                 )
             },
         ));
@@ -189,6 +225,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         ),
                         box FlatExpression::from(*a_id),
                     ),
+                    None,
                 )
             },
         ));
@@ -202,6 +239,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         box FlatExpression::from(*t0_id),
                         box FlatExpression::from(t1_id),
                     ),
+                    None,
                 )
             },
         ));
@@ -241,6 +279,11 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 symbols,
                                 statements_flattened,
                             ),
+                            Type::Uint(..) => UExpression::try_from(v).unwrap().flatten(
+                                self,
+                                symbols,
+                                statements_flattened,
+                            ),
                             Type::Array(..) => ArrayExpression::try_from(v).unwrap().flatten(
                                 self,
                                 symbols,
@@ -251,6 +294,11 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 symbols,
                                 statements_flattened,
                             ),
+                            Type::Tuple(..) => TupleExpression::try_from(v).unwrap().flatten(
+                                self,
+                                symbols,
+                                statements_flattened,
+                            ),
                         })
                         .collect()
                 }
@@ -324,6 +372,13 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             BooleanExpression::member(consequence.clone(), member_id.clone()),
                             BooleanExpression::member(alternative.clone(), member_id),
                         ),
+                        Type::Uint(..) => self.flatten_if_else_expression(
+                            symbols,
+                            statements_flattened,
+                            condition.clone(),
+                            UExpression::member(consequence.clone(), member_id.clone()),
+                            UExpression::member(alternative.clone(), member_id),
+                        ),
                         Type::Struct(..) => self.flatten_if_else_expression(
                             symbols,
                             statements_flattened,
@@ -338,8 +393,35 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             ArrayExpression::member(consequence.clone(), member_id.clone()),
                             ArrayExpression::member(alternative.clone(), member_id),
                         ),
+                        Type::Tuple(..) => self.flatten_if_else_expression(
+                            symbols,
+                            statements_flattened,
+                            condition.clone(),
+                            TupleExpression::member(consequence.clone(), member_id.clone()),
+                            TupleExpression::member(alternative.clone(), member_id),
+                        ),
                     }
                 }
+                StructExpressionInner::Element(box t, index) => {
+                    let e =
+                        self.flatten_element_expression(symbols, statements_flattened, t, index);
+
+                    let offset = members
+                        .iter()
+                        .take_while(|(id, _)| *id != member_id)
+                        .map(|(_, ty)| ty.get_primitive_count())
+                        .sum();
+
+                    // we also need the size of this member
+                    let size = members
+                        .iter()
+                        .find(|(id, _)| *id == member_id)
+                        .unwrap()
+                        .1
+                        .get_primitive_count();
+
+                    e[offset..(offset + size)].into()
+                }
                 StructExpressionInner::Member(box s0, m_id) => {
                     let e = self.flatten_member_expression(symbols, statements_flattened, s0, m_id);
 
@@ -365,6 +447,92 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         res
     }
 
+    fn flatten_element_expression(
+        &mut self,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        t: TupleExpression<'ast, T>,
+        index: usize,
+    ) -> Vec<FlatExpression<T>> {
+        let elements = t.ty().clone();
+
+        // the tuple is encoded as a sequence, so we need to identify the offset at which this element starts
+        let offset = elements[..index]
+            .iter()
+            .map(|ty| ty.get_primitive_count())
+            .sum::<usize>();
+
+        // we also need the size of this element
+        let size = elements[index].get_primitive_count();
+
+        let res = match t.into_inner() {
+            TupleExpressionInner::Value(values) => {
+                // If the tuple has an explicit value, we only flatten the value at the given index
+                assert_eq!(values.len(), elements.len());
+                let v = values.into_iter().nth(index).unwrap();
+                self.flatten_expression(symbols, statements_flattened, v)
+            }
+            TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+                // if the tuple is `(if c then a else b)`, we want to access `(if c then a else b).index`
+                // we reduce to `if c then a.index else b.index`
+                match elements[index] {
+                    Type::FieldElement => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        FieldElementExpression::element(consequence, index),
+                        FieldElementExpression::element(alternative, index),
+                    ),
+                    Type::Boolean => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        BooleanExpression::element(consequence, index),
+                        BooleanExpression::element(alternative, index),
+                    ),
+                    Type::Uint(..) => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        UExpression::element(consequence, index),
+                        UExpression::element(alternative, index),
+                    ),
+                    Type::Array(..) => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        ArrayExpression::element(consequence, index),
+                        ArrayExpression::element(alternative, index),
+                    ),
+                    Type::Struct(..) => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        StructExpression::element(consequence, index),
+                        StructExpression::element(alternative, index),
+                    ),
+                    Type::Tuple(..) => self.flatten_if_else_expression(
+                        symbols,
+                        statements_flattened,
+                        condition,
+                        TupleExpression::element(consequence, index),
+                        TupleExpression::element(alternative, index),
+                    ),
+                }
+            }
+            // in the other cases, we flatten the whole tuple and keep the elements we need
+            inner => self.flatten_tuple_expression(
+                symbols,
+                statements_flattened,
+                inner.annotate(elements),
+            )[offset..(offset + size)]
+                .to_vec(),
+        };
+
+        assert_eq!(res.len(), size);
+        res
+    }
+
     /// Flatten an array selection expression
     ///
     /// # Arguments
@@ -422,6 +590,13 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         [n * ty.get_primitive_count()..(n + 1) * ty.get_primitive_count()]
                         .to_vec()
                 }
+                ArrayExpressionInner::Element(box t, index) => {
+                    assert!(n < T::from(size));
+                    let n = n.to_dec_string().parse::<usize>().unwrap();
+                    self.flatten_element_expression(symbols, statements_flattened, t, index)
+                        [n * element_size..(n + 1) * element_size]
+                        .to_vec()
+                }
                 ArrayExpressionInner::Select(box array, box index) => {
                     assert!(n < T::from(size));
                     let n = n.to_dec_string().parse::<usize>().unwrap();
@@ -441,6 +616,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 array,
                                 index,
                             ),
+                        Type::Uint(..) => self.flatten_select_expression::<UExpression<'ast, T>>(
+                            symbols,
+                            statements_flattened,
+                            array,
+                            index,
+                        ),
                         Type::Array(..) => self
                             .flatten_select_expression::<ArrayExpression<'ast, T>>(
                                 symbols,
@@ -455,6 +636,13 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 array,
                                 index,
                             ),
+                        Type::Tuple(..) => self
+                            .flatten_select_expression::<TupleExpression<'ast, T>>(
+                                symbols,
+                                statements_flattened,
+                                array,
+                                index,
+                            ),
                     };
 
                     e[n * element_size..(n + 1) * element_size]
@@ -485,6 +673,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 let range_check_statement = TypedStatement::Condition(
                     FieldElementExpression::Number(T::from(1)).into(),
                     range_check.into(),
+                    None,
                 );
 
                 self.flatten_statement(symbols, statements_flattened, range_check_statement);
@@ -519,6 +708,11 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 )
                             }
                             ArrayExpressionInner::Member(box s, id) => U::member(s, id),
+                            ArrayExpressionInner::Element(box t, index) => U::select(
+                                ArrayExpressionInner::Element(box t, index)
+                                    .annotate(ty.clone(), size),
+                                FieldElementExpression::Number(T::from(i)),
+                            ),
                             ArrayExpressionInner::Select(box array, box index) => U::select(
                                 ArrayExpressionInner::Select(box array, box index)
                                     .annotate(ty.clone(), size),
@@ -579,7 +773,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                 // lhs
                 let lhs_id = self.use_sym();
-                statements_flattened.push(FlatStatement::Definition(lhs_id, lhs_flattened));
+                statements_flattened.push(FlatStatement::Definition(lhs_id, lhs_flattened, None));
 
                 // check that lhs and rhs are within the right range, ie, their higher two bits are zero. We use big-endian so they are at positions 0 and 1
 
@@ -590,11 +784,10 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         (0..bitwidth).map(|_| self.use_sym()).collect();
 
                     // add a directive to get the bits
-                    statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                        lhs_bits_be.clone(),
-                        Helper::bits(),
-                        vec![lhs_id],
-                    )));
+                    statements_flattened.push(FlatStatement::Directive(
+                        DirectiveStatement::new(lhs_bits_be.clone(), Helper::bits(), vec![lhs_id]),
+                        None,
+                    ));
 
                     // bitness checks
                     for i in 0..bitwidth - 2 {
@@ -604,6 +797,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box FlatExpression::Identifier(lhs_bits_be[i + 2]),
                                 box FlatExpression::Identifier(lhs_bits_be[i + 2]),
                             ),
+                            None,
+                            None,
                         ));
                     }
 
@@ -623,12 +818,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(lhs_id),
                         lhs_sum,
+                        None,
+                        None,
                     ));
                 }
 
                 // rhs
                 let rhs_id = self.use_sym();
-                statements_flattened.push(FlatStatement::Definition(rhs_id, rhs_flattened));
+                statements_flattened.push(FlatStatement::Definition(rhs_id, rhs_flattened, None));
 
                 // rhs
                 {
@@ -637,11 +834,10 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         (0..bitwidth).map(|_| self.use_sym()).collect();
 
                     // add a directive to get the bits
-                    statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                        rhs_bits_be.clone(),
-                        Helper::bits(),
-                        vec![rhs_id],
-                    )));
+                    statements_flattened.push(FlatStatement::Directive(
+                        DirectiveStatement::new(rhs_bits_be.clone(), Helper::bits(), vec![rhs_id]),
+                        None,
+                    ));
 
                     // bitness checks
                     for i in 0..bitwidth - 2 {
@@ -651,6 +847,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                 box FlatExpression::Identifier(rhs_bits_be[i + 2]),
                                 box FlatExpression::Identifier(rhs_bits_be[i + 2]),
                             ),
+                            None,
+                            None,
                         ));
                     }
 
@@ -670,6 +868,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened.push(FlatStatement::Condition(
                         FlatExpression::Identifier(rhs_id),
                         rhs_sum,
+                        None,
+                        None,
                     ));
                 }
 
@@ -690,11 +890,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     (0..bitwidth).map(|_| self.use_sym()).collect();
 
                 // add a directive to get the bits
-                statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                    sub_bits_be.clone(),
-                    Helper::bits(),
-                    vec![subtraction_result.clone()],
-                )));
+                statements_flattened.push(FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        sub_bits_be.clone(),
+                        Helper::bits(),
+                        vec![subtraction_result.clone()],
+                    ),
+                    None,
+                ));
 
                 // bitness checks
                 for i in 0..bitwidth {
@@ -704,6 +907,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             box FlatExpression::Identifier(sub_bits_be[i]),
                             box FlatExpression::Identifier(sub_bits_be[i]),
                         ),
+                        None,
+                        None,
                     ));
                 }
 
@@ -720,7 +925,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     );
                 }
 
-                statements_flattened.push(FlatStatement::Condition(subtraction_result, expr));
+                statements_flattened.push(FlatStatement::Condition(
+                    subtraction_result,
+                    expr,
+                    None,
+                    None,
+                ));
 
                 FlatExpression::Identifier(sub_bits_be[bitwidth - 1])
             }
@@ -744,14 +954,19 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     FieldElementExpression::Sub(box lhs, box rhs),
                 );
 
-                statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                    vec![name_y, name_m],
-                    Helper::Rust(RustHelper::ConditionEq),
-                    vec![x.clone()],
-                )));
+                statements_flattened.push(FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![name_y, name_m],
+                        Helper::Rust(RustHelper::ConditionEq),
+                        vec![x.clone()],
+                    ),
+                    None,
+                ));
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Identifier(name_y),
                     FlatExpression::Mult(box x.clone(), box FlatExpression::Identifier(name_m)),
+                    None,
+                    None,
                 ));
 
                 let res = FlatExpression::Sub(
@@ -762,6 +977,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::zero()),
                     FlatExpression::Mult(box res.clone(), box x),
+                    None,
+                    None,
                 ));
 
                 res
@@ -797,6 +1014,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Definition(
                     name_x_and_y,
                     FlatExpression::Mult(x.clone(), y.clone()),
+                    None,
                 ));
                 FlatExpression::Sub(
                     box FlatExpression::Add(x, y),
@@ -812,6 +1030,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened.push(FlatStatement::Definition(
                     name_x_and_y,
                     FlatExpression::Mult(box x, box y),
+                    None,
                 ));
 
                 FlatExpression::Identifier(name_x_and_y)
@@ -836,6 +1055,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             BooleanExpression::Member(box s, id) => {
                 self.flatten_member_expression(symbols, statements_flattened, s, id)[0].clone()
             }
+            BooleanExpression::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)[0].clone()
+            }
             BooleanExpression::Select(box array, box index) => self
                 .flatten_select_expression::<BooleanExpression<'ast, T>>(
                     symbols,
@@ -872,6 +1094,20 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
         let funct = self.get_function(&key, &symbols);
 
+        // attribute the statements of the called function to a call from the statement being flattened
+        let call_span = self.span.clone().map(|span| {
+            let mut stack = span.stack.clone();
+            stack.push(CallFrame {
+                module: String::from("EMBED"),
+                function: key.id.to_string(),
+                call_site: Some(SourceSpan {
+                    stack: vec![],
+                    ..span.clone()
+                }),
+            });
+            SourceSpan { stack, ..span }
+        });
+
         let mut replacement_map = HashMap::new();
 
         // Handle complex parameters and assign values:
@@ -887,7 +1123,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             params_flattened.into_iter().zip(funct.arguments)
         {
             let new_var = self.use_sym();
-            statements_flattened.push(FlatStatement::Definition(new_var, concrete_argument));
+            statements_flattened.push(FlatStatement::Definition(new_var, concrete_argument, None));
             replacement_map.insert(formal_argument.id, new_var);
         }
 
@@ -905,18 +1141,18 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             .map(|stat| match stat {
                 // set return statements as expression result
                 FlatStatement::Return(..) => unreachable!(),
-                FlatStatement::Definition(var, rhs) => {
+                FlatStatement::Definition(var, rhs, span) => {
                     let new_var = self.use_sym();
                     replacement_map.insert(var, new_var);
                     let new_rhs = rhs.apply_substitution(&replacement_map);
-                    FlatStatement::Definition(new_var, new_rhs)
+                    FlatStatement::Definition(new_var, new_rhs, span.or(call_span.clone()))
                 }
-                FlatStatement::Condition(lhs, rhs) => {
+                FlatStatement::Condition(lhs, rhs, metadata, span) => {
                     let new_lhs = lhs.apply_substitution(&replacement_map);
                     let new_rhs = rhs.apply_substitution(&replacement_map);
-                    FlatStatement::Condition(new_lhs, new_rhs)
+                    FlatStatement::Condition(new_lhs, new_rhs, metadata, span.or(call_span.clone()))
                 }
-                FlatStatement::Directive(d) => {
+                FlatStatement::Directive(d, span) => {
                     let new_outputs = d
                         .outputs
                         .into_iter()
@@ -931,11 +1167,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         .into_iter()
                         .map(|i| i.apply_substitution(&replacement_map))
                         .collect();
-                    FlatStatement::Directive(DirectiveStatement {
-                        outputs: new_outputs,
-                        helper: d.helper,
-                        inputs: new_inputs,
-                    })
+                    FlatStatement::Directive(
+                        DirectiveStatement {
+                            outputs: new_outputs,
+                            helper: d.helper,
+                            inputs: new_inputs,
+                        },
+                        span.or(call_span.clone()),
+                    )
                 }
             })
             .collect();
@@ -943,7 +1182,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         statements_flattened.extend(statements);
 
         match return_statements.pop().unwrap() {
-            FlatStatement::Return(list) => FlatExpressionList {
+            FlatStatement::Return(list, _) => FlatExpressionList {
                 expressions: list
                     .expressions
                     .into_iter()
@@ -974,6 +1213,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             TypedExpression::Boolean(e) => {
                 vec![self.flatten_boolean_expression(symbols, statements_flattened, e)]
             }
+            TypedExpression::Uint(e) => {
+                vec![self.flatten_uint_expression(symbols, statements_flattened, e)]
+            }
             TypedExpression::Array(e) => match e.inner_type().clone() {
                 Type::FieldElement => self
                     .flatten_array_expression::<FieldElementExpression<'ast, T>>(
@@ -991,15 +1233,28 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     statements_flattened,
                     e,
                 ),
+                Type::Uint(..) => self.flatten_array_expression::<UExpression<'ast, T>>(
+                    symbols,
+                    statements_flattened,
+                    e,
+                ),
                 Type::Struct(..) => self.flatten_array_expression::<StructExpression<'ast, T>>(
                     symbols,
                     statements_flattened,
                     e,
                 ),
+                Type::Tuple(..) => self.flatten_array_expression::<TupleExpression<'ast, T>>(
+                    symbols,
+                    statements_flattened,
+                    e,
+                ),
             },
             TypedExpression::Struct(e) => {
                 self.flatten_struct_expression(symbols, statements_flattened, e)
             }
+            TypedExpression::Tuple(e) => {
+                self.flatten_tuple_expression(symbols, statements_flattened, e)
+            }
         }
     }
 
@@ -1030,14 +1285,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     left_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, left_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, left_flattened, None));
                     FlatExpression::Identifier(id)
                 };
                 let new_right = if right_flattened.is_linear() {
                     right_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, right_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, right_flattened, None));
                     FlatExpression::Identifier(id)
                 };
                 FlatExpression::Add(box new_left, box new_right)
@@ -1052,14 +1307,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     left_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, left_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, left_flattened, None));
                     FlatExpression::Identifier(id)
                 };
                 let new_right = if right_flattened.is_linear() {
                     right_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, right_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, right_flattened, None));
                     FlatExpression::Identifier(id)
                 };
 
@@ -1074,14 +1329,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     left_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, left_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, left_flattened, None));
                     FlatExpression::Identifier(id)
                 };
                 let new_right = if right_flattened.is_linear() {
                     right_flattened
                 } else {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, right_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, right_flattened, None));
                     FlatExpression::Identifier(id)
                 };
                 FlatExpression::Mult(box new_left, box new_right)
@@ -1093,12 +1348,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     self.flatten_field_expression(symbols, statements_flattened, right);
                 let new_left: FlatExpression<T> = {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, left_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, left_flattened, None));
                     id.into()
                 };
                 let new_right: FlatExpression<T> = {
                     let id = self.use_sym();
-                    statements_flattened.push(FlatStatement::Definition(id, right_flattened));
+                    statements_flattened.push(FlatStatement::Definition(id, right_flattened, None));
                     id.into()
                 };
 
@@ -1106,29 +1361,39 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 let inverse = self.use_sym();
 
                 // # invb = 1/b
-                statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                    vec![invb],
-                    Helper::Rust(RustHelper::Div),
-                    vec![FlatExpression::Number(T::one()), new_right.clone()],
-                )));
+                statements_flattened.push(FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![invb],
+                        Helper::Rust(RustHelper::Div),
+                        vec![FlatExpression::Number(T::one()), new_right.clone()],
+                    ),
+                    None,
+                ));
 
                 // assert(invb * b == 1)
                 statements_flattened.push(FlatStatement::Condition(
                     FlatExpression::Number(T::one()),
                     FlatExpression::Mult(box invb.into(), box new_right.clone().into()),
+                    None,
+                    None,
                 ));
 
                 // # c = a/b
-                statements_flattened.push(FlatStatement::Directive(DirectiveStatement::new(
-                    vec![inverse],
-                    Helper::Rust(RustHelper::Div),
-                    vec![new_left.clone(), new_right.clone()],
-                )));
+                statements_flattened.push(FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![inverse],
+                        Helper::Rust(RustHelper::Div),
+                        vec![new_left.clone(), new_right.clone()],
+                    ),
+                    None,
+                ));
 
                 // assert(c * b == a)
                 statements_flattened.push(FlatStatement::Condition(
                     new_left.into(),
                     FlatExpression::Mult(box new_right, box inverse.into()),
+                    None,
+                    None,
                 ));
 
                 inverse.into()
@@ -1182,6 +1447,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                                 box previous.clone(),
                                                 box previous.clone(),
                                             ),
+                                            None,
                                         ));
                                         // store it in the state for later squaring
                                         *state = Some(FlatExpression::Identifier(id.clone()));
@@ -1201,7 +1467,8 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                                     let id = self.use_sym();
                                     statements_flattened.push(FlatStatement::Definition(
                                         id,
-                                        FlatExpression::Mult(box acc.clone(), box power), // set the new result to the current result times the current power
+                                        FlatExpression::Mult(box acc.clone(), box power), // set the new result to the current result times the current power,
+                                        None,
                                     ));
                                     FlatExpression::Identifier(id)
                                 }
@@ -1212,6 +1479,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     _ => panic!("Expected number as pow exponent"),
                 }
             }
+            FieldElementExpression::FromUint(box e) => {
+                self.flatten_uint_expression(symbols, statements_flattened, e)
+            }
             FieldElementExpression::IfElse(box condition, box consequence, box alternative) => self
                 .flatten_if_else_expression(
                     symbols,
@@ -1235,6 +1505,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             FieldElementExpression::Member(box s, id) => {
                 self.flatten_member_expression(symbols, statements_flattened, s, id)[0].clone()
             }
+            FieldElementExpression::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)[0].clone()
+            }
             FieldElementExpression::Select(box array, box index) => self
                 .flatten_select_expression::<FieldElementExpression<'ast, T>>(
                     symbols,
@@ -1246,6 +1519,359 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         }
     }
 
+    /// Flattens an unsigned integer expression
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - Available functions in in this context
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `expr` - `UExpression` that will be flattened.
+    ///
+    /// # Remarks
+    /// * Unsigned integers are represented as a single field element in `[0, 2**bitwidth)`.
+    ///   Operations which can leave this range are reduced by decomposing the result into bits.
+    fn flatten_uint_expression(
+        &mut self,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        expr: UExpression<'ast, T>,
+    ) -> FlatExpression<T> {
+        let bitwidth = expr.bitwidth();
+
+        match expr.into_inner() {
+            UExpressionInner::Value(v) => FlatExpression::Number(T::from(v as usize)),
+            UExpressionInner::Identifier(x) => {
+                FlatExpression::Identifier(self.layout.get(&x).unwrap().clone()[0])
+            }
+            UExpressionInner::Add(box left, box right) => {
+                let left_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, left);
+                let right_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, right);
+
+                // the sum fits in `bitwidth + 1` bits, we drop the carry
+                let bits = self.uint_bits(
+                    statements_flattened,
+                    FlatExpression::Add(box left_flattened, box right_flattened),
+                    bitwidth + 1,
+                );
+
+                Self::uint_from_bits(&bits[1..])
+            }
+            UExpressionInner::Sub(box left, box right) => {
+                let left_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, left);
+                let right_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, right);
+
+                // left + 2**bitwidth - right fits in `bitwidth + 1` bits, we drop the borrow
+                let bits = self.uint_bits(
+                    statements_flattened,
+                    FlatExpression::Sub(
+                        box FlatExpression::Add(
+                            box left_flattened,
+                            box FlatExpression::Number(T::from(2).pow(bitwidth)),
+                        ),
+                        box right_flattened,
+                    ),
+                    bitwidth + 1,
+                );
+
+                Self::uint_from_bits(&bits[1..])
+            }
+            UExpressionInner::Mult(box left, box right) => {
+                let left_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, left);
+                let right_flattened =
+                    self.flatten_uint_expression(symbols, statements_flattened, right);
+
+                let left_id = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(left_id, left_flattened, None));
+                let right_id = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(
+                    right_id,
+                    right_flattened,
+                    None,
+                ));
+
+                // the product fits in `2 * bitwidth` bits, we keep the lower half
+                let bits = self.uint_bits(
+                    statements_flattened,
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(left_id),
+                        box FlatExpression::Identifier(right_id),
+                    ),
+                    2 * bitwidth,
+                );
+
+                Self::uint_from_bits(&bits[bitwidth..])
+            }
+            UExpressionInner::Not(box e) => {
+                // e is in range, so flipping all bits is 2**bitwidth - 1 - e
+                let e_flattened = self.flatten_uint_expression(symbols, statements_flattened, e);
+
+                FlatExpression::Sub(
+                    box FlatExpression::Number(T::from(2).pow(bitwidth) - T::one()),
+                    box e_flattened,
+                )
+            }
+            UExpressionInner::And(box left, box right) => self.flatten_bitwise_expression(
+                symbols,
+                statements_flattened,
+                left,
+                right,
+                // l * r
+                |_, _, product| FlatExpression::Identifier(product),
+            ),
+            UExpressionInner::Or(box left, box right) => self.flatten_bitwise_expression(
+                symbols,
+                statements_flattened,
+                left,
+                right,
+                // l + r - l * r
+                |l, r, product| {
+                    FlatExpression::Sub(
+                        box FlatExpression::Add(
+                            box FlatExpression::Identifier(l),
+                            box FlatExpression::Identifier(r),
+                        ),
+                        box FlatExpression::Identifier(product),
+                    )
+                },
+            ),
+            UExpressionInner::Xor(box left, box right) => self.flatten_bitwise_expression(
+                symbols,
+                statements_flattened,
+                left,
+                right,
+                // l + r - 2 * l * r
+                |l, r, product| {
+                    FlatExpression::Sub(
+                        box FlatExpression::Add(
+                            box FlatExpression::Identifier(l),
+                            box FlatExpression::Identifier(r),
+                        ),
+                        box FlatExpression::Mult(
+                            box FlatExpression::Number(T::from(2)),
+                            box FlatExpression::Identifier(product),
+                        ),
+                    )
+                },
+            ),
+            UExpressionInner::LeftShift(box e, box by) => {
+                let by = Self::shift_amount(by, bitwidth);
+
+                let e_flattened = self.flatten_uint_expression(symbols, statements_flattened, e);
+                let bits = self.uint_bits(statements_flattened, e_flattened, bitwidth);
+
+                // drop the `by` most significant bits, the lower ones are filled with zeros
+                FlatExpression::Mult(
+                    box Self::uint_from_bits(&bits[by..]),
+                    box FlatExpression::Number(T::from(2).pow(by)),
+                )
+            }
+            UExpressionInner::RightShift(box e, box by) => {
+                let by = Self::shift_amount(by, bitwidth);
+
+                let e_flattened = self.flatten_uint_expression(symbols, statements_flattened, e);
+                let bits = self.uint_bits(statements_flattened, e_flattened, bitwidth);
+
+                // drop the `by` least significant bits
+                Self::uint_from_bits(&bits[..bitwidth - by])
+            }
+            UExpressionInner::FromField(box e) => {
+                let e_flattened = self.flatten_field_expression(symbols, statements_flattened, e);
+
+                // the decomposition enforces that the value is in range
+                let bits = self.uint_bits(statements_flattened, e_flattened, bitwidth);
+
+                Self::uint_from_bits(&bits)
+            }
+            UExpressionInner::FromUint(box e) => {
+                let from = e.bitwidth();
+                let e_flattened = self.flatten_uint_expression(symbols, statements_flattened, e);
+
+                match from <= bitwidth {
+                    // widening keeps the value unchanged
+                    true => e_flattened,
+                    // narrowing keeps the lower bits
+                    false => {
+                        let bits = self.uint_bits(statements_flattened, e_flattened, from);
+                        Self::uint_from_bits(&bits[from - bitwidth..])
+                    }
+                }
+            }
+            UExpressionInner::IfElse(box condition, box consequence, box alternative) => self
+                .flatten_if_else_expression(
+                    symbols,
+                    statements_flattened,
+                    condition,
+                    consequence,
+                    alternative,
+                )[0]
+            .clone(),
+            UExpressionInner::FunctionCall(key, param_expressions) => {
+                let exprs_flattened = self.flatten_function_call(
+                    symbols,
+                    statements_flattened,
+                    key.id,
+                    vec![Type::Uint(bitwidth)],
+                    param_expressions,
+                );
+                assert!(exprs_flattened.expressions.len() == 1); // outside of MultipleDefinition, FunctionCalls must return a single value
+                exprs_flattened.expressions[0].clone()
+            }
+            UExpressionInner::Member(box s, id) => {
+                self.flatten_member_expression(symbols, statements_flattened, s, id)[0].clone()
+            }
+            UExpressionInner::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)[0].clone()
+            }
+            UExpressionInner::Select(box array, box index) => self
+                .flatten_select_expression::<UExpression<'ast, T>>(
+                    symbols,
+                    statements_flattened,
+                    array,
+                    index,
+                )[0]
+            .clone(),
+        }
+    }
+
+    /// Flattens a bitwise operation on two unsigned integers
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - Available functions in in this context
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `left` - `UExpression` on the left of the operator
+    /// * `right` - `UExpression` on the right of the operator
+    /// * `op` - builds each bit of the result from the bits `l`, `r` and their product `l * r`
+    fn flatten_bitwise_expression<
+        F: Fn(FlatVariable, FlatVariable, FlatVariable) -> FlatExpression<T>,
+    >(
+        &mut self,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        left: UExpression<'ast, T>,
+        right: UExpression<'ast, T>,
+        op: F,
+    ) -> FlatExpression<T> {
+        let bitwidth = left.bitwidth();
+
+        let left_flattened = self.flatten_uint_expression(symbols, statements_flattened, left);
+        let right_flattened = self.flatten_uint_expression(symbols, statements_flattened, right);
+
+        let left_bits = self.uint_bits(statements_flattened, left_flattened, bitwidth);
+        let right_bits = self.uint_bits(statements_flattened, right_flattened, bitwidth);
+
+        let res_bits: Vec<FlatVariable> = left_bits
+            .into_iter()
+            .zip(right_bits.into_iter())
+            .map(|(l, r)| {
+                let product = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(
+                    product,
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(l),
+                        box FlatExpression::Identifier(r),
+                    ),
+                    None,
+                ));
+
+                let res = self.use_sym();
+                statements_flattened.push(FlatStatement::Definition(res, op(l, r, product), None));
+                res
+            })
+            .collect();
+
+        Self::uint_from_bits(&res_bits)
+    }
+
+    /// Decomposes an expression into `bitwidth` bits, in big-endian order
+    ///
+    /// # Arguments
+    ///
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `e` - `FlatExpression` to decompose. The resulting constraints are only satisfied if it is smaller than `2**bitwidth`
+    /// * `bitwidth` - the number of bits to return
+    fn uint_bits(
+        &mut self,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        e: FlatExpression<T>,
+        bitwidth: usize,
+    ) -> Vec<FlatVariable> {
+        let field_bitwidth = T::get_required_bits();
+        assert!(bitwidth < field_bitwidth);
+
+        let id = self.use_sym();
+        statements_flattened.push(FlatStatement::Definition(id, e, None));
+
+        // define variables for the bits
+        let bits_be: Vec<FlatVariable> = (0..field_bitwidth).map(|_| self.use_sym()).collect();
+
+        // add a directive to get the bits
+        statements_flattened.push(FlatStatement::Directive(
+            DirectiveStatement::new(bits_be.clone(), Helper::bits(), vec![id]),
+            None,
+        ));
+
+        // only the lower bits are used
+        let bits_be = bits_be[field_bitwidth - bitwidth..].to_vec();
+
+        // bitness checks
+        for bit in &bits_be {
+            statements_flattened.push(FlatStatement::Condition(
+                FlatExpression::Identifier(*bit),
+                FlatExpression::Mult(
+                    box FlatExpression::Identifier(*bit),
+                    box FlatExpression::Identifier(*bit),
+                ),
+                None,
+                None,
+            ));
+        }
+
+        // bit decomposition check
+        statements_flattened.push(FlatStatement::Condition(
+            FlatExpression::Identifier(id),
+            Self::uint_from_bits(&bits_be),
+            None,
+            None,
+        ));
+
+        bits_be
+    }
+
+    /// The amount of a shift of a `bitwidth` bit integer, capped at `bitwidth` as larger shifts
+    /// clear all bits
+    fn shift_amount(by: FieldElementExpression<'ast, T>, bitwidth: usize) -> usize {
+        match by {
+            FieldElementExpression::Number(n) => n
+                .to_dec_string()
+                .parse::<usize>()
+                .map(|by| std::cmp::min(by, bitwidth))
+                .unwrap_or(bitwidth),
+            _ => unreachable!("shift amounts should have been checked to be constants"),
+        }
+    }
+
+    /// Recomposes big-endian bits into a single linear expression
+    fn uint_from_bits(bits_be: &[FlatVariable]) -> FlatExpression<T> {
+        bits_be.iter().rev().enumerate().fold(
+            FlatExpression::Number(T::from(0)),
+            |acc, (i, bit)| {
+                FlatExpression::Add(
+                    box acc,
+                    box FlatExpression::Mult(
+                        box FlatExpression::Identifier(*bit),
+                        box FlatExpression::Number(T::from(2).pow(i)),
+                    ),
+                )
+            },
+        )
+    }
+
     /// Flattens an array expression
     ///
     /// # Arguments
@@ -1301,6 +1927,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             BooleanExpression::member(alternative.clone(), id.clone()),
                         )
                         .flatten(self, symbols, statements_flattened),
+                        Type::Uint(..) => UExpression::if_else(
+                            condition.clone(),
+                            UExpression::member(consequence.clone(), id.clone()),
+                            UExpression::member(alternative.clone(), id.clone()),
+                        )
+                        .flatten(self, symbols, statements_flattened),
                         Type::Struct(..) => StructExpression::if_else(
                             condition.clone(),
                             StructExpression::member(consequence.clone(), id.clone()),
@@ -1313,12 +1945,21 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             ArrayExpression::member(alternative.clone(), id.clone()),
                         )
                         .flatten(self, symbols, statements_flattened),
+                        Type::Tuple(..) => TupleExpression::if_else(
+                            condition.clone(),
+                            TupleExpression::member(consequence.clone(), id.clone()),
+                            TupleExpression::member(alternative.clone(), id.clone()),
+                        )
+                        .flatten(self, symbols, statements_flattened),
                     })
                     .collect()
             }
             StructExpressionInner::Member(box s, id) => {
                 self.flatten_member_expression(symbols, statements_flattened, s, id)
             }
+            StructExpressionInner::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)
+            }
             StructExpressionInner::Select(box array, box index) => self
                 .flatten_select_expression::<StructExpression<'ast, T>>(
                     symbols,
@@ -1332,6 +1973,71 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         res
     }
 
+    /// Flattens a tuple expression
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - Available functions in in this context
+    /// * `statements_flattened` - Vector where new flattened statements can be added.
+    /// * `expr` - `TupleExpression` that will be flattened.
+    fn flatten_tuple_expression(
+        &mut self,
+        symbols: &TypedFunctionSymbols<'ast, T>,
+        statements_flattened: &mut Vec<FlatStatement<T>>,
+        expr: TupleExpression<'ast, T>,
+    ) -> Vec<FlatExpression<T>> {
+        let ty = expr.get_type();
+        let expected_output_size = ty.get_primitive_count();
+
+        let res = match expr.into_inner() {
+            TupleExpressionInner::Identifier(x) => self
+                .layout
+                .get(&x)
+                .unwrap()
+                .iter()
+                .map(|v| FlatExpression::Identifier(v.clone()))
+                .collect(),
+            TupleExpressionInner::Value(values) => values
+                .into_iter()
+                .flat_map(|v| self.flatten_expression(symbols, statements_flattened, v))
+                .collect(),
+            TupleExpressionInner::FunctionCall(key, param_expressions) => {
+                let exprs_flattened = self.flatten_function_call(
+                    symbols,
+                    statements_flattened,
+                    key.id,
+                    vec![ty],
+                    param_expressions,
+                );
+                exprs_flattened.expressions
+            }
+            TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => self
+                .flatten_if_else_expression(
+                    symbols,
+                    statements_flattened,
+                    condition,
+                    consequence,
+                    alternative,
+                ),
+            TupleExpressionInner::Member(box s, id) => {
+                self.flatten_member_expression(symbols, statements_flattened, s, id)
+            }
+            TupleExpressionInner::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)
+            }
+            TupleExpressionInner::Select(box array, box index) => self
+                .flatten_select_expression::<TupleExpression<'ast, T>>(
+                    symbols,
+                    statements_flattened,
+                    array,
+                    index,
+                ),
+        };
+
+        assert_eq!(res.len(), expected_output_size);
+        res
+    }
+
     /// Flattens an array expression
     ///
     /// # Arguments
@@ -1401,6 +2107,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             ArrayExpressionInner::Member(box s, id) => {
                 self.flatten_member_expression(symbols, statements_flattened, s, id)
             }
+            ArrayExpressionInner::Element(box t, index) => {
+                self.flatten_element_expression(symbols, statements_flattened, t, index)
+            }
             ArrayExpressionInner::Select(box array, box index) => self
                 .flatten_select_expression::<ArrayExpression<'ast, T>>(
                     symbols,
@@ -1425,22 +2134,25 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         stat: TypedStatement<'ast, T>,
     ) {
         match stat {
-            TypedStatement::Return(exprs) => {
+            TypedStatement::Return(exprs, _) => {
                 let flat_expressions = exprs
                     .into_iter()
                     .map(|expr| self.flatten_expression(symbols, statements_flattened, expr))
                     .flat_map(|x| x)
                     .collect::<Vec<_>>();
 
-                statements_flattened.push(FlatStatement::Return(FlatExpressionList {
-                    expressions: flat_expressions,
-                }));
+                statements_flattened.push(FlatStatement::Return(
+                    FlatExpressionList {
+                        expressions: flat_expressions,
+                    },
+                    None,
+                ));
             }
             TypedStatement::Declaration(_) => {
                 // declarations have already been checked
                 ()
             }
-            TypedStatement::Definition(assignee, expr) => {
+            TypedStatement::Definition(assignee, expr, _) => {
                 // define n variables with n the number of primitive types for v_type
                 // assign them to the n primitive types for expr
 
@@ -1453,7 +2165,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         statements_flattened.extend(
                             vars.into_iter()
                                 .zip(rhs)
-                                .map(|(v, e)| FlatStatement::Definition(v, e)),
+                                .map(|(v, e)| FlatStatement::Definition(v, e, None)),
                         );
                     }
                     TypedAssignee::Select(..) => unreachable!(
@@ -1464,7 +2176,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     ),
                 }
             }
-            TypedStatement::Condition(lhs, rhs) => {
+            TypedStatement::Condition(lhs, rhs, _) => {
                 // flatten expr1 and expr2 to n flattened expressions with n the number of primitive types for expr1
                 // add n conditions to check equality of the n expressions
 
@@ -1475,17 +2187,46 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                 for (l, r) in lhs.into_iter().zip(rhs.into_iter()) {
                     if l.is_linear() {
-                        statements_flattened.push(FlatStatement::Condition(l, r));
+                        statements_flattened.push(FlatStatement::Condition(l, r, None, None));
                     } else if r.is_linear() {
                         // swap so that left side is linear
-                        statements_flattened.push(FlatStatement::Condition(r, l));
+                        statements_flattened.push(FlatStatement::Condition(r, l, None, None));
                     } else {
                         unimplemented!()
                     }
                 }
             }
-            TypedStatement::For(..) => unreachable!("static analyser should have unrolled"),
-            TypedStatement::MultipleDefinition(vars, rhs) => {
+            TypedStatement::Assertion(e, metadata) => {
+                // equality checks are enforced directly, other booleans are checked to be true
+                let (lhs, rhs) = match e {
+                    BooleanExpression::Eq(box lhs, box rhs) => (
+                        self.flatten_field_expression(symbols, statements_flattened, lhs),
+                        self.flatten_field_expression(symbols, statements_flattened, rhs),
+                    ),
+                    e => (
+                        self.flatten_boolean_expression(symbols, statements_flattened, e),
+                        FlatExpression::Number(T::one()),
+                    ),
+                };
+
+                let (l, r) = if lhs.is_linear() {
+                    (lhs, rhs)
+                } else if rhs.is_linear() {
+                    // swap so that left side is linear
+                    (rhs, lhs)
+                } else {
+                    let id = self.use_sym();
+                    statements_flattened.push(FlatStatement::Definition(id, rhs, None));
+                    (FlatExpression::Identifier(id), lhs)
+                };
+
+                statements_flattened.push(FlatStatement::Condition(l, r, Some(metadata), None));
+            }
+            TypedStatement::For(..) | TypedStatement::BoundedFor(..) => {
+                unreachable!("static analyser should have unrolled")
+            }
+            TypedStatement::IfElse(..) => unreachable!("static analyser should have lowered"),
+            TypedStatement::MultipleDefinition(vars, rhs, _) => {
                 // flatten the right side to p = sum(var_i.type.primitive_count) expressions
                 // define p new variables to the right side expressions
 
@@ -1505,8 +2246,22 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                         let vars = vars.into_iter().flat_map(|v| self.use_variable(&v));
 
-                        statements_flattened
-                            .extend(vars.zip(rhs).map(|(v, r)| FlatStatement::Definition(v, r)));
+                        statements_flattened.extend(
+                            vars.zip(rhs)
+                                .map(|(v, r)| FlatStatement::Definition(v, r, None)),
+                        );
+                    }
+                    TypedExpressionList::Tuple(e) => {
+                        let rhs = self
+                            .flatten_tuple_expression(symbols, statements_flattened, e)
+                            .into_iter();
+
+                        let vars = vars.into_iter().flat_map(|v| self.use_variable(&v));
+
+                        statements_flattened.extend(
+                            vars.zip(rhs)
+                                .map(|(v, r)| FlatStatement::Definition(v, r, None)),
+                        );
                     }
                 }
             }
@@ -1553,7 +2308,19 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
         // flatten statements in functions and apply substitution
         for stat in funct.statements {
+            let span = stat.span();
+            let first = statements_flattened.len();
+
+            self.span = span.clone();
+
             self.flatten_statement(symbols, &mut statements_flattened, stat);
+
+            // attribute the resulting flat statements to the source of the typed statement
+            for s in statements_flattened[first..].iter_mut() {
+                if s.span_mut().is_none() {
+                    *s.span_mut() = span.clone();
+                }
+            }
         }
 
         FlatFunction {
@@ -1669,6 +2436,7 @@ mod tests {
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("a".into())),
                     FieldElementExpression::Number(FieldPrime::from(7)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("b".into())),
@@ -1677,8 +2445,12 @@ mod tests {
                         box FieldElementExpression::Number(FieldPrime::from(0)),
                     )
                     .into(),
+                    None,
+                ),
+                TypedStatement::Return(
+                    vec![FieldElementExpression::Identifier("b".into()).into()],
+                    None,
                 ),
-                TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -1694,14 +2466,19 @@ mod tests {
                 FlatStatement::Definition(
                     FlatVariable::new(0),
                     FlatExpression::Number(FieldPrime::from(7)),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(1),
                     FlatExpression::Number(FieldPrime::from(1)),
+                    None,
+                ),
+                FlatStatement::Return(
+                    FlatExpressionList {
+                        expressions: vec![FlatExpression::Identifier(FlatVariable::new(1))],
+                    },
+                    None,
                 ),
-                FlatStatement::Return(FlatExpressionList {
-                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(1))],
-                }),
             ],
             signature: Signature::new().outputs(vec![Type::FieldElement]),
         };
@@ -1729,6 +2506,7 @@ mod tests {
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("a".into())),
                     FieldElementExpression::Number(FieldPrime::from(7)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("b".into())),
@@ -1737,8 +2515,12 @@ mod tests {
                         box FieldElementExpression::Number(FieldPrime::from(1)),
                     )
                     .into(),
+                    None,
+                ),
+                TypedStatement::Return(
+                    vec![FieldElementExpression::Identifier("b".into()).into()],
+                    None,
                 ),
-                TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -1754,6 +2536,7 @@ mod tests {
                 FlatStatement::Definition(
                     FlatVariable::new(0),
                     FlatExpression::Number(FieldPrime::from(7)),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(1),
@@ -1761,14 +2544,19 @@ mod tests {
                         box FlatExpression::Number(FieldPrime::from(1)),
                         box FlatExpression::Identifier(FlatVariable::new(0)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(2),
                     FlatExpression::Identifier(FlatVariable::new(1)),
+                    None,
+                ),
+                FlatStatement::Return(
+                    FlatExpressionList {
+                        expressions: vec![FlatExpression::Identifier(FlatVariable::new(2))],
+                    },
+                    None,
                 ),
-                FlatStatement::Return(FlatExpressionList {
-                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(2))],
-                }),
             ],
             signature: Signature::new().outputs(vec![Type::FieldElement]),
         };
@@ -1814,6 +2602,7 @@ mod tests {
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("a".into())),
                     FieldElementExpression::Number(FieldPrime::from(7)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element("b".into())),
@@ -1822,8 +2611,12 @@ mod tests {
                         box FieldElementExpression::Number(FieldPrime::from(13)),
                     )
                     .into(),
+                    None,
+                ),
+                TypedStatement::Return(
+                    vec![FieldElementExpression::Identifier("b".into()).into()],
+                    None,
                 ),
-                TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
             ],
             signature: Signature {
                 inputs: vec![],
@@ -1839,6 +2632,7 @@ mod tests {
                 FlatStatement::Definition(
                     FlatVariable::new(0),
                     FlatExpression::Number(FieldPrime::from(7)),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(1),
@@ -1846,6 +2640,7 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(0)),
                         box FlatExpression::Identifier(FlatVariable::new(0)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(2),
@@ -1853,6 +2648,7 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(1)),
                         box FlatExpression::Identifier(FlatVariable::new(1)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(3),
@@ -1860,6 +2656,7 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(2)),
                         box FlatExpression::Identifier(FlatVariable::new(2)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(4),
@@ -1867,6 +2664,7 @@ mod tests {
                         box FlatExpression::Number(FieldPrime::from(1)),
                         box FlatExpression::Identifier(FlatVariable::new(0)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(5),
@@ -1874,6 +2672,7 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(4)),
                         box FlatExpression::Identifier(FlatVariable::new(2)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(6),
@@ -1881,14 +2680,19 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(5)),
                         box FlatExpression::Identifier(FlatVariable::new(3)),
                     ),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(7),
                     FlatExpression::Identifier(FlatVariable::new(6)),
+                    None,
+                ),
+                FlatStatement::Return(
+                    FlatExpressionList {
+                        expressions: vec![FlatExpression::Identifier(FlatVariable::new(7))],
+                    },
+                    None,
                 ),
-                FlatStatement::Return(FlatExpressionList {
-                    expressions: vec![FlatExpression::Identifier(FlatVariable::new(7))],
-                }),
             ],
             signature: Signature::new().outputs(vec![Type::FieldElement]),
         };
@@ -1963,6 +2767,7 @@ mod tests {
         let definition = TypedStatement::Definition(
             TypedAssignee::Identifier(Variable::field_element("b".into())),
             FieldElementExpression::Number(FieldPrime::from(42)).into(),
+            None,
         );
 
         let statement = TypedStatement::Definition(
@@ -1975,6 +2780,7 @@ mod tests {
                 box FieldElementExpression::Identifier("b".into()),
             )
             .into(),
+            None,
         );
 
         flattener.flatten_statement(&HashMap::new(), &mut statements_flattened, definition);
@@ -2004,55 +2810,75 @@ mod tests {
         assert_eq!(
             statements_flattened,
             vec![
-                FlatStatement::Definition(b, FlatExpression::Number(FieldPrime::from(42))),
+                FlatStatement::Definition(b, FlatExpression::Number(FieldPrime::from(42)), None),
                 // inputs to first div (5/b)
-                FlatStatement::Definition(five, FlatExpression::Number(FieldPrime::from(5))),
-                FlatStatement::Definition(b0, b.into()),
+                FlatStatement::Definition(five, FlatExpression::Number(FieldPrime::from(5)), None),
+                FlatStatement::Definition(b0, b.into(), None),
                 // check div by 0
-                FlatStatement::Directive(DirectiveStatement::new(
-                    vec![invb0],
-                    Helper::Rust(RustHelper::Div),
-                    vec![FlatExpression::Number(FieldPrime::from(1)), b0.into()]
-                )),
+                FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![invb0],
+                        Helper::Rust(RustHelper::Div),
+                        vec![FlatExpression::Number(FieldPrime::from(1)), b0.into()]
+                    ),
+                    None,
+                ),
                 FlatStatement::Condition(
                     FlatExpression::Number(FieldPrime::from(1)),
                     FlatExpression::Mult(box invb0.into(), box b0.into()),
+                    None,
+                    None,
                 ),
                 // execute div
-                FlatStatement::Directive(DirectiveStatement::new(
-                    vec![sym_0],
-                    Helper::Rust(RustHelper::Div),
-                    vec![five, b0]
-                )),
+                FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![sym_0],
+                        Helper::Rust(RustHelper::Div),
+                        vec![five, b0]
+                    ),
+                    None,
+                ),
                 FlatStatement::Condition(
                     five.into(),
                     FlatExpression::Mult(box b0.into(), box sym_0.into()),
+                    None,
+                    None,
                 ),
                 // inputs to second div (res/b)
-                FlatStatement::Definition(sym_1, sym_0.into()),
-                FlatStatement::Definition(b1, b.into()),
+                FlatStatement::Definition(sym_1, sym_0.into(), None),
+                FlatStatement::Definition(b1, b.into(), None),
                 // check div by 0
-                FlatStatement::Directive(DirectiveStatement::new(
-                    vec![invb1],
-                    Helper::Rust(RustHelper::Div),
-                    vec![FlatExpression::Number(FieldPrime::from(1)), b1.into()]
-                )),
+                FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![invb1],
+                        Helper::Rust(RustHelper::Div),
+                        vec![FlatExpression::Number(FieldPrime::from(1)), b1.into()]
+                    ),
+                    None,
+                ),
                 FlatStatement::Condition(
                     FlatExpression::Number(FieldPrime::from(1)),
                     FlatExpression::Mult(box invb1.into(), box b1.into()),
+                    None,
+                    None,
                 ),
                 // execute div
-                FlatStatement::Directive(DirectiveStatement::new(
-                    vec![sym_2],
-                    Helper::Rust(RustHelper::Div),
-                    vec![sym_1, b1]
-                )),
+                FlatStatement::Directive(
+                    DirectiveStatement::new(
+                        vec![sym_2],
+                        Helper::Rust(RustHelper::Div),
+                        vec![sym_1, b1]
+                    ),
+                    None,
+                ),
                 FlatStatement::Condition(
                     sym_1.into(),
                     FlatExpression::Mult(box b1.into(), box sym_2.into()),
+                    None,
+                    None,
                 ),
                 // result
-                FlatStatement::Definition(a, sym_2.into()),
+                FlatStatement::Definition(a, sym_2.into(), None),
             ]
         );
     }
@@ -2072,6 +2898,7 @@ mod tests {
             ])
             .annotate(Type::FieldElement, 3)
             .into(),
+            None,
         );
         let expression =
             ArrayExpressionInner::Identifier("foo".into()).annotate(Type::FieldElement, 3);
@@ -2108,6 +2935,7 @@ mod tests {
             ])
             .annotate(Type::FieldElement, 3)
             .into(),
+            None,
         );
 
         flattener.flatten_statement(&HashMap::new(), &mut statements_flattened, statement);
@@ -2117,15 +2945,18 @@ mod tests {
             vec![
                 FlatStatement::Definition(
                     FlatVariable::new(0),
-                    FlatExpression::Number(FieldPrime::from(1))
+                    FlatExpression::Number(FieldPrime::from(1)),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(1),
-                    FlatExpression::Number(FieldPrime::from(2))
+                    FlatExpression::Number(FieldPrime::from(2)),
+                    None,
                 ),
                 FlatStatement::Definition(
                     FlatVariable::new(2),
-                    FlatExpression::Number(FieldPrime::from(3))
+                    FlatExpression::Number(FieldPrime::from(3)),
+                    None,
                 ),
             ]
         );
@@ -2147,6 +2978,7 @@ mod tests {
             ])
             .annotate(Type::FieldElement, 3)
             .into(),
+            None,
         );
 
         let expression = FieldElementExpression::Select(
@@ -2186,6 +3018,7 @@ mod tests {
             ])
             .annotate(Type::FieldElement, 3)
             .into(),
+            None,
         );
 
         let sum = TypedStatement::Definition(
@@ -2210,6 +3043,7 @@ mod tests {
                 ),
             )
             .into(),
+            None,
         );
 
         flattener.flatten_statement(&HashMap::new(), &mut statements_flattened, def);
@@ -2226,7 +3060,8 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(1)),
                     ),
                     box FlatExpression::Identifier(FlatVariable::new(2)),
-                )
+                ),
+                None,
             )
         );
     }
@@ -2258,6 +3093,7 @@ mod tests {
             ])
             .annotate(Type::array(Type::FieldElement, 2), 2)
             .into(),
+            None,
         );
 
         let sum = TypedStatement::Definition(
@@ -2305,6 +3141,7 @@ mod tests {
                 ),
             )
             .into(),
+            None,
         );
 
         flattener.flatten_statement(&HashMap::new(), &mut statements_flattened, def);
@@ -2324,7 +3161,8 @@ mod tests {
                         box FlatExpression::Identifier(FlatVariable::new(2))
                     ),
                     box FlatExpression::Identifier(FlatVariable::new(3)),
-                )
+                ),
+                None,
             )
         );
     }
@@ -5,14 +5,18 @@
 //! @date 2018
 
 use crate::absy::*;
-use crate::compile::compile_module;
-use crate::compile::{CompileErrorInner, CompileErrors, Resolve};
+use crate::cache::CacheState;
+use crate::compile::load_module;
+use crate::compile::{CompileErrorInner, CompileErrors};
 use crate::embed::FlatEmbed;
 use crate::parser::Position;
+use crate::resolve::Resolver;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
-use std::io::BufRead;
+use std::path::Path;
 use typed_arena::Arena;
 use zokrates_field::field::Field;
 
@@ -116,6 +120,63 @@ impl<'ast> fmt::Debug for Import<'ast> {
     }
 }
 
+/// The modules being compiled, each with the position of the import directive it is resolving
+///
+/// A module importing one of these modules closes an import cycle.
+#[derive(Debug, Default)]
+pub struct ImportStack(Vec<(ModuleId, Option<(Position, Position)>)>);
+
+impl ImportStack {
+    pub fn push(&mut self, module_id: ModuleId) {
+        self.0.push((module_id, None));
+    }
+
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    /// Whether a module is being compiled
+    pub fn contains(&self, module_id: &str) -> bool {
+        self.0.iter().any(|(id, _)| id == module_id)
+    }
+
+    fn set_import(&mut self, pos: (Position, Position)) {
+        if let Some(last) = self.0.last_mut() {
+            last.1 = Some(pos);
+        }
+    }
+
+    /// The error to return if importing `module_id` closes a cycle, reporting each import of the cycle
+    fn cycle(&self, module_id: &str) -> Option<Error> {
+        let start = self.0.iter().position(|(id, _)| id == module_id)?;
+        let imports = &self.0[start..];
+
+        let chain = imports
+            .iter()
+            .map(|(id, _)| id.clone())
+            .chain(std::iter::once(module_id.to_string()))
+            .collect::<Vec<_>>();
+
+        let directives = imports
+            .iter()
+            .zip(chain.iter().skip(1))
+            .map(|((id, pos), imported)| match pos {
+                Some(pos) => format!("\t{}:{}: import of {}", id, pos.0, imported),
+                None => format!("\t{}: import of {}", id, imported),
+            })
+            .collect::<Vec<_>>();
+
+        Some(
+            Error::new(format!(
+                "Import cycle detected: {}\n{}",
+                chain.join(" -> "),
+                directives.join("\n")
+            ))
+            .with_pos(imports.last().and_then(|(_, pos)| *pos)),
+        )
+    }
+}
+
 pub struct Importer {}
 
 impl Importer {
@@ -123,15 +184,21 @@ impl Importer {
         Importer {}
     }
 
-    pub fn apply_imports<'ast, T: Field, S: BufRead, E: Into<Error>>(
+    /// Resolve the imports of `destination`, compiling the imported modules into `modules`
+    ///
+    /// Returns the module with its imports replaced by symbols, and the source and location of each imported module
+    pub fn apply_imports<'ast, T: Field + Serialize + DeserializeOwned, E: Into<Error>>(
         &self,
         destination: Module<'ast, T>,
         location: Option<String>,
-        resolve_option: Option<Resolve<S, E>>,
+        resolver: Option<&dyn Resolver<E>>,
         modules: &mut HashMap<ModuleId, Module<'ast, T>>,
+        cache: &mut CacheState<'ast, T>,
+        stack: &mut ImportStack,
         arena: &'ast Arena<String>,
-    ) -> Result<Module<'ast, T>, CompileErrors> {
+    ) -> Result<(Module<'ast, T>, Vec<(String, ModuleId)>), CompileErrors> {
         let mut symbols: Vec<_> = vec![];
+        let mut imported = vec![];
 
         for import in destination.imports {
             let pos = import.pos();
@@ -172,25 +239,40 @@ impl Importer {
                 }
             } else {
                 // to resolve imports, we need a resolver
-                match resolve_option {
-                    Some(resolve) => match resolve(location.clone(), &import.source) {
-                        Ok((mut reader, location, alias)) => {
-                            let mut source = String::new();
-                            reader.read_to_string(&mut source).unwrap();
+                match resolver {
+                    Some(r) => match r.resolve(location.clone(), &import.source) {
+                        Ok((source, next_location)) => {
+                            stack.set_import(pos);
 
-                            let source = arena.alloc(source);
+                            if let Some(e) = stack.cycle(&next_location) {
+                                return Err(CompileErrorInner::ImportError(e)
+                                    .with_context(&location)
+                                    .into());
+                            }
 
-                            let compiled = compile_module(
+                            // modules are identified by the location they resolve to, so that a module imported
+                            // under different paths is only compiled once
+                            load_module(
                                 source,
-                                Some(location),
-                                resolve_option,
+                                next_location.clone(),
+                                resolver,
                                 modules,
+                                cache,
+                                stack,
                                 &arena,
                             )
                             .map_err(|e| e.with_context(Some(import.source.to_string())))?;
-                            let alias = import.alias.clone().unwrap_or(alias);
+                            // the default alias of an imported symbol is its name, and that of an imported module
+                            // is its file name
+                            let alias =
+                                import.alias.clone().or(import.symbol).unwrap_or_else(|| {
+                                    Path::new(import.source)
+                                        .file_stem()
+                                        .and_then(|stem| stem.to_str())
+                                        .unwrap_or(import.source)
+                                });
 
-                            modules.insert(import.source.to_string(), compiled);
+                            imported.push((import.source.to_string(), next_location.clone()));
 
                             symbols.push(
                                 SymbolDeclaration {
@@ -198,7 +280,7 @@ impl Importer {
                                     symbol: Symbol::There(
                                         SymbolImport::with_id_in_module(
                                             import.symbol.unwrap_or("main"),
-                                            import.source.clone(),
+                                            next_location,
                                         )
                                         .start_end(pos.0, pos.1),
                                     ),
@@ -227,11 +309,14 @@ impl Importer {
 
         symbols.extend(destination.symbols);
 
-        Ok(Module {
-            imports: vec![],
-            symbols,
-            ..destination
-        })
+        Ok((
+            Module {
+                imports: vec![],
+                symbols,
+                ..destination
+            },
+            imported,
+        ))
     }
 }
 
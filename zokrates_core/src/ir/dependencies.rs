@@ -0,0 +1,218 @@
+//! Module containing the analysis of the dependencies between the statements of a function, used to execute independent
+//! statements in parallel and to drop values which are not needed anymore
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::witness::VariableMap;
+use crate::ir::{Function, LinComb, Statement};
+use std::cmp::max;
+use zokrates_field::field::Field;
+
+/// Groups the statements of `function` into levels, such that executing the levels in order, and the statements of
+/// each level in any order, assigns the same values as executing the statements sequentially
+pub(crate) fn levels<T: Field>(function: &Function<T>) -> Vec<Vec<usize>> {
+    // the first level at which each variable is assigned
+    let mut ready = VariableMap::new();
+    // the first level at which each variable can be reassigned without changing the value read by a statement
+    let mut read_after = VariableMap::new();
+
+    ready.insert(FlatVariable::one(), 0);
+    for argument in &function.arguments {
+        ready.insert(argument.clone(), 0);
+    }
+
+    let mut levels: Vec<Vec<usize>> = vec![];
+
+    for (index, statement) in function.statements.iter().enumerate() {
+        let (reads, writes) = accesses(statement, &ready);
+
+        let level = reads
+            .iter()
+            .map(|v| level_of(&ready, v))
+            .chain(
+                writes
+                    .iter()
+                    .map(|v| max(level_of(&ready, v), level_of(&read_after, v))),
+            )
+            .max()
+            .unwrap_or(0);
+
+        for v in reads {
+            let after = max(level_of(&read_after, &v), level + 1);
+            read_after.insert(v, after);
+        }
+        for v in writes {
+            ready.insert(v, level + 1);
+        }
+
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(index);
+    }
+
+    levels
+}
+
+/// For each statement of `function`, the variables it is the last to use, apart from `~one` and the outputs
+pub(crate) fn last_uses<T: Field>(function: &Function<T>) -> Vec<Vec<FlatVariable>> {
+    let mut last_use = VariableMap::new();
+
+    for (index, statement) in function.statements.iter().enumerate() {
+        for v in variables(statement) {
+            last_use.insert(v.clone(), index);
+        }
+    }
+
+    let mut res = vec![vec![]; function.statements.len()];
+    for (v, index) in last_use.iter() {
+        if v != FlatVariable::one() && !v.is_output() {
+            res[*index].push(v);
+        }
+    }
+    res
+}
+
+/// The level from which `variable` can be used according to `levels`
+fn level_of(levels: &VariableMap<usize>, variable: &FlatVariable) -> usize {
+    levels.get(variable).cloned().unwrap_or(0)
+}
+
+/// The variables `statement` reads and the ones it writes, given the variables assigned before it
+fn accesses<T: Field>(
+    statement: &Statement<T>,
+    assigned: &VariableMap<usize>,
+) -> (Vec<FlatVariable>, Vec<FlatVariable>) {
+    match statement {
+        Statement::Constraint(quad, lin, ..) => {
+            let reads = quad
+                .left
+                .0
+                .iter()
+                .chain(quad.right.0.iter())
+                .map(|(v, _)| *v);
+
+            match assignee(lin, |v| assigned.get(v).is_some()) {
+                Some(v) => (reads.collect(), vec![v]),
+                None => (reads.chain(lin.0.iter().map(|(v, _)| *v)).collect(), vec![]),
+            }
+        }
+        Statement::Directive(d, _) => (
+            d.inputs
+                .iter()
+                .flat_map(|i| i.0.iter().map(|(v, _)| *v))
+                .collect(),
+            d.outputs.clone(),
+        ),
+    }
+}
+
+/// The variables appearing in `statement`
+fn variables<'a, T: Field>(
+    statement: &'a Statement<T>,
+) -> Box<dyn Iterator<Item = &'a FlatVariable> + 'a> {
+    match statement {
+        Statement::Constraint(quad, lin, ..) => Box::new(
+            quad.left
+                .0
+                .iter()
+                .chain(quad.right.0.iter())
+                .chain(lin.0.iter())
+                .map(|(v, _)| v),
+        ),
+        Statement::Directive(d, _) => Box::new(
+            d.inputs
+                .iter()
+                .flat_map(|i| i.0.iter().map(|(v, _)| v))
+                .chain(d.outputs.iter()),
+        ),
+    }
+}
+
+/// The variable a constraint with linear combination `lin` assigns, if any: a constraint assigns its linear
+/// combination if it is a single variable with coefficient one which was not assigned before
+pub(crate) fn assignee<T: Field, F: Fn(&FlatVariable) -> bool>(
+    lin: &LinComb<T>,
+    is_assigned: F,
+) -> Option<FlatVariable> {
+    match lin.0.as_slice() {
+        [(v, c)] if *c == T::one() && !is_assigned(v) => Some(*v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{Helper, RustHelper};
+    use crate::ir::{Directive, QuadComb};
+    use zokrates_field::field::FieldPrime;
+
+    fn function(statements: Vec<Statement<FieldPrime>>) -> Function<FieldPrime> {
+        Function {
+            id: String::from("main"),
+            statements,
+            arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+            returns: vec![FlatVariable::public(0)],
+        }
+    }
+
+    fn bits(input: FlatVariable, outputs: Vec<FlatVariable>) -> Statement<FieldPrime> {
+        Statement::Directive(
+            Directive {
+                inputs: vec![input.into()],
+                outputs,
+                helper: Helper::Rust(RustHelper::Bits),
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn independent_directives_share_a_level() {
+        // # _2 = Bits(_0)
+        // # _3 = Bits(_1)
+        // _2 * _3 == _4
+        // _4 == ~out_0
+        let f = function(vec![
+            bits(FlatVariable::new(0), vec![FlatVariable::new(2)]),
+            bits(FlatVariable::new(1), vec![FlatVariable::new(3)]),
+            Statement::definition(
+                FlatVariable::new(4),
+                QuadComb::from_linear_combinations(
+                    FlatVariable::new(2).into(),
+                    FlatVariable::new(3).into(),
+                ),
+            ),
+            Statement::definition(FlatVariable::public(0), FlatVariable::new(4)),
+        ]);
+
+        assert_eq!(levels(&f), vec![vec![0, 1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn checks_follow_assignments() {
+        // _2 == _0
+        // _0 == _2
+        // _2 == _1
+        let f = function(vec![
+            Statement::definition(FlatVariable::new(2), FlatVariable::new(0)),
+            Statement::constraint(FlatVariable::new(2), FlatVariable::new(0)),
+            Statement::constraint(FlatVariable::new(1), FlatVariable::new(2)),
+        ]);
+
+        assert_eq!(levels(&f), vec![vec![0], vec![1, 2]]);
+    }
+
+    #[test]
+    fn last_uses_exclude_outputs() {
+        let f = function(vec![
+            Statement::definition(FlatVariable::new(2), FlatVariable::new(0)),
+            Statement::definition(FlatVariable::public(0), FlatVariable::new(2)),
+        ]);
+
+        assert_eq!(
+            last_uses(&f),
+            vec![vec![FlatVariable::new(0)], vec![FlatVariable::new(2)]]
+        );
+    }
+}
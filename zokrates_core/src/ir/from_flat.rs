@@ -6,11 +6,11 @@ use zokrates_field::field::Field;
 
 impl<T: Field> From<FlatFunction<T>> for Function<T> {
     fn from(flat_function: FlatFunction<T>) -> Function<T> {
-        let return_expressions: Vec<FlatExpression<T>> = flat_function
+        let (return_expressions, return_span): (Vec<FlatExpression<T>>, _) = flat_function
             .statements
             .iter()
             .filter_map(|s| match s {
-                FlatStatement::Return(el) => Some(el.expressions.clone()),
+                FlatStatement::Return(el, span) => Some((el.expressions.clone(), span.clone())),
                 _ => None,
             })
             .next()
@@ -38,6 +38,8 @@ impl<T: Field> From<FlatFunction<T>> for Function<T> {
                             Statement::Constraint(
                                 QuadComb::from_flat_expression(expression),
                                 FlatVariable::public(index).into(),
+                                None,
+                                return_span.clone(),
                             )
                         }),
                 )
@@ -106,21 +108,25 @@ impl<T: Field> From<FlatExpression<T>> for LinComb<T> {
 impl<T: Field> From<FlatStatement<T>> for Statement<T> {
     fn from(flat_statement: FlatStatement<T>) -> Statement<T> {
         match flat_statement {
-            FlatStatement::Condition(linear, quadratic) => match quadratic {
+            FlatStatement::Condition(linear, quadratic, metadata, span) => match quadratic {
                 FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
                     QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
                     linear.into(),
+                    metadata,
+                    span,
                 ),
-                e => Statement::Constraint(LinComb::from(e).into(), linear.into()),
+                e => Statement::Constraint(LinComb::from(e).into(), linear.into(), metadata, span),
             },
-            FlatStatement::Definition(var, quadratic) => match quadratic {
+            FlatStatement::Definition(var, quadratic, span) => match quadratic {
                 FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
                     QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
                     var.into(),
+                    None,
+                    span,
                 ),
-                e => Statement::Constraint(LinComb::from(e).into(), var.into()),
+                e => Statement::Constraint(LinComb::from(e).into(), var.into(), None, span),
             },
-            FlatStatement::Directive(ds) => Statement::Directive(ds.into()),
+            FlatStatement::Directive(ds, span) => Statement::Directive(ds.into(), span),
             _ => panic!("return should be handled at the function level"),
         }
     }
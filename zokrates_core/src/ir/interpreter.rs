@@ -1,60 +1,160 @@
 use crate::flat_absy::flat_variable::FlatVariable;
-use crate::helpers::Executable;
-use crate::ir::{LinComb, Prog, QuadComb, Statement, Witness};
-use std::collections::BTreeMap;
+use crate::helpers::{Executable, Helper};
+use crate::ir::dependencies::{self, assignee};
+use crate::ir::witness::{write_value, writer_of};
+use crate::ir::{Directive, LinComb, Prog, QuadComb, Statement, Witness};
+use crate::typed_absy::{AssertionMetadata, SourceSpan};
+use std::collections::HashMap;
 use std::fmt;
+use std::io::Write;
 use zokrates_field::field::Field;
 
 pub type ExecutionResult<T> = Result<Witness<T>, Error>;
 
+/// The minimum number of values the directives of a level must produce for them to be solved in parallel
+const PARALLEL_THRESHOLD: usize = 1 << 12;
+
 impl<T: Field> Prog<T> {
     pub fn execute(&self, inputs: &Vec<T>) -> ExecutionResult<T> {
-        let main = &self.main;
-        self.check_inputs(&inputs)?;
-        let mut witness = BTreeMap::new();
-        witness.insert(FlatVariable::one(), T::one());
-        for (arg, value) in main.arguments.iter().zip(inputs.iter()) {
-            witness.insert(arg.clone(), value.clone().into());
+        let mut witness = self.initial_witness(inputs)?;
+
+        for statement in &self.main.statements {
+            execute_statement(statement, &mut witness)?;
         }
 
-        for statement in &main.statements {
-            match statement {
-                Statement::Constraint(quad, lin) => match lin.is_assignee(&witness) {
-                    true => {
-                        let val = quad.evaluate(&witness).unwrap();
-                        witness.insert(lin.0.iter().next().unwrap().0.clone(), val);
-                    }
-                    false => {
-                        let lhs_value = quad.evaluate(&witness).unwrap();
-                        let rhs_value = lin.evaluate(&witness).unwrap();
-                        if lhs_value != rhs_value {
-                            return Err(Error::UnsatisfiedConstraint {
-                                left: lhs_value.to_dec_string(),
-                                right: rhs_value.to_dec_string(),
-                            });
-                        }
-                    }
-                },
-                Statement::Directive(ref d) => {
-                    let input_values: Vec<T> = d
-                        .inputs
+        Ok(witness)
+    }
+
+    /// Executes the program on up to `threads` threads, solving the directives which do not depend on each other in
+    /// parallel
+    pub fn execute_parallel(&self, inputs: &Vec<T>, threads: usize) -> ExecutionResult<T>
+    where
+        T: Send + Sync,
+    {
+        let mut witness = self.initial_witness(inputs)?;
+
+        for level in dependencies::levels(&self.main) {
+            let mut solvable = vec![];
+            let mut others = vec![];
+
+            for index in level {
+                match &self.main.statements[index] {
+                    Statement::Directive(
+                        Directive {
+                            helper: Helper::Rust(ref helper),
+                            ref inputs,
+                            ref outputs,
+                        },
+                        _,
+                    ) => solvable.push((helper, inputs, outputs)),
+                    statement => others.push(statement),
+                }
+            }
+
+            let size: usize = solvable.iter().map(|(_, _, outputs)| outputs.len()).sum();
+
+            let solutions: Vec<_> =
+                if threads > 1 && solvable.len() > 1 && size >= PARALLEL_THRESHOLD {
+                    let chunk_size = (solvable.len() + threads - 1) / threads;
+                    let witness = &witness;
+
+                    crossbeam::scope(|scope| {
+                        solvable
+                            .chunks(chunk_size)
+                            .map(|chunk| {
+                                scope.spawn(move |_| {
+                                    chunk
+                                        .iter()
+                                        .map(|(helper, inputs, _)| solve(*helper, inputs, witness))
+                                        .collect::<Vec<_>>()
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                            .flat_map(|handle| handle.join().unwrap())
+                            .collect()
+                    })
+                    .unwrap()
+                } else {
+                    solvable
                         .iter()
-                        .map(|i| i.evaluate(&witness).unwrap())
-                        .collect();
-                    match d.helper.execute(&input_values) {
-                        Ok(res) => {
-                            for (i, o) in d.outputs.iter().enumerate() {
-                                witness.insert(o.clone(), res[i].clone());
-                            }
-                            continue;
+                        .map(|(helper, inputs, _)| solve(*helper, inputs, &witness))
+                        .collect()
+                };
+
+            // the statements of a level may fail in a different order than the one of the program, so we execute
+            // it sequentially to report the first error
+            for (&(_, _, outputs), solution) in solvable.iter().zip(solutions) {
+                match solution {
+                    Ok(values) => {
+                        for (output, value) in outputs.iter().zip(values) {
+                            witness.insert(output.clone(), value);
                         }
-                        Err(_) => return Err(Error::Solver),
-                    };
+                    }
+                    Err(_) => return self.execute(inputs),
+                }
+            }
+
+            for statement in others {
+                if execute_statement(statement, &mut witness).is_err() {
+                    return self.execute(inputs);
                 }
             }
         }
 
-        Ok(Witness(witness))
+        Ok(witness)
+    }
+
+    /// Executes the program, writing the witness to `writer` as values get assigned instead of keeping it in memory,
+    /// and dropping the values which the remaining statements do not use. Returns the return values of the program.
+    pub fn execute_streaming<W: Write>(&self, inputs: &Vec<T>, writer: W) -> Result<Vec<T>, Error> {
+        self.check_inputs(&inputs)?;
+
+        let mut stream = Stream {
+            values: HashMap::new(),
+            writer: writer_of(writer),
+        };
+
+        stream.assign(FlatVariable::one(), T::one())?;
+        for (arg, value) in self.main.arguments.iter().zip(inputs.iter()) {
+            stream.assign(arg.clone(), value.clone())?;
+        }
+
+        for (statement, last_uses) in self
+            .main
+            .statements
+            .iter()
+            .zip(dependencies::last_uses(&self.main))
+        {
+            execute_statement(statement, &mut stream)?;
+            for variable in last_uses {
+                stream.values.remove(&variable);
+            }
+        }
+
+        stream
+            .writer
+            .flush()
+            .map_err(|e| Error::Io(e.to_string()))?;
+
+        Ok(self
+            .main
+            .returns
+            .iter()
+            .map(|variable| stream.values.remove(variable).unwrap())
+            .collect())
+    }
+
+    fn initial_witness(&self, inputs: &Vec<T>) -> ExecutionResult<T> {
+        self.check_inputs(&inputs)?;
+
+        let mut witness = Witness::empty();
+        witness.insert(FlatVariable::one(), T::one());
+        for (arg, value) in self.main.arguments.iter().zip(inputs.iter()) {
+            witness.insert(arg.clone(), value.clone());
+        }
+
+        Ok(witness)
     }
 
     fn check_inputs<U>(&self, inputs: &Vec<U>) -> Result<(), Error> {
@@ -69,35 +169,120 @@ impl<T: Field> Prog<T> {
     }
 }
 
-impl<T: Field> LinComb<T> {
-    fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> Result<T, ()> {
-        self.0
-            .iter()
-            .map(|(var, mult)| witness.get(var).map(|v| v.clone() * mult).ok_or(())) // get each term
-            .collect::<Result<Vec<_>, _>>() // fail if any term isn't found
-            .map(|v| v.iter().fold(T::from(0), |acc, t| acc + t)) // return the sum
+/// The values assigned to variables during an execution
+trait Values<T> {
+    fn get(&self, variable: &FlatVariable) -> Option<&T>;
+
+    fn assign(&mut self, variable: FlatVariable, value: T) -> Result<(), Error>;
+}
+
+impl<T: Field> Values<T> for Witness<T> {
+    fn get(&self, variable: &FlatVariable) -> Option<&T> {
+        Witness::get(self, variable)
+    }
+
+    fn assign(&mut self, variable: FlatVariable, value: T) -> Result<(), Error> {
+        self.insert(variable, value);
+        Ok(())
+    }
+}
+
+/// The values still in use during a streaming execution, and the writer the witness goes to
+struct Stream<T, W: Write> {
+    values: HashMap<FlatVariable, T>,
+    writer: csv::Writer<W>,
+}
+
+impl<T: Field, W: Write> Values<T> for Stream<T, W> {
+    fn get(&self, variable: &FlatVariable) -> Option<&T> {
+        self.values.get(variable)
+    }
+
+    fn assign(&mut self, variable: FlatVariable, value: T) -> Result<(), Error> {
+        write_value(&mut self.writer, &variable, &value).map_err(|e| Error::Io(e.to_string()))?;
+        self.values.insert(variable, value);
+        Ok(())
+    }
+}
+
+fn execute_statement<T: Field, V: Values<T>>(
+    statement: &Statement<T>,
+    values: &mut V,
+) -> Result<(), Error> {
+    match statement {
+        Statement::Constraint(quad, lin, metadata, span) => {
+            match assignee(lin, |v| values.get(v).is_some()) {
+                Some(variable) => {
+                    let val = quad.evaluate(values).unwrap();
+                    values.assign(variable, val)
+                }
+                None => {
+                    let lhs_value = quad.evaluate(values).unwrap();
+                    let rhs_value = lin.evaluate(values).unwrap();
+                    if lhs_value != rhs_value {
+                        return Err(match metadata {
+                            Some(metadata) => Error::AssertionFailed(metadata.clone()),
+                            None => Error::UnsatisfiedConstraint {
+                                left: lhs_value.to_dec_string(),
+                                right: rhs_value.to_dec_string(),
+                                location: span.clone(),
+                            },
+                        });
+                    }
+                    Ok(())
+                }
+            }
+        }
+        Statement::Directive(ref d, _) => {
+            let res = solve(&d.helper, &d.inputs, values)?;
+            for (o, value) in d.outputs.iter().zip(res) {
+                values.assign(o.clone(), value)?;
+            }
+            Ok(())
+        }
     }
+}
 
-    fn is_assignee<U>(&self, witness: &BTreeMap<FlatVariable, U>) -> bool {
-        self.0.iter().count() == 1
-            && self.0.iter().next().unwrap().1 == T::from(1)
-            && !witness.contains_key(&self.0.iter().next().unwrap().0)
+/// Computes the outputs of a directive from the values of its inputs
+fn solve<T: Field, H: Executable<T>, V: Values<T>>(
+    helper: &H,
+    inputs: &[LinComb<T>],
+    values: &V,
+) -> Result<Vec<T>, Error> {
+    let input_values: Vec<T> = inputs.iter().map(|i| i.evaluate(values).unwrap()).collect();
+    helper.execute(&input_values).map_err(|_| Error::Solver)
+}
+
+impl<T: Field> LinComb<T> {
+    fn evaluate<V: Values<T>>(&self, values: &V) -> Result<T, ()> {
+        self.0.iter().try_fold(T::zero(), |acc, (var, mult)| {
+            values.get(var).map(|v| acc + v.clone() * mult).ok_or(())
+        })
     }
 }
 
 impl<T: Field> QuadComb<T> {
-    pub fn evaluate(&self, witness: &BTreeMap<FlatVariable, T>) -> Result<T, ()> {
-        let left = self.left.evaluate(&witness)?;
-        let right = self.right.evaluate(&witness)?;
+    fn evaluate<V: Values<T>>(&self, values: &V) -> Result<T, ()> {
+        let left = self.left.evaluate(values)?;
+        let right = self.right.evaluate(values)?;
         Ok(left * right)
     }
 }
 
 #[derive(PartialEq, Serialize, Deserialize)]
 pub enum Error {
-    UnsatisfiedConstraint { left: String, right: String },
+    UnsatisfiedConstraint {
+        left: String,
+        right: String,
+        location: Option<SourceSpan>,
+    },
+    AssertionFailed(AssertionMetadata),
     Solver,
-    WrongInputCount { expected: usize, received: usize },
+    WrongInputCount {
+        expected: usize,
+        received: usize,
+    },
+    Io(String),
 }
 
 impl fmt::Display for Error {
@@ -106,7 +291,15 @@ impl fmt::Display for Error {
             Error::UnsatisfiedConstraint {
                 ref left,
                 ref right,
-            } => write!(f, "Expected {} to equal {}", left, right),
+                ref location,
+            } => {
+                write!(f, "Expected {} to equal {}", left, right)?;
+                match location {
+                    Some(location) => write!(f, " at {}", location),
+                    None => Ok(()),
+                }
+            }
+            Error::AssertionFailed(ref metadata) => write!(f, "assertion failed at {}", metadata),
             Error::Solver => write!(f, ""),
             Error::WrongInputCount { expected, received } => write!(
                 f,
@@ -116,6 +309,7 @@ impl fmt::Display for Error {
                 received,
                 if received == 1 { "" } else { "s" }
             ),
+            Error::Io(ref e) => write!(f, "Could not write the witness: {}", e),
         }
     }
 }
@@ -125,3 +319,108 @@ impl fmt::Debug for Error {
         write!(f, "{}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::RustHelper;
+    use crate::ir::Function;
+    use crate::typed_absy::types::{Signature, Type};
+    use std::io::Cursor;
+    use zokrates_field::field::FieldPrime;
+
+    const DECOMPOSITIONS: usize = 20;
+
+    /// A program decomposing each of its arguments into bits independently, and returning the sum of their lowest bits
+    fn decompositions(checked: bool) -> Prog<FieldPrime> {
+        let arguments: Vec<_> = (0..DECOMPOSITIONS).map(FlatVariable::new).collect();
+        let bits = |i: usize| -> Vec<_> {
+            (0..254)
+                .map(|j| FlatVariable::new(DECOMPOSITIONS + 254 * i + j))
+                .collect()
+        };
+
+        let mut statements: Vec<_> = arguments
+            .iter()
+            .enumerate()
+            .map(|(i, argument)| {
+                Statement::Directive(
+                    Directive {
+                        inputs: vec![argument.clone().into()],
+                        outputs: bits(i),
+                        helper: Helper::Rust(RustHelper::Bits),
+                    },
+                    None,
+                )
+            })
+            .collect();
+
+        if checked {
+            statements.push(Statement::constraint(
+                bits(0)[253].clone(),
+                FieldPrime::from(0),
+            ));
+        }
+
+        statements.push(Statement::definition(
+            FlatVariable::public(0),
+            LinComb(
+                (0..DECOMPOSITIONS)
+                    .map(|i| (bits(i)[253].clone(), FieldPrime::from(1)))
+                    .collect(),
+            ),
+        ));
+
+        Prog {
+            signature: Signature::new()
+                .inputs(vec![Type::FieldElement; DECOMPOSITIONS])
+                .outputs(vec![Type::FieldElement]),
+            main: Function {
+                id: String::from("main"),
+                statements,
+                arguments,
+                returns: vec![FlatVariable::public(0)],
+            },
+            private: vec![true; DECOMPOSITIONS],
+        }
+    }
+
+    fn inputs() -> Vec<FieldPrime> {
+        (0..DECOMPOSITIONS)
+            .map(|i| FieldPrime::from(i + 1))
+            .collect()
+    }
+
+    #[test]
+    fn parallel() {
+        let program = decompositions(false);
+
+        let witness = program.execute(&inputs()).unwrap();
+        assert_eq!(
+            witness.return_values(),
+            vec![FieldPrime::from(DECOMPOSITIONS / 2)]
+        );
+        assert_eq!(program.execute_parallel(&inputs(), 4).unwrap(), witness);
+    }
+
+    #[test]
+    fn parallel_error() {
+        let program = decompositions(true);
+
+        let error = program.execute(&inputs()).unwrap_err();
+        assert_eq!(program.execute_parallel(&inputs(), 4).unwrap_err(), error);
+    }
+
+    #[test]
+    fn streaming() {
+        let program = decompositions(false);
+
+        let mut buffer = Cursor::new(vec![]);
+        let return_values = program.execute_streaming(&inputs(), &mut buffer).unwrap();
+        buffer.set_position(0);
+
+        let witness = program.execute(&inputs()).unwrap();
+        assert_eq!(return_values, witness.return_values());
+        assert_eq!(Witness::read(buffer).unwrap(), witness);
+    }
+}
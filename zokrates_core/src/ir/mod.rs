@@ -3,33 +3,53 @@ use crate::flat_absy::FlatVariable;
 use crate::helpers::Helper;
 use std::fmt;
 use typed_absy::types::signature::Signature;
+use typed_absy::{AssertionMetadata, SourceSpan};
 use zokrates_field::field::Field;
 
+mod dependencies;
 mod expression;
 pub mod folder;
 mod from_flat;
 mod interpreter;
+mod profile;
+mod serialize;
 mod witness;
 
 pub use self::expression::QuadComb;
 pub use self::expression::{CanonicalLinComb, LinComb};
 
 pub use self::interpreter::{Error, ExecutionResult};
+pub use self::profile::{Cost, Profile};
+pub use self::serialize::ProgEnum;
 pub use self::witness::Witness;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash, Eq)]
 pub enum Statement<T: Field> {
-    Constraint(QuadComb<T>, LinComb<T>),
-    Directive(Directive<T>),
+    Constraint(
+        QuadComb<T>,
+        LinComb<T>,
+        Option<AssertionMetadata>,
+        Option<SourceSpan>,
+    ),
+    Directive(Directive<T>, Option<SourceSpan>),
 }
 
 impl<T: Field> Statement<T> {
     pub fn definition<U: Into<QuadComb<T>>>(v: FlatVariable, e: U) -> Self {
-        Statement::Constraint(e.into(), v.into())
+        Statement::Constraint(e.into(), v.into(), None, None)
     }
 
     pub fn constraint<U: Into<QuadComb<T>>, V: Into<LinComb<T>>>(quad: U, lin: V) -> Self {
-        Statement::Constraint(quad.into(), lin.into())
+        Statement::Constraint(quad.into(), lin.into(), None, None)
+    }
+
+    /// The location of the source this statement was compiled from, if known
+    pub fn span(&self) -> Option<&SourceSpan> {
+        match *self {
+            Statement::Constraint(_, _, _, ref span) | Statement::Directive(_, ref span) => {
+                span.as_ref()
+            }
+        }
     }
 }
 
@@ -63,8 +83,8 @@ impl<T: Field> fmt::Display for Directive<T> {
 impl<T: Field> fmt::Display for Statement<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Statement::Constraint(ref quad, ref lin) => write!(f, "{} == {}", quad, lin),
-            Statement::Directive(ref s) => write!(f, "{}", s),
+            Statement::Constraint(ref quad, ref lin, ..) => write!(f, "{} == {}", quad, lin),
+            Statement::Directive(ref s, _) => write!(f, "{}", s),
         }
     }
 }
@@ -91,7 +111,10 @@ impl<T: Field> fmt::Display for Function<T> {
             self.returns.len(),
             self.statements
                 .iter()
-                .map(|s| format!("\t{}", s))
+                .map(|s| match s.span() {
+                    Some(span) => format!("\t{} // {}", s, span),
+                    None => format!("\t{}", s),
+                })
                 .collect::<Vec<_>>()
                 .join("\n"),
             self.returns
@@ -161,8 +184,46 @@ mod tests {
                     FlatVariable::new(42).into(),
                 ),
                 FlatVariable::new(42).into(),
+                None,
+                None,
             );
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
     }
+
+    mod function {
+        use super::*;
+        use parser::Position;
+
+        #[test]
+        fn print_source_map() {
+            let f: Function<FieldPrime> = Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(1).into(),
+                        None,
+                        Some(SourceSpan {
+                            module: String::from("./foo.zok"),
+                            from: Position { line: 2, col: 5 },
+                            to: Position { line: 2, col: 10 },
+                            stack: vec![],
+                        }),
+                    ),
+                    Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+                ],
+            };
+
+            assert_eq!(
+                format!("{}", f),
+                "def main(_0) -> (1):
+\t(1 * ~one) * (1 * _0) == 1 * _1 // ./foo.zok:2:5
+\t(1 * ~one) * (1 * _1) == 1 * ~out_0
+\t return ~out_0"
+            )
+        }
+    }
 }
@@ -0,0 +1,226 @@
+//! Module attributing the cost of a compiled program to the source it was compiled from
+//!
+//! @file profile.rs
+//! @date 2020
+
+use ir::{Prog, Statement};
+use std::collections::HashMap;
+use std::fmt;
+use typed_absy::CallFrame;
+use zokrates_field::field::Field;
+
+const UNKNOWN: &str = "<unknown>";
+
+/// The cost of some part of a program
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Cost {
+    /// the part of the program the cost is attributed to
+    pub location: String,
+    /// the number of constraints
+    pub constraints: usize,
+    /// the number of directives
+    pub directives: usize,
+}
+
+/// A report of where the constraints and directives of a program come from, most expensive first
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// the number of constraints in the program
+    pub constraints: usize,
+    /// the number of directives in the program
+    pub directives: usize,
+    /// the cost of each function, excluding the functions it calls
+    pub functions: Vec<Cost>,
+    /// the cost of each call site, including the functions called from there
+    pub call_sites: Vec<Cost>,
+    /// the cost of each line of source code
+    pub lines: Vec<Cost>,
+}
+
+#[derive(Default)]
+struct Costs(HashMap<String, (usize, usize)>);
+
+impl Costs {
+    fn add(&mut self, location: String, (constraints, directives): (usize, usize)) {
+        let cost = self.0.entry(location).or_insert((0, 0));
+        cost.0 += constraints;
+        cost.1 += directives;
+    }
+
+    fn sorted(self) -> Vec<Cost> {
+        let mut costs: Vec<_> = self
+            .0
+            .into_iter()
+            .map(|(location, (constraints, directives))| Cost {
+                location,
+                constraints,
+                directives,
+            })
+            .collect();
+        costs.sort_by(|a, b| {
+            b.constraints
+                .cmp(&a.constraints)
+                .then(b.directives.cmp(&a.directives))
+                .then(a.location.cmp(&b.location))
+        });
+        costs
+    }
+}
+
+impl<T: Field> Prog<T> {
+    /// Attribute the constraints and directives of this program to the functions, call sites and lines they come from
+    pub fn profile(&self) -> Profile {
+        let mut functions = Costs::default();
+        let mut call_sites = Costs::default();
+        let mut lines = Costs::default();
+
+        for statement in &self.main.statements {
+            let cost = match statement {
+                Statement::Constraint(..) => (1, 0),
+                Statement::Directive(..) => (0, 1),
+            };
+
+            match statement.span() {
+                Some(span) => {
+                    // statements which were not inlined come from the main function
+                    let function = match span.stack.last() {
+                        Some(frame) => format!("{}", frame),
+                        None => format!(
+                            "{}",
+                            CallFrame {
+                                module: span.module.clone(),
+                                function: String::from("main"),
+                                call_site: None,
+                            }
+                        ),
+                    };
+                    functions.add(function, cost);
+
+                    for frame in &span.stack {
+                        if let Some(ref call_site) = frame.call_site {
+                            call_sites.add(format!("{} -> {}", call_site, frame), cost);
+                        }
+                    }
+
+                    lines.add(format!("{}:{}", span.module, span.from.line), cost);
+                }
+                None => {
+                    functions.add(String::from(UNKNOWN), cost);
+                    lines.add(String::from(UNKNOWN), cost);
+                }
+            }
+        }
+
+        Profile {
+            constraints: self.constraint_count(),
+            directives: self.main.statements.len() - self.constraint_count(),
+            functions: functions.sorted(),
+            call_sites: call_sites.sorted(),
+            lines: lines.sorted(),
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (title, costs) in &[
+            ("function", &self.functions),
+            ("call site", &self.call_sites),
+            ("line", &self.lines),
+        ] {
+            writeln!(f, "{:>12} {:>12}  {}", "constraints", "directives", title)?;
+            for cost in costs.iter() {
+                writeln!(
+                    f,
+                    "{:>12} {:>12}  {}",
+                    cost.constraints, cost.directives, cost.location
+                )?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "{:>12} {:>12}  total", self.constraints, self.directives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flat_absy::FlatVariable;
+    use ir::Function;
+    use parser::Position;
+    use typed_absy::types::Signature;
+    use typed_absy::SourceSpan;
+    use zokrates_field::field::FieldPrime;
+
+    fn span(line: usize, stack: Vec<CallFrame>) -> Option<SourceSpan> {
+        Some(SourceSpan {
+            module: String::from("main"),
+            from: Position { line, col: 1 },
+            to: Position { line, col: 10 },
+            stack,
+        })
+    }
+
+    fn constraint(span: Option<SourceSpan>) -> Statement<FieldPrime> {
+        Statement::Constraint(
+            FlatVariable::new(0).into(),
+            FlatVariable::new(1).into(),
+            None,
+            span,
+        )
+    }
+
+    #[test]
+    fn attribute_to_functions_call_sites_and_lines() {
+        // main calls foo on line 3, which has two constraints on line 7
+        let foo = CallFrame {
+            module: String::from("./foo"),
+            function: String::from("foo"),
+            call_site: span(3, vec![]),
+        };
+
+        let foo_span = Some(SourceSpan {
+            module: String::from("./foo"),
+            ..span(7, vec![foo]).unwrap()
+        });
+
+        let prog: Prog<FieldPrime> = Prog {
+            main: Function {
+                id: String::from("main"),
+                statements: vec![
+                    constraint(span(2, vec![])),
+                    constraint(foo_span.clone()),
+                    constraint(foo_span),
+                    constraint(None),
+                ],
+                arguments: vec![],
+                returns: vec![],
+            },
+            private: vec![],
+            signature: Signature::new(),
+        };
+
+        let profile = prog.profile();
+
+        let cost = |location: &str, constraints| Cost {
+            location: String::from(location),
+            constraints,
+            directives: 0,
+        };
+
+        assert_eq!(profile.constraints, 4);
+        assert_eq!(
+            profile.functions,
+            vec![
+                cost("./foo::foo", 2),
+                cost("<unknown>", 1),
+                cost("main::main", 1)
+            ]
+        );
+        assert_eq!(profile.call_sites, vec![cost("main:3:1 -> ./foo::foo", 2)]);
+        assert_eq!(
+            profile.lines,
+            vec![cost("./foo:7", 2), cost("<unknown>", 1), cost("main:2", 1)]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::ir::Prog;
+use bincode::{deserialize_from, serialize_into, Infinite};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Read, Write};
+use zokrates_field::field::{Bls12_381Field, Field, FieldPrime};
+
+/// A compiled program over the scalar field of one of the supported curves
+#[derive(Debug, PartialEq)]
+pub enum ProgEnum {
+    Bn128Program(Prog<FieldPrime>),
+    Bls12_381Program(Prog<Bls12_381Field>),
+}
+
+impl<T: Field + Serialize> Prog<T> {
+    /// Writes the program in binary, preceded by the name of its curve
+    pub fn serialize<W: Write>(&self, mut writer: W) -> Result<(), String> {
+        serialize_into(&mut writer, T::name(), Infinite)
+            .and_then(|_| serialize_into(&mut writer, self, Infinite))
+            .map_err(|why| why.to_string())
+    }
+}
+
+impl ProgEnum {
+    /// Reads a program written by `Prog::serialize`, over the field of the curve it is tagged with
+    pub fn deserialize<R: Read>(mut reader: R) -> Result<Self, String> {
+        let curve: String =
+            deserialize_from(&mut reader, Infinite).map_err(|why| why.to_string())?;
+
+        match curve.as_str() {
+            c if c == FieldPrime::name() => {
+                Ok(ProgEnum::Bn128Program(deserialize_program(reader)?))
+            }
+            c if c == Bls12_381Field::name() => {
+                Ok(ProgEnum::Bls12_381Program(deserialize_program(reader)?))
+            }
+            c => Err(format!(
+                "Unknown curve {}, the program may need to be compiled again",
+                c
+            )),
+        }
+    }
+
+    /// The name of the curve the program was compiled for
+    pub fn curve(&self) -> &'static str {
+        match self {
+            ProgEnum::Bn128Program(_) => FieldPrime::name(),
+            ProgEnum::Bls12_381Program(_) => Bls12_381Field::name(),
+        }
+    }
+}
+
+fn deserialize_program<T: Field + DeserializeOwned, R: Read>(
+    mut reader: R,
+) -> Result<Prog<T>, String> {
+    deserialize_from(&mut reader, Infinite).map_err(|why| why.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::Function;
+    use typed_absy::types::Signature;
+
+    fn program<T: Field>() -> Prog<T> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![],
+                returns: vec![],
+                statements: vec![],
+            },
+            private: vec![],
+            signature: Signature::new(),
+        }
+    }
+
+    #[test]
+    fn serialize_deserialize() {
+        let mut bn128 = vec![];
+        program::<FieldPrime>().serialize(&mut bn128).unwrap();
+        assert_eq!(
+            ProgEnum::deserialize(&bn128[..]).unwrap(),
+            ProgEnum::Bn128Program(program())
+        );
+
+        let mut bls12_381 = vec![];
+        program::<Bls12_381Field>()
+            .serialize(&mut bls12_381)
+            .unwrap();
+        let deserialized = ProgEnum::deserialize(&bls12_381[..]).unwrap();
+        assert_eq!(deserialized.curve(), "bls12_381");
+        assert_eq!(deserialized, ProgEnum::Bls12_381Program(program()));
+    }
+
+    #[test]
+    fn unknown_curve() {
+        let mut untagged = vec![];
+        serialize_into(&mut untagged, "foo", Infinite).unwrap();
+        serialize_into(&mut untagged, &program::<FieldPrime>(), Infinite).unwrap();
+
+        assert_eq!(
+            ProgEnum::deserialize(&untagged[..]).unwrap_err(),
+            "Unknown curve foo, the program may need to be compiled again"
+        );
+    }
+}
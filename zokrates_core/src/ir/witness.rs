@@ -1,30 +1,129 @@
 use crate::flat_absy::FlatVariable;
-use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::io;
 use std::io::{Read, Write};
+use std::iter::FromIterator;
 use zokrates_field::field::Field;
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Witness<T: Field>(pub BTreeMap<FlatVariable, T>);
+/// A map from variables to values, stored in two vectors indexed by the position of the variables among the outputs
+/// and among `~one` and the other variables
+#[derive(Clone, Debug)]
+pub(crate) struct VariableMap<V> {
+    outputs: Vec<Option<V>>,
+    variables: Vec<Option<V>>,
+}
+
+impl<V> VariableMap<V> {
+    pub fn new() -> Self {
+        VariableMap {
+            outputs: vec![],
+            variables: vec![],
+        }
+    }
+
+    fn slots(&self, variable: &FlatVariable) -> &Vec<Option<V>> {
+        if variable.is_output() {
+            &self.outputs
+        } else {
+            &self.variables
+        }
+    }
+
+    fn slots_mut(&mut self, variable: &FlatVariable) -> &mut Vec<Option<V>> {
+        if variable.is_output() {
+            &mut self.outputs
+        } else {
+            &mut self.variables
+        }
+    }
+
+    pub fn get(&self, variable: &FlatVariable) -> Option<&V> {
+        self.slots(variable)
+            .get(variable.index())
+            .and_then(|v| v.as_ref())
+    }
+
+    pub fn insert(&mut self, variable: FlatVariable, value: V) -> Option<V> {
+        let index = variable.index();
+        let slots = self.slots_mut(&variable);
+        if slots.len() <= index {
+            slots.resize_with(index + 1, || None);
+        }
+        slots[index].replace(value)
+    }
+
+    pub fn remove(&mut self, variable: &FlatVariable) -> Option<V> {
+        let index = variable.index();
+        self.slots_mut(variable)
+            .get_mut(index)
+            .and_then(|v| v.take())
+    }
+
+    /// The variables and their values, ordered as `FlatVariable`s are
+    pub fn iter(&self) -> impl Iterator<Item = (FlatVariable, &V)> {
+        let outputs = self
+            .outputs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(|(i, v)| v.as_ref().map(|v| (FlatVariable::public(i), v)));
+        let variables = self.variables.iter().enumerate().filter_map(|(i, v)| {
+            v.as_ref().map(|v| match i {
+                0 => (FlatVariable::one(), v),
+                i => (FlatVariable::new(i - 1), v),
+            })
+        });
+
+        outputs.chain(variables)
+    }
+}
+
+/// The values of the variables of a program
+#[derive(Clone, Debug)]
+pub struct Witness<T: Field>(VariableMap<T>);
 
 impl<T: Field> Witness<T> {
+    pub fn get(&self, variable: &FlatVariable) -> Option<&T> {
+        self.0.get(variable)
+    }
+
+    pub fn contains(&self, variable: &FlatVariable) -> bool {
+        self.0.get(variable).is_some()
+    }
+
+    /// Assigns `value` to `variable`, returning its previous value if it had one
+    pub fn insert(&mut self, variable: FlatVariable, value: T) -> Option<T> {
+        self.0.insert(variable, value)
+    }
+
+    pub fn remove(&mut self, variable: &FlatVariable) -> Option<T> {
+        self.0.remove(variable)
+    }
+
+    /// The variables and their values, ordered as `FlatVariable`s are
+    pub fn iter(&self) -> impl Iterator<Item = (FlatVariable, &T)> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// The values of the outputs in order, skipping the ones missing from a witness read from a file
     pub fn return_values(&self) -> Vec<T> {
-        let out = self
-            .0
+        self.0
+            .outputs
             .iter()
-            .filter(|(k, _)| k.is_output())
-            .collect::<HashMap<_, _>>();
-
-        (0..out.len())
-            .map(|i| *out.get(&FlatVariable::public(i)).unwrap())
-            .cloned()
+            .filter_map(|value| value.clone())
             .collect()
     }
 
     pub fn format_outputs(&self) -> String {
-        self.0
-            .iter()
+        self.iter()
             .filter_map(|(variable, value)| match variable {
                 variable if variable.is_output() => Some(format!("{} {}", variable, value)),
                 _ => None,
@@ -34,19 +133,15 @@ impl<T: Field> Witness<T> {
     }
 
     pub fn empty() -> Self {
-        Witness(BTreeMap::new())
+        Witness(VariableMap::new())
     }
 
     pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
-        let mut wtr = csv::WriterBuilder::new()
-            .delimiter(b' ')
-            .flexible(true)
-            .has_headers(false)
-            .from_writer(writer);
+        let mut wtr = writer_of(writer);
 
         // Write each line of the witness to the file
-        for (variable, value) in &self.0 {
-            wtr.serialize((variable.to_string(), value.to_dec_string()))?;
+        for (variable, value) in self.iter() {
+            write_value(&mut wtr, &variable, value)?;
         }
 
         Ok(())
@@ -59,8 +154,7 @@ impl<T: Field> Witness<T> {
             .has_headers(false)
             .from_reader(&mut reader);
 
-        let map = rdr
-            .deserialize::<(String, String)>()
+        rdr.deserialize::<(String, String)>()
             .map(|r| {
                 r.map(|(variable, value)| {
                     let variable =
@@ -83,9 +177,43 @@ impl<T: Field> Witness<T> {
                     e => io::Error::new(io::ErrorKind::Other, format!("{:?}", e)),
                 })?
             })
-            .collect::<io::Result<BTreeMap<FlatVariable, T>>>()?;
+            .collect()
+    }
+}
+
+/// A writer of witness lines to `writer`
+pub(crate) fn writer_of<W: Write>(writer: W) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .delimiter(b' ')
+        .flexible(true)
+        .has_headers(false)
+        .from_writer(writer)
+}
+
+/// Writes the line of the witness for `variable`
+pub(crate) fn write_value<T: Field, W: Write>(
+    writer: &mut csv::Writer<W>,
+    variable: &FlatVariable,
+    value: &T,
+) -> io::Result<()> {
+    writer
+        .serialize((variable.to_string(), value.to_dec_string()))
+        .map_err(|e| e.into())
+}
 
-        Ok(Witness(map))
+impl<T: Field> PartialEq for Witness<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Field> FromIterator<(FlatVariable, T)> for Witness<T> {
+    fn from_iter<I: IntoIterator<Item = (FlatVariable, T)>>(iter: I) -> Self {
+        let mut witness = Witness::empty();
+        for (variable, value) in iter {
+            witness.insert(variable, value);
+        }
+        witness
     }
 }
 
@@ -94,8 +222,7 @@ impl<T: Field> fmt::Display for Witness<T> {
         write!(
             f,
             "{}",
-            self.0
-                .iter()
+            self.iter()
                 .map(|(k, v)| format!("{} {}", k, v.to_dec_string()))
                 .collect::<Vec<_>>()
                 .join("\n")
@@ -108,21 +235,34 @@ mod tests {
     use super::*;
     use zokrates_field::field::FieldPrime;
 
+    #[test]
+    fn return_values_with_missing_output() {
+        let w: Witness<_> = vec![
+            (FlatVariable::public(0), FieldPrime::from(1)),
+            (FlatVariable::public(2), FieldPrime::from(3)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            w.return_values(),
+            vec![FieldPrime::from(1), FieldPrime::from(3)]
+        );
+    }
+
     mod io {
         use super::*;
         use std::io::Cursor;
 
         #[test]
         fn serialize_deserialize() {
-            let w = Witness(
-                vec![
-                    (FlatVariable::new(42), FieldPrime::from(42)),
-                    (FlatVariable::public(8), FieldPrime::from(8)),
-                    (FlatVariable::one(), FieldPrime::from(1)),
-                ]
-                .into_iter()
-                .collect(),
-            );
+            let w: Witness<_> = vec![
+                (FlatVariable::new(42), FieldPrime::from(42)),
+                (FlatVariable::public(8), FieldPrime::from(8)),
+                (FlatVariable::one(), FieldPrime::from(1)),
+            ]
+            .into_iter()
+            .collect();
 
             let mut buff = Cursor::new(vec![]);
 
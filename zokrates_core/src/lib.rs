@@ -10,11 +10,13 @@ extern crate typed_arena;
 extern crate serde_derive;
 extern crate bellman_ce as bellman;
 extern crate bincode;
+extern crate crypto;
 extern crate ff_ce as ff;
 extern crate lazy_static;
 extern crate pairing_ce as pairing;
 #[cfg(feature = "wasm")]
 extern crate parity_wasm;
+extern crate rand;
 extern crate regex;
 #[cfg(feature = "wasm")]
 extern crate rustc_hex;
@@ -36,8 +38,10 @@ mod semantics;
 mod static_analysis;
 
 pub mod absy;
+pub mod cache;
 pub mod compile;
 pub mod flat_absy;
 pub mod ir;
 pub mod proof_system;
+pub mod resolve;
 pub mod typed_absy;
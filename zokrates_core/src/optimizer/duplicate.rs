@@ -11,7 +11,12 @@ fn hash<T: Field>(s: &Statement<T>) -> Hash {
     use std::hash::Hash;
     use std::hash::Hasher;
     let mut hasher = DefaultHasher::new();
-    s.hash(&mut hasher);
+    // constraints are duplicates whatever assertion they were compiled from, in which case the
+    // metadata of the first one is kept
+    match s {
+        Statement::Constraint(quad, lin, ..) => (quad, lin).hash(&mut hasher),
+        Statement::Directive(d, _) => d.hash(&mut hasher),
+    }
     hasher.finish()
 }
 
@@ -67,6 +72,8 @@ mod tests {
                             LinComb::summand(3, FlatVariable::new(3)),
                         ),
                         LinComb::one(),
+                        None,
+                        None,
                     ),
                     Statement::Constraint(
                         QuadComb::from_linear_combinations(
@@ -74,6 +81,8 @@ mod tests {
                             LinComb::summand(3, FlatVariable::new(3)),
                         ),
                         LinComb::zero(),
+                        None,
+                        None,
                     ),
                 ],
                 returns: vec![],
@@ -97,6 +106,8 @@ mod tests {
                 LinComb::summand(3, FlatVariable::new(3)),
             ),
             LinComb::one(),
+            None,
+            None,
         );
 
         let p: Prog<FieldPrime> = Prog {
@@ -112,6 +123,8 @@ mod tests {
                             LinComb::summand(3, FlatVariable::new(3)),
                         ),
                         LinComb::zero(),
+                        None,
+                        None,
                     ),
                     constraint.clone(),
                     constraint.clone(),
@@ -134,6 +147,8 @@ mod tests {
                             LinComb::summand(3, FlatVariable::new(3)),
                         ),
                         LinComb::zero(),
+                        None,
+                        None,
                     ),
                 ],
                 returns: vec![],
@@ -144,4 +159,170 @@ mod tests {
 
         assert_eq!(DuplicateOptimizer::optimize(p), expected);
     }
+
+    #[test]
+    fn remove_duplicates_from_different_locations() {
+        use parser::Position;
+        use typed_absy::SourceSpan;
+
+        let span = |line| {
+            Some(SourceSpan {
+                module: String::from("main"),
+                from: Position { line, col: 1 },
+                to: Position { line, col: 10 },
+                stack: vec![],
+            })
+        };
+
+        let constraint = |line| {
+            Statement::Constraint(
+                QuadComb::from_linear_combinations(
+                    LinComb::summand(3, FlatVariable::new(3)),
+                    LinComb::summand(3, FlatVariable::new(3)),
+                ),
+                LinComb::one(),
+                None,
+                span(line),
+            )
+        };
+
+        let p: Prog<FieldPrime> = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint(1), constraint(2)],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        let expected = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint(1)],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        assert_eq!(DuplicateOptimizer::optimize(p), expected);
+    }
+
+    #[test]
+    fn remove_duplicate_assertions_from_different_call_sites() {
+        use parser::Position;
+        use typed_absy::{AssertionMetadata, CallFrame, SourceSpan};
+
+        // the same assertion in a function called from two different lines
+        let metadata = |line| {
+            Some(AssertionMetadata {
+                span: SourceSpan {
+                    module: String::from("main"),
+                    from: Position { line: 2, col: 5 },
+                    to: Position { line: 2, col: 15 },
+                    stack: vec![CallFrame {
+                        module: String::from("main"),
+                        function: String::from("check"),
+                        call_site: Some(SourceSpan {
+                            module: String::from("main"),
+                            from: Position { line, col: 1 },
+                            to: Position { line, col: 10 },
+                            stack: vec![],
+                        }),
+                    }],
+                },
+                message: Some(String::from("out of range")),
+            })
+        };
+
+        let constraint = |line| {
+            Statement::Constraint(
+                QuadComb::from_linear_combinations(
+                    LinComb::summand(3, FlatVariable::new(3)),
+                    LinComb::summand(3, FlatVariable::new(3)),
+                ),
+                LinComb::one(),
+                metadata(line),
+                None,
+            )
+        };
+
+        let p: Prog<FieldPrime> = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint(5), constraint(6)],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        let expected = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint(5)],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        assert_eq!(DuplicateOptimizer::optimize(p), expected);
+    }
+
+    #[test]
+    fn remove_duplicate_assertions_with_different_messages() {
+        use parser::Position;
+        use typed_absy::{AssertionMetadata, SourceSpan};
+
+        // the same constraint from two assertions with different messages
+        let constraint = |message: &str| {
+            Statement::Constraint(
+                QuadComb::from_linear_combinations(
+                    LinComb::summand(3, FlatVariable::new(3)),
+                    LinComb::summand(3, FlatVariable::new(3)),
+                ),
+                LinComb::one(),
+                Some(AssertionMetadata {
+                    span: SourceSpan {
+                        module: String::from("main"),
+                        from: Position { line: 2, col: 5 },
+                        to: Position { line: 2, col: 15 },
+                        stack: vec![],
+                    },
+                    message: Some(String::from(message)),
+                }),
+                None,
+            )
+        };
+
+        let p: Prog<FieldPrime> = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint("too low"), constraint("too high")],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        let expected = Prog {
+            private: vec![],
+            main: Function {
+                id: "main".to_string(),
+                statements: vec![constraint("too low")],
+                returns: vec![],
+                arguments: vec![],
+            },
+            signature: Signature::new(),
+        };
+
+        assert_eq!(DuplicateOptimizer::optimize(p), expected);
+    }
 }
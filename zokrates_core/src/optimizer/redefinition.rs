@@ -61,7 +61,7 @@ impl<T: Field> RedefinitionOptimizer<T> {
 impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
         match s {
-            Statement::Constraint(quad, lin) => {
+            Statement::Constraint(quad, lin, metadata, span) => {
                 let quad = self.fold_quadratic_combination(quad);
                 let lin = self.fold_linear_combination(lin);
 
@@ -94,16 +94,16 @@ impl<T: Field> Folder<T> for RedefinitionOptimizer<T> {
                 // decide whether the constraint should be kept
                 match keep_constraint {
                     false => vec![],
-                    true => vec![Statement::Constraint(quad, lin)],
+                    true => vec![Statement::Constraint(quad, lin, metadata, span)],
                 }
             }
-            Statement::Directive(d) => {
+            Statement::Directive(d, span) => {
                 let d = self.fold_directive(d);
                 // to prevent the optimiser from replacing variables introduced by directives, add them to the substitution
                 for o in d.outputs.iter() {
                     self.substitution.insert(o.clone(), o.clone().into());
                 }
-                vec![Statement::Directive(d)]
+                vec![Statement::Directive(d, span)]
             }
         }
     }
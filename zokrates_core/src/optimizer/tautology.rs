@@ -25,7 +25,7 @@ impl TautologyOptimizer {
 impl<T: Field> Folder<T> for TautologyOptimizer {
     fn fold_statement(&mut self, s: Statement<T>) -> Vec<Statement<T>> {
         match s {
-            Statement::Constraint(quad, lin) => {
+            Statement::Constraint(quad, lin, metadata, span) => {
                 match quad.try_linear() {
                     Some(l) => {
                         if l == lin {
@@ -34,7 +34,7 @@ impl<T: Field> Folder<T> for TautologyOptimizer {
                     }
                     None => {}
                 }
-                vec![Statement::Constraint(quad, lin)]
+                vec![Statement::Constraint(quad, lin, metadata, span)]
             }
             _ => fold_statement(self, s),
         }
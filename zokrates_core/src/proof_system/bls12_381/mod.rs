@@ -0,0 +1,94 @@
+//! Proving schemes over the scalar field of BLS12-381
+//!
+//! Only G16 is available, and its verifiers cannot be exported as the templates target bn128.
+
+use crate::ir;
+use crate::proof_system::bn128::g16;
+use crate::proof_system::{
+    Proof, ProofSystem, ProofSystemError, ProvingKey, SetupKeypair, VerificationKey,
+};
+use zokrates_field::field::Bls12_381Field;
+
+pub struct G16 {}
+
+impl ProofSystem<Bls12_381Field> for G16 {
+    fn setup(&self, program: ir::Prog<Bls12_381Field>) -> Result<SetupKeypair, ProofSystemError> {
+        g16::setup(program)
+    }
+
+    fn generate_proof(
+        &self,
+        program: ir::Prog<Bls12_381Field>,
+        witness: ir::Witness<Bls12_381Field>,
+        proving_key: &ProvingKey,
+    ) -> Result<Proof, ProofSystemError> {
+        g16::generate_proof(program, witness, proving_key)
+    }
+
+    fn export_solidity_verifier(
+        &self,
+        _vk: &VerificationKey,
+        _is_abiv2: bool,
+    ) -> Result<String, ProofSystemError> {
+        Err(ProofSystemError::Unsupported(
+            "Solidity verifiers are only available on bn128".to_string(),
+        ))
+    }
+
+    fn export_rust_verifier(&self, _vk: &VerificationKey) -> Result<String, ProofSystemError> {
+        Err(ProofSystemError::Unsupported(
+            "Rust verifiers are only available on bn128".to_string(),
+        ))
+    }
+
+    fn verify(&self, vk: &VerificationKey, proof: &Proof) -> Result<bool, ProofSystemError> {
+        g16::verify::<Bls12_381Field>(vk, proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, Prog, Statement};
+    use typed_absy::types::{Signature, Type};
+
+    #[test]
+    fn setup_prove_verify() {
+        let program: Prog<Bls12_381Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                    None,
+                    None,
+                )],
+            },
+            private: vec![false],
+            signature: Signature::new()
+                .inputs(vec![Type::FieldElement])
+                .outputs(vec![Type::FieldElement]),
+        };
+
+        let witness = program
+            .clone()
+            .execute(&vec![Bls12_381Field::from(42)])
+            .unwrap();
+
+        let keypair = G16 {}.setup(program.clone()).unwrap();
+        assert!(keypair.vk.as_str().contains("\"curve\": \"bls12_381\""));
+
+        let proof = G16 {}
+            .generate_proof(program, witness, &keypair.pk)
+            .unwrap();
+        assert!(G16 {}.verify(&keypair.vk, &proof).unwrap());
+
+        // keys on another curve are rejected
+        assert!(crate::proof_system::G16 {}
+            .verify(&keypair.vk, &proof)
+            .is_err());
+    }
+}
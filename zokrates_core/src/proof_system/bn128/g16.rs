@@ -1,185 +1,290 @@
 use crate::ir;
-use crate::proof_system::bn128::utils::bellman::Computation;
+use crate::proof_system::bn128::utils::bellman::{
+    parse_g1_coordinates, parse_g2_coordinates, Computation,
+};
+use crate::proof_system::bn128::utils::parser::{
+    g1_from_json, g2_from_json, linear_combination, negate, pairing_product_is_one,
+    vk_g1_from_json, vk_g2_from_json, G1Json, G2Json, KeyPoints, ProofJson, VerificationKeyEntries,
+    VerificationKeyJson,
+};
+use crate::proof_system::bn128::utils::rust::{
+    g1_array_literal, g1_literal, g2_literal, RUST_VERIFIER_LIB,
+};
 use crate::proof_system::bn128::utils::solidity::{
+    g1_arguments, g1_assignments, g1_constants, g2_arguments, g2_constants,
     SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
 };
-use crate::proof_system::ProofSystem;
+use crate::proof_system::{
+    Proof, ProofSystem, ProofSystemError, ProvingKey, SetupKeypair, VerificationKey,
+};
 use bellman::groth16::Parameters;
-use regex::Regex;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
-use zokrates_field::field::FieldPrime;
+use pairing::bn256::{Bn256, G1Affine, G2Affine};
+use zokrates_field::field::{Field, FieldPrime};
+
+const SCHEME: &str = "g16";
 
 const G16_WARNING: &str = "WARNING: You are using the G16 scheme which is subject to malleability. See zokrates.github.io/reference/proving_schemes.html#g16-malleability for implications.";
 
 pub struct G16 {}
-impl ProofSystem for G16 {
-    fn setup(&self, program: ir::Prog<FieldPrime>, pk_path: &str, vk_path: &str) {
-        std::env::set_var("BELLMAN_VERBOSE", "0");
-
-        println!("{}", G16_WARNING);
-
-        let parameters = Computation::without_witness(program).setup();
-        let parameters_file = File::create(PathBuf::from(pk_path)).unwrap();
-        parameters.write(parameters_file).unwrap();
-        let mut vk_file = File::create(PathBuf::from(vk_path)).unwrap();
-        vk_file
-            .write(serialize::serialize_vk(parameters.vk).as_ref())
-            .unwrap();
-    }
 
-    fn generate_proof(
-        &self,
-        program: ir::Prog<FieldPrime>,
-        witness: ir::Witness<FieldPrime>,
-        pk_path: &str,
-        proof_path: &str,
-    ) -> bool {
-        std::env::set_var("BELLMAN_VERBOSE", "0");
+/// The keys for `parameters`, for example parameters generated in a multi-party setup
+pub fn keypair(parameters: &Parameters<Bn256>) -> Result<SetupKeypair, ProofSystemError> {
+    keypair_of::<FieldPrime>(parameters)
+}
 
-        println!("{}", G16_WARNING);
+fn keypair_of<T: Field>(
+    parameters: &Parameters<T::BellmanEngine>,
+) -> Result<SetupKeypair, ProofSystemError> {
+    let mut pk = vec![];
+    parameters.write(&mut pk)?;
 
-        let computation = Computation::with_witness(program, witness);
-        let parameters_file = File::open(PathBuf::from(pk_path)).unwrap();
+    Ok(SetupKeypair {
+        vk: serialize::serialize_vk(&parameters.vk, T::name()).to_verification_key(),
+        pk: ProvingKey::new(pk),
+    })
+}
 
-        let params = Parameters::read(parameters_file, true).unwrap();
+/// A G16 setup over the scalar field of any supported curve
+pub(crate) fn setup<T: Field>(program: ir::Prog<T>) -> Result<SetupKeypair, ProofSystemError> {
+    std::env::set_var("BELLMAN_VERBOSE", "0");
 
-        let proof = computation.clone().prove(&params);
+    println!("{}", G16_WARNING);
 
-        let mut proof_file = File::create(PathBuf::from(proof_path)).unwrap();
-        write!(
-            proof_file,
-            "{}",
-            serialize::serialize_proof(&proof, &computation.public_inputs_values())
-        )
-        .unwrap();
-        true
-    }
+    let parameters = Computation::without_witness(program).setup()?;
 
-    fn export_solidity_verifier(&self, reader: BufReader<File>, is_abiv2: bool) -> String {
-        let mut lines = reader.lines();
+    keypair_of::<T>(&parameters)
+}
 
-        let (mut template_text, solidity_pairing_lib) = if is_abiv2 {
-            (
-                String::from(CONTRACT_TEMPLATE_V2),
-                String::from(SOLIDITY_PAIRING_LIB_V2),
-            )
-        } else {
-            (
-                String::from(CONTRACT_TEMPLATE),
-                String::from(SOLIDITY_PAIRING_LIB),
-            )
-        };
+/// A G16 proof over the scalar field of any supported curve
+pub(crate) fn generate_proof<T: Field>(
+    program: ir::Prog<T>,
+    witness: ir::Witness<T>,
+    proving_key: &ProvingKey,
+) -> Result<Proof, ProofSystemError> {
+    std::env::set_var("BELLMAN_VERBOSE", "0");
 
-        let gamma_abc_template = String::from("vk.gamma_abc[index] = Pairing.G1Point(points);"); //copy this for each entry
+    println!("{}", G16_WARNING);
 
-        //replace things in template
-        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
-        let vk_gamma_abc_len_regex = Regex::new(r#"(<%vk_gamma_abc_length%>)"#).unwrap();
-        let vk_gamma_abc_index_regex = Regex::new(r#"index"#).unwrap();
-        let vk_gamma_abc_points_regex = Regex::new(r#"points"#).unwrap();
-        let vk_gamma_abc_repeat_regex = Regex::new(r#"(<%vk_gamma_abc_pts%>)"#).unwrap();
-        let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
+    let computation = Computation::with_witness(program, witness);
 
-        for _ in 0..4 {
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            template_text = vk_regex
-                .replace(template_text.as_str(), current_line_split[1].trim())
-                .into_owned();
-        }
+    let params = Parameters::read(proving_key.as_bytes(), true)
+        .map_err(|e| ProofSystemError::InvalidProvingKey(e.to_string()))?;
+
+    let proof = computation.clone().prove(&params)?;
+
+    Ok(Proof::new(serialize::serialize_proof(
+        &proof,
+        &computation.public_inputs_values(),
+    )))
+}
+
+/// Checks a G16 proof against a key on the curve of `T`
+pub(crate) fn verify<T: Field>(
+    vk: &VerificationKey,
+    proof: &Proof,
+) -> Result<bool, ProofSystemError> {
+    let vk =
+        VerificationKeyJson::<VerificationKeyPoints>::parse_on(vk.as_str(), SCHEME, T::name())?
+            .points;
+    let proof = ProofJson::<ProofPoints>::parse(proof.as_str())?;
+
+    let gamma_abc = vk
+        .gamma_abc
+        .iter()
+        .map(vk_g1_from_json)
+        .collect::<Result<Vec<_>, _>>()?;
+    let vk_x = linear_combination(&gamma_abc, &proof.inputs()?)?;
+
+    // e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)
+    Ok(pairing_product_is_one::<T::BellmanEngine>(&[
+        (g1_from_json(&proof.proof.a)?, g2_from_json(&proof.proof.b)?),
+        (negate(vk_x), vk_g2_from_json(&vk.gamma)?),
+        (
+            negate(g1_from_json(&proof.proof.c)?),
+            vk_g2_from_json(&vk.delta)?,
+        ),
+        (
+            negate(vk_g1_from_json(&vk.alpha)?),
+            vk_g2_from_json(&vk.beta)?,
+        ),
+    ]))
+}
+
+#[derive(Deserialize)]
+struct ProofPoints {
+    a: G1Json,
+    b: G2Json,
+    c: G1Json,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VerificationKeyPoints {
+    alpha: G1Json,
+    beta: G2Json,
+    gamma: G2Json,
+    delta: G2Json,
+    gamma_abc: Vec<G1Json>,
+}
+
+impl KeyPoints for VerificationKeyPoints {
+    fn from_entries(entries: &VerificationKeyEntries) -> Result<Self, ProofSystemError> {
+        Ok(VerificationKeyPoints {
+            alpha: entries.g1("alpha")?,
+            beta: entries.g2("beta")?,
+            gamma: entries.g2("gamma")?,
+            delta: entries.g2("delta")?,
+            gamma_abc: entries.g1_vec("gamma_abc")?,
+        })
+    }
+
+    fn query(&self) -> &[G1Json] {
+        &self.gamma_abc
+    }
+}
+
+impl ProofSystem for G16 {
+    fn setup(&self, program: ir::Prog<FieldPrime>) -> Result<SetupKeypair, ProofSystemError> {
+        self::setup(program)
+    }
+
+    fn generate_proof(
+        &self,
+        program: ir::Prog<FieldPrime>,
+        witness: ir::Witness<FieldPrime>,
+        proving_key: &ProvingKey,
+    ) -> Result<Proof, ProofSystemError> {
+        self::generate_proof(program, witness, proving_key)
+    }
+
+    fn export_solidity_verifier(
+        &self,
+        vk: &VerificationKey,
+        is_abiv2: bool,
+    ) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
 
-        let current_line: String = lines
-            .next()
-            .expect("Unexpected end of file in verification key!")
-            .unwrap();
-        let current_line_split: Vec<&str> = current_line.split("=").collect();
-        assert_eq!(current_line_split.len(), 2);
-        let gamma_abc_count: i32 = current_line_split[1].trim().parse().unwrap();
+        let (template, solidity_pairing_lib) = if is_abiv2 {
+            (CONTRACT_TEMPLATE_V2, SOLIDITY_PAIRING_LIB_V2)
+        } else {
+            (CONTRACT_TEMPLATE, SOLIDITY_PAIRING_LIB)
+        };
 
-        template_text = vk_gamma_abc_len_regex
+        let template_text = template
+            .replace("<%vk_a%>", &g1_arguments(&vk.points.alpha)?)
+            .replace("<%vk_b%>", &g2_arguments(&vk.points.beta)?)
+            .replace("<%vk_gamma%>", &g2_arguments(&vk.points.gamma)?)
+            .replace("<%vk_delta%>", &g2_arguments(&vk.points.delta)?)
             .replace(
-                template_text.as_str(),
-                format!("{}", gamma_abc_count).as_str(),
+                "<%vk_gamma_abc_length%>",
+                &vk.points.gamma_abc.len().to_string(),
             )
-            .into_owned();
-        template_text = vk_input_len_regex
             .replace(
-                template_text.as_str(),
-                format!("{}", gamma_abc_count - 1).as_str(),
+                "<%vk_gamma_abc_pts%>",
+                &g1_assignments("gamma_abc", &vk.points.gamma_abc)?,
             )
-            .into_owned();
-
-        let mut gamma_abc_repeat_text = String::new();
-        for x in 0..gamma_abc_count {
-            let mut curr_template = gamma_abc_template.clone();
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            curr_template = vk_gamma_abc_index_regex
-                .replace(curr_template.as_str(), format!("{}", x).as_str())
-                .into_owned();
-            curr_template = vk_gamma_abc_points_regex
-                .replace(curr_template.as_str(), current_line_split[1].trim())
-                .into_owned();
-            gamma_abc_repeat_text.push_str(curr_template.as_str());
-            if x < gamma_abc_count - 1 {
-                gamma_abc_repeat_text.push_str("\n        ");
-            }
-        }
-        template_text = vk_gamma_abc_repeat_regex
-            .replace(template_text.as_str(), gamma_abc_repeat_text.as_str())
-            .into_owned();
-
-        let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
-        template_text = re.replace_all(&template_text, "uint256($v)").to_string();
+            .replace("<%vk_input_length%>", &vk.inputs.to_string());
 
-        format!(
+        Ok(format!(
             "{}{}{}",
             SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
+        ))
+    }
+
+    fn export_optimized_solidity_verifier(
+        &self,
+        vk: &VerificationKey,
+    ) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
+
+        // negating the constant points here saves negating the proof points on chain
+        let neg_alpha =
+            parse_g1_coordinates(&negate(vk_g1_from_json::<G1Affine>(&vk.points.alpha)?));
+        let neg_gamma =
+            parse_g2_coordinates(&negate(vk_g2_from_json::<G2Affine>(&vk.points.gamma)?));
+        let neg_delta =
+            parse_g2_coordinates(&negate(vk_g2_from_json::<G2Affine>(&vk.points.delta)?));
+
+        let constants = vec![
+            g1_constants("NEG_ALPHA", &neg_alpha)?,
+            g2_constants("BETA", &vk.points.beta)?,
+            g2_constants("NEG_GAMMA", &neg_gamma)?,
+            g2_constants("NEG_DELTA", &neg_delta)?,
+        ]
+        .into_iter()
+        .chain(
+            vk.points
+                .gamma_abc
+                .iter()
+                .enumerate()
+                .map(|(i, point)| g1_constants(&format!("GAMMA_ABC_{}", i), point))
+                .collect::<Result<Vec<_>, _>>()?,
         )
+        .collect::<Vec<_>>();
+
+        let vk_x = (1..vk.points.gamma_abc.len())
+            .map(|i| {
+                format!(
+                    "accumulate(vk_x, GAMMA_ABC_{}_X, GAMMA_ABC_{}_Y, input[{}]);",
+                    i,
+                    i,
+                    i - 1
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(OPTIMIZED_CONTRACT_TEMPLATE
+            .replace("<%vk_constants%>", &constants.join("\n    "))
+            .replace("<%vk_x%>", &vk_x.join("\n        "))
+            .replace("<%vk_input_length%>", &vk.inputs.to_string()))
+    }
+
+    fn export_rust_verifier(&self, vk: &VerificationKey) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
+
+        let template_text = RUST_TEMPLATE
+            .replace("<%inputs%>", &vk.inputs.to_string())
+            .replace("<%alpha%>", &g1_literal(&vk.points.alpha)?)
+            .replace("<%beta%>", &g2_literal(&vk.points.beta)?)
+            .replace("<%gamma%>", &g2_literal(&vk.points.gamma)?)
+            .replace("<%delta%>", &g2_literal(&vk.points.delta)?)
+            .replace("<%gamma_abc%>", &g1_array_literal(&vk.points.gamma_abc)?);
+
+        Ok(format!("{}{}", template_text, RUST_VERIFIER_LIB))
+    }
+
+    fn verify(&self, vk: &VerificationKey, proof: &Proof) -> Result<bool, ProofSystemError> {
+        self::verify::<FieldPrime>(vk, proof)
     }
 }
 
 mod serialize {
 
+    use super::{VerificationKeyPoints, SCHEME};
     use crate::proof_system::bn128::utils::bellman::{
-        parse_fr_json, parse_g1_hex, parse_g1_json, parse_g2_hex, parse_g2_json,
+        parse_fr_json, parse_g1_coordinates, parse_g1_json, parse_g2_coordinates, parse_g2_json,
     };
+    use crate::proof_system::bn128::utils::parser::VerificationKeyJson;
     use bellman::groth16::{Proof, VerifyingKey};
-    use pairing::bn256::{Bn256, Fr};
-
-    pub fn serialize_vk(vk: VerifyingKey<Bn256>) -> String {
-        format!(
-            "vk.alpha = {}
-    vk.beta = {}
-    vk.gamma = {}
-    vk.delta = {}
-    vk.gamma_abc.len() = {}
-    {}",
-            parse_g1_hex(&vk.alpha_g1),
-            parse_g2_hex(&vk.beta_g2),
-            parse_g2_hex(&vk.gamma_g2),
-            parse_g2_hex(&vk.delta_g2),
-            vk.ic.len(),
-            vk.ic
-                .iter()
-                .enumerate()
-                .map(|(i, x)| format!("vk.gamma_abc[{}] = {}", i, parse_g1_hex(x)))
-                .collect::<Vec<_>>()
-                .join("\n")
+    use pairing::Engine;
+
+    pub fn serialize_vk<E: Engine>(
+        vk: &VerifyingKey<E>,
+        curve: &str,
+    ) -> VerificationKeyJson<VerificationKeyPoints> {
+        VerificationKeyJson::new_on(
+            SCHEME,
+            curve,
+            VerificationKeyPoints {
+                alpha: parse_g1_coordinates(&vk.alpha_g1),
+                beta: parse_g2_coordinates(&vk.beta_g2),
+                gamma: parse_g2_coordinates(&vk.gamma_g2),
+                delta: parse_g2_coordinates(&vk.delta_g2),
+                gamma_abc: vk.ic.iter().map(parse_g1_coordinates).collect(),
+            },
         )
     }
 
-    pub fn serialize_proof(p: &Proof<Bn256>, inputs: &Vec<Fr>) -> String {
+    pub fn serialize_proof<E: Engine>(p: &Proof<E>, inputs: &[E::Fr]) -> String {
         format!(
             "{{
         \"proof\": {{
@@ -201,6 +306,60 @@ mod serialize {
     }
 }
 
+const RUST_TEMPLATE: &str = r#"//! Verifies G16 proofs of a ZoKrates program, generated by `zokrates export-verifier --target rust`
+//!
+//! Requires `pairing_ce = "0.18"`.
+
+use pairing_ce::bn256::{
+    Bn256, Fq12, Fr, FrRepr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed,
+};
+use pairing_ce::ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+
+/// The number of public inputs of the program, including its outputs
+pub const INPUTS: usize = <%inputs%>;
+
+const ALPHA: [&str; 2] = <%alpha%>;
+const BETA: [[&str; 2]; 2] = <%beta%>;
+const GAMMA: [[&str; 2]; 2] = <%gamma%>;
+const DELTA: [[&str; 2]; 2] = <%delta%>;
+const GAMMA_ABC: [[&str; 2]; INPUTS + 1] = <%gamma_abc%>;
+
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl Proof {
+    /// Reads a proof from the coordinates of its points as written in `proof.json`, if they are points of the curve
+    pub fn from_hex(a: [&str; 2], b: [[&str; 2]; 2], c: [&str; 2]) -> Option<Self> {
+        Some(Proof {
+            a: g1(a)?,
+            b: g2(b)?,
+            c: g1(c)?,
+        })
+    }
+}
+
+/// Checks `proof` for the public `inputs`, that is the public arguments of the program followed by its outputs
+pub fn verify(proof: &Proof, inputs: &[Fr]) -> bool {
+    if inputs.len() != INPUTS {
+        return false;
+    }
+
+    let vk_x = linear_combination(&GAMMA_ABC, inputs);
+
+    // e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)
+    pairing_product_is_one(&[
+        (proof.a, proof.b),
+        (negate(vk_x), g2(GAMMA).unwrap()),
+        (negate(proof.c), g2(DELTA).unwrap()),
+        (negate(g1(ALPHA).unwrap()), g2(BETA).unwrap()),
+    ])
+}
+"#;
+
 const CONTRACT_TEMPLATE_V2: &str = r#"
 contract Verifier {
     using Pairing for *;
@@ -327,6 +486,60 @@ contract Verifier {
 }
 "#;
 
+const OPTIMIZED_CONTRACT_TEMPLATE: &str = r#"// This file is LGPL3 Licensed
+
+pragma solidity ^0.5.0;
+
+contract Verifier {
+    uint256 constant SNARK_SCALAR_FIELD = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+    <%vk_constants%>
+    event Verified(string s);
+    /// Checks e(a, b) * e(vk_x, -gamma) * e(c, -delta) * e(-alpha, beta) == 1
+    /// `proof` is packed as [a.x, a.y, b.x[0], b.x[1], b.y[0], b.y[1], c.x, c.y]
+    function verifyTx(
+            uint256[8] calldata proof,
+            uint256[<%vk_input_length%>] calldata input
+        ) external returns (bool r) {
+        uint256[2] memory vk_x = [GAMMA_ABC_0_X, GAMMA_ABC_0_Y];
+        <%vk_x%>
+        uint256[24] memory points = [
+            proof[0], proof[1], proof[2], proof[3], proof[4], proof[5],
+            vk_x[0], vk_x[1], NEG_GAMMA_X0, NEG_GAMMA_X1, NEG_GAMMA_Y0, NEG_GAMMA_Y1,
+            proof[6], proof[7], NEG_DELTA_X0, NEG_DELTA_X1, NEG_DELTA_Y0, NEG_DELTA_Y1,
+            NEG_ALPHA_X, NEG_ALPHA_Y, BETA_X0, BETA_X1, BETA_Y0, BETA_Y1
+        ];
+        uint256[1] memory out;
+        bool success;
+        assembly {
+            success := staticcall(sub(gas, 2000), 8, points, 768, out, 0x20)
+        }
+        require(success, "pairing failed");
+        if (out[0] == 1) {
+            emit Verified("Transaction successfully verified.");
+            return true;
+        } else {
+            return false;
+        }
+    }
+    /// Adds `s` times the point (x, y) to `acc`
+    function accumulate(uint256[2] memory acc, uint256 x, uint256 y, uint256 s) internal view {
+        require(s < SNARK_SCALAR_FIELD, "input not in field");
+        bool success;
+        assembly {
+            let m := mload(0x40)
+            mstore(m, x)
+            mstore(add(m, 0x20), y)
+            mstore(add(m, 0x40), s)
+            success := staticcall(sub(gas, 2000), 7, m, 0x60, m, 0x40)
+            mstore(add(m, 0x40), mload(acc))
+            mstore(add(m, 0x60), mload(add(acc, 0x20)))
+            success := and(success, staticcall(sub(gas, 2000), 6, m, 0x80, acc, 0x40))
+        }
+        require(success, "ec operation failed");
+    }
+}
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,6 +578,8 @@ mod tests {
                         statements: vec![Statement::Constraint(
                             FlatVariable::new(0).into(),
                             FlatVariable::public(0).into(),
+                            None,
+                            None,
                         )],
                     },
                     private: vec![false],
@@ -381,12 +596,301 @@ mod tests {
 
                 let public_inputs_values = computation.public_inputs_values();
 
-                let params = computation.clone().setup();
-                let proof = computation.prove(&params);
+                let params = computation.clone().setup().unwrap();
+                let proof = computation.prove(&params).unwrap();
 
                 let serialized_proof = serialize_proof(&proof, &public_inputs_values);
                 serde_json::from_str::<G16Proof>(&serialized_proof).unwrap();
             }
         }
     }
+
+    mod verify {
+        use super::*;
+        use crate::flat_absy::FlatVariable;
+        use crate::ir::*;
+        use regex::Regex;
+        use serde_json::Value;
+        use std::collections::HashMap;
+        use typed_absy::types::{Signature, Type};
+
+        /// A verification key and a proof for `x == 42`, with `x` public
+        fn setup_and_prove() -> (VerificationKey, Proof) {
+            let program: Prog<FieldPrime> = Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                    statements: vec![Statement::Constraint(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::public(0).into(),
+                        None,
+                        None,
+                    )],
+                },
+                private: vec![false],
+                signature: Signature::new()
+                    .inputs(vec![Type::FieldElement])
+                    .outputs(vec![Type::FieldElement]),
+            };
+
+            let witness = program
+                .clone()
+                .execute(&vec![FieldPrime::from(42)])
+                .unwrap();
+
+            let keypair = G16 {}.setup(program.clone()).unwrap();
+            let proof = G16 {}
+                .generate_proof(program, witness, &keypair.pk)
+                .unwrap();
+
+            (keypair.vk, proof)
+        }
+
+        fn with_inputs(proof: &Proof, inputs: Vec<&str>) -> Proof {
+            let mut proof: Value = serde_json::from_str(proof.as_str()).unwrap();
+            proof["inputs"] = inputs.into();
+            Proof::new(proof.to_string())
+        }
+
+        #[test]
+        fn verify() {
+            let (vk, proof) = setup_and_prove();
+
+            assert_eq!(G16 {}.verify(&vk, &proof).unwrap(), true);
+
+            let forty_two = format!("0x{:064x}", 42);
+            let forty_three = format!("0x{:064x}", 43);
+
+            assert_eq!(
+                G16 {}
+                    .verify(&vk, &with_inputs(&proof, vec![&forty_two, &forty_two]))
+                    .unwrap(),
+                true
+            );
+            assert_eq!(
+                G16 {}
+                    .verify(&vk, &with_inputs(&proof, vec![&forty_three, &forty_two]))
+                    .unwrap(),
+                false
+            );
+            assert_eq!(
+                G16 {}
+                    .verify(&vk, &with_inputs(&proof, vec![&forty_two]))
+                    .unwrap_err()
+                    .to_string(),
+                "Invalid proof: Expected 2 public inputs, found 1"
+            );
+        }
+
+        #[test]
+        fn verify_invalid_key() {
+            let (vk, proof) = setup_and_prove();
+
+            let vk_without_delta =
+                VerificationKey::new(vk.as_str().replace("\"delta\"", "\"epsilon\""));
+
+            assert!(G16 {}.verify(&vk_without_delta, &proof).is_err());
+            assert!(G16 {}.verify(&vk, &Proof::new(String::from("{}"))).is_err());
+        }
+
+        #[test]
+        fn generate_proof_invalid_key() {
+            let program: Prog<FieldPrime> = Prog {
+                main: Function {
+                    id: String::from("main"),
+                    arguments: vec![],
+                    returns: vec![],
+                    statements: vec![],
+                },
+                private: vec![],
+                signature: Signature::new(),
+            };
+
+            let witness = program.clone().execute(&vec![]).unwrap();
+
+            let res = G16 {}.generate_proof(program, witness, &ProvingKey::new(vec![1, 2, 3]));
+
+            match res {
+                Err(ProofSystemError::InvalidProvingKey(_)) => {}
+                _ => panic!("Expected an invalid proving key"),
+            };
+        }
+
+        /// `vk` in the `vk.<name> = <value>` format written by earlier versions
+        fn legacy(vk: &VerificationKey) -> VerificationKey {
+            let vk: Value = serde_json::from_str(vk.as_str()).unwrap();
+
+            let g1 = |p: &Value| format!("{}, {}", p[0].as_str().unwrap(), p[1].as_str().unwrap());
+            let g2 = |p: &Value| format!("[{}], [{}]", g1(&p[0]), g1(&p[1]));
+
+            let gamma_abc = vk["gamma_abc"].as_array().unwrap();
+
+            VerificationKey::new(format!(
+                "vk.alpha = {}\nvk.beta = {}\nvk.gamma = {}\nvk.delta = {}\nvk.gamma_abc.len() = {}\n{}",
+                g1(&vk["alpha"]),
+                g2(&vk["beta"]),
+                g2(&vk["gamma"]),
+                g2(&vk["delta"]),
+                gamma_abc.len(),
+                gamma_abc
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| format!("vk.gamma_abc[{}] = {}", i, g1(p)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+        }
+
+        #[test]
+        fn json_key() {
+            let (vk, _) = setup_and_prove();
+
+            let json: Value = serde_json::from_str(vk.as_str()).unwrap();
+
+            assert_eq!(json["version"], 1);
+            assert_eq!(json["scheme"], "g16");
+            assert_eq!(json["curve"], "bn128");
+            assert_eq!(json["inputs"], 2);
+            assert_eq!(json["gamma_abc"].as_array().unwrap().len(), 3);
+        }
+
+        #[test]
+        fn legacy_key() {
+            let (vk, proof) = setup_and_prove();
+
+            assert_eq!(G16 {}.verify(&legacy(&vk), &proof).unwrap(), true);
+            assert_eq!(
+                G16 {}.export_solidity_verifier(&legacy(&vk), true).unwrap(),
+                G16 {}.export_solidity_verifier(&vk, true).unwrap()
+            );
+        }
+
+        #[test]
+        fn unsupported_key() {
+            let (vk, proof) = setup_and_prove();
+
+            let with = |key: &str, value: Value| {
+                let mut vk: Value = serde_json::from_str(vk.as_str()).unwrap();
+                vk[key] = value;
+                VerificationKey::new(vk.to_string())
+            };
+
+            assert_eq!(
+                G16 {}
+                    .verify(&with("version", 2.into()), &proof)
+                    .unwrap_err()
+                    .to_string(),
+                "Invalid verification key: Unsupported version 2"
+            );
+            assert_eq!(
+                G16 {}
+                    .verify(&with("scheme", "gm17".into()), &proof)
+                    .unwrap_err()
+                    .to_string(),
+                "Invalid verification key: Expected a key for g16, found a key for gm17"
+            );
+            assert!(G16 {}
+                .verify(&with("curve", "bls12_381".into()), &proof)
+                .is_err());
+            assert!(G16 {}
+                .export_solidity_verifier(&with("inputs", 1.into()), false)
+                .is_err());
+        }
+
+        #[test]
+        fn export_invalid_key() {
+            let (vk, _) = setup_and_prove();
+
+            let truncated =
+                VerificationKey::new(vk.as_str().lines().take(3).collect::<Vec<_>>().join("\n"));
+
+            assert!(G16 {}.export_solidity_verifier(&vk, false).is_ok());
+            assert!(G16 {}.export_solidity_verifier(&truncated, false).is_err());
+            assert!(G16 {}
+                .export_optimized_solidity_verifier(&truncated)
+                .is_err());
+            assert!(G16 {}.export_rust_verifier(&truncated).is_err());
+        }
+
+        #[test]
+        fn export_optimized_solidity_verifier() {
+            let (vk, proof) = setup_and_prove();
+
+            let contract = G16 {}.export_optimized_solidity_verifier(&vk).unwrap();
+
+            assert!(!contract.contains("<%"));
+            assert!(contract.contains("uint256[2] calldata input"));
+
+            // replay the checks of the contract with the constants it declares
+            let constants: HashMap<String, String> =
+                Regex::new(r"uint256 constant (\w+) = (0x[0-9a-f]{64});")
+                    .unwrap()
+                    .captures_iter(&contract)
+                    .map(|c| (c[1].to_string(), c[2].to_string()))
+                    .collect();
+            let constant = |name: String| constants[&name].clone();
+            let g1 = |name: &str| {
+                vk_g1_from_json::<G1Affine>(&[
+                    constant(format!("{}_X", name)),
+                    constant(format!("{}_Y", name)),
+                ])
+                .unwrap()
+            };
+            let g2 = |name: &str| {
+                vk_g2_from_json::<G2Affine>(&[
+                    [
+                        constant(format!("{}_X0", name)),
+                        constant(format!("{}_X1", name)),
+                    ],
+                    [
+                        constant(format!("{}_Y0", name)),
+                        constant(format!("{}_Y1", name)),
+                    ],
+                ])
+                .unwrap()
+            };
+            let gamma_abc: Vec<_> = (0..3).map(|i| g1(&format!("GAMMA_ABC_{}", i))).collect();
+
+            let verify_tx = |proof: &Proof| {
+                let proof = ProofJson::<ProofPoints>::parse(proof.as_str()).unwrap();
+                let vk_x = linear_combination(&gamma_abc, &proof.inputs().unwrap()).unwrap();
+
+                pairing_product_is_one::<Bn256>(&[
+                    (
+                        g1_from_json(&proof.proof.a).unwrap(),
+                        g2_from_json(&proof.proof.b).unwrap(),
+                    ),
+                    (vk_x, g2("NEG_GAMMA")),
+                    (g1_from_json(&proof.proof.c).unwrap(), g2("NEG_DELTA")),
+                    (g1("NEG_ALPHA"), g2("BETA")),
+                ])
+            };
+
+            let forty_two = format!("0x{:064x}", 42);
+            let forty_three = format!("0x{:064x}", 43);
+
+            for proof in vec![
+                proof.clone(),
+                with_inputs(&proof, vec![&forty_three, &forty_two]),
+            ] {
+                assert_eq!(verify_tx(&proof), G16 {}.verify(&vk, &proof).unwrap());
+            }
+            assert!(verify_tx(&proof));
+        }
+
+        #[test]
+        fn export_rust_verifier() {
+            let (vk, _) = setup_and_prove();
+
+            let json: Value = serde_json::from_str(vk.as_str()).unwrap();
+            let verifier = G16 {}.export_rust_verifier(&vk).unwrap();
+
+            assert!(!verifier.contains("<%"));
+            assert!(verifier.contains("pub const INPUTS: usize = 2;"));
+            assert!(verifier.contains("pub fn verify(proof: &Proof, inputs: &[Fr]) -> bool"));
+            assert!(verifier.contains(json["gamma_abc"][2][1].as_str().unwrap()));
+            assert_eq!(verifier, G16 {}.export_rust_verifier(&legacy(&vk)).unwrap());
+        }
+    }
 }
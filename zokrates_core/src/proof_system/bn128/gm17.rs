@@ -1,18 +1,31 @@
 extern crate libc;
 
-use self::libc::{c_char, c_int};
+use self::libc::c_int;
 use ir;
-use proof_system::bn128::utils::libsnark::{prepare_generate_proof, prepare_setup};
+use pairing::bn256::Bn256;
+use proof_system::bn128::utils::libsnark::{
+    prepare_generate_proof, prepare_setup, Buffer, SetupResult,
+};
+use proof_system::bn128::utils::parser::{
+    add, g1_from_json, g2_from_json, linear_combination, negate, pairing_product_is_one,
+    vk_g1_from_json, vk_g2_from_json, G1Json, G2Json, KeyPoints, ProofJson, VerificationKeyEntries,
+    VerificationKeyJson,
+};
+use proof_system::bn128::utils::rust::{
+    g1_array_literal, g1_literal, g2_literal, RUST_VERIFIER_LIB,
+};
 use proof_system::bn128::utils::solidity::{
-    SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+    g1_arguments, g1_assignments, g2_arguments, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB,
+    SOLIDITY_PAIRING_LIB_V2,
+};
+use proof_system::{
+    Proof, ProofSystem, ProofSystemError, ProvingKey, SetupKeypair, VerificationKey,
 };
-use proof_system::ProofSystem;
-use regex::Regex;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 use zokrates_field::field::FieldPrime;
 
+const SCHEME: &str = "gm17";
+
 pub struct GM17 {}
 
 impl GM17 {
@@ -21,6 +34,40 @@ impl GM17 {
     }
 }
 
+#[derive(Deserialize)]
+struct ProofPoints {
+    a: G1Json,
+    b: G2Json,
+    c: G1Json,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VerificationKeyPoints {
+    h: G2Json,
+    g_alpha: G1Json,
+    h_beta: G2Json,
+    g_gamma: G1Json,
+    h_gamma: G2Json,
+    query: Vec<G1Json>,
+}
+
+impl KeyPoints for VerificationKeyPoints {
+    fn from_entries(entries: &VerificationKeyEntries) -> Result<Self, ProofSystemError> {
+        Ok(VerificationKeyPoints {
+            h: entries.g2("h")?,
+            g_alpha: entries.g1("g_alpha")?,
+            h_beta: entries.g2("h_beta")?,
+            g_gamma: entries.g1("g_gamma")?,
+            h_gamma: entries.g2("h_gamma")?,
+            query: entries.g1_vec("query")?,
+        })
+    }
+
+    fn query(&self) -> &[G1Json] {
+        &self.query
+    }
+}
+
 extern "C" {
     fn _gm17_setup(
         A: *const u8,
@@ -32,38 +79,25 @@ extern "C" {
         constraints: c_int,
         variables: c_int,
         inputs: c_int,
-        pk_path: *const c_char,
-        vk_path: *const c_char,
-    ) -> bool;
+    ) -> SetupResult;
 
     fn _gm17_generate_proof(
-        pk_path: *const c_char,
-        proof_path: *const c_char,
+        pk: *const u8,
+        pk_length: c_int,
         publquery_inputs: *const u8,
         publquery_inputs_length: c_int,
         private_inputs: *const u8,
         private_inputs_length: c_int,
-    ) -> bool;
+    ) -> Buffer;
 }
 
 impl ProofSystem for GM17 {
-    fn setup(&self, program: ir::Prog<FieldPrime>, pk_path: &str, vk_path: &str) {
-        let (
-            a_arr,
-            b_arr,
-            c_arr,
-            a_vec,
-            b_vec,
-            c_vec,
-            num_constraints,
-            num_variables,
-            num_inputs,
-            pk_path_cstring,
-            vk_path_cstring,
-        ) = prepare_setup(program, pk_path, vk_path);
-
-        unsafe {
-            _gm17_setup(
+    fn setup(&self, program: ir::Prog<FieldPrime>) -> Result<SetupKeypair, ProofSystemError> {
+        let (a_arr, b_arr, c_arr, a_vec, b_vec, c_vec, num_constraints, num_variables, num_inputs) =
+            prepare_setup(program);
+
+        let (vk, pk) = unsafe {
+            let result = _gm17_setup(
                 a_arr.as_ptr(),
                 b_arr.as_ptr(),
                 c_arr.as_ptr(),
@@ -73,129 +107,192 @@ impl ProofSystem for GM17 {
                 num_constraints as i32,
                 num_variables as i32,
                 num_inputs as i32,
-                pk_path_cstring.as_ptr(),
-                vk_path_cstring.as_ptr(),
             );
-        }
+            (result.vk.into_string(), result.pk.into_vec())
+        };
+
+        let points: VerificationKeyPoints = serde_json::from_str(&vk?)
+            .map_err(|e| ProofSystemError::Backend(format!("Invalid verification key: {}", e)))?;
+
+        Ok(SetupKeypair {
+            vk: VerificationKeyJson::new(SCHEME, points).to_verification_key(),
+            pk: ProvingKey::new(pk),
+        })
     }
 
     fn generate_proof(
         &self,
         program: ir::Prog<FieldPrime>,
         witness: ir::Witness<FieldPrime>,
-        pk_path: &str,
-        proof_path: &str,
-    ) -> bool {
-        let (
-            pk_path_cstring,
-            proof_path_cstring,
-            public_inputs_arr,
-            public_inputs_length,
-            private_inputs_arr,
-            private_inputs_length,
-        ) = prepare_generate_proof(program, witness, pk_path, proof_path);
-
-        unsafe {
+        proving_key: &ProvingKey,
+    ) -> Result<Proof, ProofSystemError> {
+        let (public_inputs_arr, public_inputs_length, private_inputs_arr, private_inputs_length) =
+            prepare_generate_proof(program, witness)?;
+
+        let proof = unsafe {
             _gm17_generate_proof(
-                pk_path_cstring.as_ptr(),
-                proof_path_cstring.as_ptr(),
+                proving_key.as_bytes().as_ptr(),
+                proving_key.as_bytes().len() as i32,
                 public_inputs_arr[0].as_ptr(),
                 public_inputs_length as i32,
                 private_inputs_arr[0].as_ptr(),
                 private_inputs_length as i32,
             )
-        }
+            .into_string()
+        };
+
+        Ok(Proof::new(proof?))
     }
 
-    fn export_solidity_verifier(&self, reader: BufReader<File>, is_abiv2: bool) -> String {
-        let mut lines = reader.lines();
+    fn export_solidity_verifier(
+        &self,
+        vk: &VerificationKey,
+        is_abiv2: bool,
+    ) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
 
-        let (mut template_text, solidity_pairing_lib) = if is_abiv2 {
-            (
-                String::from(CONTRACT_TEMPLATE_V2),
-                String::from(SOLIDITY_PAIRING_LIB_V2),
-            )
+        let (template, solidity_pairing_lib) = if is_abiv2 {
+            (CONTRACT_TEMPLATE_V2, SOLIDITY_PAIRING_LIB_V2)
         } else {
-            (
-                String::from(CONTRACT_TEMPLATE),
-                String::from(SOLIDITY_PAIRING_LIB),
-            )
+            (CONTRACT_TEMPLATE, SOLIDITY_PAIRING_LIB)
         };
 
-        let query_template = String::from("vk.query[index] = Pairing.G1Point(points);"); //copy this for each entry
-
-        //replace things in template
-        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
-        let vk_query_len_regex = Regex::new(r#"(<%vk_query_length%>)"#).unwrap();
-        let vk_query_index_regex = Regex::new(r#"index"#).unwrap();
-        let vk_query_points_regex = Regex::new(r#"points"#).unwrap();
-        let vk_query_repeat_regex = Regex::new(r#"(<%vk_query_pts%>)"#).unwrap();
-        let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
-
-        for _ in 0..5 {
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            template_text = vk_regex
-                .replace(template_text.as_str(), current_line_split[1].trim())
-                .into_owned();
-        }
-
-        let current_line: String = lines
-            .next()
-            .expect("Unexpected end of file in verification key!")
-            .unwrap();
-        let current_line_split: Vec<&str> = current_line.split("=").collect();
-        assert_eq!(current_line_split.len(), 2);
-        let query_count: i32 = current_line_split[1].trim().parse().unwrap();
-
-        template_text = vk_query_len_regex
-            .replace(template_text.as_str(), format!("{}", query_count).as_str())
-            .into_owned();
-        template_text = vk_input_len_regex
+        let template_text = template
+            .replace("<%vk_h%>", &g2_arguments(&vk.points.h)?)
+            .replace("<%vk_g_alpha%>", &g1_arguments(&vk.points.g_alpha)?)
+            .replace("<%vk_h_beta%>", &g2_arguments(&vk.points.h_beta)?)
+            .replace("<%vk_g_gamma%>", &g1_arguments(&vk.points.g_gamma)?)
+            .replace("<%vk_h_gamma%>", &g2_arguments(&vk.points.h_gamma)?)
+            .replace("<%vk_query_length%>", &vk.points.query.len().to_string())
             .replace(
-                template_text.as_str(),
-                format!("{}", query_count - 1).as_str(),
+                "<%vk_query_pts%>",
+                &g1_assignments("query", &vk.points.query)?,
             )
-            .into_owned();
-
-        let mut query_repeat_text = String::new();
-        for x in 0..query_count {
-            let mut curr_template = query_template.clone();
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            curr_template = vk_query_index_regex
-                .replace(curr_template.as_str(), format!("{}", x).as_str())
-                .into_owned();
-            curr_template = vk_query_points_regex
-                .replace(curr_template.as_str(), current_line_split[1].trim())
-                .into_owned();
-            query_repeat_text.push_str(curr_template.as_str());
-            if x < query_count - 1 {
-                query_repeat_text.push_str("\n        ");
-            }
-        }
-        template_text = vk_query_repeat_regex
-            .replace(template_text.as_str(), query_repeat_text.as_str())
-            .into_owned();
-
-        let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
-        template_text = re.replace_all(&template_text, "uint256($v)").to_string();
+            .replace("<%vk_input_length%>", &vk.inputs.to_string());
 
-        format!(
+        Ok(format!(
             "{}{}{}",
             SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
-        )
+        ))
+    }
+
+    fn export_rust_verifier(&self, vk: &VerificationKey) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
+
+        let template_text = RUST_TEMPLATE
+            .replace("<%inputs%>", &vk.inputs.to_string())
+            .replace("<%h%>", &g2_literal(&vk.points.h)?)
+            .replace("<%g_alpha%>", &g1_literal(&vk.points.g_alpha)?)
+            .replace("<%h_beta%>", &g2_literal(&vk.points.h_beta)?)
+            .replace("<%g_gamma%>", &g1_literal(&vk.points.g_gamma)?)
+            .replace("<%h_gamma%>", &g2_literal(&vk.points.h_gamma)?)
+            .replace("<%query%>", &g1_array_literal(&vk.points.query)?);
+
+        Ok(format!("{}{}", template_text, RUST_VERIFIER_LIB))
+    }
+
+    fn verify(&self, vk: &VerificationKey, proof: &Proof) -> Result<bool, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?.points;
+        let proof = ProofJson::<ProofPoints>::parse(proof.as_str())?;
+
+        let query = vk
+            .query
+            .iter()
+            .map(vk_g1_from_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        let vk_x = linear_combination(&query, &proof.inputs()?)?;
+
+        let a = g1_from_json(&proof.proof.a)?;
+        let b = g2_from_json(&proof.proof.b)?;
+        let c = g1_from_json(&proof.proof.c)?;
+
+        let g_alpha = vk_g1_from_json(&vk.g_alpha)?;
+        let h_beta = vk_g2_from_json(&vk.h_beta)?;
+        let h_gamma = vk_g2_from_json(&vk.h_gamma)?;
+
+        // e(A * G^{alpha}, B * H^{beta}) = e(G^{alpha}, H^{beta}) * e(G^{psi}, H^{gamma}) * e(C, H)
+        // e(A, H^{gamma}) = e(G^{gamma}, B)
+        Ok(pairing_product_is_one::<Bn256>(&[
+            (g_alpha, h_beta),
+            (vk_x, h_gamma),
+            (c, vk_g2_from_json(&vk.h)?),
+            (negate(add(a, g_alpha)), add(b, h_beta)),
+        ]) && pairing_product_is_one::<Bn256>(&[
+            (a, h_gamma),
+            (negate(vk_g1_from_json(&vk.g_gamma)?), b),
+        ]))
     }
 }
 
+const RUST_TEMPLATE: &str = r#"//! Verifies GM17 proofs of a ZoKrates program, generated by `zokrates export-verifier --target rust`
+//!
+//! Requires `pairing_ce = "0.18"`.
+
+use pairing_ce::bn256::{
+    Bn256, Fq12, Fr, FrRepr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed,
+};
+use pairing_ce::ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+
+/// The number of public inputs of the program, including its outputs
+pub const INPUTS: usize = <%inputs%>;
+
+const H: [[&str; 2]; 2] = <%h%>;
+const G_ALPHA: [&str; 2] = <%g_alpha%>;
+const H_BETA: [[&str; 2]; 2] = <%h_beta%>;
+const G_GAMMA: [&str; 2] = <%g_gamma%>;
+const H_GAMMA: [[&str; 2]; 2] = <%h_gamma%>;
+const QUERY: [[&str; 2]; INPUTS + 1] = <%query%>;
+
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl Proof {
+    /// Reads a proof from the coordinates of its points as written in `proof.json`, if they are points of the curve
+    pub fn from_hex(a: [&str; 2], b: [[&str; 2]; 2], c: [&str; 2]) -> Option<Self> {
+        Some(Proof {
+            a: g1(a)?,
+            b: g2(b)?,
+            c: g1(c)?,
+        })
+    }
+}
+
+/// Checks `proof` for the public `inputs`, that is the public arguments of the program followed by its outputs
+pub fn verify(proof: &Proof, inputs: &[Fr]) -> bool {
+    if inputs.len() != INPUTS {
+        return false;
+    }
+
+    let vk_x = linear_combination(&QUERY, inputs);
+
+    let g_alpha = g1(G_ALPHA).unwrap();
+    let h_beta = g2(H_BETA).unwrap();
+    let h_gamma = g2(H_GAMMA).unwrap();
+
+    // e(A * G^{alpha}, B * H^{beta}) = e(G^{alpha}, H^{beta}) * e(G^{psi}, H^{gamma}) * e(C, H)
+    // e(A, H^{gamma}) = e(G^{gamma}, B)
+    pairing_product_is_one(&[
+        (g_alpha, h_beta),
+        (vk_x, h_gamma),
+        (proof.c, g2(H).unwrap()),
+        (negate(add(proof.a, g_alpha)), add(proof.b, h_beta)),
+    ]) && pairing_product_is_one(&[
+        (proof.a, h_gamma),
+        (negate(g1(G_GAMMA).unwrap()), proof.b),
+    ])
+}
+
+fn add<G: CurveAffine>(a: G, b: G) -> G {
+    let mut sum = a.into_projective();
+    sum.add_assign_mixed(&b);
+    sum.into_affine()
+}
+"#;
+
 const CONTRACT_TEMPLATE_V2: &str = r#"
 contract Verifier {
     using Pairing for *;
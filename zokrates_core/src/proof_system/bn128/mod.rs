@@ -1,8 +1,10 @@
-mod g16;
+pub(crate) mod g16;
 #[cfg(feature = "libsnark")]
 mod gm17;
+pub mod mpc;
 #[cfg(feature = "libsnark")]
 mod pghr13;
+pub mod r1cs;
 
 mod utils;
 
@@ -0,0 +1,968 @@
+//! Circuit specific G16 parameters generated in a multi-party computation, following the second phase of
+//! [Bowe, Gabizon and Miers](https://eprint.iacr.org/2017/1050)
+//!
+//! The parameters are first derived from the result of a powers of tau ceremony, with `delta = 1`. Each participant
+//! then multiplies `delta` by a random secret and publishes a proof of knowledge of that secret. The parameters are
+//! secure as long as a single participant discarded their secret. A last contribution is derived from a random
+//! beacon, so that the last participant cannot bias the result.
+
+use crate::ir;
+use crate::proof_system::bn128::g16;
+use crate::proof_system::bn128::utils::bellman::Computation;
+use crate::proof_system::{ProofSystemError, SetupKeypair};
+use bellman::groth16::{Parameters, VerifyingKey};
+use bellman::{Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use crypto::blake2b::Blake2b;
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
+use ff::{Field, PrimeField};
+use pairing::bn256::{Bn256, Fr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed, G1, G2};
+use pairing::{CurveAffine, CurveProjective, EncodedPoint};
+use rand::chacha::ChaChaRng;
+use rand::{thread_rng, Rand, Rng, SeedableRng};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::sync::Arc;
+use zokrates_field::field::FieldPrime;
+
+const HASH_SIZE: u64 = 64;
+const G1_SIZE: u64 = 64;
+const G2_SIZE: u64 = 128;
+
+/// The parameters of a circuit along with the contributions made to them
+#[derive(Clone)]
+pub struct MPCParameters {
+    params: Parameters<Bn256>,
+    cs_hash: [u8; 64],
+    contributions: Vec<PublicKey>,
+}
+
+impl MPCParameters {
+    /// Creates the initial parameters of `program` from an uncompressed powers of tau accumulator, in the format of
+    /// the `challenge` files of the powers of tau ceremony
+    pub fn new<R: Read + Seek>(
+        program: ir::Prog<FieldPrime>,
+        phase1: R,
+    ) -> Result<Self, ProofSystemError> {
+        let mut assembly = KeypairAssembly::default();
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(Fr::one()))?;
+
+        Computation::without_witness(program).synthesize(&mut assembly)?;
+
+        // Input constraints to ensure full density of IC query
+        // x * 0 = 0
+        for i in 0..assembly.num_inputs {
+            assembly.enforce(
+                || "",
+                |lc| lc + Variable::new_unchecked(Index::Input(i)),
+                |lc| lc,
+                |lc| lc,
+            );
+        }
+
+        let phase1 = Phase1::read(phase1, assembly.num_constraints)?;
+
+        let inputs = phase1.eval(
+            &assembly.at_inputs,
+            &assembly.bt_inputs,
+            &assembly.ct_inputs,
+        );
+        let aux = phase1.eval(&assembly.at_aux, &assembly.bt_aux, &assembly.ct_aux);
+
+        let ic = inputs.ext;
+        let l = aux.ext;
+
+        // Don't allow any elements be unconstrained, so that the L query is always fully dense
+        if l.iter().any(|e| e.is_zero()) {
+            return Err(SynthesisError::UnconstrainedVariable.into());
+        }
+
+        let vk = VerifyingKey {
+            alpha_g1: phase1.alpha_g1,
+            beta_g1: phase1.beta_g1,
+            beta_g2: phase1.beta_g2,
+            gamma_g2: G2Affine::one(),
+            delta_g1: G1Affine::one(),
+            delta_g2: G2Affine::one(),
+            ic: into_affine(ic),
+        };
+
+        let params = Parameters {
+            vk,
+            h: Arc::new(phase1.h),
+            l: Arc::new(into_affine(l)),
+            // Filter points at infinity away from A/B queries
+            a: Arc::new(non_zero(inputs.a_g1, aux.a_g1)),
+            b_g1: Arc::new(non_zero(inputs.b_g1, aux.b_g1)),
+            b_g2: Arc::new(non_zero(inputs.b_g2, aux.b_g2)),
+        };
+
+        let cs_hash = {
+            let mut sink = HashWriter::new();
+            params.write(&mut sink)?;
+            sink.into_hash()
+        };
+
+        Ok(MPCParameters {
+            params,
+            cs_hash,
+            contributions: vec![],
+        })
+    }
+
+    /// Multiplies `delta` by a secret drawn from `rng`, and returns the hash of the contribution
+    ///
+    /// The secret is dropped when this function returns, so `rng` must not be reproducible by anyone else
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> [u8; 64] {
+        let delta = loop {
+            let delta = Fr::rand(rng);
+            if !delta.is_zero() {
+                break delta;
+            }
+        };
+        let delta_inv = delta.inverse().unwrap();
+
+        let s = G1::rand(rng).into_affine();
+        let s_delta = s.mul(delta.into_repr()).into_affine();
+
+        // H(cs_hash | <previous public keys> | s | s_delta)
+        let transcript = {
+            let mut sink = self.transcript();
+            sink.write_all(s.into_uncompressed().as_ref()).unwrap();
+            sink.write_all(s_delta.into_uncompressed().as_ref())
+                .unwrap();
+            sink.into_hash()
+        };
+
+        let r = hash_to_g2(&transcript).into_affine();
+        let r_delta = r.mul(delta.into_repr()).into_affine();
+
+        let public_key = PublicKey {
+            delta_after: self.params.vk.delta_g1.mul(delta.into_repr()).into_affine(),
+            s,
+            s_delta,
+            r_delta,
+            transcript,
+        };
+
+        self.params.h = Arc::new(mul_all(&self.params.h, delta_inv));
+        self.params.l = Arc::new(mul_all(&self.params.l, delta_inv));
+        self.params.vk.delta_g1 = public_key.delta_after;
+        self.params.vk.delta_g2 = self.params.vk.delta_g2.mul(delta.into_repr()).into_affine();
+
+        let hash = public_key.hash();
+        self.contributions.push(public_key);
+        hash
+    }
+
+    /// Contributes a secret derived from a public random `beacon`, for example a future block hash, by hashing it
+    /// `2^iterations_exp` times with SHA-256
+    pub fn beacon(
+        &mut self,
+        beacon: &[u8],
+        iterations_exp: u8,
+    ) -> Result<[u8; 64], ProofSystemError> {
+        if iterations_exp < 10 || iterations_exp > 63 {
+            return Err(ProofSystemError::InvalidMpcParameters(format!(
+                "The number of iterations of the beacon must be between 2^10 and 2^63, found 2^{}",
+                iterations_exp
+            )));
+        }
+
+        let mut hash = [0u8; 32];
+        {
+            let mut hasher = Sha256::new();
+            hasher.input(beacon);
+            hasher.result(&mut hash);
+        }
+
+        for _ in 0..(1u64 << iterations_exp) {
+            let mut hasher = Sha256::new();
+            hasher.input(&hash);
+            hasher.result(&mut hash);
+        }
+
+        let seed: Vec<u32> = hash
+            .chunks(4)
+            .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+
+        Ok(self.contribute(&mut ChaChaRng::from_seed(&seed)))
+    }
+
+    /// Checks that these parameters were derived from `program` and the powers of tau in `phase1` through valid
+    /// contributions, and returns the hashes of these contributions
+    pub fn verify<R: Read + Seek>(
+        &self,
+        program: ir::Prog<FieldPrime>,
+        phase1: R,
+    ) -> Result<Vec<[u8; 64]>, ProofSystemError> {
+        let initial = MPCParameters::new(program, phase1)?;
+
+        let invalid = |message: &str| {
+            Err(ProofSystemError::InvalidMpcParameters(String::from(
+                message,
+            )))
+        };
+
+        // only delta and the H and L queries change with contributions
+        if initial.cs_hash[..] != self.cs_hash[..]
+            || initial.params.vk.alpha_g1 != self.params.vk.alpha_g1
+            || initial.params.vk.beta_g1 != self.params.vk.beta_g1
+            || initial.params.vk.beta_g2 != self.params.vk.beta_g2
+            || initial.params.vk.gamma_g2 != self.params.vk.gamma_g2
+            || initial.params.vk.ic != self.params.vk.ic
+            || initial.params.a != self.params.a
+            || initial.params.b_g1 != self.params.b_g1
+            || initial.params.b_g2 != self.params.b_g2
+            || initial.params.h.len() != self.params.h.len()
+            || initial.params.l.len() != self.params.l.len()
+        {
+            return invalid("The parameters do not match the program and the powers of tau");
+        }
+
+        let mut sink = HashWriter::new();
+        sink.write_all(&self.cs_hash)?;
+
+        let mut current_delta = G1Affine::one();
+        let mut hashes = vec![];
+
+        for (index, public_key) in self.contributions.iter().enumerate() {
+            let mut transcript = sink.clone();
+            transcript.write_all(public_key.s.into_uncompressed().as_ref())?;
+            transcript.write_all(public_key.s_delta.into_uncompressed().as_ref())?;
+            let transcript = transcript.into_hash();
+
+            public_key.write(&mut sink)?;
+
+            if transcript[..] != public_key.transcript[..] {
+                return Err(ProofSystemError::InvalidMpcParameters(format!(
+                    "The transcript of contribution {} is inconsistent",
+                    index + 1
+                )));
+            }
+
+            let r = hash_to_g2(&transcript).into_affine();
+
+            // proof of knowledge of delta
+            if !same_ratio((r, public_key.r_delta), (public_key.s, public_key.s_delta))
+                || !same_ratio(
+                    (current_delta, public_key.delta_after),
+                    (r, public_key.r_delta),
+                )
+            {
+                return Err(ProofSystemError::InvalidMpcParameters(format!(
+                    "Contribution {} is invalid",
+                    index + 1
+                )));
+            }
+
+            current_delta = public_key.delta_after;
+            hashes.push(public_key.hash());
+        }
+
+        if current_delta != self.params.vk.delta_g1
+            || !same_ratio(
+                (G1Affine::one(), current_delta),
+                (G2Affine::one(), self.params.vk.delta_g2),
+            )
+        {
+            return invalid("Delta does not match the contributions");
+        }
+
+        // H and L queries should be updated with delta^-1
+        if !same_ratio(
+            merge_pairs(&initial.params.h, &self.params.h),
+            (self.params.vk.delta_g2, G2Affine::one()),
+        ) || !same_ratio(
+            merge_pairs(&initial.params.l, &self.params.l),
+            (self.params.vk.delta_g2, G2Affine::one()),
+        ) {
+            return invalid("The H and L queries do not match delta");
+        }
+
+        Ok(hashes)
+    }
+
+    /// The keys to generate and verify proofs with these parameters
+    pub fn keypair(&self) -> Result<SetupKeypair, ProofSystemError> {
+        g16::keypair(&self.params)
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let params = Parameters::read(&mut reader, true)
+            .map_err(|e| ProofSystemError::InvalidMpcParameters(e.to_string()))?;
+
+        let mut cs_hash = [0u8; 64];
+        reader.read_exact(&mut cs_hash)?;
+
+        let mut count = [0u8; 4];
+        reader.read_exact(&mut count)?;
+
+        let contributions = (0..u32::from_be_bytes(count))
+            .map(|_| PublicKey::read(&mut reader))
+            .collect::<Result<_, _>>()?;
+
+        Ok(MPCParameters {
+            params,
+            cs_hash,
+            contributions,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        self.params.write(&mut writer)?;
+        writer.write_all(&self.cs_hash)?;
+        writer.write_all(&(self.contributions.len() as u32).to_be_bytes())?;
+
+        for public_key in &self.contributions {
+            public_key.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// A hash of the circuit and the previous contributions
+    fn transcript(&self) -> HashWriter {
+        let mut sink = HashWriter::new();
+        sink.write_all(&self.cs_hash).unwrap();
+        for public_key in &self.contributions {
+            public_key.write(&mut sink).unwrap();
+        }
+        sink
+    }
+}
+
+/// The proof of knowledge of the secret of a contribution
+#[derive(Clone)]
+struct PublicKey {
+    /// delta after this contribution
+    delta_after: G1Affine,
+    /// a random point and its multiple by the secret
+    s: G1Affine,
+    s_delta: G1Affine,
+    /// a point derived from the transcript, multiplied by the secret
+    r_delta: G2Affine,
+    /// the hash of the transcript before this contribution
+    transcript: [u8; 64],
+}
+
+impl PublicKey {
+    fn hash(&self) -> [u8; 64] {
+        let mut sink = HashWriter::new();
+        self.write(&mut sink).unwrap();
+        sink.into_hash()
+    }
+
+    fn read<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let delta_after = read_point::<G1Uncompressed, _>(&mut reader)?;
+        let s = read_point::<G1Uncompressed, _>(&mut reader)?;
+        let s_delta = read_point::<G1Uncompressed, _>(&mut reader)?;
+        let r_delta = read_point::<G2Uncompressed, _>(&mut reader)?;
+
+        if delta_after.is_zero() || s.is_zero() || s_delta.is_zero() || r_delta.is_zero() {
+            return Err(ProofSystemError::InvalidMpcParameters(String::from(
+                "Unexpected point at infinity in a contribution",
+            )));
+        }
+
+        let mut transcript = [0u8; 64];
+        reader.read_exact(&mut transcript)?;
+
+        Ok(PublicKey {
+            delta_after,
+            s,
+            s_delta,
+            r_delta,
+            transcript,
+        })
+    }
+
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.delta_after.into_uncompressed().as_ref())?;
+        writer.write_all(self.s.into_uncompressed().as_ref())?;
+        writer.write_all(self.s_delta.into_uncompressed().as_ref())?;
+        writer.write_all(self.r_delta.into_uncompressed().as_ref())?;
+        writer.write_all(&self.transcript)
+    }
+}
+
+/// The part of a powers of tau accumulator used by a circuit, in the Lagrange basis of its evaluation domain of size `m`
+struct Phase1 {
+    alpha_g1: G1Affine,
+    beta_g1: G1Affine,
+    beta_g2: G2Affine,
+    coeffs_g1: Vec<G1Affine>,
+    coeffs_g2: Vec<G2Affine>,
+    alpha_coeffs_g1: Vec<G1Affine>,
+    beta_coeffs_g1: Vec<G1Affine>,
+    /// `tau^i * Z(tau)` for `i` in `0..m - 1`
+    h: Vec<G1Affine>,
+}
+
+impl Phase1 {
+    fn read<R: Read + Seek>(
+        mut reader: R,
+        num_constraints: usize,
+    ) -> Result<Self, ProofSystemError> {
+        let invalid = |message: String| ProofSystemError::InvalidMpcParameters(message);
+
+        // a hash, then 2n - 1 powers of tau in G1, n in G2, n multiplied by alpha and n by beta in G1, and beta in G2
+        let length = reader.seek(SeekFrom::End(0))?;
+        let n = match length.checked_sub(HASH_SIZE - G1_SIZE + G2_SIZE) {
+            Some(l) if l % (4 * G1_SIZE + G2_SIZE) == 0 => l / (4 * G1_SIZE + G2_SIZE),
+            _ => {
+                return Err(invalid(format!(
+                    "Unexpected size of powers of tau: {} bytes",
+                    length
+                )))
+            }
+        };
+
+        if !n.is_power_of_two() {
+            return Err(invalid(format!(
+                "Unexpected size of powers of tau: {} bytes",
+                length
+            )));
+        }
+
+        let m = num_constraints.next_power_of_two();
+
+        if (m as u64) > n {
+            return Err(invalid(format!(
+                "The powers of tau support up to {} constraints, found {}",
+                n, num_constraints
+            )));
+        }
+
+        let tau_powers_g1 = HASH_SIZE;
+        let tau_powers_g2 = tau_powers_g1 + (2 * n - 1) * G1_SIZE;
+        let alpha_tau_powers_g1 = tau_powers_g2 + n * G2_SIZE;
+        let beta_tau_powers_g1 = alpha_tau_powers_g1 + n * G1_SIZE;
+        let beta_g2 = beta_tau_powers_g1 + n * G1_SIZE;
+
+        let tau_g1 = read_points::<G1Uncompressed, _>(&mut reader, tau_powers_g1, 2 * m - 1)?;
+        let tau_g2 = read_points::<G2Uncompressed, _>(&mut reader, tau_powers_g2, m)?;
+        let alpha_tau_g1 = read_points::<G1Uncompressed, _>(&mut reader, alpha_tau_powers_g1, m)?;
+        let beta_tau_g1 = read_points::<G1Uncompressed, _>(&mut reader, beta_tau_powers_g1, m)?;
+        let beta_g2 = read_points::<G2Uncompressed, _>(&mut reader, beta_g2, 1)?[0];
+
+        // Z(tau) = tau^m - 1
+        let h = into_affine(
+            (0..m - 1)
+                .map(|i| {
+                    let mut p = tau_g1[i + m].into_projective();
+                    p.sub_assign(&tau_g1[i].into_projective());
+                    p
+                })
+                .collect(),
+        );
+
+        Ok(Phase1 {
+            alpha_g1: alpha_tau_g1[0],
+            beta_g1: beta_tau_g1[0],
+            beta_g2,
+            coeffs_g1: lagrange::<G1>(&tau_g1[..m]),
+            coeffs_g2: lagrange::<G2>(&tau_g2),
+            alpha_coeffs_g1: lagrange::<G1>(&alpha_tau_g1),
+            beta_coeffs_g1: lagrange::<G1>(&beta_tau_g1),
+            h,
+        })
+    }
+
+    /// Evaluates the QAP polynomials `at`, `bt` and `ct` of some variables at tau
+    fn eval(
+        &self,
+        at: &[Vec<(Fr, usize)>],
+        bt: &[Vec<(Fr, usize)>],
+        ct: &[Vec<(Fr, usize)>],
+    ) -> Evaluation {
+        let mut evaluation = Evaluation {
+            a_g1: vec![G1::zero(); at.len()],
+            b_g1: vec![G1::zero(); at.len()],
+            b_g2: vec![G2::zero(); at.len()],
+            ext: vec![G1::zero(); at.len()],
+        };
+
+        for i in 0..at.len() {
+            for &(coeff, lag) in &at[i] {
+                let coeff = coeff.into_repr();
+                evaluation.a_g1[i].add_assign(&self.coeffs_g1[lag].mul(coeff));
+                evaluation.ext[i].add_assign(&self.beta_coeffs_g1[lag].mul(coeff));
+            }
+
+            for &(coeff, lag) in &bt[i] {
+                let coeff = coeff.into_repr();
+                evaluation.b_g1[i].add_assign(&self.coeffs_g1[lag].mul(coeff));
+                evaluation.b_g2[i].add_assign(&self.coeffs_g2[lag].mul(coeff));
+                evaluation.ext[i].add_assign(&self.alpha_coeffs_g1[lag].mul(coeff));
+            }
+
+            for &(coeff, lag) in &ct[i] {
+                evaluation.ext[i].add_assign(&self.coeffs_g1[lag].mul(coeff.into_repr()));
+            }
+        }
+
+        evaluation
+    }
+}
+
+/// The QAP polynomials of some variables evaluated at tau, where `ext` is `beta * a + alpha * b + c`
+struct Evaluation {
+    a_g1: Vec<G1>,
+    b_g1: Vec<G1>,
+    b_g2: Vec<G2>,
+    ext: Vec<G1>,
+}
+
+/// Records the QAP polynomials of a circuit in the Lagrange basis, as in bellman's own parameter generation
+#[derive(Default)]
+struct KeypairAssembly {
+    num_inputs: usize,
+    num_aux: usize,
+    num_constraints: usize,
+    at_inputs: Vec<Vec<(Fr, usize)>>,
+    bt_inputs: Vec<Vec<(Fr, usize)>>,
+    ct_inputs: Vec<Vec<(Fr, usize)>>,
+    at_aux: Vec<Vec<(Fr, usize)>>,
+    bt_aux: Vec<Vec<(Fr, usize)>>,
+    ct_aux: Vec<Vec<(Fr, usize)>>,
+}
+
+impl ConstraintSystem<Bn256> for KeypairAssembly {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        self.at_aux.push(vec![]);
+        self.bt_aux.push(vec![]);
+        self.ct_aux.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        self.at_inputs.push(vec![]);
+        self.bt_inputs.push(vec![]);
+        self.ct_inputs.push(vec![]);
+
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+        LB: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+        LC: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+    {
+        fn eval(
+            l: LinearCombination<Bn256>,
+            inputs: &mut [Vec<(Fr, usize)>],
+            aux: &mut [Vec<(Fr, usize)>],
+            this_constraint: usize,
+        ) {
+            for &(var, coeff) in l.as_ref() {
+                match var.get_unchecked() {
+                    Index::Input(id) => inputs[id].push((coeff, this_constraint)),
+                    Index::Aux(id) => aux[id].push((coeff, this_constraint)),
+                }
+            }
+        }
+
+        eval(
+            a(LinearCombination::zero()),
+            &mut self.at_inputs,
+            &mut self.at_aux,
+            self.num_constraints,
+        );
+        eval(
+            b(LinearCombination::zero()),
+            &mut self.bt_inputs,
+            &mut self.bt_aux,
+            self.num_constraints,
+        );
+        eval(
+            c(LinearCombination::zero()),
+            &mut self.ct_inputs,
+            &mut self.ct_aux,
+            self.num_constraints,
+        );
+
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+/// A writer which hashes its input with BLAKE2b
+#[derive(Clone)]
+struct HashWriter(Blake2b);
+
+impl HashWriter {
+    fn new() -> Self {
+        HashWriter(Blake2b::new(64))
+    }
+
+    fn into_hash(mut self) -> [u8; 64] {
+        let mut hash = [0u8; 64];
+        self.0.result(&mut hash);
+        hash
+    }
+}
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.input(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn read_point<P: EncodedPoint, R: Read>(reader: &mut R) -> Result<P::Affine, ProofSystemError> {
+    let mut encoded = P::empty();
+    reader.read_exact(encoded.as_mut())?;
+    encoded
+        .into_affine()
+        .map_err(|e| ProofSystemError::InvalidMpcParameters(e.to_string()))
+}
+
+fn read_points<P: EncodedPoint, R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    count: usize,
+) -> Result<Vec<P::Affine>, ProofSystemError> {
+    reader.seek(SeekFrom::Start(offset))?;
+    (0..count).map(|_| read_point::<P, _>(reader)).collect()
+}
+
+/// The points of `inputs` then `aux` which are not at infinity, as the A and B queries only contain those
+fn non_zero<G: CurveProjective>(inputs: Vec<G>, aux: Vec<G>) -> Vec<G::Affine> {
+    into_affine(
+        inputs
+            .into_iter()
+            .chain(aux)
+            .filter(|e| !e.is_zero())
+            .collect(),
+    )
+}
+
+fn into_affine<G: CurveProjective>(mut points: Vec<G>) -> Vec<G::Affine> {
+    G::batch_normalization(&mut points);
+    points.into_iter().map(|p| p.into_affine()).collect()
+}
+
+fn mul_all(points: &[G1Affine], by: Fr) -> Vec<G1Affine> {
+    let by = by.into_repr();
+    into_affine(points.iter().map(|p| p.mul(by)).collect())
+}
+
+/// Converts `x^i` for `i` in `0..m` to the Lagrange basis `L_i(x)` of the evaluation domain of size `m`, which is
+/// the inverse FFT of these powers
+fn lagrange<G: CurveProjective<Scalar = Fr>>(powers: &[G::Affine]) -> Vec<G::Affine> {
+    let m = powers.len();
+    let log_m = m.trailing_zeros();
+
+    let mut omega = Fr::root_of_unity();
+    for _ in log_m..Fr::S {
+        omega.square();
+    }
+    let omega_inv = omega.inverse().unwrap();
+    let m_inv = Fr::from_str(&m.to_string()).unwrap().inverse().unwrap();
+
+    let mut points: Vec<G> = powers.iter().map(|p| p.into_projective()).collect();
+
+    fft(&mut points, &omega_inv, log_m);
+
+    for p in points.iter_mut() {
+        p.mul_assign(m_inv.into_repr());
+    }
+
+    into_affine(points)
+}
+
+fn fft<G: CurveProjective<Scalar = Fr>>(points: &mut [G], omega: &Fr, log_n: u32) {
+    fn bitreverse(mut n: usize, l: u32) -> usize {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    let len = points.len();
+
+    for i in 0..len {
+        let reversed = bitreverse(i, log_n);
+        if i < reversed {
+            points.swap(reversed, i);
+        }
+    }
+
+    let mut half = 1;
+    for _ in 0..log_n {
+        let root = omega.pow(&[(len / (2 * half)) as u64]);
+
+        for start in (0..len).step_by(2 * half) {
+            let mut twiddle = Fr::one();
+            for i in start..start + half {
+                let mut odd = points[i + half];
+                odd.mul_assign(twiddle.into_repr());
+                let mut even = points[i];
+                even.sub_assign(&odd);
+                points[i + half] = even;
+                points[i].add_assign(&odd);
+                twiddle.mul_assign(&root);
+            }
+        }
+
+        half *= 2;
+    }
+}
+
+/// A point of G2 derived from a hash
+fn hash_to_g2(digest: &[u8; 64]) -> G2 {
+    let seed: Vec<u32> = digest[..32]
+        .chunks(4)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    ChaChaRng::from_seed(&seed).gen()
+}
+
+/// Whether `g1.1 / g1.0 == g2.1 / g2.0`
+fn same_ratio<G1: CurveAffine<Engine = Bn256, Scalar = Fr>>(
+    g1: (G1, G1),
+    g2: (G1::Pair, G1::Pair),
+) -> bool {
+    g1.0.pairing_with(&g2.1) == g1.1.pairing_with(&g2.0)
+}
+
+/// Random linear combinations of `v1` and `v2` with the same coefficients, so that `same_ratio` on the results checks
+/// that all pairs `(v1[i], v2[i])` have the same ratio
+fn merge_pairs(v1: &[G1Affine], v2: &[G1Affine]) -> (G1Affine, G1Affine) {
+    let rng = &mut thread_rng();
+
+    let mut s = G1::zero();
+    let mut sx = G1::zero();
+
+    for (a, b) in v1.iter().zip(v2) {
+        let rho = Fr::rand(rng).into_repr();
+        s.add_assign(&a.mul(rho));
+        sx.add_assign(&b.mul(rho));
+    }
+
+    (s.into_affine(), sx.into_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, LinComb, Prog, QuadComb, Statement};
+    use crate::proof_system::{ProofSystem, G16};
+    use rand::XorShiftRng;
+    use std::io::Cursor;
+    use typed_absy::types::{Signature, Type};
+
+    /// `x * y == 42` with `x` public and `y` private
+    fn program() -> Prog<FieldPrime> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(1).into(),
+                        ),
+                        LinComb::summand(42, FlatVariable::one()),
+                    ),
+                    Statement::constraint(FlatVariable::new(0), FlatVariable::public(0)),
+                ],
+            },
+            private: vec![false, true],
+            signature: Signature::new()
+                .inputs(vec![Type::FieldElement, Type::FieldElement])
+                .outputs(vec![Type::FieldElement]),
+        }
+    }
+
+    /// An accumulator of `n` powers of tau for known secrets
+    fn powers_of_tau(n: usize) -> Cursor<Vec<u8>> {
+        let tau = Fr::from_str("5").unwrap();
+        let alpha = Fr::from_str("7").unwrap();
+        let beta = Fr::from_str("11").unwrap();
+
+        let powers: Vec<Fr> = (0..2 * n - 1).map(|i| tau.pow(&[i as u64])).collect();
+
+        let mul = |a: &Fr, b: &Fr| {
+            let mut res = *a;
+            res.mul_assign(b);
+            res
+        };
+
+        let mut res = vec![0u8; HASH_SIZE as usize];
+        for p in &powers {
+            res.extend(
+                G1Affine::one()
+                    .mul(p.into_repr())
+                    .into_affine()
+                    .into_uncompressed()
+                    .as_ref(),
+            );
+        }
+        for p in &powers[..n] {
+            res.extend(
+                G2Affine::one()
+                    .mul(p.into_repr())
+                    .into_affine()
+                    .into_uncompressed()
+                    .as_ref(),
+            );
+        }
+        for p in &powers[..n] {
+            res.extend(
+                G1Affine::one()
+                    .mul(mul(p, &alpha).into_repr())
+                    .into_affine()
+                    .into_uncompressed()
+                    .as_ref(),
+            );
+        }
+        for p in &powers[..n] {
+            res.extend(
+                G1Affine::one()
+                    .mul(mul(p, &beta).into_repr())
+                    .into_affine()
+                    .into_uncompressed()
+                    .as_ref(),
+            );
+        }
+        res.extend(
+            G2Affine::one()
+                .mul(beta.into_repr())
+                .into_affine()
+                .into_uncompressed()
+                .as_ref(),
+        );
+
+        Cursor::new(res)
+    }
+
+    #[test]
+    fn ceremony() {
+        let rng = &mut XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let mut params = MPCParameters::new(program(), powers_of_tau(8)).unwrap();
+
+        let first = params.contribute(rng);
+        let second = params.contribute(rng);
+        let beacon = params.beacon(b"beacon", 10).unwrap();
+
+        // parameters are handed over between participants
+        let mut buffer = vec![];
+        params.write(&mut buffer).unwrap();
+        let params = MPCParameters::read(&buffer[..]).unwrap();
+
+        let hashes = params.verify(program(), powers_of_tau(8)).unwrap();
+        assert_eq!(hashes.len(), 3);
+        assert!(hashes
+            .iter()
+            .zip(&[first, second, beacon])
+            .all(|(a, b)| a[..] == b[..]));
+
+        // the parameters can be used to prove
+        let keypair = params.keypair().unwrap();
+        let witness = program()
+            .execute(&vec![FieldPrime::from(6), FieldPrime::from(7)])
+            .unwrap();
+        let proof = G16 {}
+            .generate_proof(program(), witness, &keypair.pk)
+            .unwrap();
+
+        assert!(G16 {}.verify(&keypair.vk, &proof).unwrap());
+    }
+
+    #[test]
+    fn beacon_is_deterministic() {
+        let mut a = MPCParameters::new(program(), powers_of_tau(8)).unwrap();
+        let mut b = a.clone();
+
+        assert_eq!(
+            a.beacon(b"beacon", 10).unwrap()[..],
+            b.beacon(b"beacon", 10).unwrap()[..]
+        );
+        assert!(a.beacon(b"beacon", 9).is_err());
+    }
+
+    #[test]
+    fn powers_of_tau_too_small() {
+        // two constraints and three inputs need a domain of size 8
+        assert!(MPCParameters::new(program(), powers_of_tau(4)).is_err());
+        assert!(MPCParameters::new(program(), Cursor::new(vec![0u8; 1000])).is_err());
+    }
+
+    #[test]
+    fn verify_invalid() {
+        let rng = &mut XorShiftRng::from_seed([1, 2, 3, 4]);
+
+        let mut params = MPCParameters::new(program(), powers_of_tau(8)).unwrap();
+        params.contribute(rng);
+
+        // the secret of the last contribution is changed without a proof of knowledge
+        let mut tampered = params.clone();
+        tampered.params.vk.delta_g1 = tampered
+            .params
+            .vk
+            .delta_g1
+            .mul(Fr::from_str("2").unwrap())
+            .into_affine();
+        assert!(tampered.verify(program(), powers_of_tau(8)).is_err());
+
+        // a contribution is removed
+        let mut tampered = params.clone();
+        tampered.contributions.clear();
+        assert!(tampered.verify(program(), powers_of_tau(8)).is_err());
+
+        // the parameters of another program
+        let mut other = program();
+        other.private = vec![true, true];
+        assert!(params.verify(other, powers_of_tau(8)).is_err());
+
+        assert!(params.verify(program(), powers_of_tau(8)).is_ok());
+    }
+}
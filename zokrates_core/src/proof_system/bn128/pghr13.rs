@@ -1,19 +1,32 @@
 extern crate libc;
 
-use self::libc::{c_char, c_int};
+use self::libc::c_int;
 use ir;
-use proof_system::bn128::utils::libsnark::{prepare_generate_proof, prepare_setup};
+use pairing::bn256::{Bn256, G2Affine};
+use pairing::CurveAffine;
+use proof_system::bn128::utils::libsnark::{
+    prepare_generate_proof, prepare_setup, Buffer, SetupResult,
+};
+use proof_system::bn128::utils::parser::{
+    add, g1_from_json, g2_from_json, linear_combination, negate, pairing_product_is_one,
+    vk_g1_from_json, vk_g2_from_json, G1Json, G2Json, KeyPoints, ProofJson, VerificationKeyEntries,
+    VerificationKeyJson,
+};
+use proof_system::bn128::utils::rust::{
+    g1_array_literal, g1_literal, g2_literal, RUST_VERIFIER_LIB,
+};
 use proof_system::bn128::utils::solidity::{
-    SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+    g1_arguments, g1_assignments, g2_arguments, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB,
+    SOLIDITY_PAIRING_LIB_V2,
+};
+use proof_system::{
+    Proof, ProofSystem, ProofSystemError, ProvingKey, SetupKeypair, VerificationKey,
 };
-use proof_system::ProofSystem;
-
-use regex::Regex;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
 use zokrates_field::field::FieldPrime;
 
+const SCHEME: &str = "pghr13";
+
 pub struct PGHR13 {}
 
 impl PGHR13 {
@@ -22,6 +35,49 @@ impl PGHR13 {
     }
 }
 
+#[derive(Deserialize)]
+struct ProofPoints {
+    a: G1Json,
+    a_p: G1Json,
+    b: G2Json,
+    b_p: G1Json,
+    c: G1Json,
+    c_p: G1Json,
+    h: G1Json,
+    k: G1Json,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VerificationKeyPoints {
+    a: G2Json,
+    b: G1Json,
+    c: G2Json,
+    gamma: G2Json,
+    gamma_beta_1: G1Json,
+    gamma_beta_2: G2Json,
+    z: G2Json,
+    ic: Vec<G1Json>,
+}
+
+impl KeyPoints for VerificationKeyPoints {
+    fn from_entries(entries: &VerificationKeyEntries) -> Result<Self, ProofSystemError> {
+        Ok(VerificationKeyPoints {
+            a: entries.g2("a")?,
+            b: entries.g1("b")?,
+            c: entries.g2("c")?,
+            gamma: entries.g2("gamma")?,
+            gamma_beta_1: entries.g1("gamma_beta_1")?,
+            gamma_beta_2: entries.g2("gamma_beta_2")?,
+            z: entries.g2("z")?,
+            ic: entries.g1_vec("ic")?,
+        })
+    }
+
+    fn query(&self) -> &[G1Json] {
+        &self.ic
+    }
+}
+
 extern "C" {
     fn _pghr13_setup(
         A: *const u8,
@@ -33,38 +89,25 @@ extern "C" {
         constraints: c_int,
         variables: c_int,
         inputs: c_int,
-        pk_path: *const c_char,
-        vk_path: *const c_char,
-    ) -> bool;
+    ) -> SetupResult;
 
     fn _pghr13_generate_proof(
-        pk_path: *const c_char,
-        proof_path: *const c_char,
+        pk: *const u8,
+        pk_length: c_int,
         public_inputs: *const u8,
         public_inputs_length: c_int,
         private_inputs: *const u8,
         private_inputs_length: c_int,
-    ) -> bool;
+    ) -> Buffer;
 }
 
 impl ProofSystem for PGHR13 {
-    fn setup(&self, program: ir::Prog<FieldPrime>, pk_path: &str, vk_path: &str) {
-        let (
-            a_arr,
-            b_arr,
-            c_arr,
-            a_vec,
-            b_vec,
-            c_vec,
-            num_constraints,
-            num_variables,
-            num_inputs,
-            pk_path_cstring,
-            vk_path_cstring,
-        ) = prepare_setup(program, pk_path, vk_path);
-
-        unsafe {
-            _pghr13_setup(
+    fn setup(&self, program: ir::Prog<FieldPrime>) -> Result<SetupKeypair, ProofSystemError> {
+        let (a_arr, b_arr, c_arr, a_vec, b_vec, c_vec, num_constraints, num_variables, num_inputs) =
+            prepare_setup(program);
+
+        let (vk, pk) = unsafe {
+            let result = _pghr13_setup(
                 a_arr.as_ptr(),
                 b_arr.as_ptr(),
                 c_arr.as_ptr(),
@@ -74,131 +117,227 @@ impl ProofSystem for PGHR13 {
                 num_constraints as i32,
                 num_variables as i32,
                 num_inputs as i32,
-                pk_path_cstring.as_ptr(),
-                vk_path_cstring.as_ptr(),
             );
-        }
+            (result.vk.into_string(), result.pk.into_vec())
+        };
+
+        let points: VerificationKeyPoints = serde_json::from_str(&vk?)
+            .map_err(|e| ProofSystemError::Backend(format!("Invalid verification key: {}", e)))?;
+
+        Ok(SetupKeypair {
+            vk: VerificationKeyJson::new(SCHEME, points).to_verification_key(),
+            pk: ProvingKey::new(pk),
+        })
     }
 
     fn generate_proof(
         &self,
         program: ir::Prog<FieldPrime>,
         witness: ir::Witness<FieldPrime>,
-        pk_path: &str,
-        proof_path: &str,
-    ) -> bool {
-        let (
-            pk_path_cstring,
-            proof_path_cstring,
-            public_inputs_arr,
-            public_inputs_length,
-            private_inputs_arr,
-            private_inputs_length,
-        ) = prepare_generate_proof(program, witness, pk_path, proof_path);
-
-        println!(
-            "{:?}",
-            (pk_path_cstring.clone(), proof_path_cstring.clone(),)
-        );
-
-        unsafe {
+        proving_key: &ProvingKey,
+    ) -> Result<Proof, ProofSystemError> {
+        let (public_inputs_arr, public_inputs_length, private_inputs_arr, private_inputs_length) =
+            prepare_generate_proof(program, witness)?;
+
+        let proof = unsafe {
             _pghr13_generate_proof(
-                pk_path_cstring.as_ptr(),
-                proof_path_cstring.as_ptr(),
+                proving_key.as_bytes().as_ptr(),
+                proving_key.as_bytes().len() as i32,
                 public_inputs_arr[0].as_ptr(),
                 public_inputs_length as i32,
                 private_inputs_arr[0].as_ptr(),
                 private_inputs_length as i32,
             )
-        }
+            .into_string()
+        };
+
+        Ok(Proof::new(proof?))
     }
 
-    fn export_solidity_verifier(&self, reader: BufReader<File>, is_abiv2: bool) -> String {
-        let mut lines = reader.lines();
+    fn export_solidity_verifier(
+        &self,
+        vk: &VerificationKey,
+        is_abiv2: bool,
+    ) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
 
-        let (mut template_text, solidity_pairing_lib) = if is_abiv2 {
-            (
-                String::from(CONTRACT_TEMPLATE_V2),
-                String::from(SOLIDITY_PAIRING_LIB_V2),
-            )
+        let (template, solidity_pairing_lib) = if is_abiv2 {
+            (CONTRACT_TEMPLATE_V2, SOLIDITY_PAIRING_LIB_V2)
         } else {
-            (
-                String::from(CONTRACT_TEMPLATE),
-                String::from(SOLIDITY_PAIRING_LIB),
-            )
+            (CONTRACT_TEMPLATE, SOLIDITY_PAIRING_LIB)
         };
 
-        let ic_template = String::from("vk.ic[index] = Pairing.G1Point(points);"); //copy this for each entry
-
-        //replace things in template
-        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
-        let vk_ic_len_regex = Regex::new(r#"(<%vk_ic_length%>)"#).unwrap();
-        let vk_ic_index_regex = Regex::new(r#"index"#).unwrap();
-        let vk_ic_points_regex = Regex::new(r#"points"#).unwrap();
-        let vk_ic_repeat_regex = Regex::new(r#"(<%vk_ic_pts%>)"#).unwrap();
-        let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
-
-        for _ in 0..7 {
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            template_text = vk_regex
-                .replace(template_text.as_str(), current_line_split[1].trim())
-                .into_owned();
-        }
-
-        let current_line: String = lines
-            .next()
-            .expect("Unexpected end of file in verification key!")
-            .unwrap();
-        let current_line_split: Vec<&str> = current_line.split("=").collect();
-        assert_eq!(current_line_split.len(), 2);
-        let ic_count: i32 = current_line_split[1].trim().parse().unwrap();
-
-        template_text = vk_ic_len_regex
-            .replace(template_text.as_str(), format!("{}", ic_count).as_str())
-            .into_owned();
-        template_text = vk_input_len_regex
-            .replace(template_text.as_str(), format!("{}", ic_count - 1).as_str())
-            .into_owned();
-
-        let mut ic_repeat_text = String::new();
-        for x in 0..ic_count {
-            let mut curr_template = ic_template.clone();
-            let current_line: String = lines
-                .next()
-                .expect("Unexpected end of file in verification key!")
-                .unwrap();
-            let current_line_split: Vec<&str> = current_line.split("=").collect();
-            assert_eq!(current_line_split.len(), 2);
-            curr_template = vk_ic_index_regex
-                .replace(curr_template.as_str(), format!("{}", x).as_str())
-                .into_owned();
-            curr_template = vk_ic_points_regex
-                .replace(curr_template.as_str(), current_line_split[1].trim())
-                .into_owned();
-            ic_repeat_text.push_str(curr_template.as_str());
-            if x < ic_count - 1 {
-                ic_repeat_text.push_str("\n        ");
-            }
-        }
-        template_text = vk_ic_repeat_regex
-            .replace(template_text.as_str(), ic_repeat_text.as_str())
-            .into_owned();
+        let template_text = template
+            .replace("<%vk_a%>", &g2_arguments(&vk.points.a)?)
+            .replace("<%vk_b%>", &g1_arguments(&vk.points.b)?)
+            .replace("<%vk_c%>", &g2_arguments(&vk.points.c)?)
+            .replace("<%vk_g%>", &g2_arguments(&vk.points.gamma)?)
+            .replace("<%vk_gb1%>", &g1_arguments(&vk.points.gamma_beta_1)?)
+            .replace("<%vk_gb2%>", &g2_arguments(&vk.points.gamma_beta_2)?)
+            .replace("<%vk_z%>", &g2_arguments(&vk.points.z)?)
+            .replace("<%vk_ic_length%>", &vk.points.ic.len().to_string())
+            .replace("<%vk_ic_pts%>", &g1_assignments("ic", &vk.points.ic)?)
+            .replace("<%vk_input_length%>", &vk.inputs.to_string());
 
-        let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
-        template_text = re.replace_all(&template_text, "uint256($v)").to_string();
-
-        format!(
+        Ok(format!(
             "{}{}{}",
             SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
-        )
+        ))
+    }
+
+    fn export_rust_verifier(&self, vk: &VerificationKey) -> Result<String, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?;
+
+        let template_text = RUST_TEMPLATE
+            .replace("<%inputs%>", &vk.inputs.to_string())
+            .replace("<%a%>", &g2_literal(&vk.points.a)?)
+            .replace("<%b%>", &g1_literal(&vk.points.b)?)
+            .replace("<%c%>", &g2_literal(&vk.points.c)?)
+            .replace("<%gamma%>", &g2_literal(&vk.points.gamma)?)
+            .replace("<%gamma_beta_1%>", &g1_literal(&vk.points.gamma_beta_1)?)
+            .replace("<%gamma_beta_2%>", &g2_literal(&vk.points.gamma_beta_2)?)
+            .replace("<%z%>", &g2_literal(&vk.points.z)?)
+            .replace("<%ic%>", &g1_array_literal(&vk.points.ic)?);
+
+        Ok(format!("{}{}", template_text, RUST_VERIFIER_LIB))
+    }
+
+    fn verify(&self, vk: &VerificationKey, proof: &Proof) -> Result<bool, ProofSystemError> {
+        let vk = VerificationKeyJson::<VerificationKeyPoints>::parse(vk.as_str(), SCHEME)?.points;
+        let proof = ProofJson::<ProofPoints>::parse(proof.as_str())?;
+
+        let ic = vk
+            .ic
+            .iter()
+            .map(vk_g1_from_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        let vk_x = linear_combination(&ic, &proof.inputs()?)?;
+
+        let a = g1_from_json(&proof.proof.a)?;
+        let b = g2_from_json(&proof.proof.b)?;
+        let c = g1_from_json(&proof.proof.c)?;
+        let p2 = G2Affine::one();
+
+        Ok(pairing_product_is_one::<Bn256>(&[
+            (a, vk_g2_from_json(&vk.a)?),
+            (negate(g1_from_json(&proof.proof.a_p)?), p2),
+        ]) && pairing_product_is_one::<Bn256>(&[
+            (vk_g1_from_json(&vk.b)?, b),
+            (negate(g1_from_json(&proof.proof.b_p)?), p2),
+        ]) && pairing_product_is_one::<Bn256>(&[
+            (c, vk_g2_from_json(&vk.c)?),
+            (negate(g1_from_json(&proof.proof.c_p)?), p2),
+        ]) && pairing_product_is_one::<Bn256>(&[
+            (g1_from_json(&proof.proof.k)?, vk_g2_from_json(&vk.gamma)?),
+            (
+                negate(add(vk_x, add(a, c))),
+                vk_g2_from_json(&vk.gamma_beta_2)?,
+            ),
+            (negate(vk_g1_from_json(&vk.gamma_beta_1)?), b),
+        ]) && pairing_product_is_one::<Bn256>(&[
+            (add(vk_x, a), b),
+            (
+                negate(g1_from_json(&proof.proof.h)?),
+                vk_g2_from_json(&vk.z)?,
+            ),
+            (negate(c), p2),
+        ]))
     }
 }
 
+const RUST_TEMPLATE: &str = r#"//! Verifies PGHR13 proofs of a ZoKrates program, generated by `zokrates export-verifier --target rust`
+//!
+//! Requires `pairing_ce = "0.18"`.
+
+use pairing_ce::bn256::{
+    Bn256, Fq12, Fr, FrRepr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed,
+};
+use pairing_ce::ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+
+/// The number of public inputs of the program, including its outputs
+pub const INPUTS: usize = <%inputs%>;
+
+const A: [[&str; 2]; 2] = <%a%>;
+const B: [&str; 2] = <%b%>;
+const C: [[&str; 2]; 2] = <%c%>;
+const GAMMA: [[&str; 2]; 2] = <%gamma%>;
+const GAMMA_BETA_1: [&str; 2] = <%gamma_beta_1%>;
+const GAMMA_BETA_2: [[&str; 2]; 2] = <%gamma_beta_2%>;
+const Z: [[&str; 2]; 2] = <%z%>;
+const IC: [[&str; 2]; INPUTS + 1] = <%ic%>;
+
+pub struct Proof {
+    pub a: G1Affine,
+    pub a_p: G1Affine,
+    pub b: G2Affine,
+    pub b_p: G1Affine,
+    pub c: G1Affine,
+    pub c_p: G1Affine,
+    pub h: G1Affine,
+    pub k: G1Affine,
+}
+
+impl Proof {
+    /// Reads a proof from the coordinates of its points as written in `proof.json`, if they are points of the curve
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_hex(
+        a: [&str; 2],
+        a_p: [&str; 2],
+        b: [[&str; 2]; 2],
+        b_p: [&str; 2],
+        c: [&str; 2],
+        c_p: [&str; 2],
+        h: [&str; 2],
+        k: [&str; 2],
+    ) -> Option<Self> {
+        Some(Proof {
+            a: g1(a)?,
+            a_p: g1(a_p)?,
+            b: g2(b)?,
+            b_p: g1(b_p)?,
+            c: g1(c)?,
+            c_p: g1(c_p)?,
+            h: g1(h)?,
+            k: g1(k)?,
+        })
+    }
+}
+
+/// Checks `proof` for the public `inputs`, that is the public arguments of the program followed by its outputs
+pub fn verify(proof: &Proof, inputs: &[Fr]) -> bool {
+    if inputs.len() != INPUTS {
+        return false;
+    }
+
+    let vk_x = linear_combination(&IC, inputs);
+    let p2 = G2Affine::one();
+
+    pairing_product_is_one(&[(proof.a, g2(A).unwrap()), (negate(proof.a_p), p2)])
+        && pairing_product_is_one(&[(g1(B).unwrap(), proof.b), (negate(proof.b_p), p2)])
+        && pairing_product_is_one(&[(proof.c, g2(C).unwrap()), (negate(proof.c_p), p2)])
+        && pairing_product_is_one(&[
+            (proof.k, g2(GAMMA).unwrap()),
+            (
+                negate(add(vk_x, add(proof.a, proof.c))),
+                g2(GAMMA_BETA_2).unwrap(),
+            ),
+            (negate(g1(GAMMA_BETA_1).unwrap()), proof.b),
+        ])
+        && pairing_product_is_one(&[
+            (add(vk_x, proof.a), proof.b),
+            (negate(proof.h), g2(Z).unwrap()),
+            (negate(proof.c), p2),
+        ])
+}
+
+fn add<G: CurveAffine>(a: G, b: G) -> G {
+    let mut sum = a.into_projective();
+    sum.add_assign_mixed(&b);
+    sum.into_affine()
+}
+"#;
+
 const CONTRACT_TEMPLATE_V2: &str = r#"contract Verifier {
     using Pairing for *;
     struct VerifyingKey {
@@ -0,0 +1,897 @@
+//! Export and import of constraint systems and witnesses in the binary `.r1cs` and `.wtns` formats of
+//! [iden3](https://github.com/iden3/r1csfile), as read by snarkjs and the other tools of the circom ecosystem
+//!
+//! Wires are grouped as the formats require: the constant `1`, the outputs, the public inputs, the private inputs and
+//! finally the intermediate variables. Within each group, they are in the order in which `Computation::synthesize`
+//! allocates the variables of the proving schemes.
+//!
+//! Imported constraint systems become programs without directives: their witness cannot be computed by ZoKrates and
+//! is imported along with them.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Function, LinComb, Prog, QuadComb, Statement, Witness};
+use crate::proof_system::ProofSystemError;
+use bellman::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use num_bigint::BigUint;
+use pairing::bn256::{Bn256, Fr, FrRepr};
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use typed_absy::types::{Signature, Type};
+use zokrates_field::field::{Field as ZokratesField, FieldPrime};
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+
+const HEADER_SECTION: u32 = 1;
+const CONSTRAINTS_SECTION: u32 = 2;
+const WIRE_TO_LABEL_SECTION: u32 = 3;
+const WITNESS_SECTION: u32 = 2;
+
+/// The size in bytes of an element of the scalar field
+const FIELD_SIZE: u32 = 32;
+
+/// Writes the constraint system of `program` in the `.r1cs` format
+///
+/// Wires are labelled with their own index, as ZoKrates does not keep track of the names of intermediate variables.
+pub fn write_r1cs<W: Write>(
+    program: Prog<FieldPrime>,
+    mut writer: W,
+) -> Result<(), ProofSystemError> {
+    let private_inputs = program.private.iter().filter(|private| **private).count();
+
+    let assembly = Assembly::synthesize(program, None)?;
+    let wires = assembly.wires();
+
+    let mut header = vec![];
+    header.write_all(&FIELD_SIZE.to_le_bytes())?;
+    Fr::char().write_le(&mut header)?;
+    for count in &[
+        wires,
+        assembly.outputs(),
+        assembly.public_inputs,
+        private_inputs,
+    ] {
+        write_u32(&mut header, *count)?;
+    }
+    header.write_all(&(wires as u64).to_le_bytes())?;
+    write_u32(&mut header, assembly.constraints.len())?;
+
+    let mut constraints = vec![];
+    for constraint in assembly.constraints() {
+        for lc in constraint.iter() {
+            write_u32(&mut constraints, lc.len())?;
+            for (wire, coeff) in lc {
+                write_u32(&mut constraints, *wire)?;
+                coeff.into_repr().write_le(&mut constraints)?;
+            }
+        }
+    }
+
+    let mut labels = vec![];
+    for wire in 0..wires {
+        labels.write_all(&(wire as u64).to_le_bytes())?;
+    }
+
+    writer.write_all(R1CS_MAGIC)?;
+    writer.write_all(&R1CS_VERSION.to_le_bytes())?;
+    write_u32(&mut writer, 3)?;
+    write_section(&mut writer, HEADER_SECTION, &header)?;
+    write_section(&mut writer, CONSTRAINTS_SECTION, &constraints)?;
+    write_section(&mut writer, WIRE_TO_LABEL_SECTION, &labels)?;
+
+    Ok(())
+}
+
+/// Writes the values of all wires of `program` for `witness` in the `.wtns` format
+///
+/// Returns an error if the witness is incomplete or does not satisfy the constraints of `program`.
+pub fn write_wtns<W: Write>(
+    program: Prog<FieldPrime>,
+    witness: Witness<FieldPrime>,
+    mut writer: W,
+) -> Result<(), ProofSystemError> {
+    let assembly = Assembly::synthesize(program, Some(witness))?;
+    let values = assembly.values()?;
+
+    if !assembly.is_satisfied_by(&values) {
+        return Err(SynthesisError::Unsatisfiable.into());
+    }
+
+    let mut header = vec![];
+    header.write_all(&FIELD_SIZE.to_le_bytes())?;
+    Fr::char().write_le(&mut header)?;
+    write_u32(&mut header, values.len())?;
+
+    let mut witness = vec![];
+    for value in values {
+        value.into_repr().write_le(&mut witness)?;
+    }
+
+    writer.write_all(WTNS_MAGIC)?;
+    writer.write_all(&WTNS_VERSION.to_le_bytes())?;
+    write_u32(&mut writer, 2)?;
+    write_section(&mut writer, HEADER_SECTION, &header)?;
+    write_section(&mut writer, WITNESS_SECTION, &witness)?;
+
+    Ok(())
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: usize) -> io::Result<()> {
+    writer.write_all(&(value as u32).to_le_bytes())
+}
+
+fn write_section<W: Write>(writer: &mut W, section: u32, content: &[u8]) -> io::Result<()> {
+    writer.write_all(&section.to_le_bytes())?;
+    writer.write_all(&(content.len() as u64).to_le_bytes())?;
+    writer.write_all(content)
+}
+
+/// Reads a constraint system as a program, either in the `.r1cs` format or in the JSON format of
+/// `snarkjs r1cs export json`
+///
+/// The outputs become the return values of the program, and the public and private inputs its arguments, in this
+/// order. The program has no directives, so its witness cannot be computed and must be imported with `read_wtns`.
+pub fn read_r1cs<R: Read>(mut reader: R) -> Result<Prog<FieldPrime>, ProofSystemError> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+
+    let r1cs = if bytes.starts_with(R1CS_MAGIC) {
+        R1cs::from_bytes(&bytes)
+    } else {
+        serde_json::from_slice::<JsonR1cs>(&bytes)
+            .map_err(|e| e.to_string())
+            .and_then(R1cs::from_json)
+    }
+    .map_err(ProofSystemError::InvalidR1cs)?;
+
+    Ok(r1cs.into_program())
+}
+
+/// Reads the values of the wires of a program imported with `read_r1cs`, either in the `.wtns` format or as a JSON
+/// array of decimal strings, as written by `snarkjs wtns export json`
+///
+/// Returns an error if the values do not satisfy the constraints of `program`.
+pub fn read_wtns<R: Read>(
+    program: &Prog<FieldPrime>,
+    mut reader: R,
+) -> Result<Witness<FieldPrime>, ProofSystemError> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+
+    let values = if bytes.starts_with(WTNS_MAGIC) {
+        read_wtns_values(&bytes)
+    } else {
+        serde_json::from_slice::<Vec<String>>(&bytes)
+            .map_err(|e| e.to_string())
+            .and_then(|values| values.iter().map(|v| parse_fr(v)).collect())
+    }
+    .map_err(ProofSystemError::InvalidWitness)?;
+
+    let outputs = program.main.returns.len();
+    let wires = 1 + outputs + program.main.arguments.len();
+    if values.len() < wires {
+        return Err(ProofSystemError::InvalidWitness(format!(
+            "expected at least {} values, found {}",
+            wires,
+            values.len()
+        )));
+    }
+    if values[0] != Fr::one() {
+        return Err(ProofSystemError::InvalidWitness(String::from(
+            "the value of wire 0 must be 1",
+        )));
+    }
+
+    let witness: Witness<_> = values
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .map(|(wire, value)| (variable(wire, outputs), FieldPrime::from_bellman(value)))
+        .collect();
+
+    let assembly = Assembly::synthesize(program.clone(), Some(witness.clone()))?;
+    if !assembly.is_satisfied_by(&assembly.values()?) {
+        return Err(SynthesisError::Unsatisfiable.into());
+    }
+
+    Ok(witness)
+}
+
+/// The variable of an imported wire
+fn variable(wire: usize, outputs: usize) -> FlatVariable {
+    match wire {
+        0 => FlatVariable::one(),
+        w if w <= outputs => FlatVariable::public(w - 1),
+        w => FlatVariable::new(w - outputs - 1),
+    }
+}
+
+/// The sections of a file in one of the iden3 formats, by type
+fn read_sections<'a>(
+    mut bytes: &'a [u8],
+    magic: &[u8],
+    version: u32,
+) -> Result<BTreeMap<u32, &'a [u8]>, String> {
+    bytes = &bytes[magic.len()..];
+
+    let found = read_u32(&mut bytes)?;
+    if found != version {
+        return Err(format!("unsupported version {}", found));
+    }
+
+    let mut sections = BTreeMap::new();
+    for _ in 0..read_u32(&mut bytes)? {
+        let section = read_u32(&mut bytes)?;
+        let size = read_u64(&mut bytes)? as usize;
+        if size > bytes.len() {
+            return Err(format!("section {} is truncated", section));
+        }
+        sections.insert(section, &bytes[..size]);
+        bytes = &bytes[size..];
+    }
+
+    Ok(sections)
+}
+
+fn section<'a>(sections: &BTreeMap<u32, &'a [u8]>, section: u32) -> Result<&'a [u8], String> {
+    sections
+        .get(&section)
+        .cloned()
+        .ok_or_else(|| format!("section {} is missing", section))
+}
+
+/// Checks that the header at the start of `bytes` describes the scalar field of the curve and moves past it
+fn read_field(bytes: &mut &[u8]) -> Result<(), String> {
+    let size = read_u32(bytes)?;
+    if size != FIELD_SIZE {
+        return Err(format!(
+            "expected field elements of {} bytes, found {}",
+            FIELD_SIZE, size
+        ));
+    }
+
+    let mut prime = FrRepr::default();
+    prime.read_le(bytes).map_err(|e| e.to_string())?;
+    if prime != Fr::char() {
+        return Err(String::from(
+            "the prime does not match the scalar field of the curve",
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_wtns_values(bytes: &[u8]) -> Result<Vec<Fr>, String> {
+    let sections = read_sections(bytes, WTNS_MAGIC, WTNS_VERSION)?;
+
+    let mut header = section(&sections, HEADER_SECTION)?;
+    read_field(&mut header)?;
+    let count = read_u32(&mut header)?;
+
+    let mut values = section(&sections, WITNESS_SECTION)?;
+    (0..count).map(|_| read_fr(&mut values)).collect()
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, String> {
+    let mut value = [0u8; 4];
+    bytes
+        .read_exact(&mut value)
+        .map_err(|_| String::from("unexpected end of file"))?;
+    Ok(u32::from_le_bytes(value))
+}
+
+fn read_u64(bytes: &mut &[u8]) -> Result<u64, String> {
+    let mut value = [0u8; 8];
+    bytes
+        .read_exact(&mut value)
+        .map_err(|_| String::from("unexpected end of file"))?;
+    Ok(u64::from_le_bytes(value))
+}
+
+fn read_fr(bytes: &mut &[u8]) -> Result<Fr, String> {
+    let mut repr = FrRepr::default();
+    repr.read_le(bytes)
+        .map_err(|_| String::from("unexpected end of file"))?;
+    Fr::from_repr(repr).map_err(|e| e.to_string())
+}
+
+fn parse_fr(value: &str) -> Result<Fr, String> {
+    Fr::from_str(value).ok_or_else(|| format!("invalid field element {}", value))
+}
+
+/// A constraint system in the JSON format of snarkjs, of which only the fields needed to import it are read
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonR1cs {
+    n8: u32,
+    prime: String,
+    n_vars: usize,
+    n_outputs: usize,
+    n_pub_inputs: usize,
+    n_prv_inputs: usize,
+    /// The linear combinations `a`, `b` and `c` of each constraint `a * b == c`, as maps from wires to coefficients
+    constraints: Vec<[BTreeMap<String, String>; 3]>,
+}
+
+/// A constraint system over the wires of the iden3 formats
+struct R1cs {
+    wires: usize,
+    outputs: usize,
+    public_inputs: usize,
+    private_inputs: usize,
+    constraints: Vec<[Vec<(usize, Fr)>; 3]>,
+}
+
+impl R1cs {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let sections = read_sections(bytes, R1CS_MAGIC, R1CS_VERSION)?;
+
+        let mut header = section(&sections, HEADER_SECTION)?;
+        read_field(&mut header)?;
+        let wires = read_u32(&mut header)? as usize;
+        let outputs = read_u32(&mut header)? as usize;
+        let public_inputs = read_u32(&mut header)? as usize;
+        let private_inputs = read_u32(&mut header)? as usize;
+        let _labels = read_u64(&mut header)?;
+        let count = read_u32(&mut header)?;
+
+        let mut bytes = section(&sections, CONSTRAINTS_SECTION)?;
+        let mut lc = || -> Result<Vec<(usize, Fr)>, String> {
+            (0..read_u32(&mut bytes)?)
+                .map(|_| Ok((read_u32(&mut bytes)? as usize, read_fr(&mut bytes)?)))
+                .collect()
+        };
+        let constraints = (0..count)
+            .map(|_| Ok([lc()?, lc()?, lc()?]))
+            .collect::<Result<_, String>>()?;
+
+        R1cs {
+            wires,
+            outputs,
+            public_inputs,
+            private_inputs,
+            constraints,
+        }
+        .check()
+    }
+
+    fn from_json(r1cs: JsonR1cs) -> Result<Self, String> {
+        if r1cs.n8 != FIELD_SIZE {
+            return Err(format!(
+                "expected field elements of {} bytes, found {}",
+                FIELD_SIZE, r1cs.n8
+            ));
+        }
+
+        let mut prime = vec![];
+        Fr::char().write_le(&mut prime).map_err(|e| e.to_string())?;
+        if r1cs.prime != BigUint::from_bytes_le(&prime).to_str_radix(10) {
+            return Err(String::from(
+                "the prime does not match the scalar field of the curve",
+            ));
+        }
+
+        let lc = |terms: &BTreeMap<String, String>| -> Result<Vec<(usize, Fr)>, String> {
+            terms
+                .iter()
+                .map(|(wire, coeff)| {
+                    let wire = wire.parse().map_err(|_| format!("invalid wire {}", wire))?;
+                    Ok((wire, parse_fr(coeff)?))
+                })
+                .collect()
+        };
+        let constraints = r1cs
+            .constraints
+            .iter()
+            .map(|[a, b, c]| Ok([lc(a)?, lc(b)?, lc(c)?]))
+            .collect::<Result<_, String>>()?;
+
+        R1cs {
+            wires: r1cs.n_vars,
+            outputs: r1cs.n_outputs,
+            public_inputs: r1cs.n_pub_inputs,
+            private_inputs: r1cs.n_prv_inputs,
+            constraints,
+        }
+        .check()
+    }
+
+    /// Checks that the counts of the header are consistent with each other and with the constraints
+    fn check(self) -> Result<Self, String> {
+        if 1 + self.outputs + self.public_inputs + self.private_inputs > self.wires {
+            return Err(format!(
+                "{} wires cannot hold the constant, {} outputs and {} inputs",
+                self.wires,
+                self.outputs,
+                self.public_inputs + self.private_inputs
+            ));
+        }
+
+        for (wire, _) in self.constraints.iter().flatten().flatten() {
+            if *wire >= self.wires {
+                return Err(format!(
+                    "wire {} is out of bounds, there are {} wires",
+                    wire, self.wires
+                ));
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn into_program(self) -> Prog<FieldPrime> {
+        let outputs = self.outputs;
+        let public_inputs = self.public_inputs;
+        let inputs = public_inputs + self.private_inputs;
+
+        let lc = |terms: Vec<(usize, Fr)>| {
+            LinComb(
+                terms
+                    .into_iter()
+                    .map(|(wire, coeff)| (variable(wire, outputs), FieldPrime::from_bellman(coeff)))
+                    .collect(),
+            )
+        };
+
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: (0..inputs).map(FlatVariable::new).collect(),
+                returns: (0..outputs).map(FlatVariable::public).collect(),
+                statements: self
+                    .constraints
+                    .into_iter()
+                    .map(|[a, b, c]| {
+                        Statement::constraint(
+                            QuadComb::from_linear_combinations(lc(a), lc(b)),
+                            lc(c),
+                        )
+                    })
+                    .collect(),
+            },
+            private: (0..inputs).map(|i| i >= public_inputs).collect(),
+            signature: Signature::new()
+                .inputs(vec![Type::FieldElement; inputs])
+                .outputs(vec![Type::FieldElement; outputs]),
+        }
+    }
+}
+
+/// Records the constraints of a circuit and the values of its wires, if they are known
+#[derive(Default)]
+struct Assembly {
+    /// The number of public inputs, which are allocated before the outputs
+    public_inputs: usize,
+    inputs: Vec<Option<Fr>>,
+    aux: Vec<Option<Fr>>,
+    constraints: Vec<[Vec<(Index, Fr)>; 3]>,
+}
+
+impl Assembly {
+    fn synthesize(
+        program: Prog<FieldPrime>,
+        witness: Option<Witness<FieldPrime>>,
+    ) -> Result<Self, ProofSystemError> {
+        let mut assembly = Assembly {
+            public_inputs: program.private.iter().filter(|private| !**private).count(),
+            ..Assembly::default()
+        };
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(Fr::one()))?;
+
+        program.synthesize(&mut assembly, witness)?;
+
+        Ok(assembly)
+    }
+
+    fn wires(&self) -> usize {
+        self.inputs.len() + self.aux.len()
+    }
+
+    fn outputs(&self) -> usize {
+        self.inputs.len() - 1 - self.public_inputs
+    }
+
+    /// The wire of a variable, where the outputs are moved before the public inputs
+    fn wire(&self, index: Index) -> usize {
+        match index {
+            Index::Input(0) => 0,
+            Index::Input(i) if i <= self.public_inputs => self.outputs() + i,
+            Index::Input(i) => i - self.public_inputs,
+            Index::Aux(i) => self.inputs.len() + i,
+        }
+    }
+
+    /// The values of all wires, if they are all known
+    fn values(&self) -> Result<Vec<Fr>, SynthesisError> {
+        let mut values = vec![Fr::zero(); self.wires()];
+
+        let inputs = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (Index::Input(i), v));
+        let aux = self.aux.iter().enumerate().map(|(i, v)| (Index::Aux(i), v));
+
+        for (index, value) in inputs.chain(aux) {
+            values[self.wire(index)] = value.ok_or(SynthesisError::AssignmentMissing)?;
+        }
+
+        Ok(values)
+    }
+
+    /// The constraints over wire numbers, with the terms of each linear combination sorted by wire
+    fn constraints(&self) -> Vec<[Vec<(usize, Fr)>; 3]> {
+        let lc = |terms: &[(Index, Fr)]| {
+            let mut merged = BTreeMap::new();
+            for (index, coeff) in terms {
+                merged
+                    .entry(self.wire(*index))
+                    .or_insert_with(Fr::zero)
+                    .add_assign(coeff);
+            }
+            merged
+                .into_iter()
+                .filter(|(_, coeff)| !coeff.is_zero())
+                .collect()
+        };
+
+        self.constraints
+            .iter()
+            .map(|[a, b, c]| [lc(a), lc(b), lc(c)])
+            .collect()
+    }
+
+    fn is_satisfied_by(&self, values: &[Fr]) -> bool {
+        let eval = |lc: &[(usize, Fr)]| {
+            lc.iter().fold(Fr::zero(), |mut sum, (wire, coeff)| {
+                let mut term = values[*wire];
+                term.mul_assign(coeff);
+                sum.add_assign(&term);
+                sum
+            })
+        };
+
+        self.constraints().iter().all(|[a, b, c]| {
+            let mut product = eval(a);
+            product.mul_assign(&eval(b));
+            product == eval(c)
+        })
+    }
+}
+
+impl ConstraintSystem<Bn256> for Assembly {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux.push(f().ok());
+
+        Ok(Variable::new_unchecked(Index::Aux(self.aux.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs.push(f().ok());
+
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+        LB: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+        LC: FnOnce(LinearCombination<Bn256>) -> LinearCombination<Bn256>,
+    {
+        let terms = |lc: LinearCombination<Bn256>| {
+            lc.as_ref()
+                .iter()
+                .map(|(var, coeff)| (var.get_unchecked(), *coeff))
+                .collect()
+        };
+
+        self.constraints.push([
+            terms(a(LinearCombination::zero())),
+            terms(b(LinearCombination::zero())),
+            terms(c(LinearCombination::zero())),
+        ]);
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flat_absy::FlatVariable;
+    use crate::ir::{Function, LinComb, QuadComb, Statement};
+    use pairing::bn256::FrRepr;
+    use typed_absy::types::{Signature, Type};
+
+    /// `x * y == 42` with `x` public and `y` private, returning `y`
+    fn program() -> Prog<FieldPrime> {
+        Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![
+                    Statement::constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(1).into(),
+                        ),
+                        LinComb::summand(42, FlatVariable::one()),
+                    ),
+                    Statement::constraint(FlatVariable::new(1), FlatVariable::public(0)),
+                ],
+            },
+            private: vec![false, true],
+            signature: Signature::new()
+                .inputs(vec![Type::FieldElement, Type::FieldElement])
+                .outputs(vec![Type::FieldElement]),
+        }
+    }
+
+    fn witness(x: i32, y: i32) -> Witness<FieldPrime> {
+        vec![
+            (FlatVariable::new(0), FieldPrime::from(x)),
+            (FlatVariable::new(1), FieldPrime::from(y)),
+            (FlatVariable::public(0), FieldPrime::from(y)),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// The sections of a file in one of the iden3 formats, by type
+    fn sections(bytes: &[u8], magic: &[u8; 4], version: u32) -> BTreeMap<u32, Vec<u8>> {
+        assert_eq!(&bytes[0..4], magic);
+        assert_eq!(u32_at(bytes, 4), version);
+
+        let mut sections = BTreeMap::new();
+        let mut offset = 12;
+        for _ in 0..u32_at(bytes, 8) {
+            let mut size = [0u8; 8];
+            size.copy_from_slice(&bytes[offset + 4..offset + 12]);
+            let size = u64::from_le_bytes(size) as usize;
+            sections.insert(
+                u32_at(bytes, offset),
+                bytes[offset + 12..offset + 12 + size].to_vec(),
+            );
+            offset += 12 + size;
+        }
+        assert_eq!(offset, bytes.len());
+
+        sections
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        let mut value = [0u8; 4];
+        value.copy_from_slice(&bytes[offset..offset + 4]);
+        u32::from_le_bytes(value)
+    }
+
+    fn fr_at(bytes: &[u8], offset: usize) -> Fr {
+        let mut repr = FrRepr::default();
+        repr.read_le(&bytes[offset..offset + 32]).unwrap();
+        Fr::from_repr(repr).unwrap()
+    }
+
+    fn fr(value: &str) -> Fr {
+        Fr::from_str(value).unwrap()
+    }
+
+    fn r1cs() -> BTreeMap<u32, Vec<u8>> {
+        let mut r1cs = vec![];
+        write_r1cs(program(), &mut r1cs).unwrap();
+        sections(&r1cs, R1CS_MAGIC, R1CS_VERSION)
+    }
+
+    fn wtns(witness: Witness<FieldPrime>) -> Result<Vec<Fr>, ProofSystemError> {
+        let mut wtns = vec![];
+        write_wtns(program(), witness, &mut wtns)?;
+        let sections = sections(&wtns, WTNS_MAGIC, WTNS_VERSION);
+
+        assert_eq!(u32_at(&sections[&HEADER_SECTION], 0), FIELD_SIZE);
+        let count = u32_at(&sections[&HEADER_SECTION], 36) as usize;
+        assert_eq!(sections[&WITNESS_SECTION].len(), 32 * count);
+
+        Ok((0..count)
+            .map(|i| fr_at(&sections[&WITNESS_SECTION], 32 * i))
+            .collect())
+    }
+
+    #[test]
+    fn header() {
+        let header = &r1cs()[&HEADER_SECTION];
+
+        let mut prime = vec![];
+        Fr::char().write_le(&mut prime).unwrap();
+
+        assert_eq!(u32_at(header, 0), FIELD_SIZE);
+        assert_eq!(&header[4..36], &prime[..]);
+        // wires, public outputs, public inputs, private inputs
+        assert_eq!(
+            (0..4)
+                .map(|i| u32_at(header, 36 + 4 * i))
+                .collect::<Vec<_>>(),
+            vec![4, 1, 1, 1]
+        );
+        // labels
+        assert_eq!(u32_at(header, 52), 4);
+        assert_eq!(u32_at(header, 60), 2);
+    }
+
+    #[test]
+    fn witness_follows_synthesis() {
+        // one, the output, x, then y
+        assert_eq!(
+            wtns(witness(6, 7)).unwrap(),
+            vec![fr("1"), fr("7"), fr("6"), fr("7")]
+        );
+    }
+
+    #[test]
+    fn constraints_are_satisfied() {
+        let constraints = &r1cs()[&CONSTRAINTS_SECTION];
+        let values = wtns(witness(6, 7)).unwrap();
+
+        /// Evaluates the linear combination at `offset` and moves `offset` past it
+        fn eval(constraints: &[u8], offset: &mut usize, values: &[Fr]) -> Fr {
+            let mut sum = Fr::zero();
+            for _ in 0..u32_at(constraints, *offset) {
+                let mut term = values[u32_at(constraints, *offset + 4) as usize];
+                term.mul_assign(&fr_at(constraints, *offset + 8));
+                sum.add_assign(&term);
+                *offset += 36;
+            }
+            *offset += 4;
+            sum
+        }
+
+        let mut offset = 0;
+        for _ in 0..2 {
+            let mut product = eval(constraints, &mut offset, &values);
+            product.mul_assign(&eval(constraints, &mut offset, &values));
+            assert_eq!(product, eval(constraints, &mut offset, &values));
+        }
+        assert_eq!(offset, constraints.len());
+    }
+
+    #[test]
+    fn invalid_witness() {
+        assert_eq!(
+            wtns(witness(6, 8)).unwrap_err().to_string(),
+            "Synthesis failed: unsatisfiable constraint system"
+        );
+
+        let mut incomplete = witness(6, 7);
+        incomplete.remove(&FlatVariable::new(1));
+        assert_eq!(
+            wtns(incomplete).unwrap_err().to_string(),
+            "Synthesis failed: an assignment for a variable could not be computed"
+        );
+    }
+
+    #[test]
+    fn import_roundtrip() {
+        let mut r1cs = vec![];
+        write_r1cs(program(), &mut r1cs).unwrap();
+        let imported = read_r1cs(&r1cs[..]).unwrap();
+
+        assert_eq!(imported.signature, program().signature);
+        assert_eq!(imported.private, vec![false, true]);
+
+        // the imported program is exported to the same constraint system
+        let mut exported = vec![];
+        write_r1cs(imported.clone(), &mut exported).unwrap();
+        assert_eq!(exported, r1cs);
+
+        let mut wtns = vec![];
+        write_wtns(program(), witness(6, 7), &mut wtns).unwrap();
+        assert_eq!(read_wtns(&imported, &wtns[..]).unwrap(), witness(6, 7));
+    }
+
+    #[test]
+    fn import_json_and_prove() {
+        use crate::proof_system::bn128::G16;
+        use crate::proof_system::ProofSystem;
+        use serde_json::Value;
+
+        // `out_1 = x * y` and `out_0 = y * y` with `x` public and `y` private, the second output being used first
+        let r1cs = format!(
+            r#"{{
+                "n8": 32,
+                "prime": "{}",
+                "nVars": 5,
+                "nOutputs": 2,
+                "nPubInputs": 1,
+                "nPrvInputs": 1,
+                "nLabels": 5,
+                "nConstraints": 2,
+                "constraints": [
+                    [{{"3": "1"}}, {{"4": "1"}}, {{"2": "1"}}],
+                    [{{"4": "1"}}, {{"4": "1"}}, {{"1": "1"}}]
+                ],
+                "map": [0, 1, 2, 3, 4]
+            }}"#,
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        );
+        let program = read_r1cs(r1cs.as_bytes()).unwrap();
+        let witness = read_wtns(&program, &br#"["1", "49", "42", "6", "7"]"#[..]).unwrap();
+
+        let keypair = G16 {}.setup(program.clone()).unwrap();
+        let proof = G16 {}
+            .generate_proof(program, witness, &keypair.pk)
+            .unwrap();
+        assert!(G16 {}.verify(&keypair.vk, &proof).unwrap());
+
+        // the public inputs, followed by the outputs in order
+        let proof: Value = serde_json::from_str(proof.as_str()).unwrap();
+        assert_eq!(
+            proof["inputs"],
+            Value::from(
+                [6, 49, 42]
+                    .iter()
+                    .map(|i| format!("0x{:064x}", i))
+                    .collect::<Vec<_>>()
+            )
+        );
+    }
+
+    #[test]
+    fn import_invalid() {
+        let mut r1cs = vec![];
+        write_r1cs(program(), &mut r1cs).unwrap();
+
+        assert_eq!(
+            read_r1cs(&r1cs[..r1cs.len() - 1]).unwrap_err().to_string(),
+            "Invalid constraint system: section 3 is truncated"
+        );
+        assert_eq!(
+            read_r1cs(&br#"{"n8": 32, "prime": "7", "nVars": 1, "nOutputs": 0, "nPubInputs": 0, "nPrvInputs": 0, "constraints": []}"#[..])
+                .unwrap_err()
+                .to_string(),
+            "Invalid constraint system: the prime does not match the scalar field of the curve"
+        );
+
+        let program = read_r1cs(&r1cs[..]).unwrap();
+        assert_eq!(
+            read_wtns(&program, &br#"["1", "7", "6"]"#[..])
+                .unwrap_err()
+                .to_string(),
+            "Invalid witness: expected at least 4 values, found 3"
+        );
+        assert_eq!(
+            read_wtns(&program, &br#"["1", "8", "6", "8"]"#[..])
+                .unwrap_err()
+                .to_string(),
+            "Synthesis failed: unsatisfiable constraint system"
+        );
+    }
+}
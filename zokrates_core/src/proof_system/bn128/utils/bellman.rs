@@ -7,12 +7,13 @@ use bellman::groth16::{
     Parameters,
 };
 use bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
-use pairing::bn256::{Bn256, Fr};
+use ff::ScalarEngine;
 use std::collections::BTreeMap;
-use zokrates_field::field::{Field, FieldPrime};
+use zokrates_field::field::Field;
 
 use self::rand::*;
 use crate::flat_absy::FlatVariable;
+use crate::proof_system::ProofSystemError;
 
 pub use self::parse::*;
 
@@ -38,54 +39,51 @@ impl<T: Field> Computation<T> {
     }
 }
 
-fn bellman_combination<CS: ConstraintSystem<Bn256>>(
-    l: CanonicalLinComb<FieldPrime>,
+fn bellman_combination<T: Field, CS: ConstraintSystem<T::BellmanEngine>>(
+    l: CanonicalLinComb<T>,
     cs: &mut CS,
     symbols: &mut BTreeMap<FlatVariable, Variable>,
-    witness: &mut Witness<FieldPrime>,
-) -> LinearCombination<Bn256> {
-    l.0.into_iter()
-        .map(|(k, v)| {
-            (
-                v.into_bellman(),
-                symbols
-                    .entry(k)
-                    .or_insert_with(|| {
-                        match k.is_output() {
-                            true => cs.alloc_input(
-                                || format!("{}", k),
-                                || {
-                                    Ok(witness
-                                        .0
-                                        .remove(&k)
-                                        .ok_or(SynthesisError::AssignmentMissing)?
-                                        .into_bellman())
-                                },
-                            ),
-                            false => cs.alloc(
-                                || format!("{}", k),
-                                || {
-                                    Ok(witness
-                                        .0
-                                        .remove(&k)
-                                        .ok_or(SynthesisError::AssignmentMissing)?
-                                        .into_bellman())
-                                },
-                            ),
-                        }
-                        .unwrap()
-                    })
-                    .clone(),
-            )
-        })
-        .fold(LinearCombination::zero(), |acc, e| acc + e)
+    witness: &mut Witness<T>,
+) -> Result<LinearCombination<T::BellmanEngine>, SynthesisError> {
+    let mut res = LinearCombination::zero();
+
+    for (k, v) in l.0 {
+        let variable = match symbols.get(&k) {
+            Some(variable) => *variable,
+            None => {
+                let value = || {
+                    Ok(witness
+                        .remove(&k)
+                        .ok_or(SynthesisError::AssignmentMissing)?
+                        .into_bellman())
+                };
+
+                let variable = match k.is_output() {
+                    true => cs.alloc_input(|| format!("{}", k), value)?,
+                    false => cs.alloc(|| format!("{}", k), value)?,
+                };
+                symbols.insert(k, variable);
+                variable
+            }
+        };
+
+        res = res + (v.into_bellman(), variable);
+    }
+
+    Ok(res)
 }
 
-impl Prog<FieldPrime> {
-    pub fn synthesize<CS: ConstraintSystem<Bn256>>(
+impl From<SynthesisError> for ProofSystemError {
+    fn from(error: SynthesisError) -> Self {
+        ProofSystemError::Synthesis(error.to_string())
+    }
+}
+
+impl<T: Field> Prog<T> {
+    pub fn synthesize<CS: ConstraintSystem<T::BellmanEngine>>(
         self,
         cs: &mut CS,
-        witness: Option<Witness<FieldPrime>>,
+        witness: Option<Witness<T>>,
     ) -> Result<(), SynthesisError> {
         // mapping from IR variables
         let mut symbols = BTreeMap::new();
@@ -94,59 +92,55 @@ impl Prog<FieldPrime> {
 
         assert!(symbols.insert(FlatVariable::one(), CS::one()).is_none());
 
-        symbols.extend(
-            self.main
-                .arguments
-                .iter()
-                .zip(self.private)
-                .enumerate()
-                .map(|(index, (var, private))| {
-                    let wire = match private {
-                        true => cs.alloc(
-                            || format!("PRIVATE_INPUT_{}", index),
-                            || {
-                                Ok(witness
-                                    .0
-                                    .remove(&var)
-                                    .ok_or(SynthesisError::AssignmentMissing)?
-                                    .into_bellman())
-                            },
-                        ),
-                        false => cs.alloc_input(
-                            || format!("PUBLIC_INPUT_{}", index),
-                            || {
-                                Ok(witness
-                                    .0
-                                    .remove(&var)
-                                    .ok_or(SynthesisError::AssignmentMissing)?
-                                    .into_bellman())
-                            },
-                        ),
-                    }
-                    .unwrap();
-                    (var.clone(), wire)
-                }),
-        );
+        for (index, (var, private)) in self.main.arguments.iter().zip(self.private).enumerate() {
+            let value = || {
+                Ok(witness
+                    .remove(&var)
+                    .ok_or(SynthesisError::AssignmentMissing)?
+                    .into_bellman())
+            };
+
+            let wire = match private {
+                true => cs.alloc(|| format!("PRIVATE_INPUT_{}", index), value)?,
+                false => cs.alloc_input(|| format!("PUBLIC_INPUT_{}", index), value)?,
+            };
+
+            symbols.insert(var.clone(), wire);
+        }
+
+        // outputs are public inputs following the arguments, in order, regardless of where they are first used
+        for var in &self.main.returns {
+            let value = || {
+                Ok(witness
+                    .remove(&var)
+                    .ok_or(SynthesisError::AssignmentMissing)?
+                    .into_bellman())
+            };
+
+            let wire = cs.alloc_input(|| format!("{}", var), value)?;
+
+            symbols.insert(var.clone(), wire);
+        }
 
         let main = self.main;
 
         for statement in main.statements {
             match statement {
-                Statement::Constraint(quad, lin) => {
+                Statement::Constraint(quad, lin, ..) => {
                     let a = &bellman_combination(
                         quad.left.clone().as_canonical(),
                         cs,
                         &mut symbols,
                         &mut witness,
-                    );
+                    )?;
                     let b = &bellman_combination(
                         quad.right.clone().as_canonical(),
                         cs,
                         &mut symbols,
                         &mut witness,
-                    );
+                    )?;
                     let c =
-                        &bellman_combination(lin.as_canonical(), cs, &mut symbols, &mut witness);
+                        &bellman_combination(lin.as_canonical(), cs, &mut symbols, &mut witness)?;
 
                     cs.enforce(|| "Constraint", |lc| lc + a, |lc| lc + b, |lc| lc + c);
                 }
@@ -158,22 +152,28 @@ impl Prog<FieldPrime> {
     }
 }
 
-impl Computation<FieldPrime> {
-    pub fn prove(self, params: &Parameters<Bn256>) -> Proof<Bn256> {
+impl<T: Field> Computation<T> {
+    pub fn prove(
+        self,
+        params: &Parameters<T::BellmanEngine>,
+    ) -> Result<Proof<T::BellmanEngine>, SynthesisError> {
         let rng = &mut thread_rng();
-        let proof = create_random_proof(self.clone(), params, rng).unwrap();
+        let proof = create_random_proof(self.clone(), params, rng)?;
 
         let pvk = prepare_verifying_key(&params.vk);
 
         // extract public inputs
         let public_inputs = self.public_inputs_values();
 
-        assert!(verify_proof(&pvk, &proof, &public_inputs).unwrap());
-
-        proof
+        // a witness which does not satisfy the constraints yields an invalid proof
+        if verify_proof(&pvk, &proof, &public_inputs)? {
+            Ok(proof)
+        } else {
+            Err(SynthesisError::Unsatisfiable)
+        }
     }
 
-    pub fn public_inputs_values(&self) -> Vec<Fr> {
+    pub fn public_inputs_values(&self) -> Vec<<T::BellmanEngine as ScalarEngine>::Fr> {
         self.program
             .main
             .arguments
@@ -182,87 +182,78 @@ impl Computation<FieldPrime> {
             .zip(self.program.private.clone())
             .filter(|(_, p)| !p)
             .map(|(a, _)| a)
-            .map(|v| self.witness.clone().unwrap().0.get(v).unwrap().clone())
-            .chain(self.witness.clone().unwrap().return_values())
+            .map(|v| self.witness.as_ref().unwrap().get(v).unwrap().clone())
+            .chain(self.witness.as_ref().unwrap().return_values())
             .map(|v| v.clone().into_bellman())
             .collect()
     }
 
-    pub fn setup(self) -> Parameters<Bn256> {
+    pub fn setup(self) -> Result<Parameters<T::BellmanEngine>, SynthesisError> {
         let rng = &mut thread_rng();
         // run setup phase
-        generate_random_parameters(self, rng).unwrap()
+        generate_random_parameters(self, rng)
     }
 }
 
-impl Circuit<Bn256> for Computation<FieldPrime> {
-    fn synthesize<CS: ConstraintSystem<Bn256>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+impl<T: Field> Circuit<T::BellmanEngine> for Computation<T> {
+    fn synthesize<CS: ConstraintSystem<T::BellmanEngine>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
         self.program.synthesize(cs, self.witness)
     }
 }
 
 mod parse {
-    use lazy_static::lazy_static;
-
-    use super::*;
-    use regex::Regex;
+    use crate::proof_system::bn128::utils::parser::{G1Json, G2Json};
+    use ff::{PrimeField, PrimeFieldRepr};
+    use pairing::CurveAffine;
 
-    lazy_static! {
-        static ref G2_REGEX: Regex = Regex::new(r"G2\(x=Fq2\(Fq\((?P<x0>0[xX][0-9a-fA-F]{64})\) \+ Fq\((?P<x1>0[xX][0-9a-fA-F]{64})\) \* u\), y=Fq2\(Fq\((?P<y0>0[xX][0-9a-fA-F]{64})\) \+ Fq\((?P<y1>0[xX][0-9a-fA-F]{64})\) \* u\)\)").unwrap();
+    fn hex(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .fold(String::from("0x"), |hex, b| format!("{}{:02x}", hex, b))
     }
 
-    lazy_static! {
-        static ref G1_REGEX: Regex = Regex::new(
-            r"G1\(x=Fq\((?P<x>0[xX][0-9a-fA-F]{64})\), y=Fq\((?P<y>0[xX][0-9a-fA-F]{64})\)\)"
-        )
-        .unwrap();
-    }
+    /// The `count` coordinates of the uncompressed encoding of `e`, which are big endian
+    fn parse_point<G: CurveAffine>(e: &G, count: usize) -> Vec<String> {
+        let encoded = e.into_uncompressed();
+        let bytes = encoded.as_ref();
 
-    lazy_static! {
-        static ref FR_REGEX: Regex = Regex::new(r"Fr\((?P<x>0[xX][0-9a-fA-F]{64})\)").unwrap();
+        bytes.chunks(bytes.len() / count).map(hex).collect()
     }
 
-    fn parse_g1(e: &<Bn256 as bellman::pairing::Engine>::G1Affine) -> (String, String) {
-        let raw_e = e.to_string();
-
-        let captures = G1_REGEX.captures(&raw_e).unwrap();
+    fn parse_g1<G: CurveAffine>(e: &G) -> (String, String) {
+        let parsed = parse_point(e, 2);
 
-        (
-            captures.name(&"x").unwrap().as_str().to_string(),
-            captures.name(&"y").unwrap().as_str().to_string(),
-        )
+        (parsed[0].clone(), parsed[1].clone())
     }
 
-    fn parse_g2(
-        e: &<Bn256 as bellman::pairing::Engine>::G2Affine,
-    ) -> (String, String, String, String) {
-        let raw_e = e.to_string();
-
-        let captures = G2_REGEX.captures(&raw_e).unwrap();
+    fn parse_g2<G: CurveAffine>(e: &G) -> (String, String, String, String) {
+        let parsed = parse_point(e, 4);
 
         (
-            captures.name(&"x1").unwrap().as_str().to_string(),
-            captures.name(&"x0").unwrap().as_str().to_string(),
-            captures.name(&"y1").unwrap().as_str().to_string(),
-            captures.name(&"y0").unwrap().as_str().to_string(),
+            parsed[0].clone(),
+            parsed[1].clone(),
+            parsed[2].clone(),
+            parsed[3].clone(),
         )
     }
 
-    fn parse_fr(e: &Fr) -> String {
-        let raw_e = e.to_string();
-
-        let captures = FR_REGEX.captures(&raw_e).unwrap();
+    fn parse_fr<F: PrimeField>(e: &F) -> String {
+        let mut bytes = vec![];
+        e.into_repr().write_be(&mut bytes).unwrap();
 
-        captures.name(&"x").unwrap().as_str().to_string()
+        hex(&bytes)
     }
 
-    pub fn parse_g1_json(e: &<Bn256 as bellman::pairing::Engine>::G1Affine) -> String {
+    pub fn parse_g1_json<G: CurveAffine>(e: &G) -> String {
         let parsed = parse_g1(e);
 
         format!("[\"{}\", \"{}\"]", parsed.0, parsed.1)
     }
 
-    pub fn parse_g2_json(e: &<Bn256 as bellman::pairing::Engine>::G2Affine) -> String {
+    pub fn parse_g2_json<G: CurveAffine>(e: &G) -> String {
         let parsed = parse_g2(e);
 
         format!(
@@ -271,22 +262,22 @@ mod parse {
         )
     }
 
-    pub fn parse_fr_json(e: &Fr) -> String {
+    pub fn parse_fr_json<F: PrimeField>(e: &F) -> String {
         let parsed = parse_fr(e);
 
         format!("\"{}\"", parsed)
     }
 
-    pub fn parse_g1_hex(e: &<Bn256 as bellman::pairing::Engine>::G1Affine) -> String {
+    pub fn parse_g1_coordinates<G: CurveAffine>(e: &G) -> G1Json {
         let parsed = parse_g1(e);
 
-        format!("{}, {}", parsed.0, parsed.1)
+        [parsed.0, parsed.1]
     }
 
-    pub fn parse_g2_hex(e: &<Bn256 as bellman::pairing::Engine>::G2Affine) -> String {
+    pub fn parse_g2_coordinates<G: CurveAffine>(e: &G) -> G2Json {
         let parsed = parse_g2(e);
 
-        format!("[{}, {}], [{}, {}]", parsed.0, parsed.1, parsed.2, parsed.3,)
+        [[parsed.0, parsed.1], [parsed.2, parsed.3]]
     }
 }
 
@@ -316,8 +307,8 @@ mod tests {
             let witness = program.clone().execute(&vec![]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -330,6 +321,8 @@ mod tests {
                     statements: vec![Statement::Constraint(
                         FlatVariable::new(0).into(),
                         FlatVariable::public(0).into(),
+                        None,
+                        None,
                     )],
                 },
                 private: vec![true],
@@ -341,8 +334,8 @@ mod tests {
             let witness = program.clone().execute(&vec![FieldPrime::from(0)]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -355,6 +348,8 @@ mod tests {
                     statements: vec![Statement::Constraint(
                         FlatVariable::new(0).into(),
                         FlatVariable::public(0).into(),
+                        None,
+                        None,
                     )],
                 },
                 private: vec![false],
@@ -366,8 +361,8 @@ mod tests {
             let witness = program.clone().execute(&vec![FieldPrime::from(0)]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -380,6 +375,8 @@ mod tests {
                     statements: vec![Statement::Constraint(
                         FlatVariable::one().into(),
                         FlatVariable::public(0).into(),
+                        None,
+                        None,
                     )],
                 },
                 private: vec![],
@@ -389,8 +386,8 @@ mod tests {
             let witness = program.clone().execute(&vec![]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -408,12 +405,16 @@ mod tests {
                                 + LinComb::from(FlatVariable::new(51)))
                             .into(),
                             FlatVariable::public(0).into(),
+                            None,
+                            None,
                         ),
                         Statement::Constraint(
                             (LinComb::from(FlatVariable::one())
                                 + LinComb::from(FlatVariable::new(42)))
                             .into(),
                             FlatVariable::public(1).into(),
+                            None,
+                            None,
                         ),
                     ],
                 },
@@ -429,8 +430,8 @@ mod tests {
                 .unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -443,6 +444,8 @@ mod tests {
                     statements: vec![Statement::Constraint(
                         (LinComb::from(FlatVariable::new(42)) + LinComb::one()).into(),
                         FlatVariable::public(0).into(),
+                        None,
+                        None,
                     )],
                 },
                 private: vec![false],
@@ -454,8 +457,8 @@ mod tests {
             let witness = program.clone().execute(&vec![FieldPrime::from(3)]).unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
 
         #[test]
@@ -470,6 +473,8 @@ mod tests {
                             + LinComb::from(FlatVariable::new(51)))
                         .into(),
                         FlatVariable::public(0).into(),
+                        None,
+                        None,
                     )],
                 },
                 private: vec![true, false],
@@ -484,8 +489,8 @@ mod tests {
                 .unwrap();
             let computation = Computation::with_witness(program, witness);
 
-            let params = computation.clone().setup();
-            let _proof = computation.prove(&params);
+            let params = computation.clone().setup().unwrap();
+            let _proof = computation.prove(&params).unwrap();
         }
     }
 }
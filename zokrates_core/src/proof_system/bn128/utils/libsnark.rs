@@ -1,10 +1,52 @@
 use flat_absy::FlatVariable;
 use ir::{self, Statement};
+use proof_system::ProofSystemError;
 use std::cmp::max;
 use std::collections::HashMap;
-use std::ffi::CString;
+use std::slice;
 use zokrates_field::field::Field;
 
+/// A buffer allocated by the libsnark backend
+#[repr(C)]
+pub struct Buffer {
+    data: *mut u8,
+    length: i32,
+}
+
+/// The keys returned by a libsnark setup
+#[repr(C)]
+pub struct SetupResult {
+    pub vk: Buffer,
+    pub pk: Buffer,
+}
+
+extern "C" {
+    fn __free(ptr: *mut u8);
+}
+
+impl Buffer {
+    /// Copies the contents of the buffer and releases it
+    ///
+    /// # Safety
+    ///
+    /// The buffer must have been returned by the backend and not been released yet
+    pub unsafe fn into_vec(self) -> Vec<u8> {
+        let vec = match self.data.is_null() {
+            true => vec![],
+            false => slice::from_raw_parts(self.data, self.length as usize).to_vec(),
+        };
+        __free(self.data);
+        vec
+    }
+
+    /// # Safety
+    ///
+    /// See `into_vec`
+    pub unsafe fn into_string(self) -> Result<String, ProofSystemError> {
+        String::from_utf8(self.into_vec()).map_err(|e| ProofSystemError::Backend(e.to_string()))
+    }
+}
+
 // utility function. Converts a Fields vector-based byte representation to fixed size array.
 fn vec_as_u8_32_array(vec: &Vec<u8>) -> [u8; 32] {
     assert!(vec.len() <= 32);
@@ -18,8 +60,6 @@ fn vec_as_u8_32_array(vec: &Vec<u8>) -> [u8; 32] {
 // proof-system-independent preparation for the setup phase
 pub fn prepare_setup<T: Field>(
     program: ir::Prog<T>,
-    pk_path: &str,
-    vk_path: &str,
 ) -> (
     Vec<u8>,
     Vec<u8>,
@@ -30,8 +70,6 @@ pub fn prepare_setup<T: Field>(
     usize,
     usize,
     usize,
-    CString,
-    CString,
 ) {
     // transform to R1CS
     let (variables, public_variables_count, a, b, c) = r1cs_program(program);
@@ -129,10 +167,6 @@ pub fn prepare_setup<T: Field>(
         }
     }
 
-    // convert String slices to 'CString's
-    let pk_path_cstring = CString::new(pk_path).unwrap();
-    let vk_path_cstring = CString::new(vk_path).unwrap();
-
     (
         a_arr,
         b_arr,
@@ -143,8 +177,6 @@ pub fn prepare_setup<T: Field>(
         num_constraints,
         num_variables,
         num_inputs,
-        pk_path_cstring,
-        vk_path_cstring,
     )
 }
 
@@ -152,21 +184,23 @@ pub fn prepare_setup<T: Field>(
 pub fn prepare_generate_proof<T: Field>(
     program: ir::Prog<T>,
     witness: ir::Witness<T>,
-    pk_path: &str,
-    proof_path: &str,
-) -> (CString, CString, Vec<[u8; 32]>, usize, Vec<[u8; 32]>, usize) {
+) -> Result<(Vec<[u8; 32]>, usize, Vec<[u8; 32]>, usize), ProofSystemError> {
     // recover variable order from the program
     let (variables, public_variables_count, _, _, _) = r1cs_program(program);
 
-    let witness: Vec<_> = variables.iter().map(|x| witness.0[x].clone()).collect();
+    let witness: Vec<_> = variables
+        .iter()
+        .map(|x| {
+            witness.get(x).cloned().ok_or_else(|| {
+                ProofSystemError::Synthesis(format!("Missing value for variable {}", x))
+            })
+        })
+        .collect::<Result<_, _>>()?;
 
     // split witness into public and private inputs at offset
     let mut public_inputs: Vec<_> = witness.clone();
     let private_inputs: Vec<_> = public_inputs.split_off(public_variables_count);
 
-    let pk_path_cstring = CString::new(pk_path).unwrap();
-    let proof_path_cstring = CString::new(proof_path).unwrap();
-
     let public_inputs_length = public_inputs.len();
     let private_inputs_length = private_inputs.len();
 
@@ -182,14 +216,12 @@ pub fn prepare_generate_proof<T: Field>(
         private_inputs_arr[index] = vec_as_u8_32_array(&value.into_byte_vector());
     }
 
-    (
-        pk_path_cstring,
-        proof_path_cstring,
+    Ok((
         public_inputs_arr,
         public_inputs_length,
         private_inputs_arr,
         private_inputs_length,
-    )
+    ))
 }
 
 /// Returns the index of `var` in `variables`, adding `var` with incremented index if it not yet exists.
@@ -251,7 +283,7 @@ pub fn r1cs_program<T: Field>(
 
     // first pass through statements to populate `variables`
     for (quad, lin) in main.statements.iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, ..) => Some((quad, lin)),
         Statement::Directive(..) => None,
     }) {
         for (k, _) in &quad.left.0 {
@@ -271,7 +303,7 @@ pub fn r1cs_program<T: Field>(
 
     // second pass to convert program to raw sparse vectors
     for (quad, lin) in main.statements.into_iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, ..) => Some((quad, lin)),
         Statement::Directive(..) => None,
     }) {
         a.push(
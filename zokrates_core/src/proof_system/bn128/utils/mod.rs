@@ -1,4 +1,6 @@
 pub mod bellman;
 #[cfg(feature = "libsnark")]
 pub mod libsnark;
+pub mod parser;
+pub mod rust;
 pub mod solidity;
@@ -0,0 +1,426 @@
+//! Parsing of the verification keys and proofs written by the proving schemes, and the curve operations needed
+//! to verify proofs the same way the exported Solidity verifiers do
+
+use crate::proof_system::{ProofSystemError, VerificationKey};
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The version of the JSON verification keys written by `setup`
+pub const VERIFICATION_KEY_VERSION: u32 = 1;
+
+/// The curve all proving schemes of this module work on
+pub const CURVE: &str = "bn128";
+
+pub type G1Json = [String; 2];
+pub type G2Json = [[String; 2]; 2];
+
+/// A proof as written to `proof.json`, where `T` holds the points of the proof
+#[derive(Deserialize)]
+pub struct ProofJson<T> {
+    pub proof: T,
+    pub inputs: Vec<String>,
+}
+
+impl<T: DeserializeOwned> ProofJson<T> {
+    pub fn parse(proof: &str) -> Result<Self, ProofSystemError> {
+        serde_json::from_str(proof).map_err(|e| ProofSystemError::InvalidProof(e.to_string()))
+    }
+
+    pub fn inputs<F: PrimeField>(&self) -> Result<Vec<F>, ProofSystemError> {
+        self.inputs.iter().map(|i| fr_from_hex(i)).collect()
+    }
+}
+
+/// The points of the verification key of a proving scheme
+pub trait KeyPoints: Serialize + DeserializeOwned {
+    /// Reads the points from a key in the legacy text format
+    fn from_entries(entries: &VerificationKeyEntries) -> Result<Self, ProofSystemError>;
+
+    /// The points combined with the public inputs, the first one being added as is
+    fn query(&self) -> &[G1Json];
+}
+
+/// A verification key as written by `setup`, where `T` holds the points of the key
+///
+/// Coordinates are written in hex, in the same layout as in proofs.
+#[derive(Serialize, Deserialize)]
+pub struct VerificationKeyJson<T> {
+    pub version: u32,
+    pub scheme: String,
+    pub curve: String,
+    /// The number of public inputs, that is `query().len() - 1`
+    pub inputs: usize,
+    #[serde(flatten)]
+    pub points: T,
+}
+
+impl<T: KeyPoints> VerificationKeyJson<T> {
+    pub fn new(scheme: &str, points: T) -> Self {
+        Self::new_on(scheme, CURVE, points)
+    }
+
+    /// A key of `scheme` on the curve named `curve`
+    pub fn new_on(scheme: &str, curve: &str, points: T) -> Self {
+        VerificationKeyJson {
+            version: VERIFICATION_KEY_VERSION,
+            scheme: String::from(scheme),
+            curve: String::from(curve),
+            inputs: points.query().len().saturating_sub(1),
+            points,
+        }
+    }
+
+    /// Reads a key of `scheme`, either in JSON or in the legacy `vk.<name> = <value>` text format
+    pub fn parse(vk: &str, scheme: &str) -> Result<Self, ProofSystemError> {
+        Self::parse_on(vk, scheme, CURVE)
+    }
+
+    /// Reads a key of `scheme` on the curve named `curve`. Keys in the legacy text format are on bn128.
+    pub fn parse_on(vk: &str, scheme: &str, curve: &str) -> Result<Self, ProofSystemError> {
+        let invalid = |message: String| Err(ProofSystemError::InvalidVerificationKey(message));
+
+        if !vk.trim_start().starts_with('{') {
+            if curve != CURVE {
+                return invalid(format!(
+                    "Expected a key on {}, found a key in the legacy format, which is on {}",
+                    curve, CURVE
+                ));
+            }
+
+            let points = T::from_entries(&VerificationKeyEntries::parse(vk)?)?;
+
+            if points.query().is_empty() {
+                return invalid(String::from("Empty query"));
+            }
+
+            return Ok(Self::new(scheme, points));
+        }
+
+        let vk: Self = serde_json::from_str(vk)
+            .map_err(|e| ProofSystemError::InvalidVerificationKey(e.to_string()))?;
+
+        if vk.version != VERIFICATION_KEY_VERSION {
+            return invalid(format!("Unsupported version {}", vk.version));
+        }
+        if !vk.scheme.eq_ignore_ascii_case(scheme) {
+            return invalid(format!(
+                "Expected a key for {}, found a key for {}",
+                scheme, vk.scheme
+            ));
+        }
+        if vk.curve != curve {
+            return invalid(format!("Expected a key on {}, found {}", curve, vk.curve));
+        }
+        if vk.inputs + 1 != vk.points.query().len() {
+            return invalid(format!(
+                "Expected {} public inputs, found {} points to combine them with",
+                vk.inputs,
+                vk.points.query().len()
+            ));
+        }
+
+        Ok(vk)
+    }
+
+    pub fn to_verification_key(&self) -> VerificationKey {
+        VerificationKey::new(serde_json::to_string_pretty(self).unwrap())
+    }
+}
+
+/// The entries of a verification key in the legacy text format, one `vk.<name> = <value>` per line
+pub struct VerificationKeyEntries(HashMap<String, String>);
+
+impl VerificationKeyEntries {
+    pub fn parse(vk: &str) -> Result<Self, ProofSystemError> {
+        vk.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut split = line.splitn(2, '=');
+                match (split.next(), split.next()) {
+                    // libsnark keys are not consistent in the case of names, for example `vk.ic[0]` and `vk.IC[1]`
+                    (Some(name), Some(value)) => {
+                        Ok((name.trim().to_lowercase(), value.trim().to_string()))
+                    }
+                    _ => Err(ProofSystemError::InvalidVerificationKey(format!(
+                        "Invalid line: {}",
+                        line
+                    ))),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(VerificationKeyEntries)
+    }
+
+    fn get(&self, name: &str) -> Result<&str, ProofSystemError> {
+        self.0
+            .get(&format!("vk.{}", name))
+            .map(|value| value.as_str())
+            .ok_or_else(|| ProofSystemError::InvalidVerificationKey(format!("Missing vk.{}", name)))
+    }
+
+    /// The coordinates of `vk.<name>`, of which there must be `count`
+    fn coordinates(&self, name: &str, count: usize) -> Result<Vec<String>, ProofSystemError> {
+        let value = self.get(name)?;
+        let coordinates = coordinates(value);
+
+        if coordinates.len() != count {
+            return Err(ProofSystemError::InvalidVerificationKey(format!(
+                "Invalid point vk.{}: {}",
+                name, value
+            )));
+        }
+
+        coordinates
+            .into_iter()
+            .map(|c| normalize_coordinate(c).map_err(ProofSystemError::InvalidVerificationKey))
+            .collect()
+    }
+
+    pub fn g1(&self, name: &str) -> Result<G1Json, ProofSystemError> {
+        let c = self.coordinates(name, 2)?;
+        Ok([c[0].clone(), c[1].clone()])
+    }
+
+    pub fn g2(&self, name: &str) -> Result<G2Json, ProofSystemError> {
+        let c = self.coordinates(name, 4)?;
+        Ok([[c[0].clone(), c[1].clone()], [c[2].clone(), c[3].clone()]])
+    }
+
+    /// The G1 points `vk.<name>[i]`, whose number is given by `vk.<name>.len()`
+    pub fn g1_vec(&self, name: &str) -> Result<Vec<G1Json>, ProofSystemError> {
+        let len = self.get(&format!("{}.len()", name))?;
+        let len: usize = len.parse().map_err(|_| {
+            ProofSystemError::InvalidVerificationKey(format!(
+                "Invalid length of vk.{}: {}",
+                name, len
+            ))
+        })?;
+
+        (0..len)
+            .map(|i| self.g1(&format!("{}[{}]", name, i)))
+            .collect()
+    }
+}
+
+/// The coordinates in `0x.., 0x..` or `[0x.., 0x..], [0x.., 0x..]`
+fn coordinates(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(|c| c.trim().trim_matches(|c| c == '[' || c == ']').trim())
+        .collect()
+}
+
+/// The `size` big endian bytes of a hex encoded coordinate
+fn bytes(coordinate: &str, size: usize) -> Result<Vec<u8>, String> {
+    let hex = coordinate.trim_start_matches("0x").trim_start_matches("0X");
+
+    if hex.is_empty() || hex.len() > 2 * size {
+        return Err(format!("Invalid coordinate: {}", coordinate));
+    }
+
+    let hex = format!("{:0>width$}", hex, width = 2 * size);
+
+    (0..size)
+        .map(|i| {
+            u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .map_err(|_| format!("Invalid coordinate: {}", coordinate))
+        })
+        .collect()
+}
+
+/// A coordinate as `0x` followed by 64 lowercase hex digits
+pub fn normalize_coordinate(coordinate: &str) -> Result<String, String> {
+    Ok(bytes(coordinate, 32)?
+        .iter()
+        .fold(String::from("0x"), |hex, b| format!("{}{:02x}", hex, b)))
+}
+
+/// Decodes a point from the big endian bytes of its coordinates, all zeroes being the point at infinity
+fn decode<P: EncodedPoint>(coordinates: &[&str]) -> Result<P::Affine, String> {
+    let mut encoded = P::empty();
+
+    {
+        let size = encoded.as_ref().len() / coordinates.len().max(1);
+        let bytes = coordinates
+            .iter()
+            .map(|c| bytes(c, size))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        if bytes.len() != encoded.as_ref().len() {
+            return Err(format!("Invalid point: {}", coordinates.join(", ")));
+        }
+
+        encoded.as_mut().copy_from_slice(&bytes);
+
+        if bytes.iter().all(|b| *b == 0) {
+            encoded.as_mut()[0] |= 1 << 6;
+        }
+    }
+
+    encoded
+        .into_affine()
+        .map_err(|e| format!("Invalid point {}: {}", coordinates.join(", "), e))
+}
+
+/// A G1 point from its coordinates `x, y`
+fn g1_from_coordinates<G: CurveAffine>(coordinates: &[&str]) -> Result<G, String> {
+    decode::<G::Uncompressed>(coordinates)
+}
+
+/// A G2 point from its coordinates `x.c1, x.c0, y.c1, y.c0`, in the order they are written in
+fn g2_from_coordinates<G: CurveAffine>(coordinates: &[&str]) -> Result<G, String> {
+    decode::<G::Uncompressed>(coordinates)
+}
+
+pub fn g1_from_json<G: CurveAffine>(point: &G1Json) -> Result<G, ProofSystemError> {
+    g1_from_coordinates(&[&point[0], &point[1]]).map_err(ProofSystemError::InvalidProof)
+}
+
+pub fn g2_from_json<G: CurveAffine>(point: &G2Json) -> Result<G, ProofSystemError> {
+    g2_from_coordinates(&[&point[0][0], &point[0][1], &point[1][0], &point[1][1]])
+        .map_err(ProofSystemError::InvalidProof)
+}
+
+pub fn vk_g1_from_json<G: CurveAffine>(point: &G1Json) -> Result<G, ProofSystemError> {
+    g1_from_coordinates(&[&point[0], &point[1]]).map_err(ProofSystemError::InvalidVerificationKey)
+}
+
+pub fn vk_g2_from_json<G: CurveAffine>(point: &G2Json) -> Result<G, ProofSystemError> {
+    g2_from_coordinates(&[&point[0][0], &point[0][1], &point[1][0], &point[1][1]])
+        .map_err(ProofSystemError::InvalidVerificationKey)
+}
+
+pub fn fr_from_hex<F: PrimeField>(value: &str) -> Result<F, ProofSystemError> {
+    let mut repr = F::Repr::default();
+    let size = 8 * repr.as_ref().len();
+    repr.read_be(&bytes(value, size).map_err(ProofSystemError::InvalidProof)?[..])?;
+    F::from_repr(repr).map_err(|_| {
+        ProofSystemError::InvalidProof(format!("Input {} is not in the scalar field", value))
+    })
+}
+
+pub fn negate<G: CurveAffine>(mut point: G) -> G {
+    point.negate();
+    point
+}
+
+#[cfg(feature = "libsnark")]
+pub fn add<G: CurveAffine>(a: G, b: G) -> G {
+    let mut sum = a.into_projective();
+    sum.add_assign_mixed(&b);
+    sum.into_affine()
+}
+
+/// `query[0] + inputs[0] * query[1] + ... + inputs[n - 1] * query[n]`, the part of the verification which depends
+/// on the public inputs
+pub fn linear_combination<G: CurveAffine>(
+    query: &[G],
+    inputs: &[G::Scalar],
+) -> Result<G, ProofSystemError> {
+    if query.len() != inputs.len() + 1 {
+        return Err(ProofSystemError::InvalidProof(format!(
+            "Expected {} public inputs, found {}",
+            query.len().saturating_sub(1),
+            inputs.len()
+        )));
+    }
+
+    let mut res = query[0].into_projective();
+
+    for (point, input) in query[1..].iter().zip(inputs) {
+        res.add_assign(&point.mul(*input));
+    }
+
+    Ok(res.into_affine())
+}
+
+/// Whether the product of the pairings of `pairs` is one
+pub fn pairing_product_is_one<E: Engine>(pairs: &[(E::G1Affine, E::G2Affine)]) -> bool {
+    let prepared: Vec<_> = pairs
+        .iter()
+        .map(|(p, q)| (p.prepare(), q.prepare()))
+        .collect();
+    let prepared: Vec<_> = prepared.iter().map(|(p, q)| (p, q)).collect();
+
+    E::final_exponentiation(&E::miller_loop(prepared.iter()))
+        .map(|res| res == E::Fqk::one())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pairing::bn256::G1Affine;
+
+    #[test]
+    fn parse_points() {
+        let one = G1Affine::one();
+        assert_eq!(
+            g1_from_coordinates::<G1Affine>(&coordinates("0x01, 0x02")).unwrap(),
+            one
+        );
+        assert_eq!(
+            g1_from_coordinates::<G1Affine>(&coordinates(&format!("0x{:064}, 0x{:064}", 0, 0)))
+                .unwrap(),
+            G1Affine::zero()
+        );
+        // not on the curve
+        assert!(g1_from_coordinates::<G1Affine>(&coordinates("0x01, 0x03")).is_err());
+        assert!(g1_from_coordinates::<G1Affine>(&coordinates("0x01")).is_err());
+        assert!(g1_from_coordinates::<G1Affine>(&coordinates("0xzz, 0x02")).is_err());
+    }
+
+    #[test]
+    fn parse_verification_key() {
+        let vk = VerificationKeyEntries::parse(
+            "vk.a = 0x1, 0x2
+            vk.ic.len() = 2
+            vk.ic[0] = 0x1, 0x2
+            vk.IC[1] = 0x0, 0x0",
+        )
+        .unwrap();
+
+        let one = [format!("0x{:064x}", 1), format!("0x{:064x}", 2)];
+        let zero = [format!("0x{:064x}", 0), format!("0x{:064x}", 0)];
+
+        assert_eq!(vk.g1("a").unwrap(), one);
+        assert_eq!(vk_g1_from_json::<G1Affine>(&one).unwrap(), G1Affine::one());
+        assert_eq!(vk.g1_vec("ic").unwrap(), vec![one, zero]);
+        assert!(vk.g1("b").is_err());
+        assert!(vk.g2("a").is_err());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct QueryPoints {
+        query: Vec<G1Json>,
+    }
+
+    impl KeyPoints for QueryPoints {
+        fn from_entries(entries: &VerificationKeyEntries) -> Result<Self, ProofSystemError> {
+            Ok(QueryPoints {
+                query: entries.g1_vec("ic")?,
+            })
+        }
+
+        fn query(&self) -> &[G1Json] {
+            &self.query
+        }
+    }
+
+    #[test]
+    fn parse_legacy_verification_key_curve() {
+        let vk = "vk.ic.len() = 1
+            vk.ic[0] = 0x1, 0x2";
+
+        let parsed = VerificationKeyJson::<QueryPoints>::parse_on(vk, "g16", CURVE).unwrap();
+        assert_eq!(parsed.curve, CURVE);
+
+        // legacy keys are only written for bn128
+        assert!(VerificationKeyJson::<QueryPoints>::parse_on(vk, "g16", "bls12_381").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::proof_system::bn128::utils::parser::{normalize_coordinate, G1Json, G2Json};
+use crate::proof_system::ProofSystemError;
+
+/// The literal of type `[&str; 2]` for `point` in a Rust verifier
+pub fn g1_literal(point: &G1Json) -> Result<String, ProofSystemError> {
+    Ok(format!("[{}, {}]", string(&point[0])?, string(&point[1])?))
+}
+
+/// The literal of type `[[&str; 2]; 2]` for `point` in a Rust verifier
+pub fn g2_literal(point: &G2Json) -> Result<String, ProofSystemError> {
+    Ok(format!(
+        "[[{}, {}], [{}, {}]]",
+        string(&point[0][0])?,
+        string(&point[0][1])?,
+        string(&point[1][0])?,
+        string(&point[1][1])?
+    ))
+}
+
+/// The literal of type `[[&str; 2]; n]` for `points` in a Rust verifier, one point per line
+pub fn g1_array_literal(points: &[G1Json]) -> Result<String, ProofSystemError> {
+    points
+        .iter()
+        .map(|point| Ok(format!("    {},\n", g1_literal(point)?)))
+        .collect::<Result<String, _>>()
+        .map(|points| format!("[\n{}]", points))
+}
+
+fn string(coordinate: &str) -> Result<String, ProofSystemError> {
+    normalize_coordinate(coordinate)
+        .map(|coordinate| format!("\"{}\"", coordinate))
+        .map_err(ProofSystemError::InvalidVerificationKey)
+}
+
+/// The helpers shared by the Rust verifiers of all proving schemes, which decode the points and inputs written in
+/// hex and compute the pairings with `pairing_ce`
+pub const RUST_VERIFIER_LIB: &str = r#"
+/// Reads a public input as written in `proof.json`, if it is an element of the scalar field
+pub fn input(value: &str) -> Option<Fr> {
+    let mut repr = FrRepr::default();
+    repr.read_be(&bytes(value)?[..]).ok()?;
+    Fr::from_repr(repr).ok()
+}
+
+fn g1(point: [&str; 2]) -> Option<G1Affine> {
+    decode::<G1Uncompressed>(&point)
+}
+
+fn g2(point: [[&str; 2]; 2]) -> Option<G2Affine> {
+    decode::<G2Uncompressed>(&[point[0][0], point[0][1], point[1][0], point[1][1]])
+}
+
+/// The 32 big endian bytes of a hex encoded number
+fn bytes(value: &str) -> Option<Vec<u8>> {
+    let hex = value.trim_start_matches("0x");
+
+    if hex.is_empty() || hex.len() > 64 {
+        return None;
+    }
+
+    let hex = format!("{:0>64}", hex);
+
+    (0..32)
+        .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok())
+        .collect()
+}
+
+/// Decodes a point from the big endian bytes of its coordinates, all zeroes being the point at infinity
+fn decode<P: EncodedPoint>(coordinates: &[&str]) -> Option<P::Affine> {
+    let bytes = coordinates
+        .iter()
+        .map(|c| bytes(c))
+        .collect::<Option<Vec<_>>>()?
+        .concat();
+
+    let mut encoded = P::empty();
+
+    if bytes.len() != encoded.as_ref().len() {
+        return None;
+    }
+
+    encoded.as_mut().copy_from_slice(&bytes);
+
+    if bytes.iter().all(|b| *b == 0) {
+        encoded.as_mut()[0] |= 1 << 6;
+    }
+
+    encoded.into_affine().ok()
+}
+
+/// `query[0] + inputs[0] * query[1] + ... + inputs[n - 1] * query[n]`
+fn linear_combination(query: &[[&str; 2]], inputs: &[Fr]) -> G1Affine {
+    let mut res = g1(query[0]).unwrap().into_projective();
+
+    for (point, input) in query[1..].iter().zip(inputs) {
+        res.add_assign(&g1(*point).unwrap().mul(*input));
+    }
+
+    res.into_affine()
+}
+
+fn negate<G: CurveAffine>(mut point: G) -> G {
+    point.negate();
+    point
+}
+
+/// Whether the product of the pairings of `pairs` is one
+fn pairing_product_is_one(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    let prepared: Vec<_> = pairs
+        .iter()
+        .map(|(p, q)| (p.prepare(), q.prepare()))
+        .collect();
+    let prepared: Vec<_> = prepared.iter().map(|(p, q)| (p, q)).collect();
+
+    Bn256::final_exponentiation(&Bn256::miller_loop(prepared.iter()))
+        .map(|res| res == Fq12::one())
+        .unwrap_or(false)
+}
+"#;
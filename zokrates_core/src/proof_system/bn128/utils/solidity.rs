@@ -1,3 +1,79 @@
+use crate::proof_system::bn128::utils::parser::{normalize_coordinate, G1Json, G2Json};
+use crate::proof_system::ProofSystemError;
+
+/// The arguments of `Pairing.G1Point` for `point`
+pub fn g1_arguments(point: &G1Json) -> Result<String, ProofSystemError> {
+    Ok(format!("{}, {}", uint256(&point[0])?, uint256(&point[1])?))
+}
+
+/// The arguments of `Pairing.G2Point` for `point`
+pub fn g2_arguments(point: &G2Json) -> Result<String, ProofSystemError> {
+    Ok(format!(
+        "[{}, {}], [{}, {}]",
+        uint256(&point[0][0])?,
+        uint256(&point[0][1])?,
+        uint256(&point[1][0])?,
+        uint256(&point[1][1])?
+    ))
+}
+
+/// The statements setting `vk.<name>[i]` to the i-th point of `points` in the `verifyingKey` function of a verifier
+pub fn g1_assignments(name: &str, points: &[G1Json]) -> Result<String, ProofSystemError> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            Ok(format!(
+                "vk.{}[{}] = Pairing.G1Point({});",
+                name,
+                i,
+                g1_arguments(point)?
+            ))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|assignments| assignments.join("\n        "))
+}
+
+/// The declarations of `<name>_X` and `<name>_Y` as constants for `point`
+pub fn g1_constants(name: &str, point: &G1Json) -> Result<String, ProofSystemError> {
+    Ok(format!(
+        "uint256 constant {}_X = {};\n    uint256 constant {}_Y = {};",
+        name,
+        hex(&point[0])?,
+        name,
+        hex(&point[1])?
+    ))
+}
+
+/// The declarations of `<name>_X0`, `<name>_X1`, `<name>_Y0` and `<name>_Y1` as constants for `point`
+pub fn g2_constants(name: &str, point: &G2Json) -> Result<String, ProofSystemError> {
+    Ok([
+        ("X0", &point[0][0]),
+        ("X1", &point[0][1]),
+        ("Y0", &point[1][0]),
+        ("Y1", &point[1][1]),
+    ]
+    .iter()
+    .map(|(suffix, coordinate)| {
+        Ok(format!(
+            "uint256 constant {}_{} = {};",
+            name,
+            suffix,
+            hex(coordinate)?
+        ))
+    })
+    .collect::<Result<Vec<_>, ProofSystemError>>()?
+    .join("\n    "))
+}
+
+fn hex(coordinate: &str) -> Result<String, ProofSystemError> {
+    normalize_coordinate(coordinate).map_err(ProofSystemError::InvalidVerificationKey)
+}
+
+fn uint256(coordinate: &str) -> Result<String, ProofSystemError> {
+    hex(coordinate).map(|coordinate| format!("uint256({})", coordinate))
+}
+
 pub const SOLIDITY_G2_ADDITION_LIB: &str = r#"// This file is LGPL3 Licensed
 
 /**
@@ -1,8 +1,12 @@
+pub mod bls12_381;
 mod bn128;
 
-use std::fs::File;
-use zokrates_field::field::FieldPrime;
+use std::fmt;
+use std::io::{self, Read, Write};
+use zokrates_field::field::{Field, FieldPrime};
 
+pub use self::bn128::mpc;
+pub use self::bn128::r1cs;
 pub use self::bn128::G16;
 #[cfg(feature = "libsnark")]
 pub use self::bn128::GM17;
@@ -10,18 +14,170 @@ pub use self::bn128::GM17;
 pub use self::bn128::PGHR13;
 
 use crate::ir;
-use std::io::BufReader;
 
-pub trait ProofSystem {
-    fn setup(&self, program: ir::Prog<FieldPrime>, pk_path: &str, vk_path: &str);
+#[derive(Debug)]
+pub enum ProofSystemError {
+    Io(io::Error),
+    /// The program could not be turned into a constraint system, for example because the witness is incomplete
+    Synthesis(String),
+    InvalidProvingKey(String),
+    InvalidVerificationKey(String),
+    InvalidProof(String),
+    /// The parameters of a G16 multi-party setup or the powers of tau they are derived from are invalid
+    InvalidMpcParameters(String),
+    /// The constraint system to import is malformed or not over the scalar field of the curve
+    InvalidR1cs(String),
+    /// The witness to import is malformed or does not fit the program
+    InvalidWitness(String),
+    /// The proving scheme backend failed
+    Backend(String),
+    /// The proving scheme does not support the requested operation
+    Unsupported(String),
+}
+
+impl fmt::Display for ProofSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofSystemError::Io(e) => write!(f, "{}", e),
+            ProofSystemError::Synthesis(e) => write!(f, "Synthesis failed: {}", e),
+            ProofSystemError::InvalidProvingKey(e) => write!(f, "Invalid proving key: {}", e),
+            ProofSystemError::InvalidVerificationKey(e) => {
+                write!(f, "Invalid verification key: {}", e)
+            }
+            ProofSystemError::InvalidProof(e) => write!(f, "Invalid proof: {}", e),
+            ProofSystemError::InvalidMpcParameters(e) => write!(f, "Invalid MPC parameters: {}", e),
+            ProofSystemError::InvalidR1cs(e) => write!(f, "Invalid constraint system: {}", e),
+            ProofSystemError::InvalidWitness(e) => write!(f, "Invalid witness: {}", e),
+            ProofSystemError::Backend(e) => write!(f, "Backend error: {}", e),
+            ProofSystemError::Unsupported(e) => write!(f, "Unsupported: {}", e),
+        }
+    }
+}
+
+impl From<io::Error> for ProofSystemError {
+    fn from(error: io::Error) -> Self {
+        ProofSystemError::Io(error)
+    }
+}
+
+/// A proving key, in the binary format of the proving scheme which generated it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvingKey(Vec<u8>);
+
+impl ProvingKey {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        ProvingKey(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Ok(ProvingKey(bytes))
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        writer.write_all(&self.0)?;
+        Ok(())
+    }
+}
+
+/// A verification key, in JSON with the version of the format, the proving scheme, the curve, the number of public
+/// inputs and the points of the key
+///
+/// Keys in the `vk.<name> = <value>` text format written by earlier versions can still be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationKey(String);
+
+impl VerificationKey {
+    pub fn new(vk: String) -> Self {
+        VerificationKey(vk)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let mut vk = String::new();
+        reader.read_to_string(&mut vk)?;
+        Ok(VerificationKey(vk))
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        writer.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// A proof along with its public inputs, in JSON
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof(String);
+
+impl Proof {
+    pub fn new(proof: String) -> Self {
+        Proof(proof)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ProofSystemError> {
+        let mut proof = String::new();
+        reader.read_to_string(&mut proof)?;
+        Ok(Proof(proof))
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        writer.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// The keys generated by a setup
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetupKeypair {
+    pub vk: VerificationKey,
+    pub pk: ProvingKey,
+}
+
+/// A proving scheme over the scalar field `T` of a curve, bn128 unless stated otherwise
+pub trait ProofSystem<T: Field = FieldPrime> {
+    fn setup(&self, program: ir::Prog<T>) -> Result<SetupKeypair, ProofSystemError>;
 
     fn generate_proof(
         &self,
-        program: ir::Prog<FieldPrime>,
-        witness: ir::Witness<FieldPrime>,
-        pk_path: &str,
-        proof_path: &str,
-    ) -> bool;
+        program: ir::Prog<T>,
+        witness: ir::Witness<T>,
+        proving_key: &ProvingKey,
+    ) -> Result<Proof, ProofSystemError>;
+
+    fn export_solidity_verifier(
+        &self,
+        vk: &VerificationKey,
+        is_abiv2: bool,
+    ) -> Result<String, ProofSystemError>;
+
+    /// A Solidity verifier with the key as constants, which takes proofs packed as `uint256[8]` and checks them
+    /// with a single call to the pairing precompile
+    fn export_optimized_solidity_verifier(
+        &self,
+        _vk: &VerificationKey,
+    ) -> Result<String, ProofSystemError> {
+        Err(ProofSystemError::Unsupported(
+            "optimized Solidity verifiers are only available for G16".to_string(),
+        ))
+    }
+
+    /// A self-contained Rust module which verifies proofs against `vk` with `pairing_ce`
+    fn export_rust_verifier(&self, vk: &VerificationKey) -> Result<String, ProofSystemError>;
 
-    fn export_solidity_verifier(&self, reader: BufReader<File>, is_abiv2: bool) -> String;
+    /// Checks `proof` against `vk`
+    ///
+    /// Returns an error if the key or the proof cannot be read, or if the number of public inputs does not match
+    fn verify(&self, vk: &VerificationKey, proof: &Proof) -> Result<bool, ProofSystemError>;
 }
@@ -0,0 +1,316 @@
+//! Module containing the `Resolver` trait used to find imported modules, and resolvers combining other resolvers
+//!
+//! @file resolve.rs
+//! @date 2020
+
+use imports;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// A way of finding the modules imported by a program
+pub trait Resolver<E: Into<imports::Error>> {
+    /// Resolve the import of `import_location` in the module at `current_location`
+    ///
+    /// Returns the source code of the imported module and its location, which identifies the module uniquely
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), E>;
+}
+
+impl<'a, E: Into<imports::Error>, R: Resolver<E> + ?Sized> Resolver<E> for &'a R {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), E> {
+        (**self).resolve(current_location, import_location)
+    }
+}
+
+impl<E: Into<imports::Error>, R: Resolver<E> + ?Sized> Resolver<E> for Box<R> {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), E> {
+        (**self).resolve(current_location, import_location)
+    }
+}
+
+/// The path of a relative import, that is one starting with `./` or `../`, in the module at `current_location`
+///
+/// Returns `None` if the import is not relative. The path is normalized without accessing the file system.
+pub fn relative_path(current_location: &str, import_location: &str) -> Option<PathBuf> {
+    let import_location = Path::new(import_location);
+
+    match import_location.components().next() {
+        Some(Component::CurDir) | Some(Component::ParentDir) => {}
+        _ => return None,
+    };
+
+    let base = Path::new(current_location)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+
+    let mut path = PathBuf::new();
+    for component in base.join(import_location).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match path.components().last() {
+                Some(Component::Normal(_)) => {
+                    path.pop();
+                }
+                _ => path.push(component),
+            },
+            c => path.push(c),
+        }
+    }
+
+    Some(path)
+}
+
+/// A resolver trying each of its resolvers in turn until one of them finds the imported module
+///
+/// If none of them does, the error of the first one is returned.
+pub struct ChainedResolver<'a, E> {
+    resolvers: Vec<Box<dyn Resolver<E> + 'a>>,
+}
+
+impl<'a, E: Into<imports::Error>> ChainedResolver<'a, E> {
+    pub fn new<R: Resolver<E> + 'a>(resolver: R) -> Self {
+        ChainedResolver {
+            resolvers: vec![Box::new(resolver)],
+        }
+    }
+
+    pub fn then<R: Resolver<E> + 'a>(mut self, resolver: R) -> Self {
+        self.resolvers.push(Box::new(resolver));
+        self
+    }
+}
+
+impl<'a, E: Into<imports::Error>> Resolver<E> for ChainedResolver<'a, E> {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), E> {
+        let mut error = None;
+
+        for resolver in &self.resolvers {
+            match resolver.resolve(current_location.clone(), import_location) {
+                Ok(res) => return Ok(res),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+
+        // there is at least one resolver, so at least one error
+        Err(error.unwrap())
+    }
+}
+
+/// A resolver delegating imports to the resolver registered for their prefix, and to a default resolver otherwise
+///
+/// Relative imports are delegated to the resolver of the module they appear in.
+pub struct CompositeResolver<'a, E> {
+    default: Box<dyn Resolver<E> + 'a>,
+    resolvers: Vec<(String, Box<dyn Resolver<E> + 'a>)>,
+}
+
+impl<'a, E: Into<imports::Error>> CompositeResolver<'a, E> {
+    pub fn new<R: Resolver<E> + 'a>(default: R) -> Self {
+        CompositeResolver {
+            default: Box::new(default),
+            resolvers: vec![],
+        }
+    }
+
+    pub fn with_prefix<S: Into<String>, R: Resolver<E> + 'a>(
+        mut self,
+        prefix: S,
+        resolver: R,
+    ) -> Self {
+        self.resolvers.push((prefix.into(), Box::new(resolver)));
+        self
+    }
+
+    fn resolver(
+        &self,
+        current_location: &Option<String>,
+        import_location: &str,
+    ) -> &(dyn Resolver<E> + 'a) {
+        let location = match current_location {
+            Some(ref current_location)
+                if relative_path(current_location, import_location).is_some() =>
+            {
+                current_location
+            }
+            _ => import_location,
+        };
+
+        self.resolvers
+            .iter()
+            .find(|(prefix, _)| location.starts_with(prefix.as_str()))
+            .map(|(_, resolver)| resolver.as_ref())
+            .unwrap_or_else(|| self.default.as_ref())
+    }
+}
+
+impl<'a, E: Into<imports::Error>> Resolver<E> for CompositeResolver<'a, E> {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), E> {
+        self.resolver(&current_location, import_location)
+            .resolve(current_location, import_location)
+    }
+}
+
+/// A resolver finding modules in memory, for example when embedding the compiler in other tools
+///
+/// Modules are registered with their path. As with files, the `.zok` extension can be omitted when importing them.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryResolver {
+    modules: HashMap<String, String>,
+}
+
+impl MemoryResolver {
+    pub fn new() -> Self {
+        MemoryResolver::default()
+    }
+
+    pub fn with_module<L: Into<String>, S: Into<String>>(mut self, location: L, source: S) -> Self {
+        self.insert(location, source);
+        self
+    }
+
+    pub fn insert<L: Into<String>, S: Into<String>>(&mut self, location: L, source: S) {
+        self.modules.insert(location.into(), source.into());
+    }
+}
+
+impl Resolver<io::Error> for MemoryResolver {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), io::Error> {
+        let path = current_location
+            .and_then(|current_location| relative_path(&current_location, import_location))
+            .unwrap_or_else(|| PathBuf::from(import_location))
+            .with_extension("zok");
+
+        let location = path.to_string_lossy().to_string();
+
+        self.modules
+            .get(&location)
+            .map(|source| (source.clone(), location.clone()))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Module {} not found", location),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths() {
+        assert_eq!(relative_path("a/b.zok", "c"), None);
+        assert_eq!(relative_path("a/b.zok", "./c"), Some(PathBuf::from("a/c")));
+        assert_eq!(
+            relative_path("a/b/c.zok", "../d/./e"),
+            Some(PathBuf::from("a/d/e"))
+        );
+        assert_eq!(relative_path("a.zok", "../b"), Some(PathBuf::from("../b")));
+        assert_eq!(
+            relative_path("a.zok", "../../b"),
+            Some(PathBuf::from("../../b"))
+        );
+    }
+
+    #[test]
+    fn memory() {
+        let resolver = MemoryResolver::new()
+            .with_module("main.zok", "main")
+            .with_module("lib/foo.zok", "foo")
+            .with_module("lib/bar.zok", "bar");
+
+        assert_eq!(
+            resolver
+                .resolve(Some("main.zok".into()), "./lib/foo")
+                .unwrap(),
+            ("foo".to_string(), "lib/foo.zok".to_string())
+        );
+        assert_eq!(
+            resolver
+                .resolve(Some("lib/foo.zok".into()), "../lib/./bar.zok")
+                .unwrap(),
+            ("bar".to_string(), "lib/bar.zok".to_string())
+        );
+        assert_eq!(
+            resolver
+                .resolve(Some("lib/foo.zok".into()), "lib/bar")
+                .unwrap(),
+            ("bar".to_string(), "lib/bar.zok".to_string())
+        );
+        assert_eq!(
+            resolver
+                .resolve(Some("main.zok".into()), "./baz")
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn chained() {
+        let resolver = ChainedResolver::new(MemoryResolver::new().with_module("foo.zok", "foo"))
+            .then(
+                MemoryResolver::new()
+                    .with_module("foo.zok", "other foo")
+                    .with_module("bar.zok", "bar"),
+            );
+
+        // the first resolver finding the module is used
+        assert_eq!(resolver.resolve(None, "foo").unwrap().0, "foo");
+        assert_eq!(resolver.resolve(None, "bar").unwrap().0, "bar");
+        assert!(resolver.resolve(None, "baz").is_err());
+    }
+
+    #[test]
+    fn composite() {
+        let resolver = CompositeResolver::new(MemoryResolver::new().with_module("foo.zok", "foo"))
+            .with_prefix(
+                "remote/",
+                MemoryResolver::new()
+                    .with_module("remote/foo.zok", "remote foo")
+                    .with_module("remote/bar.zok", "remote bar"),
+            );
+
+        assert_eq!(resolver.resolve(None, "foo").unwrap().0, "foo");
+        assert_eq!(
+            resolver.resolve(None, "remote/foo").unwrap().0,
+            "remote foo"
+        );
+        // relative imports in remote modules are remote too
+        assert_eq!(
+            resolver
+                .resolve(Some("remote/foo.zok".into()), "./bar")
+                .unwrap()
+                .0,
+            "remote bar"
+        );
+        assert!(resolver.resolve(Some("foo.zok".into()), "./bar").is_err());
+    }
+}
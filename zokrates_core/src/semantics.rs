@@ -14,7 +14,8 @@ use zokrates_field::field::Field;
 
 use crate::parser::Position;
 
-use crate::absy::types::{UnresolvedSignature, UnresolvedType, UserTypeId};
+use crate::absy::types::{ArraySize, UnresolvedSignature, UnresolvedType, UserTypeId};
+use crate::static_analysis::Propagator;
 use crate::typed_absy::types::{FunctionKey, Signature, Type};
 
 use std::hash::{Hash, Hasher};
@@ -25,7 +26,10 @@ pub struct Error {
     message: String,
 }
 
-type TypeMap = HashMap<ModuleId, HashMap<UserTypeId, Type>>;
+pub type TypeMap = HashMap<ModuleId, HashMap<UserTypeId, Type>>;
+
+/// The name given to `_` in a destructuring pattern. It is never brought into scope
+const WILDCARD: &str = "_";
 
 /// The global state of the program during semantic checks
 #[derive(Debug)]
@@ -36,6 +40,46 @@ struct State<'ast, T: Field> {
     typed_modules: TypedModules<'ast, T>,
     /// The user-defined types, which we keep track at this phase only. In later phases, we rely only on basic types and combinations thereof
     types: TypeMap,
+    /// The generic functions available in each checked module, be they declared there or imported
+    generics: HashMap<ModuleId, HashMap<Identifier<'ast>, Generic<'ast>>>,
+    /// The declarations of generic functions, by module and identifier, to check their instances against
+    generic_functions: HashMap<(ModuleId, Identifier<'ast>), GenericFunction<'ast, T>>,
+    /// The constants available in each checked module, be they declared there or imported
+    constants: HashMap<ModuleId, Constants<'ast, T>>,
+}
+
+/// The values of constants, by identifier
+type Constants<'ast, T> = HashMap<Identifier<'ast>, TypedExpression<'ast, T>>;
+
+/// A generic function as seen from the modules it is available in
+#[derive(Clone, Debug)]
+struct Generic<'ast> {
+    /// The module the function is declared in
+    module: ModuleId,
+    /// The identifier of the function in that module
+    id: Identifier<'ast>,
+    signature: UnresolvedSignature,
+}
+
+/// The declaration of a generic function, along with the symbols its body can refer to
+#[derive(Debug)]
+struct GenericFunction<'ast, T: Field> {
+    function: FunctionNode<'ast, T>,
+    functions: HashSet<FunctionKey<'ast>>,
+    generics: HashMap<Identifier<'ast>, Generic<'ast>>,
+    constants: Constants<'ast, T>,
+}
+
+/// An instance of a generic function required by a call
+#[derive(Debug)]
+struct Instance<'ast> {
+    /// The module the call is in
+    module: ModuleId,
+    /// The key of the instance in that module
+    key: FunctionKey<'ast>,
+    generic: Generic<'ast>,
+    /// The values of the generic parameters
+    bindings: HashMap<String, usize>,
 }
 
 /// A symbol for a given name: either a type, or a group of functions. Not both!
@@ -43,6 +87,8 @@ struct State<'ast, T: Field> {
 enum SymbolType {
     Type,
     Functions(BTreeSet<Signature>),
+    GenericFunction,
+    Constant,
 }
 
 /// A data structure to keep track of all symbols in a module
@@ -71,8 +117,8 @@ impl SymbolUnifier {
             // if anything is already called `id`, it depends what it is
             Entry::Occupied(mut o) => {
                 match o.get_mut() {
-                    // if it's a Type, then we can't introduce a function
-                    SymbolType::Type => false,
+                    // if it's a Type, a generic function or a constant, then we can't introduce a function
+                    SymbolType::Type | SymbolType::GenericFunction | SymbolType::Constant => false,
                     // if it's a Function, we can introduce a new function only if it has a different signature
                     SymbolType::Functions(signatures) => signatures.insert(signature),
                 }
@@ -86,12 +132,50 @@ impl SymbolUnifier {
     }
 }
 
+impl SymbolUnifier {
+    fn insert_generic_function<S: Into<String>>(&mut self, id: S) -> bool {
+        let s_type = self.symbols.entry(id.into());
+        match s_type {
+            // generic functions cannot be overloaded, so nothing else can be called `id`
+            Entry::Occupied(..) => false,
+            Entry::Vacant(v) => {
+                v.insert(SymbolType::GenericFunction);
+                true
+            }
+        }
+    }
+
+    fn insert_constant<S: Into<String>>(&mut self, id: S) -> bool {
+        let s_type = self.symbols.entry(id.into());
+        match s_type {
+            // if anything is already called `id`, we cannot introduce this constant
+            Entry::Occupied(..) => false,
+            Entry::Vacant(v) => {
+                v.insert(SymbolType::Constant);
+                true
+            }
+        }
+    }
+}
+
 impl<'ast, T: Field> State<'ast, T> {
+    #[cfg(test)]
     fn new(modules: Modules<'ast, T>) -> Self {
+        Self::with_checked_modules(modules, HashMap::new(), HashMap::new())
+    }
+
+    fn with_checked_modules(
+        modules: Modules<'ast, T>,
+        typed_modules: TypedModules<'ast, T>,
+        types: TypeMap,
+    ) -> Self {
         State {
             modules,
-            typed_modules: HashMap::new(),
-            types: HashMap::new(),
+            typed_modules,
+            types,
+            generics: HashMap::new(),
+            generic_functions: HashMap::new(),
+            constants: HashMap::new(),
         }
     }
 }
@@ -194,35 +278,68 @@ impl<'ast> Hash for ScopedVariable<'ast> {
 impl<'ast> Eq for ScopedVariable<'ast> {}
 
 /// Checker, checks the semantics of a program, keeping track of functions and variables in scope
-pub struct Checker<'ast> {
+pub struct Checker<'ast, T: Field> {
     scope: HashSet<ScopedVariable<'ast>>,
     functions: HashSet<FunctionKey<'ast>>,
+    /// The generic functions in scope
+    generics: HashMap<Identifier<'ast>, Generic<'ast>>,
+    /// The values of the generic parameters of the instance being checked, if any
+    bindings: HashMap<String, usize>,
+    /// The instances of generic functions required by the calls checked so far
+    instances: Vec<Instance<'ast>>,
+    /// The constants in scope, along with their values
+    constants: Constants<'ast, T>,
     level: usize,
 }
 
-impl<'ast> Checker<'ast> {
-    fn new() -> Checker<'ast> {
+impl<'ast, T: Field> Checker<'ast, T> {
+    fn new() -> Checker<'ast, T> {
         Checker {
             scope: HashSet::new(),
             functions: HashSet::new(),
+            generics: HashMap::new(),
+            bindings: HashMap::new(),
+            instances: vec![],
+            constants: HashMap::new(),
             level: 0,
         }
     }
 
-    /// Check a `Program`
+    /// Check a `Program` some modules of which were already checked, for example in a previous compilation
     ///
     /// # Arguments
     ///
-    /// * `prog` - The `Program` to be checked
-    pub fn check<T: Field>(prog: Program<'ast, T>) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
-        Checker::new().check_program(prog)
+    /// * `prog` - The `Program` to be checked, which does not contain the modules already checked
+    /// * `typed_modules` - The modules already checked
+    /// * `types` - The user-defined types of the modules already checked
+    ///
+    /// Returns the checked program along with the user-defined types of all its modules, and the modules which declare
+    /// or import generic functions or constants. The instances of generic functions depend on the calls to them, and
+    /// constants are only known during this phase, so these modules cannot be reused in another compilation.
+    pub fn check_with_checked_modules(
+        prog: Program<'ast, T>,
+        typed_modules: TypedModules<'ast, T>,
+        types: TypeMap,
+    ) -> Result<(TypedProgram<'ast, T>, TypeMap, HashSet<ModuleId>), Vec<Error>> {
+        Checker::new().check_program_with_checked_modules(prog, typed_modules, types)
     }
 
-    fn check_program<T: Field>(
+    #[cfg(test)]
+    fn check_program(
         &mut self,
         program: Program<'ast, T>,
     ) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
-        let mut state = State::new(program.modules);
+        self.check_program_with_checked_modules(program, HashMap::new(), HashMap::new())
+            .map(|(program, ..)| program)
+    }
+
+    fn check_program_with_checked_modules(
+        &mut self,
+        program: Program<'ast, T>,
+        typed_modules: TypedModules<'ast, T>,
+        types: TypeMap,
+    ) -> Result<(TypedProgram<'ast, T>, TypeMap, HashSet<ModuleId>), Vec<Error>> {
+        let mut state = State::with_checked_modules(program.modules, typed_modules, types);
 
         let mut errors = vec![];
 
@@ -239,10 +356,29 @@ impl<'ast> Checker<'ast> {
         Checker::check_single_main(state.typed_modules.get(&program.main).unwrap())
             .map_err(|e| vec![e])?;
 
-        Ok(TypedProgram {
-            main: program.main,
-            modules: state.typed_modules,
-        })
+        let uncached_modules = state
+            .generics
+            .iter()
+            .filter(|(_, generics)| !generics.is_empty())
+            .map(|(module_id, _)| module_id)
+            .chain(
+                state
+                    .constants
+                    .iter()
+                    .filter(|(_, constants)| !constants.is_empty())
+                    .map(|(module_id, _)| module_id),
+            )
+            .cloned()
+            .collect();
+
+        Ok((
+            TypedProgram {
+                main: program.main,
+                modules: state.typed_modules,
+            },
+            state.types,
+            uncached_modules,
+        ))
     }
 
     fn check_struct_type_declaration(
@@ -284,7 +420,7 @@ impl<'ast> Checker<'ast> {
         Ok(Type::Struct(fields))
     }
 
-    fn check_symbol_declaration<T: Field>(
+    fn check_symbol_declaration(
         &mut self,
         declaration: SymbolDeclarationNode<'ast, T>,
         module_id: &ModuleId,
@@ -298,6 +434,19 @@ impl<'ast> Checker<'ast> {
         let declaration = declaration.value;
 
         match declaration.symbol {
+            Symbol::HereConstant(c) => match self.check_constant(c, module_id, &state.types) {
+                Ok(value) => {
+                    match symbol_unifier.insert_constant(declaration.id) {
+                        false => errors.push(Error {
+                            pos: Some(pos),
+                            message: format!("{} conflicts with another symbol", declaration.id,),
+                        }),
+                        true => {}
+                    };
+                    self.constants.insert(declaration.id, value);
+                }
+                Err(e) => errors.push(e),
+            },
             Symbol::HereType(t) => {
                 match self.check_struct_type_declaration(t.clone(), module_id, &state.types) {
                     Ok(ty) => {
@@ -320,6 +469,53 @@ impl<'ast> Checker<'ast> {
                     Err(e) => errors.extend(e),
                 }
             }
+            Symbol::HereFunction(mut f) if !f.value.generics.is_empty() => {
+                // constants are resolved once and for all, so that only generic parameters are left to infer
+                for ty in f
+                    .value
+                    .signature
+                    .inputs
+                    .iter_mut()
+                    .chain(f.value.signature.outputs.iter_mut())
+                {
+                    self.resolve_constant_sizes(ty, &f.value.generics);
+                }
+
+                match self.check_generic_declaration(declaration.id, &f) {
+                    Ok(()) => {
+                        match symbol_unifier.insert_generic_function(declaration.id) {
+                            false => errors.push(Error {
+                                pos: Some(pos),
+                                message: format!(
+                                    "{} conflicts with another symbol",
+                                    declaration.id,
+                                ),
+                            }),
+                            true => {}
+                        };
+
+                        // the body of the function is checked for each instance, against the symbols declared so far
+                        state.generic_functions.insert(
+                            (module_id.clone(), declaration.id),
+                            GenericFunction {
+                                functions: self.functions.clone(),
+                                generics: self.generics.clone(),
+                                constants: self.constants.clone(),
+                                function: f.clone(),
+                            },
+                        );
+                        self.generics.insert(
+                            declaration.id,
+                            Generic {
+                                module: module_id.clone(),
+                                id: declaration.id,
+                                signature: f.value.signature,
+                            },
+                        );
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
             Symbol::HereFunction(f) => match self.check_function(f, module_id, &state.types) {
                 Ok(funct) => {
                     match symbol_unifier.insert_function(declaration.id, funct.signature.clone()) {
@@ -349,6 +545,50 @@ impl<'ast> Checker<'ast> {
                 let import = import.value;
 
                 match Checker::new().check_module(&import.module_id, state) {
+                    // we imported a generic function, whose instances are not imported as such
+                    Ok(())
+                        if state
+                            .generics
+                            .get(&import.module_id)
+                            .map_or(false, |generics| generics.contains_key(import.symbol_id)) =>
+                    {
+                        let generic = state.generics[&import.module_id][import.symbol_id].clone();
+
+                        match symbol_unifier.insert_generic_function(declaration.id) {
+                            false => errors.push(Error {
+                                pos: Some(pos),
+                                message: format!(
+                                    "{} conflicts with another symbol",
+                                    declaration.id,
+                                ),
+                            }),
+                            true => {}
+                        };
+                        self.generics.insert(declaration.id, generic);
+                    }
+                    // we imported a constant, whose value is known
+                    Ok(())
+                        if state
+                            .constants
+                            .get(&import.module_id)
+                            .map_or(false, |constants| {
+                                constants.contains_key(import.symbol_id)
+                            }) =>
+                    {
+                        let value = state.constants[&import.module_id][import.symbol_id].clone();
+
+                        match symbol_unifier.insert_constant(declaration.id) {
+                            false => errors.push(Error {
+                                pos: Some(pos),
+                                message: format!(
+                                    "{} conflicts with another symbol",
+                                    declaration.id,
+                                ),
+                            }),
+                            true => {}
+                        };
+                        self.constants.insert(declaration.id, value);
+                    }
                     Ok(()) => {
                         // find candidates in the checked module
                         let function_candidates: Vec<_> = state
@@ -467,7 +707,7 @@ impl<'ast> Checker<'ast> {
         Ok(())
     }
 
-    fn check_module<T: Field>(
+    fn check_module(
         &mut self,
         module_id: &ModuleId,
         state: &mut State<'ast, T>,
@@ -505,6 +745,13 @@ impl<'ast> Checker<'ast> {
                     }
                 }
 
+                state
+                    .generics
+                    .insert(module_id.clone(), self.generics.clone());
+                state
+                    .constants
+                    .insert(module_id.clone(), self.constants.clone());
+
                 Some(TypedModule {
                     functions: checked_functions,
                 })
@@ -528,10 +775,186 @@ impl<'ast> Checker<'ast> {
             None => {}
         };
 
+        self.check_instances(state)
+    }
+
+    /// Check the instances of generic functions required by the calls checked so far, adding them to the modules the
+    /// generic functions are declared in
+    fn check_instances(&mut self, state: &mut State<'ast, T>) -> Result<(), Vec<Error>> {
+        while let Some(instance) = self.instances.pop() {
+            let generic = instance.generic;
+            let key = instance.key.clone().id(generic.id);
+
+            // calls from other modules go through an import of the instance
+            if instance.module != generic.module {
+                state
+                    .typed_modules
+                    .get_mut(&instance.module)
+                    .unwrap()
+                    .functions
+                    .insert(
+                        instance.key,
+                        TypedFunctionSymbol::There(key.clone(), generic.module.clone()),
+                    );
+            }
+
+            if state.typed_modules[&generic.module]
+                .functions
+                .contains_key(&key)
+            {
+                continue;
+            }
+
+            let declaration = &state.generic_functions[&(generic.module.clone(), generic.id)];
+
+            let mut checker = Checker {
+                functions: declaration.functions.clone(),
+                generics: declaration.generics.clone(),
+                constants: declaration.constants.clone(),
+                bindings: instance.bindings,
+                ..Checker::new()
+            };
+
+            let function = checker.check_function(
+                declaration.function.clone(),
+                &generic.module,
+                &state.types,
+            )?;
+
+            state
+                .typed_modules
+                .get_mut(&generic.module)
+                .unwrap()
+                .functions
+                .insert(key, TypedFunctionSymbol::Here(function));
+
+            self.instances.extend(checker.instances);
+        }
+
         Ok(())
     }
 
-    fn check_single_main<T: Field>(module: &TypedModule<T>) -> Result<(), Error> {
+    fn check_constant(
+        &mut self,
+        constant: ConstantNode<'ast, T>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<TypedExpression<'ast, T>, Error> {
+        let pos = constant.pos();
+        let constant = constant.value;
+
+        let ty = self.check_type(constant.ty, module_id, types)?;
+        let checked_expr = self.check_expression(constant.expression, module_id, types)?;
+        let expression_type = checked_expr.get_type();
+
+        if expression_type != ty {
+            return Err(Error {
+                pos: Some(pos),
+                message: format!(
+                    "Expression {} of type {} cannot be assigned to constant of type {}",
+                    checked_expr, expression_type, ty
+                ),
+            });
+        }
+
+        // constants are evaluated at compile time
+        Propagator::evaluate(checked_expr.clone()).ok_or_else(|| Error {
+            pos: Some(pos),
+            message: format!(
+                "Expression {} cannot be evaluated at compile time",
+                checked_expr
+            ),
+        })
+    }
+
+    /// The value of the constant `id` as an array size, if it is a field element small enough
+    fn constant_size(&self, id: &str) -> Option<usize> {
+        match self.constants.get(id) {
+            Some(TypedExpression::FieldElement(FieldElementExpression::Number(n))) => {
+                n.to_dec_string().parse().ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace the sizes in `ty` which refer to constants by their values, leaving generic parameters in `generics`
+    /// untouched
+    fn resolve_constant_sizes(&self, ty: &mut UnresolvedTypeNode, generics: &[Identifier<'ast>]) {
+        match &mut ty.value {
+            UnresolvedType::Array(inner, size) => {
+                if let ArraySize::Identifier(id) = size {
+                    if !generics.contains(&id.as_str()) {
+                        if let Some(n) = self.constant_size(id) {
+                            *size = ArraySize::Number(n);
+                        }
+                    }
+                }
+                self.resolve_constant_sizes(inner, generics);
+            }
+            UnresolvedType::Tuple(elements) => {
+                for element in elements {
+                    self.resolve_constant_sizes(element, generics);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_generic_declaration(
+        &self,
+        id: Identifier<'ast>,
+        function: &FunctionNode<'ast, T>,
+    ) -> Result<(), Error> {
+        let pos = function.pos();
+        let function = &function.value;
+
+        if id == "main" {
+            return Err(Error {
+                pos: Some(pos),
+                message: String::from("Function main cannot be generic"),
+            });
+        }
+
+        let mut declared = HashSet::new();
+        for generic in &function.generics {
+            if !declared.insert(String::from(*generic)) {
+                return Err(Error {
+                    pos: Some(pos),
+                    message: format!("Duplicate generic parameter {}", generic),
+                });
+            }
+        }
+
+        let mut inferred = HashSet::new();
+        for ty in &function.signature.inputs {
+            generic_sizes(&ty.value, &mut inferred);
+        }
+        let mut used = inferred.clone();
+        for ty in &function.signature.outputs {
+            generic_sizes(&ty.value, &mut used);
+        }
+
+        // generic parameters are inferred from the arguments, so each of them has to be the size of an argument
+        match (
+            used.difference(&declared).next(),
+            declared.difference(&inferred).next(),
+        ) {
+            (Some(generic), _) => Err(Error {
+                pos: Some(pos),
+                message: format!("Undefined generic parameter {}", generic),
+            }),
+            (None, Some(generic)) => Err(Error {
+                pos: Some(pos),
+                message: format!(
+                    "Generic parameter {} of {} is not the size of an argument",
+                    generic, id
+                ),
+            }),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn check_single_main(module: &TypedModule<T>) -> Result<(), Error> {
         match module
             .functions
             .iter()
@@ -560,7 +983,36 @@ impl<'ast> Checker<'ast> {
         }
     }
 
-    fn check_function<T: Field>(
+    /// Check the bound of a for loop, which has to be a constant, possibly computed from generic parameters
+    fn check_for_bound(
+        &mut self,
+        bound: ExpressionNode<'ast, T>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<T, Error> {
+        let pos = bound.pos();
+
+        match self.check_expression(bound, module_id, types)? {
+            TypedExpression::FieldElement(e) => {
+                match Propagator::evaluate(TypedExpression::FieldElement(e.clone())) {
+                    Some(TypedExpression::FieldElement(FieldElementExpression::Number(n))) => Ok(n),
+                    _ => Err(Error {
+                        pos: Some(pos),
+                        message: format!("For loop bounds should be constants, found {}", e),
+                    }),
+                }
+            }
+            e => Err(Error {
+                pos: Some(pos),
+                message: format!(
+                    "For loop bounds should be of type field, found {}",
+                    e.get_type()
+                ),
+            }),
+        }
+    }
+
+    fn check_function(
         &mut self,
         funct_node: FunctionNode<'ast, T>,
         module_id: &ModuleId,
@@ -595,7 +1047,7 @@ impl<'ast> Checker<'ast> {
                     match self.check_statement(stat, module_id, types) {
                         Ok(statement) => {
                             match &statement {
-                                TypedStatement::Return(e) => {
+                                TypedStatement::Return(e, _) => {
                                     match e.iter().map(|e| e.get_type()).collect::<Vec<_>>()
                                         == s.outputs
                                     {
@@ -705,6 +1157,17 @@ impl<'ast> Checker<'ast> {
         ty: UnresolvedTypeNode,
         module_id: &ModuleId,
         types: &TypeMap,
+    ) -> Result<Type, Error> {
+        self.check_type_with(ty, module_id, types, &self.bindings)
+    }
+
+    /// Check a type given the values of the generic parameters it can refer to
+    fn check_type_with(
+        &self,
+        ty: UnresolvedTypeNode,
+        module_id: &ModuleId,
+        types: &TypeMap,
+        bindings: &HashMap<String, usize>,
     ) -> Result<Type, Error> {
         let pos = ty.pos();
         let ty = ty.value;
@@ -712,9 +1175,33 @@ impl<'ast> Checker<'ast> {
         match ty {
             UnresolvedType::FieldElement => Ok(Type::FieldElement),
             UnresolvedType::Boolean => Ok(Type::Boolean),
+            UnresolvedType::Uint(bitwidth) => Ok(Type::Uint(bitwidth)),
             UnresolvedType::Array(t, size) => Ok(Type::Array(
-                box self.check_type(*t, module_id, types)?,
-                size,
+                box self.check_type_with(*t, module_id, types, bindings)?,
+                match size {
+                    ArraySize::Number(n) => n,
+                    ArraySize::Identifier(id) => match bindings.get(&id) {
+                        Some(size) => *size,
+                        None => match self.constants.get(id.as_str()) {
+                            Some(value) => self.constant_size(&id).ok_or_else(|| Error {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Constant {} with value {} is not a valid array size",
+                                    id, value
+                                ),
+                            })?,
+                            None => {
+                                return Err(Error {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "Undefined generic parameter or constant {}",
+                                        id
+                                    ),
+                                })
+                            }
+                        },
+                    },
+                },
             )),
             UnresolvedType::User(id) => {
                 types
@@ -727,6 +1214,12 @@ impl<'ast> Checker<'ast> {
                         message: format!("Undefined type {}", id),
                     })
             }
+            UnresolvedType::Tuple(elements) => Ok(Type::Tuple(
+                elements
+                    .into_iter()
+                    .map(|e| self.check_type_with(e, module_id, types, bindings))
+                    .collect::<Result<_, _>>()?,
+            )),
         }
     }
 
@@ -743,13 +1236,19 @@ impl<'ast> Checker<'ast> {
         ))
     }
 
-    fn check_statement<T: Field>(
+    fn check_statement(
         &mut self,
         stat: StatementNode<'ast, T>,
         module_id: &ModuleId,
         types: &TypeMap,
     ) -> Result<TypedStatement<'ast, T>, Vec<Error>> {
         let pos = stat.pos();
+        let span = SourceSpan {
+            module: module_id.clone(),
+            from: pos.0,
+            to: pos.1,
+            stack: vec![],
+        };
 
         match stat.value {
             Statement::Return(list) => {
@@ -761,7 +1260,7 @@ impl<'ast> Checker<'ast> {
                     expression_list_checked.push(e_checked);
                 }
 
-                Ok(TypedStatement::Return(expression_list_checked))
+                Ok(TypedStatement::Return(expression_list_checked, Some(span)))
             }
             Statement::Declaration(var) => {
                 let var = self.check_variable(var, module_id, types)?;
@@ -797,7 +1296,7 @@ impl<'ast> Checker<'ast> {
 
                 // make sure the assignee has the same type as the rhs
                 match var_type == expression_type {
-                    true => Ok(TypedStatement::Definition(var, checked_expr)),
+                    true => Ok(TypedStatement::Definition(var, checked_expr, Some(span))),
                     false => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -808,6 +1307,30 @@ impl<'ast> Checker<'ast> {
                 }
                 .map_err(|e| vec![e])
             }
+            Statement::Assertion(e, message) => {
+                let checked_e = self
+                    .check_expression(e, module_id, &types)
+                    .map_err(|e| vec![e])?;
+
+                match checked_e {
+                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(
+                        e,
+                        AssertionMetadata {
+                            span,
+                            message,
+                        },
+                    )),
+                    e => Err(Error {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected {} to be of type bool, found {}",
+                            e,
+                            e.get_type(),
+                        ),
+                    }),
+                }
+                .map_err(|e| vec![e])
+            }
             Statement::Condition(lhs, rhs) => {
                 let checked_lhs = self
                     .check_expression(lhs, module_id, &types)
@@ -817,7 +1340,11 @@ impl<'ast> Checker<'ast> {
                     .map_err(|e| vec![e])?;
 
                 if checked_lhs.get_type() == checked_rhs.get_type() {
-                    Ok(TypedStatement::Condition(checked_lhs, checked_rhs))
+                    Ok(TypedStatement::Condition(
+                        checked_lhs,
+                        checked_rhs,
+                        Some(span),
+                    ))
                 } else {
                     Err(Error {
                         pos: Some(pos),
@@ -833,6 +1360,13 @@ impl<'ast> Checker<'ast> {
                 .map_err(|e| vec![e])
             }
             Statement::For(var, from, to, statements) => {
+                let from = self
+                    .check_for_bound(from, module_id, types)
+                    .map_err(|e| vec![e])?;
+                let to = self
+                    .check_for_bound(to, module_id, types)
+                    .map_err(|e| vec![e])?;
+
                 self.enter_scope();
 
                 self.check_for_var(&var).map_err(|e| vec![e])?;
@@ -851,32 +1385,110 @@ impl<'ast> Checker<'ast> {
                 self.exit_scope();
                 Ok(TypedStatement::For(var, from, to, checked_statements))
             }
+            Statement::BoundedFor(var, from, to, max, statements) => {
+                let from = self
+                    .check_for_bound(from, module_id, types)
+                    .map_err(|e| vec![e])?;
+                let max = self
+                    .check_for_bound(max, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                // the upper bound is only known at execution time, so any field element is accepted
+                let to_pos = to.pos();
+                let to = match self
+                    .check_expression(to, module_id, &types)
+                    .map_err(|e| vec![e])?
+                {
+                    TypedExpression::FieldElement(e) => e,
+                    e => {
+                        return Err(vec![Error {
+                            pos: Some(to_pos),
+                            message: format!(
+                                "For loop bounds should be of type field, found {}",
+                                e.get_type()
+                            ),
+                        }])
+                    }
+                };
+
+                self.enter_scope();
+
+                self.check_for_var(&var).map_err(|e| vec![e])?;
+
+                let var = self.check_variable(var, module_id, types).unwrap();
+
+                self.insert_into_scope(var.clone());
+
+                let mut checked_statements = vec![];
+
+                for stat in statements {
+                    let checked_stat = self.check_statement(stat, module_id, types)?;
+                    checked_statements.push(checked_stat);
+                }
+
+                self.exit_scope();
+                Ok(TypedStatement::BoundedFor(
+                    var,
+                    from,
+                    to,
+                    max,
+                    checked_statements,
+                ))
+            }
+            Statement::IfElse(condition, consequence, alternative) => {
+                let condition = match self
+                    .check_expression(condition, module_id, &types)
+                    .map_err(|e| vec![e])?
+                {
+                    TypedExpression::Boolean(c) => c,
+                    c => {
+                        return Err(vec![Error {
+                            pos: Some(pos),
+                            message: format!(
+                                "{{condition}} after `if` should be a boolean, found {}",
+                                c.get_type()
+                            ),
+                        }])
+                    }
+                };
+
+                let consequence = self.check_branch(consequence, module_id, types)?;
+                let alternative = self.check_branch(alternative, module_id, types)?;
+
+                Ok(TypedStatement::IfElse(condition, consequence, alternative))
+            }
             Statement::MultipleDefinition(assignees, rhs) => {
+                // find lhs types
+                let mut vars_types: Vec<Option<Type>> = vec![];
+                let mut var_names = vec![];
+                for assignee in assignees {
+                    let (name, t) = match assignee.value {
+                        Assignee::Identifier(name) => Ok((
+                            name,
+                            self.get_scope(&name).map(|sv| sv.id.get_type()),
+                        )),
+                        Assignee::Wildcard => Ok((WILDCARD, None)),
+                        ref a => Err(Error {
+                            pos: Some(pos),
+                            message: format!(
+                                "Left hand side of function return assignment must be a list of identifiers, found {}",
+                                a
+                            ),
+                        }),
+                    }
+                    .map_err(|e| vec![e])?;
+                    vars_types.push(t);
+                    var_names.push(name);
+                }
+
                 match rhs.value {
-                    // Right side has to be a function call
                     Expression::FunctionCall(fun_id, arguments) => {
-                        // find lhs types
-                        let mut vars_types: Vec<Option<Type>> = vec![];
-                        let mut var_names = vec![];
-                        for assignee in assignees {
-                            let (name, t) = match assignee.value {
-                    			Assignee::Identifier(name) => {
-                    				Ok((name, match self.get_scope(&name) {
-					            		None => None,
-					            		Some(sv) => Some(sv.id.get_type())
-					            	}))
-                    			}
-                    			ref a => Err(Error {
-                                    pos: Some(pos),
- message: format!("Left hand side of function return assignment must be a list of identifiers, found {}", a)})
-                    		}.map_err(|e| vec![e])?;
-                            vars_types.push(t);
-                            var_names.push(name);
-                        }
                         // find arguments types
                         let mut arguments_checked = vec![];
                         for arg in arguments {
-                            let arg_checked = self.check_expression(arg, module_id, &types).map_err(|e| vec![e])?;
+                            let arg_checked = self
+                                .check_expression(arg, module_id, &types)
+                                .map_err(|e| vec![e])?;
                             arguments_checked.push(arg_checked);
                         }
 
@@ -884,44 +1496,85 @@ impl<'ast> Checker<'ast> {
                             arguments_checked.iter().map(|a| a.get_type()).collect();
 
                         let query = FunctionQuery::new(&fun_id, &arguments_types, &vars_types);
-                        let candidates = self.find_candidates(&query);
+                        let candidates = self.find_candidates(&query, module_id, &types);
 
                         match candidates.len() {
-                    		// the function has to be defined
-                    		1 => {
-                    			let f = &candidates[0];
+                            // the function has to be defined
+                            1 => {
+                                let f = &candidates[0];
 
                                 // we can infer the left hand side to be typed as the return values
-                    			let lhs: Vec<Variable> = var_names.iter().zip(f.signature.outputs.iter()).map(|(name, ty)|
-                    				Variable::with_id_and_type(crate::typed_absy::Identifier::from(*name), ty.clone())
-                    			).collect();
+                                let lhs =
+                                    self.define_variables(var_names, f.signature.outputs.clone());
 
-                                let assignees: Vec<_> = lhs.iter().map(|v| v.clone().into()).collect();
-
-                                let call = TypedExpressionList::FunctionCall(f.clone(), arguments_checked, f.signature.outputs.clone());
+                                let call = TypedExpressionList::FunctionCall(
+                                    f.clone(),
+                                    arguments_checked,
+                                    f.signature.outputs.clone(),
+                                );
 
-                                for var in lhs {
-                                    self.insert_into_scope(var);
+                                Ok(TypedStatement::MultipleDefinition(lhs, call, Some(span)))
+                            }
+                            0 => {
+                                // a function returning a single tuple can be destructured
+                                let tuple_query =
+                                    FunctionQuery::new(&fun_id, &arguments_types, &vec![None]);
+                                let tuple_candidates =
+                                    self.find_candidates(&tuple_query, module_id, &types);
+
+                                match tuple_candidates.as_slice() {
+                                    [f] => match &f.signature.outputs[0] {
+                                        Type::Tuple(elements) if var_names.len() > 1 => {
+                                            let tuple = TupleExpressionInner::FunctionCall(
+                                                f.clone(),
+                                                arguments_checked,
+                                            )
+                                            .annotate(elements.clone());
+                                            self.check_destructuring(
+                                                var_names, vars_types, tuple, pos, span,
+                                            )
+                                        }
+                                        _ => Err(Error {
+                                            pos: Some(pos),
+                                            message: format!("Function definition for function {} with signature {} not found.", fun_id, query),
+                                        }),
+                                    },
+                                    _ => Err(Error {
+                                        pos: Some(pos),
+                                        message: format!("Function definition for function {} with signature {} not found.", fun_id, query),
+                                    }),
                                 }
-
-                                Ok(TypedStatement::MultipleDefinition(assignees, call))
-                    		},
-                    		0 => Err(Error {                         pos: Some(pos),
- message: format!("Function definition for function {} with signature {} not found.", fun_id, query) }),
-                    		_ => Err(Error {                         pos: Some(pos),
- message: format!("Function call for function {} with arguments {:?} is ambiguous.", fun_id, arguments_types) }),
-                    	}
+                            }
+                            _ => Err(Error {
+                                pos: Some(pos),
+                                message: format!("Function call for function {} with arguments {:?} is ambiguous.", fun_id, arguments_types),
+                            }),
+                        }
                     }
-                    _ => Err(Error {
-                        pos: Some(pos),
-                        message: format!("{} should be a FunctionCall", rhs),
-                    }),
-                }.map_err(|e| vec![e])
+                    // otherwise the right side has to be a tuple which we destructure
+                    value => match self
+                        .check_expression(ExpressionNode { value, ..rhs }, module_id, &types)
+                    {
+                        Ok(TypedExpression::Tuple(tuple)) => {
+                            self.check_destructuring(var_names, vars_types, tuple, pos, span)
+                        }
+                        Ok(e) => Err(Error {
+                            pos: Some(pos),
+                            message: format!(
+                                "Expected a tuple to destructure, found {} of type {}",
+                                e,
+                                e.get_type()
+                            ),
+                        }),
+                        Err(e) => Err(e),
+                    },
+                }
+                .map_err(|e| vec![e])
             }
         }
     }
 
-    fn check_assignee<T: Field>(
+    fn check_assignee(
         &mut self,
         assignee: AssigneeNode<'ast, T>,
         module_id: &ModuleId,
@@ -940,6 +1593,10 @@ impl<'ast> Checker<'ast> {
                     message: format!("Undeclared variable: {:?}", variable_name),
                 }),
             },
+            Assignee::Wildcard => Err(Error {
+                pos: Some(pos),
+                message: String::from("`_` can only be used to discard values when destructuring"),
+            }),
             Assignee::Select(box assignee, box index) => {
                 let checked_assignee = self.check_assignee(assignee, module_id, &types)?;
 
@@ -1009,7 +1666,7 @@ impl<'ast> Checker<'ast> {
         }
     }
 
-    fn check_spread_or_expression<T: Field>(
+    fn check_spread_or_expression(
         &mut self,
         spread_or_expression: SpreadOrExpression<'ast, T>,
         module_id: &ModuleId,
@@ -1042,6 +1699,12 @@ impl<'ast> Checker<'ast> {
                                         box FieldElementExpression::Number(T::from(i)),
                                     )
                                     .into(),
+                                    Type::Uint(bitwidth) => UExpressionInner::Select(
+                                        box e.clone().annotate(Type::Uint(*bitwidth), size),
+                                        box FieldElementExpression::Number(T::from(i)),
+                                    )
+                                    .annotate(*bitwidth)
+                                    .into(),
                                     Type::Array(box ty, s) => ArrayExpressionInner::Select(
                                         box e
                                             .clone()
@@ -1056,6 +1719,12 @@ impl<'ast> Checker<'ast> {
                                     )
                                     .annotate(fields.clone())
                                     .into(),
+                                    Type::Tuple(elements) => TupleExpressionInner::Select(
+                                        box e.clone().annotate(Type::Tuple(elements.clone()), size),
+                                        box FieldElementExpression::Number(T::from(i)),
+                                    )
+                                    .annotate(elements.clone())
+                                    .into(),
                                 })
                                 .collect()),
                         }
@@ -1076,7 +1745,7 @@ impl<'ast> Checker<'ast> {
         }
     }
 
-    fn check_expression<T: Field>(
+    fn check_expression(
         &mut self,
         expr: ExpressionNode<'ast, T>,
         module_id: &ModuleId,
@@ -1094,17 +1763,28 @@ impl<'ast> Checker<'ast> {
                         Type::FieldElement => {
                             Ok(FieldElementExpression::Identifier(name.into()).into())
                         }
+                        Type::Uint(bitwidth) => Ok(UExpressionInner::Identifier(name.into())
+                            .annotate(bitwidth)
+                            .into()),
                         Type::Array(ty, size) => Ok(ArrayExpressionInner::Identifier(name.into())
                             .annotate(*ty, size)
                             .into()),
                         Type::Struct(members) => Ok(StructExpressionInner::Identifier(name.into())
                             .annotate(members)
                             .into()),
+                        Type::Tuple(elements) => Ok(TupleExpressionInner::Identifier(name.into())
+                            .annotate(elements)
+                            .into()),
+                    },
+                    // inside an instance of a generic function, generic parameters stand for their values
+                    None => match self.bindings.get(name) {
+                        Some(value) => Ok(FieldElementExpression::Number(T::from(*value)).into()),
+                        // constants stand for their values
+                        None => self.constants.get(name).cloned().ok_or_else(|| Error {
+                            pos: Some(pos),
+                            message: format!("Identifier \"{}\" is undefined", name),
+                        }),
                     },
-                    None => Err(Error {
-                        pos: Some(pos),
-                        message: format!("Identifier \"{}\" is undefined", name),
-                    }),
                 }
             }
             Expression::Add(box e1, box e2) => {
@@ -1115,11 +1795,19 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Add(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::Add(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
                     (t1, t2) => Err(Error {
                         pos: Some(pos),
 
                         message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
+                            "Expected only field elements or unsigned integers of the same type, found {:?}, {:?}",
                             t1.get_type(),
                             t2.get_type()
                         ),
@@ -1134,11 +1822,19 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Sub(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::Sub(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
                     (t1, t2) => Err(Error {
                         pos: Some(pos),
 
                         message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
+                            "Expected only field elements or unsigned integers of the same type, found {:?}, {:?}",
                             t1.get_type(),
                             t2.get_type()
                         ),
@@ -1153,11 +1849,19 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(FieldElementExpression::Mult(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::Mult(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
                     (t1, t2) => Err(Error {
                         pos: Some(pos),
 
                         message: format!(
-                            "Expected only field elements, found {:?}, {:?}",
+                            "Expected only field elements or unsigned integers of the same type, found {:?}, {:?}",
                             t1.get_type(),
                             t2.get_type()
                         ),
@@ -1219,6 +1923,9 @@ impl<'ast> Checker<'ast> {
                                 (TypedExpression::Boolean(consequence), TypedExpression::Boolean(alternative)) => {
                                     Ok(BooleanExpression::IfElse(box condition, box consequence, box alternative).into())
                                 },
+                                (TypedExpression::Uint(consequence), TypedExpression::Uint(alternative)) => {
+                                    Ok(UExpression::if_else(condition, consequence, alternative).into())
+                                },
                                 (TypedExpression::Array(consequence), TypedExpression::Array(alternative)) => {
                                     let inner_type = consequence.inner_type().clone();
                                     let size = consequence.size();
@@ -1232,6 +1939,9 @@ impl<'ast> Checker<'ast> {
                                         unimplemented!("handle consequence alternative inner type mismatch")
                                     }
                                 },
+                                (TypedExpression::Tuple(consequence), TypedExpression::Tuple(alternative)) => {
+                                    Ok(TupleExpression::if_else(condition, consequence, alternative).into())
+                                },
                                 _ => unreachable!("types should match here as we checked them explicitly")
                             }
                             false => Err(Error {
@@ -1250,6 +1960,9 @@ impl<'ast> Checker<'ast> {
                 }
             }
             Expression::FieldConstant(n) => Ok(FieldElementExpression::Number(n).into()),
+            Expression::U8Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(8).into()),
+            Expression::U16Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(16).into()),
+            Expression::U32Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(32).into()),
             Expression::FunctionCall(fun_id, arguments) => {
                 // check the arguments
                 let mut arguments_checked = vec![];
@@ -1267,7 +1980,7 @@ impl<'ast> Checker<'ast> {
                 // we use type inference to determine the type of the return, so we don't specify it
                 let query = FunctionQuery::new(&fun_id, &arguments_types, &vec![None]);
 
-                let candidates = self.find_candidates(&query);
+                let candidates = self.find_candidates(&query, module_id, &types);
 
                 match candidates.len() {
                     // the function has to be defined
@@ -1293,6 +2006,15 @@ impl<'ast> Checker<'ast> {
                                 )
                                 .annotate(members.clone())
                                 .into()),
+                                Type::Uint(bitwidth) => Ok(UExpressionInner::FunctionCall(
+                                    FunctionKey {
+                                        id: f.id.clone(),
+                                        signature: f.signature.clone(),
+                                    },
+                                    arguments_checked,
+                                )
+                                .annotate(*bitwidth)
+                                .into()),
                                 Type::Array(box ty, size) => {
                                     Ok(ArrayExpressionInner::FunctionCall(
                                         FunctionKey {
@@ -1304,6 +2026,15 @@ impl<'ast> Checker<'ast> {
                                     .annotate(ty.clone(), size.clone())
                                     .into())
                                 }
+                                Type::Tuple(elements) => Ok(TupleExpressionInner::FunctionCall(
+                                    FunctionKey {
+                                        id: f.id.clone(),
+                                        signature: f.signature.clone(),
+                                    },
+                                    arguments_checked,
+                                )
+                                .annotate(elements.clone())
+                                .into()),
                                 _ => unimplemented!(),
                             },
                             n => Err(Error {
@@ -1336,6 +2067,15 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Lt(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::Lt(
+                            box FieldElementExpression::FromUint(box e1),
+                            box FieldElementExpression::FromUint(box e2),
+                        )
+                        .into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -1355,6 +2095,15 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Le(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::Le(
+                            box FieldElementExpression::FromUint(box e1),
+                            box FieldElementExpression::FromUint(box e2),
+                        )
+                        .into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -1374,6 +2123,15 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Eq(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::Eq(
+                            box FieldElementExpression::FromUint(box e1),
+                            box FieldElementExpression::FromUint(box e2),
+                        )
+                        .into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -1393,6 +2151,15 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Ge(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::Ge(
+                            box FieldElementExpression::FromUint(box e1),
+                            box FieldElementExpression::FromUint(box e2),
+                        )
+                        .into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -1412,6 +2179,15 @@ impl<'ast> Checker<'ast> {
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
                         Ok(BooleanExpression::Gt(box e1, box e2).into())
                     }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(BooleanExpression::Gt(
+                            box FieldElementExpression::FromUint(box e1),
+                            box FieldElementExpression::FromUint(box e2),
+                        )
+                        .into())
+                    }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
                         message: format!(
@@ -1501,6 +2277,11 @@ impl<'ast> Checker<'ast> {
                                     Type::Boolean => {
                                         Ok(BooleanExpression::Select(box a, box i).into())
                                     }
+                                    Type::Uint(bitwidth) => {
+                                        Ok(UExpressionInner::Select(box a, box i)
+                                            .annotate(bitwidth)
+                                            .into())
+                                    }
                                     Type::Array(box ty, size) => {
                                         Ok(ArrayExpressionInner::Select(box a, box i)
                                             .annotate(ty.clone(), size.clone())
@@ -1511,6 +2292,11 @@ impl<'ast> Checker<'ast> {
                                             .annotate(members.clone())
                                             .into())
                                     }
+                                    Type::Tuple(elements) => {
+                                        Ok(TupleExpressionInner::Select(box a, box i)
+                                            .annotate(elements.clone())
+                                            .into())
+                                    }
                                 }
                             }
                             (a, e) => Err(Error {
@@ -1545,6 +2331,11 @@ impl<'ast> Checker<'ast> {
                                 Type::Boolean => {
                                     Ok(BooleanExpression::Member(box s, id.to_string()).into())
                                 }
+                                Type::Uint(bitwidth) => {
+                                    Ok(UExpressionInner::Member(box s.clone(), id.to_string())
+                                        .annotate(*bitwidth)
+                                        .into())
+                                }
                                 Type::Array(box ty, size) => {
                                     Ok(ArrayExpressionInner::Member(box s.clone(), id.to_string())
                                         .annotate(ty.clone(), *size)
@@ -1555,6 +2346,11 @@ impl<'ast> Checker<'ast> {
                                         .annotate(members.clone())
                                         .into())
                                 }
+                                Type::Tuple(elements) => {
+                                    Ok(TupleExpressionInner::Member(box s.clone(), id.to_string())
+                                        .annotate(elements.clone())
+                                        .into())
+                                }
                             },
                             None => Err(Error {
                                 pos: Some(pos),
@@ -1572,6 +2368,52 @@ impl<'ast> Checker<'ast> {
                     }),
                 }
             }
+            Expression::Element(box e, index) => {
+                let e = self.check_expression(e, module_id, &types)?;
+
+                match e {
+                    TypedExpression::Tuple(t) => {
+                        // check that the tuple has that element and return its type if it does
+                        match t.ty().get(index).cloned() {
+                            Some(ty) => Ok(match ty {
+                                Type::FieldElement => {
+                                    FieldElementExpression::element(t, index).into()
+                                }
+                                Type::Boolean => BooleanExpression::element(t, index).into(),
+                                Type::Uint(..) => UExpression::element(t, index).into(),
+                                Type::Array(..) => ArrayExpression::element(t, index).into(),
+                                Type::Struct(..) => StructExpression::element(t, index).into(),
+                                Type::Tuple(..) => TupleExpression::element(t, index).into(),
+                            }),
+                            None => Err(Error {
+                                pos: Some(pos),
+                                message: format!("{} doesn't have element {}", t.get_type(), index),
+                            }),
+                        }
+                    }
+                    e => Err(Error {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot access element {} on expression of type {}",
+                            index,
+                            e.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::InlineTuple(expressions) => {
+                let mut expressions_checked = vec![];
+                for e in expressions {
+                    let e_checked = self.check_expression(e, module_id, &types)?;
+                    expressions_checked.push(e_checked);
+                }
+
+                let ty = expressions_checked.iter().map(|e| e.get_type()).collect();
+
+                Ok(TupleExpressionInner::Value(expressions_checked)
+                    .annotate(ty)
+                    .into())
+            }
             Expression::InlineArray(expressions) => {
                 // check each expression, getting its type
                 let mut expressions_checked = vec![];
@@ -1638,6 +2480,48 @@ impl<'ast> Checker<'ast> {
                             .annotate(Type::Boolean, size)
                             .into())
                     }
+                    ty @ Type::Uint(..) => {
+                        // we check all expressions have that same type
+                        let mut unwrapped_expressions = vec![];
+
+                        for e in expressions_checked {
+                            let unwrapped_e = match e {
+                                TypedExpression::Uint(e) => {
+                                    if e.get_type() == ty {
+                                        Ok(e)
+                                    } else {
+                                        Err(Error {
+                                            pos: Some(pos),
+
+                                            message: format!(
+                                                "Expected {} to have type {}, but type is {}",
+                                                e,
+                                                ty,
+                                                e.get_type()
+                                            ),
+                                        })
+                                    }
+                                }
+                                e => Err(Error {
+                                    pos: Some(pos),
+
+                                    message: format!(
+                                        "Expected {} to have type {}, but type is {}",
+                                        e,
+                                        ty,
+                                        e.get_type()
+                                    ),
+                                }),
+                            }?;
+                            unwrapped_expressions.push(unwrapped_e.into());
+                        }
+
+                        let size = unwrapped_expressions.len();
+
+                        Ok(ArrayExpressionInner::Value(unwrapped_expressions)
+                            .annotate(ty, size)
+                            .into())
+                    }
                     ty @ Type::Array(..) => {
                         // we check all expressions have that same type
                         let mut unwrapped_expressions = vec![];
@@ -1718,6 +2602,48 @@ impl<'ast> Checker<'ast> {
 
                         let size = unwrapped_expressions.len();
 
+                        Ok(ArrayExpressionInner::Value(unwrapped_expressions)
+                            .annotate(ty, size)
+                            .into())
+                    }
+                    ty @ Type::Tuple(..) => {
+                        // we check all expressions have that same type
+                        let mut unwrapped_expressions = vec![];
+
+                        for e in expressions_checked {
+                            let unwrapped_e = match e {
+                                TypedExpression::Tuple(e) => {
+                                    if e.get_type() == ty {
+                                        Ok(e)
+                                    } else {
+                                        Err(Error {
+                                            pos: Some(pos),
+
+                                            message: format!(
+                                                "Expected {} to have type {}, but type is {}",
+                                                e,
+                                                ty,
+                                                e.get_type()
+                                            ),
+                                        })
+                                    }
+                                }
+                                e => Err(Error {
+                                    pos: Some(pos),
+
+                                    message: format!(
+                                        "Expected {} to have type {}, but type is {}",
+                                        e,
+                                        ty,
+                                        e.get_type()
+                                    ),
+                                }),
+                            }?;
+                            unwrapped_expressions.push(unwrapped_e.into());
+                        }
+
+                        let size = unwrapped_expressions.len();
+
                         Ok(ArrayExpressionInner::Value(unwrapped_expressions)
                             .annotate(ty, size)
                             .into())
@@ -1796,56 +2722,290 @@ impl<'ast> Checker<'ast> {
                     }
                 }
 
-                Ok(StructExpressionInner::Value(result)
-                    .annotate(members)
-                    .into())
+                Ok(StructExpressionInner::Value(result)
+                    .annotate(members)
+                    .into())
+            }
+            Expression::And(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+                        Ok(BooleanExpression::And(box e1, box e2).into())
+                    }
+                    (e1, e2) => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "cannot apply boolean operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Or(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+                        Ok(BooleanExpression::Or(box e1, box e2).into())
+                    }
+                    (e1, e2) => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!("cannot compare {} to {}", e1.get_type(), e2.get_type()),
+                    }),
+                }
+            }
+            Expression::Not(box e) => {
+                let e_checked = self.check_expression(e, module_id, &types)?;
+                match e_checked {
+                    TypedExpression::Boolean(e) => Ok(BooleanExpression::Not(box e).into()),
+                    TypedExpression::Uint(e) => {
+                        let bitwidth = e.bitwidth();
+                        Ok(UExpressionInner::Not(box e).annotate(bitwidth).into())
+                    }
+                    e => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!("cannot negate {}", e.get_type()),
+                    }),
+                }
+            }
+            Expression::BitAnd(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::And(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
+                    (e1, e2) => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "cannot apply bitwise operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
             }
-            Expression::And(box e1, box e2) => {
+            Expression::BitOr(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, &types)?;
                 let e2_checked = self.check_expression(e2, module_id, &types)?;
                 match (e1_checked, e2_checked) {
-                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
-                        Ok(BooleanExpression::And(box e1, box e2).into())
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::Or(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
                     }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
 
                         message: format!(
-                            "cannot apply boolean operators to {} and {}",
+                            "cannot apply bitwise operators to {} and {}",
                             e1.get_type(),
                             e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Or(box e1, box e2) => {
+            Expression::BitXor(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, &types)?;
                 let e2_checked = self.check_expression(e2, module_id, &types)?;
                 match (e1_checked, e2_checked) {
-                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
-                        Ok(BooleanExpression::Or(box e1, box e2).into())
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::Xor(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
                     }
                     (e1, e2) => Err(Error {
                         pos: Some(pos),
 
-                        message: format!("cannot compare {} to {}", e1.get_type(), e2.get_type()),
+                        message: format!(
+                            "cannot apply bitwise operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
                     }),
                 }
             }
-            Expression::Not(box e) => {
+            Expression::LeftShift(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Uint(e1), TypedExpression::FieldElement(e2)) => {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::LeftShift(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
+                    (e1, e2) => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "cannot shift {} by {}, expected an unsigned integer and a field element",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::RightShift(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, &types)?;
+                let e2_checked = self.check_expression(e2, module_id, &types)?;
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Uint(e1), TypedExpression::FieldElement(e2)) => {
+                        let bitwidth = e1.bitwidth();
+                        Ok(UExpressionInner::RightShift(box e1, box e2)
+                            .annotate(bitwidth)
+                            .into())
+                    }
+                    (e1, e2) => Err(Error {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "cannot shift {} by {}, expected an unsigned integer and a field element",
+                            e1.get_type(),
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Cast(ty, box e) => {
+                let ty = self.check_type(ty, module_id, &types)?;
                 let e_checked = self.check_expression(e, module_id, &types)?;
-                match e_checked {
-                    TypedExpression::Boolean(e) => Ok(BooleanExpression::Not(box e).into()),
-                    e => Err(Error {
+                match (ty, e_checked) {
+                    (Type::FieldElement, TypedExpression::FieldElement(e)) => Ok(e.into()),
+                    (Type::FieldElement, TypedExpression::Uint(e)) => {
+                        Ok(FieldElementExpression::FromUint(box e).into())
+                    }
+                    (Type::Uint(bitwidth), TypedExpression::FieldElement(e)) => {
+                        Ok(UExpressionInner::FromField(box e).annotate(bitwidth).into())
+                    }
+                    (Type::Uint(bitwidth), TypedExpression::Uint(e)) => {
+                        if e.bitwidth() == bitwidth {
+                            Ok(e.into())
+                        } else {
+                            Ok(UExpressionInner::FromUint(box e).annotate(bitwidth).into())
+                        }
+                    }
+                    (Type::Boolean, TypedExpression::Boolean(e)) => Ok(e.into()),
+                    (ty, e) => Err(Error {
                         pos: Some(pos),
 
-                        message: format!("cannot negate {}", e.get_type()),
+                        message: format!("cannot cast {} of type {} to {}", e, e.get_type(), ty),
                     }),
                 }
             }
         }
     }
 
+    /// Check the statements of a branch of an `if/else` block in their own scope
+    fn check_branch(
+        &mut self,
+        statements: Vec<StatementNode<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Vec<Error>> {
+        self.enter_scope();
+
+        let mut checked_statements = vec![];
+
+        for stat in statements {
+            // branches are lowered to conditional assignments, so they cannot return early
+            if let Statement::Return(..) = stat.value {
+                return Err(vec![Error {
+                    pos: Some(stat.pos()),
+                    message: String::from("Return statements are not allowed in `if/else` blocks"),
+                }]);
+            }
+            let checked_stat = self.check_statement(stat, module_id, types)?;
+            checked_statements.push(checked_stat);
+        }
+
+        self.exit_scope();
+        Ok(checked_statements)
+    }
+
+    /// Check that a tuple can be destructured into variables of types `vars_types`, where known
+    fn check_destructuring(
+        &mut self,
+        var_names: Vec<Identifier<'ast>>,
+        vars_types: Vec<Option<Type>>,
+        tuple: TupleExpression<'ast, T>,
+        pos: (Position, Position),
+        span: SourceSpan,
+    ) -> Result<TypedStatement<'ast, T>, Error> {
+        let elements = tuple.ty().clone();
+
+        if elements.len() != var_names.len() {
+            return Err(Error {
+                pos: Some(pos),
+                message: format!(
+                    "Expected a tuple of {} elements, found {} of type {}",
+                    var_names.len(),
+                    tuple,
+                    tuple.get_type()
+                ),
+            });
+        }
+
+        for ((name, ty), element) in var_names.iter().zip(vars_types).zip(elements.iter()) {
+            match ty {
+                Some(ref ty) if ty != element => {
+                    return Err(Error {
+                        pos: Some(pos),
+                        message: format!(
+                            "Element of type {} cannot be assigned to {} of type {}",
+                            element, name, ty
+                        ),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        let lhs = self.define_variables(var_names, elements);
+
+        Ok(TypedStatement::MultipleDefinition(
+            lhs,
+            TypedExpressionList::Tuple(tuple),
+            Some(span),
+        ))
+    }
+
+    /// Create the variables defined by a multiple definition and bring them into scope, except for wildcards
+    fn define_variables(
+        &mut self,
+        var_names: Vec<Identifier<'ast>>,
+        types: Vec<Type>,
+    ) -> Vec<Variable<'ast>> {
+        var_names
+            .into_iter()
+            .zip(types)
+            .map(|(name, ty)| {
+                let var = Variable::with_id_and_type(crate::typed_absy::Identifier::from(name), ty);
+                if name != WILDCARD {
+                    self.insert_into_scope(var.clone());
+                }
+                var
+            })
+            .collect()
+    }
+
     fn get_scope(&self, variable_name: &'ast str) -> Option<&'ast ScopedVariable> {
         self.scope.get(&ScopedVariable {
             id: Variable::with_id_and_type(
@@ -1863,8 +3023,100 @@ impl<'ast> Checker<'ast> {
         })
     }
 
-    fn find_candidates(&self, query: &FunctionQuery<'ast>) -> Vec<FunctionKey<'ast>> {
-        query.match_funcs(&self.functions)
+    fn find_candidates(
+        &mut self,
+        query: &FunctionQuery<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap,
+    ) -> Vec<FunctionKey<'ast>> {
+        match self.generics.get(query.id).cloned() {
+            // a generic function is instantiated with the sizes of the arrays it is called with
+            Some(generic) => match self.instantiate(&generic, &query.inputs, types) {
+                Some((bindings, signature)) => {
+                    let key = FunctionKey::with_id(query.id).signature(signature);
+                    match query.match_func(&key) {
+                        true => {
+                            self.instances.push(Instance {
+                                module: module_id.clone(),
+                                key: key.clone(),
+                                generic,
+                                bindings,
+                            });
+                            vec![key]
+                        }
+                        false => vec![],
+                    }
+                }
+                None => vec![],
+            },
+            None => query.match_funcs(&self.functions),
+        }
+    }
+
+    /// Infer the values of the generic parameters of `generic` when called with arguments of types `inputs`, and the
+    /// signature of the corresponding instance
+    fn instantiate(
+        &self,
+        generic: &Generic<'ast>,
+        inputs: &Vec<Type>,
+        types: &TypeMap,
+    ) -> Option<(HashMap<String, usize>, Signature)> {
+        if generic.signature.inputs.len() != inputs.len() {
+            return None;
+        }
+
+        let mut bindings = HashMap::new();
+        for (ty, input) in generic.signature.inputs.iter().zip(inputs) {
+            if !self.infer(ty, input, &generic.module, types, &mut bindings) {
+                return None;
+            }
+        }
+
+        let outputs = generic
+            .signature
+            .outputs
+            .iter()
+            .map(|ty| self.check_type_with(ty.clone(), &generic.module, types, &bindings))
+            .collect::<Result<_, _>>()
+            .ok()?;
+
+        Some((
+            bindings,
+            Signature::new().inputs(inputs.clone()).outputs(outputs),
+        ))
+    }
+
+    /// Match `ty` against `input`, binding the generic parameters found in `ty` along the way
+    fn infer(
+        &self,
+        ty: &UnresolvedTypeNode,
+        input: &Type,
+        module_id: &ModuleId,
+        types: &TypeMap,
+        bindings: &mut HashMap<String, usize>,
+    ) -> bool {
+        match (&ty.value, input) {
+            (UnresolvedType::Array(inner, size), Type::Array(input_inner, input_size)) => {
+                let size_matches = match size {
+                    ArraySize::Number(n) => n == input_size,
+                    ArraySize::Identifier(id) => {
+                        bindings.entry(id.clone()).or_insert(*input_size) == input_size
+                    }
+                };
+                size_matches && self.infer(inner, input_inner, module_id, types, bindings)
+            }
+            (UnresolvedType::Tuple(elements), Type::Tuple(input_elements)) => {
+                elements.len() == input_elements.len()
+                    && elements
+                        .iter()
+                        .zip(input_elements)
+                        .all(|(e, input)| self.infer(e, input, module_id, types, bindings))
+            }
+            _ => self
+                .check_type_with(ty.clone(), module_id, types, bindings)
+                .map(|ty| &ty == input)
+                .unwrap_or(false),
+        }
     }
 
     fn enter_scope(&mut self) -> () {
@@ -1879,6 +3131,24 @@ impl<'ast> Checker<'ast> {
     }
 }
 
+/// Collect the generic parameters `ty` refers to
+fn generic_sizes(ty: &UnresolvedType, sizes: &mut HashSet<String>) {
+    match ty {
+        UnresolvedType::Array(inner, size) => {
+            if let ArraySize::Identifier(id) = size {
+                sizes.insert(id.clone());
+            }
+            generic_sizes(&inner.value, sizes);
+        }
+        UnresolvedType::Tuple(elements) => {
+            for element in elements {
+                generic_sizes(&element.value, sizes);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1942,6 +3212,126 @@ mod tests {
         }
     }
 
+    mod uint {
+        use super::*;
+
+        #[test]
+        fn bitwidth_mismatch() {
+            let types = HashMap::new();
+            let module_id = String::from("");
+            // 0x01 + 0x0001
+            let e = Expression::Add(
+                box Expression::U8Constant(1).mock(),
+                box Expression::U16Constant(1).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .message,
+                "Expected only field elements or unsigned integers of the same type, found u8, u16"
+            );
+
+            // 0x01 & 0x0001
+            let e = Expression::BitAnd(
+                box Expression::U8Constant(1).mock(),
+                box Expression::U16Constant(1).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .message,
+                "cannot apply bitwise operators to u8 and u16"
+            );
+        }
+
+        #[test]
+        fn shift() {
+            let types = HashMap::new();
+            let module_id = String::from("");
+            // 0x01 << 2
+            let e = Expression::LeftShift(
+                box Expression::U8Constant(1).mock(),
+                box Expression::FieldConstant(FieldPrime::from(2)).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::new().check_expression(e, &module_id, &types),
+                Ok(UExpressionInner::LeftShift(
+                    box UExpressionInner::Value(1).annotate(8),
+                    box FieldElementExpression::Number(FieldPrime::from(2))
+                )
+                .annotate(8)
+                .into())
+            );
+
+            // 0x01 << 0x02
+            let e = Expression::LeftShift(
+                box Expression::U8Constant(1).mock(),
+                box Expression::U8Constant(2).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .message,
+                "cannot shift u8 by u8, expected an unsigned integer and a field element"
+            );
+        }
+
+        #[test]
+        fn cast() {
+            let types = HashMap::new();
+            let module_id = String::from("");
+            // u16(0x01)
+            let e = Expression::Cast(
+                UnresolvedType::Uint(16).mock(),
+                box Expression::U8Constant(1).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new().check_expression(e, &module_id, &types),
+                Ok(
+                    UExpressionInner::FromUint(box UExpressionInner::Value(1).annotate(8))
+                        .annotate(16)
+                        .into()
+                )
+            );
+
+            // field(0x01)
+            let e = Expression::Cast(
+                UnresolvedType::FieldElement.mock(),
+                box Expression::U8Constant(1).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new().check_expression(e, &module_id, &types),
+                Ok(
+                    FieldElementExpression::FromUint(box UExpressionInner::Value(1).annotate(8))
+                        .into()
+                )
+            );
+
+            // bool(0x01)
+            let e = Expression::Cast(
+                UnresolvedType::Boolean.mock(),
+                box Expression::U8Constant(1).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<FieldPrime>::new()
+                    .check_expression(e, &module_id, &types)
+                    .unwrap_err()
+                    .message,
+                "cannot cast 0x01 of type u8 to bool"
+            );
+        }
+    }
+
     mod symbols {
         use super::*;
 
@@ -1960,6 +3350,7 @@ mod tests {
             let signature = UnresolvedSignature::new();
 
             Function {
+                generics: vec![],
                 arguments,
                 statements,
                 signature,
@@ -1987,6 +3378,7 @@ mod tests {
                 UnresolvedSignature::new().inputs(vec![UnresolvedType::FieldElement.mock()]);
 
             Function {
+                generics: vec![],
                 arguments,
                 statements,
                 signature,
@@ -2344,15 +3736,16 @@ mod tests {
         }
     }
 
-    pub fn new_with_args<'ast>(
+    pub fn new_with_args<'ast, T: Field>(
         scope: HashSet<ScopedVariable<'ast>>,
         level: usize,
         functions: HashSet<FunctionKey<'ast>>,
-    ) -> Checker<'ast> {
+    ) -> Checker<'ast, T> {
         Checker {
             scope: scope,
             functions: functions,
             level: level,
+            ..Checker::new()
         }
     }
 
@@ -2406,11 +3799,71 @@ mod tests {
             checker.check_statement(statement, &module_id, &types),
             Ok(TypedStatement::Definition(
                 TypedAssignee::Identifier(typed_absy::Variable::field_element("a".into())),
-                FieldElementExpression::Identifier("b".into()).into()
+                FieldElementExpression::Identifier("b".into()).into(),
+                Some(SourceSpan::mock()),
+            ))
+        );
+    }
+
+    #[test]
+    fn assertion() {
+        // assert(1 == 1, "message")
+        // should succeed and record the module, position and message
+        let statement: StatementNode<FieldPrime> = Statement::Assertion(
+            Expression::Eq(
+                box Expression::FieldConstant(FieldPrime::from(1)).mock(),
+                box Expression::FieldConstant(FieldPrime::from(1)).mock(),
+            )
+            .mock(),
+            Some(String::from("message")),
+        )
+        .mock();
+
+        let types = HashMap::new();
+        let module_id = String::from("main");
+
+        let mut checker = Checker::new();
+        assert_eq!(
+            checker.check_statement(statement, &module_id, &types),
+            Ok(TypedStatement::Assertion(
+                BooleanExpression::Eq(
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                ),
+                AssertionMetadata {
+                    span: SourceSpan {
+                        module: String::from("main"),
+                        from: Position::mock(),
+                        to: Position::mock(),
+                        stack: vec![],
+                    },
+                    message: Some(String::from("message")),
+                }
             ))
         );
     }
 
+    #[test]
+    fn assertion_on_field_element() {
+        // assert(1)
+        // should fail
+        let statement: StatementNode<FieldPrime> =
+            Statement::Assertion(Expression::FieldConstant(FieldPrime::from(1)).mock(), None)
+                .mock();
+
+        let types = HashMap::new();
+        let module_id = String::from("");
+
+        let mut checker = Checker::new();
+        assert_eq!(
+            checker.check_statement(statement, &module_id, &types),
+            Err(vec![Error {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "Expected 1 to be of type bool, found field".to_string()
+            }])
+        );
+    }
+
     #[test]
     fn declared_in_other_function() {
         // def foo():
@@ -2431,6 +3884,7 @@ mod tests {
             .mock(),
         ];
         let foo = Function {
+            generics: vec![],
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature {
@@ -2450,6 +3904,7 @@ mod tests {
         .mock()];
 
         let bar = Function {
+            generics: vec![],
             arguments: bar_args,
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -2512,6 +3967,7 @@ mod tests {
         ];
 
         let foo = Function {
+            generics: vec![],
             arguments: foo_args,
             statements: foo_statements,
             signature: UnresolvedSignature {
@@ -2541,6 +3997,7 @@ mod tests {
             .mock(),
         ];
         let bar = Function {
+            generics: vec![],
             arguments: bar_args,
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -2560,6 +4017,7 @@ mod tests {
         .mock()];
 
         let main = Function {
+            generics: vec![],
             arguments: main_args,
             statements: main_statements,
             signature: UnresolvedSignature {
@@ -2609,8 +4067,8 @@ mod tests {
         let foo_statements = vec![
             Statement::For(
                 absy::Variable::new("i", UnresolvedType::FieldElement.mock()).mock(),
-                FieldPrime::from(0),
-                FieldPrime::from(10),
+                Expression::FieldConstant(FieldPrime::from(0)).mock(),
+                Expression::FieldConstant(FieldPrime::from(10)).mock(),
                 vec![],
             )
             .mock(),
@@ -2623,6 +4081,7 @@ mod tests {
             .mock(),
         ];
         let foo = Function {
+            generics: vec![],
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
@@ -2667,8 +4126,8 @@ mod tests {
 
         let foo_statements = vec![Statement::For(
             absy::Variable::new("i", UnresolvedType::FieldElement.mock()).mock(),
-            FieldPrime::from(0),
-            FieldPrime::from(10),
+            Expression::FieldConstant(FieldPrime::from(0)).mock(),
+            Expression::FieldConstant(FieldPrime::from(10)).mock(),
             for_statements,
         )
         .mock()];
@@ -2678,6 +4137,7 @@ mod tests {
             TypedStatement::Definition(
                 TypedAssignee::Identifier(typed_absy::Variable::field_element("a".into())),
                 FieldElementExpression::Identifier("i".into()).into(),
+                Some(SourceSpan::mock()),
             ),
         ];
 
@@ -2689,6 +4149,7 @@ mod tests {
         )];
 
         let foo = Function {
+            generics: vec![],
             arguments: vec![],
             statements: foo_statements,
             signature: UnresolvedSignature {
@@ -2747,6 +4208,7 @@ mod tests {
         let functions = vec![foo].into_iter().collect();
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -2795,6 +4257,7 @@ mod tests {
         let functions = vec![foo].into_iter().collect();
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -2836,6 +4299,7 @@ mod tests {
         ];
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -2882,6 +4346,7 @@ mod tests {
         .mock()];
 
         let foo = Function {
+            generics: vec![],
             arguments: vec![crate::absy::Parameter {
                 id: absy::Variable::new("x", UnresolvedType::FieldElement.mock()).mock(),
                 private: false,
@@ -2925,6 +4390,7 @@ mod tests {
         ];
 
         let main = Function {
+            generics: vec![],
             arguments: vec![],
             statements: main_statements,
             signature: UnresolvedSignature {
@@ -2974,6 +4440,7 @@ mod tests {
         .mock()];
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -3015,6 +4482,7 @@ mod tests {
         .mock()];
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -3094,12 +4562,16 @@ mod tests {
                     vec![],
                     vec![Type::FieldElement, Type::FieldElement],
                 ),
+                Some(SourceSpan::mock()),
+            ),
+            TypedStatement::Return(
+                vec![FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("b".into()),
+                )
+                .into()],
+                Some(SourceSpan::mock()),
             ),
-            TypedStatement::Return(vec![FieldElementExpression::Add(
-                box FieldElementExpression::Identifier("a".into()),
-                box FieldElementExpression::Identifier("b".into()),
-            )
-            .into()]),
         ];
 
         let foo = FunctionKey {
@@ -3114,6 +4586,7 @@ mod tests {
         functions.insert(foo);
 
         let bar = Function {
+            generics: vec![],
             arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature {
@@ -3175,6 +4648,7 @@ mod tests {
         let main2_arguments = vec![];
 
         let main1 = Function {
+            generics: vec![],
             arguments: main1_arguments,
             statements: main1_statements,
             signature: UnresolvedSignature {
@@ -3185,6 +4659,7 @@ mod tests {
         .mock();
 
         let main2 = Function {
+            generics: vec![],
             arguments: main2_arguments,
             statements: main2_statements,
             signature: UnresolvedSignature {
@@ -3304,7 +4779,7 @@ mod tests {
         /// helper function to create a module at location "" with a single symbol `Foo { foo: field }`
         fn create_module_with_foo(
             s: StructType<'static>,
-        ) -> (Checker<'static>, State<'static, FieldPrime>) {
+        ) -> (Checker<'static, FieldPrime>, State<'static, FieldPrime>) {
             let module_id = "".to_string();
 
             let module: Module<FieldPrime> = Module {
@@ -3339,7 +4814,11 @@ mod tests {
                 let expected_type = Type::Struct(vec![]);
 
                 assert_eq!(
-                    Checker::new().check_struct_type_declaration(declaration, &module_id, &types),
+                    Checker::<FieldPrime>::new().check_struct_type_declaration(
+                        declaration,
+                        &module_id,
+                        &types
+                    ),
                     Ok(expected_type)
                 );
             }
@@ -3371,7 +4850,11 @@ mod tests {
                 ]);
 
                 assert_eq!(
-                    Checker::new().check_struct_type_declaration(declaration, &module_id, &types),
+                    Checker::<FieldPrime>::new().check_struct_type_declaration(
+                        declaration,
+                        &module_id,
+                        &types
+                    ),
                     Ok(expected_type)
                 );
             }
@@ -3415,12 +4898,15 @@ mod tests {
                 .mock();
 
                 assert!(
-                    Checker::new().check_struct_type_declaration(declaration0, &module_id, &types)
-                        != Checker::new().check_struct_type_declaration(
-                            declaration1,
-                            &module_id,
-                            &types
-                        )
+                    Checker::<FieldPrime>::new().check_struct_type_declaration(
+                        declaration0,
+                        &module_id,
+                        &types
+                    ) != Checker::<FieldPrime>::new().check_struct_type_declaration(
+                        declaration1,
+                        &module_id,
+                        &types
+                    )
                 );
             }
 
@@ -3447,7 +4933,7 @@ mod tests {
                 .mock();
 
                 assert_eq!(
-                    Checker::new()
+                    Checker::<FieldPrime>::new()
                         .check_struct_type_declaration(declaration, &module_id, &types)
                         .unwrap_err()[0]
                         .message,
@@ -3742,7 +5228,7 @@ mod tests {
                 });
 
                 assert_eq!(
-                    checker.check_statement::<FieldPrime>(
+                    checker.check_statement(
                         Statement::Declaration(
                             absy::Variable::new(
                                 "a",
@@ -4131,9 +5617,9 @@ mod tests {
 
             let types = HashMap::new();
             let module_id = String::from("");
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<FieldPrime> = Checker::new();
             checker
-                .check_statement::<FieldPrime>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new("a", UnresolvedType::FieldElement.mock()).mock(),
                     )
@@ -4166,9 +5652,9 @@ mod tests {
             let types = HashMap::new();
             let module_id = String::from("");
 
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<FieldPrime> = Checker::new();
             checker
-                .check_statement::<FieldPrime>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new(
                             "a",
@@ -4214,9 +5700,9 @@ mod tests {
 
             let types = HashMap::new();
             let module_id = String::from("");
-            let mut checker: Checker = Checker::new();
+            let mut checker: Checker<FieldPrime> = Checker::new();
             checker
-                .check_statement::<FieldPrime>(
+                .check_statement(
                     Statement::Declaration(
                         absy::Variable::new(
                             "a",
@@ -35,7 +35,7 @@ impl<T: Field> From<BellmanConstraint<T::BellmanEngine>> for FlatStatement<T> {
         let rhs_b = flat_expression_from_vec(c.b);
         let lhs = flat_expression_from_vec(c.c);
 
-        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b))
+        FlatStatement::Condition(lhs, FlatExpression::Mult(box rhs_a, box rhs_b), None, None)
     }
 }
 
@@ -98,6 +98,8 @@ pub fn sha_round<T: Field>() -> FlatFunction<T> {
     let one_binding_statement = FlatStatement::Condition(
         FlatVariable::new(0).into(),
         FlatExpression::Number(T::from(1)),
+        None,
+        None,
     );
 
     let input_binding_statements =
@@ -106,6 +108,8 @@ pub fn sha_round<T: Field>() -> FlatFunction<T> {
         FlatStatement::Condition(
             FlatVariable::new(cs_index).into(),
             FlatVariable::new(argument_index).into(),
+            None,
+            None,
         )
     });
 
@@ -118,19 +122,25 @@ pub fn sha_round<T: Field>() -> FlatFunction<T> {
         .collect();
 
     // insert a directive to set the witness based on the bellman gadget and  inputs
-    let directive_statement = FlatStatement::Directive(DirectiveStatement {
-        outputs: cs_indices.map(|i| FlatVariable::new(i)).collect(),
-        inputs: input_argument_indices
-            .chain(current_hash_argument_indices)
-            .map(|i| FlatVariable::new(i).into())
-            .collect(),
-        helper: Helper::Rust(RustHelper::Sha256Round),
-    });
+    let directive_statement = FlatStatement::Directive(
+        DirectiveStatement {
+            outputs: cs_indices.map(|i| FlatVariable::new(i)).collect(),
+            inputs: input_argument_indices
+                .chain(current_hash_argument_indices)
+                .map(|i| FlatVariable::new(i).into())
+                .collect(),
+            helper: Helper::Rust(RustHelper::Sha256Round),
+        },
+        None,
+    );
 
     // insert a statement to return the subset of the witness
-    let return_statement = FlatStatement::Return(FlatExpressionList {
-        expressions: outputs,
-    });
+    let return_statement = FlatStatement::Return(
+        FlatExpressionList {
+            expressions: outputs,
+        },
+        None,
+    );
 
     let statements = std::iter::once(directive_statement)
         .chain(std::iter::once(one_binding_statement))
@@ -209,14 +219,21 @@ mod tests {
             compiled.statements[1],
             FlatStatement::Condition(
                 FlatVariable::new(0).into(),
-                FlatExpression::Number(FieldPrime::from(1))
+                FlatExpression::Number(FieldPrime::from(1)),
+                None,
+                None,
             )
         );
 
         // bellman input #0: index 1 should equal zokrates input #0: index v_count
         assert_eq!(
             compiled.statements[2],
-            FlatStatement::Condition(FlatVariable::new(1).into(), FlatVariable::new(26936).into())
+            FlatStatement::Condition(
+                FlatVariable::new(1).into(),
+                FlatVariable::new(26936).into(),
+                None,
+                None,
+            )
         );
 
         let f = crate::ir::Function::from(compiled);
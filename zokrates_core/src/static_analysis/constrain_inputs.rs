@@ -1,4 +1,4 @@
-//! Add runtime boolean checks on user inputs
+//! Add runtime boolean and unsigned integer range checks on user inputs
 //!
 //! Example:
 //! ```zokrates
@@ -53,7 +53,18 @@ impl<'ast, T: Field> InputConstrainer<'ast, T> {
             TypedExpression::Boolean(b) => self.constraints.push(TypedStatement::Condition(
                 b.clone().into(),
                 BooleanExpression::And(box b.clone(), box b).into(),
+                None,
             )),
+            TypedExpression::Uint(u) => {
+                let bitwidth = u.bitwidth();
+                self.constraints.push(TypedStatement::Condition(
+                    u.clone().into(),
+                    UExpressionInner::And(box u.clone(), box u)
+                        .annotate(bitwidth)
+                        .into(),
+                    None,
+                ))
+            }
             TypedExpression::Array(a) => {
                 for i in 0..a.size() {
                     let e = match a.inner_type() {
@@ -67,6 +78,11 @@ impl<'ast, T: Field> InputConstrainer<'ast, T> {
                             FieldElementExpression::Number(T::from(i)),
                         )
                         .into(),
+                        Type::Uint(..) => UExpression::select(
+                            a.clone(),
+                            FieldElementExpression::Number(T::from(i)),
+                        )
+                        .into(),
                         Type::Array(..) => ArrayExpression::select(
                             a.clone(),
                             FieldElementExpression::Number(T::from(i)),
@@ -77,6 +93,11 @@ impl<'ast, T: Field> InputConstrainer<'ast, T> {
                             FieldElementExpression::Number(T::from(i)),
                         )
                         .into(),
+                        Type::Tuple(..) => TupleExpression::select(
+                            a.clone(),
+                            FieldElementExpression::Number(T::from(i)),
+                        )
+                        .into(),
                     };
 
                     self.constrain_expression(e);
@@ -89,8 +110,26 @@ impl<'ast, T: Field> InputConstrainer<'ast, T> {
                             FieldElementExpression::member(s.clone(), id.clone()).into()
                         }
                         Type::Boolean => BooleanExpression::member(s.clone(), id.clone()).into(),
+                        Type::Uint(..) => UExpression::member(s.clone(), id.clone()).into(),
                         Type::Array(..) => ArrayExpression::member(s.clone(), id.clone()).into(),
                         Type::Struct(..) => StructExpression::member(s.clone(), id.clone()).into(),
+                        Type::Tuple(..) => TupleExpression::member(s.clone(), id.clone()).into(),
+                    };
+
+                    self.constrain_expression(e);
+                }
+            }
+            TypedExpression::Tuple(t) => {
+                for (index, ty) in t.ty().iter().enumerate() {
+                    let e = match ty {
+                        Type::FieldElement => {
+                            FieldElementExpression::element(t.clone(), index).into()
+                        }
+                        Type::Boolean => BooleanExpression::element(t.clone(), index).into(),
+                        Type::Uint(..) => UExpression::element(t.clone(), index).into(),
+                        Type::Array(..) => ArrayExpression::element(t.clone(), index).into(),
+                        Type::Struct(..) => StructExpression::element(t.clone(), index).into(),
+                        Type::Tuple(..) => TupleExpression::element(t.clone(), index).into(),
                     };
 
                     self.constrain_expression(e);
@@ -107,9 +146,13 @@ impl<'ast, T: Field> Folder<'ast, T> for InputConstrainer<'ast, T> {
         let e = match v.get_type() {
             Type::FieldElement => FieldElementExpression::Identifier(v.id).into(),
             Type::Boolean => BooleanExpression::Identifier(v.id).into(),
+            Type::Uint(bitwidth) => UExpressionInner::Identifier(v.id).annotate(bitwidth).into(),
             Type::Struct(members) => StructExpressionInner::Identifier(v.id)
                 .annotate(members)
                 .into(),
+            Type::Tuple(elements) => TupleExpressionInner::Identifier(v.id)
+                .annotate(elements)
+                .into(),
             Type::Array(box ty, size) => ArrayExpressionInner::Identifier(v.id)
                 .annotate(ty, size)
                 .into(),
@@ -56,32 +56,40 @@ impl<T: Field> PropagateWithContext<T> for FlatExpression<T> {
 impl<T: Field> FlatStatement<T> {
     fn propagate(self, constants: &mut HashMap<FlatVariable, T>) -> Option<FlatStatement<T>> {
         match self {
-            FlatStatement::Return(list) => Some(FlatStatement::Return(FlatExpressionList {
-                expressions: list
-                    .expressions
-                    .into_iter()
-                    .map(|e| e.propagate(constants))
-                    .collect(),
-            })),
-            FlatStatement::Definition(var, expr) => match expr.propagate(constants) {
+            FlatStatement::Return(list, span) => Some(FlatStatement::Return(
+                FlatExpressionList {
+                    expressions: list
+                        .expressions
+                        .into_iter()
+                        .map(|e| e.propagate(constants))
+                        .collect(),
+                },
+                span,
+            )),
+            FlatStatement::Definition(var, expr, span) => match expr.propagate(constants) {
                 FlatExpression::Number(n) => {
                     constants.insert(var, n);
                     None
                 }
-                e => Some(FlatStatement::Definition(var, e)),
+                e => Some(FlatStatement::Definition(var, e, span)),
             },
-            FlatStatement::Condition(e1, e2) => Some(FlatStatement::Condition(
+            FlatStatement::Condition(e1, e2, metadata, span) => Some(FlatStatement::Condition(
                 e1.propagate(constants),
                 e2.propagate(constants),
+                metadata,
+                span,
+            )),
+            FlatStatement::Directive(d, span) => Some(FlatStatement::Directive(
+                DirectiveStatement {
+                    inputs: d
+                        .inputs
+                        .into_iter()
+                        .map(|i| i.propagate(constants))
+                        .collect(),
+                    ..d
+                },
+                span,
             )),
-            FlatStatement::Directive(d) => Some(FlatStatement::Directive(DirectiveStatement {
-                inputs: d
-                    .inputs
-                    .into_iter()
-                    .map(|i| i.propagate(constants))
-                    .collect(),
-                ..d
-            })),
         }
     }
 }
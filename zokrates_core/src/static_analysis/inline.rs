@@ -29,6 +29,8 @@ pub struct Inliner<'ast, T: Field> {
     statement_buffer: Vec<TypedStatement<'ast, T>>, // a buffer of statements to be added to the inlined statements
     stack: Vec<(String, FunctionKey<'ast>, usize)>, // the current call stack
     call_count: HashMap<(String, FunctionKey<'ast>), usize>, // the call count for each function
+    span: Option<SourceSpan>,                       // the location of the statement we're visiting
+    frames: Vec<CallFrame>, // the inlined calls which led to the statement we're visiting
 }
 
 impl<'ast, T: Field> Inliner<'ast, T> {
@@ -42,6 +44,8 @@ impl<'ast, T: Field> Inliner<'ast, T> {
             statement_buffer: vec![],
             stack: vec![],
             call_count: HashMap::new(),
+            span: None,
+            frames: vec![],
         }
     }
 
@@ -123,12 +127,23 @@ impl<'ast, T: Field> Inliner<'ast, T> {
                         TypedStatement::Definition(
                             self.fold_assignee(TypedAssignee::Identifier(a.id.clone())),
                             e,
+                            self.span.clone(),
                         )
                     })
                     .collect();
 
                 self.statement_buffer.extend(inputs_bindings);
 
+                // keep track of the call site so that the statements of the function can be attributed to it
+                self.frames.push(CallFrame {
+                    module: self.module_id.clone(),
+                    function: key.id.to_string(),
+                    call_site: self.span.clone().map(|span| SourceSpan {
+                        stack: vec![],
+                        ..span
+                    }),
+                });
+
                 // filter out the return statement and keep it aside
                 let (statements, mut ret): (Vec<_>, Vec<_>) = function
                     .statements
@@ -144,9 +159,10 @@ impl<'ast, T: Field> Inliner<'ast, T> {
 
                 // pop this call from the stack
                 self.stack.pop();
+                self.frames.pop();
 
                 match ret.pop().unwrap() {
-                    TypedStatement::Return(exprs) => Ok(exprs),
+                    TypedStatement::Return(exprs, _) => Ok(exprs),
                     _ => unreachable!(""),
                 }
             }
@@ -178,8 +194,15 @@ impl<'ast, T: Field> Inliner<'ast, T> {
 impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
     // add extra statements before the modified statement
     fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
-        let folded = match s {
-            TypedStatement::MultipleDefinition(variables, elist) => match elist {
+        // statements generated while inlining calls are attributed to the calling statement
+        let span = s.span().map(|span| SourceSpan {
+            stack: self.frames.clone(),
+            ..span
+        });
+        let caller_span = std::mem::replace(&mut self.span, span);
+
+        let mut folded = match s {
+            TypedStatement::MultipleDefinition(variables, elist, span) => match elist {
                 TypedExpressionList::FunctionCall(key, exps, types) => {
                     let variables: Vec<_> = variables
                         .into_iter()
@@ -192,18 +215,69 @@ impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
                             .into_iter()
                             .zip(ret.into_iter())
                             .map(|(v, e)| {
-                                TypedStatement::Definition(TypedAssignee::Identifier(v), e)
+                                TypedStatement::Definition(
+                                    TypedAssignee::Identifier(v),
+                                    e,
+                                    span.clone(),
+                                )
                             })
                             .collect(),
                         Err((key, expressions)) => vec![TypedStatement::MultipleDefinition(
                             variables,
                             TypedExpressionList::FunctionCall(key, expressions, types),
+                            span,
                         )],
                     }
                 }
+                elist => fold_statement(
+                    self,
+                    TypedStatement::MultipleDefinition(variables, elist, span),
+                ),
             },
+            // calls in the condition of a branch or the bound of a loop are made before entering
+            // them, so their statements must not be inlined in the body
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                let condition = self.fold_boolean_expression(condition);
+                let buffer = std::mem::replace(&mut self.statement_buffer, vec![]);
+                let consequence = consequence
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect();
+                let alternative = alternative
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect();
+                self.statement_buffer = buffer;
+                vec![TypedStatement::IfElse(condition, consequence, alternative)]
+            }
+            TypedStatement::BoundedFor(v, from, to, max, statements) => {
+                let to = self.fold_field_expression(to);
+                let buffer = std::mem::replace(&mut self.statement_buffer, vec![]);
+                let statements = statements
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect();
+                self.statement_buffer = buffer;
+                vec![TypedStatement::BoundedFor(
+                    self.fold_variable(v),
+                    from,
+                    to,
+                    max,
+                    statements,
+                )]
+            }
             s => fold_statement(self, s),
         };
+
+        // attribute the resulting statements to the function we're in
+        for s in folded.iter_mut() {
+            if let Some(span) = s.span_mut() {
+                span.stack = self.frames.clone();
+            }
+        }
+
+        self.span = caller_span;
+
         self.statement_buffer.drain(..).chain(folded).collect()
     }
 
@@ -238,6 +312,29 @@ impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
         }
     }
 
+    // inline calls which return an unsigned integer
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: usize,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        match e {
+            UExpressionInner::FunctionCall(key, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                match self.try_inline_call(&key, exps) {
+                    Ok(mut ret) => match ret.pop().unwrap() {
+                        TypedExpression::Uint(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    Err((key, expressions)) => UExpressionInner::FunctionCall(key, expressions),
+                }
+            }
+            // default
+            e => fold_uint_expression_inner(self, bitwidth, e),
+        }
+    }
+
     fn fold_array_expression_inner(
         &mut self,
         ty: &Type,
@@ -284,6 +381,28 @@ impl<'ast, T: Field> Folder<'ast, T> for Inliner<'ast, T> {
             e => fold_struct_expression_inner(self, ty, e),
         }
     }
+
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &Vec<Type>,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        match e {
+            TupleExpressionInner::FunctionCall(key, exps) => {
+                let exps: Vec<_> = exps.into_iter().map(|e| self.fold_expression(e)).collect();
+
+                match self.try_inline_call(&key, exps) {
+                    Ok(mut ret) => match ret.pop().unwrap() {
+                        TypedExpression::Tuple(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    Err((key, expressions)) => TupleExpressionInner::FunctionCall(key, expressions),
+                }
+            }
+            // default
+            e => fold_tuple_expression_inner(self, ty, e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -315,14 +434,15 @@ mod tests {
                         .signature(Signature::new().outputs(vec![Type::FieldElement])),
                     TypedFunctionSymbol::Here(TypedFunction {
                         arguments: vec![],
-                        statements: vec![TypedStatement::Return(vec![
-                            FieldElementExpression::FunctionCall(
+                        statements: vec![TypedStatement::Return(
+                            vec![FieldElementExpression::FunctionCall(
                                 FunctionKey::with_id("foo")
                                     .signature(Signature::new().outputs(vec![Type::FieldElement])),
                                 vec![],
                             )
-                            .into(),
-                        ])],
+                            .into()],
+                            None,
+                        )],
                         signature: Signature::new().outputs(vec![Type::FieldElement]),
                     }),
                 ),
@@ -346,9 +466,10 @@ mod tests {
                     .signature(Signature::new().outputs(vec![Type::FieldElement])),
                 TypedFunctionSymbol::Here(TypedFunction {
                     arguments: vec![],
-                    statements: vec![TypedStatement::Return(vec![
-                        FieldElementExpression::Number(FieldPrime::from(42)).into(),
-                    ])],
+                    statements: vec![TypedStatement::Return(
+                        vec![FieldElementExpression::Number(FieldPrime::from(42)).into()],
+                        None,
+                    )],
                     signature: Signature::new().outputs(vec![Type::FieldElement]),
                 }),
             )]
@@ -381,9 +502,10 @@ mod tests {
                 .unwrap(),
             &TypedFunctionSymbol::Here(TypedFunction {
                 arguments: vec![],
-                statements: vec![TypedStatement::Return(vec![
-                    FieldElementExpression::Number(FieldPrime::from(42)).into(),
-                ])],
+                statements: vec![TypedStatement::Return(
+                    vec![FieldElementExpression::Number(FieldPrime::from(42)).into(),],
+                    None,
+                )],
                 signature: Signature::new().outputs(vec![Type::FieldElement]),
             })
         );
@@ -416,8 +538,8 @@ mod tests {
                     ),
                     TypedFunctionSymbol::Here(TypedFunction {
                         arguments: vec![Parameter::private(Variable::field_element("a".into()))],
-                        statements: vec![TypedStatement::Return(vec![
-                            FieldElementExpression::Mult(
+                        statements: vec![TypedStatement::Return(
+                            vec![FieldElementExpression::Mult(
                                 box FieldElementExpression::Identifier("a".into()),
                                 box FieldElementExpression::FunctionCall(
                                     FunctionKey::with_id("foo").signature(
@@ -428,8 +550,9 @@ mod tests {
                                     vec![FieldElementExpression::Identifier("a".into()).into()],
                                 ),
                             )
-                            .into(),
-                        ])],
+                            .into()],
+                            None,
+                        )],
                         signature: Signature::new()
                             .inputs(vec![Type::FieldElement])
                             .outputs(vec![Type::FieldElement]),
@@ -464,11 +587,14 @@ mod tests {
                 ),
                 TypedFunctionSymbol::Here(TypedFunction {
                     arguments: vec![Parameter::private(Variable::field_element("a".into()))],
-                    statements: vec![TypedStatement::Return(vec![FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("a".into()),
-                        box FieldElementExpression::Identifier("a".into()),
-                    )
-                    .into()])],
+                    statements: vec![TypedStatement::Return(
+                        vec![FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
+                        )
+                        .into()],
+                        None,
+                    )],
                     signature: Signature::new()
                         .inputs(vec![Type::FieldElement])
                         .outputs(vec![Type::FieldElement]),
@@ -522,20 +648,24 @@ mod tests {
                         TypedAssignee::Identifier(Variable::field_element(
                             Identifier::from("a").stack(stack.clone())
                         )),
-                        FieldElementExpression::Identifier("a".into()).into()
+                        FieldElementExpression::Identifier("a".into()).into(),
+                        None,
                     ),
-                    TypedStatement::Return(vec![FieldElementExpression::Mult(
-                        box FieldElementExpression::Identifier("a".into()),
-                        box FieldElementExpression::Mult(
-                            box FieldElementExpression::Identifier(
-                                Identifier::from("a").stack(stack.clone())
-                            ),
-                            box FieldElementExpression::Identifier(
-                                Identifier::from("a").stack(stack.clone())
+                    TypedStatement::Return(
+                        vec![FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Mult(
+                                box FieldElementExpression::Identifier(
+                                    Identifier::from("a").stack(stack.clone())
+                                ),
+                                box FieldElementExpression::Identifier(
+                                    Identifier::from("a").stack(stack.clone())
+                                )
                             )
                         )
+                        .into(),],
+                        None,
                     )
-                    .into(),])
                 ],
                 signature: Signature::new()
                     .inputs(vec![Type::FieldElement])
@@ -577,11 +707,12 @@ mod tests {
                                     vec![],
                                     vec![Type::FieldElement],
                                 ),
+                                None,
+                            ),
+                            TypedStatement::Return(
+                                vec![FieldElementExpression::Identifier("a".into()).into()],
+                                None,
                             ),
-                            TypedStatement::Return(vec![FieldElementExpression::Identifier(
-                                "a".into(),
-                            )
-                            .into()]),
                         ],
                         signature: Signature::new().outputs(vec![Type::FieldElement]),
                     }),
@@ -606,9 +737,10 @@ mod tests {
                     .signature(Signature::new().outputs(vec![Type::FieldElement])),
                 TypedFunctionSymbol::Here(TypedFunction {
                     arguments: vec![],
-                    statements: vec![TypedStatement::Return(vec![
-                        FieldElementExpression::Number(FieldPrime::from(42)).into(),
-                    ])],
+                    statements: vec![TypedStatement::Return(
+                        vec![FieldElementExpression::Number(FieldPrime::from(42)).into()],
+                        None,
+                    )],
                     signature: Signature::new().outputs(vec![Type::FieldElement]),
                 }),
             )]
@@ -644,11 +776,13 @@ mod tests {
                 statements: vec![
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_element("a".into())),
-                        FieldElementExpression::Number(FieldPrime::from(42)).into()
+                        FieldElementExpression::Number(FieldPrime::from(42)).into(),
+                        None,
                     ),
-                    TypedStatement::Return(vec![
-                        FieldElementExpression::Identifier("a".into()).into(),
-                    ])
+                    TypedStatement::Return(
+                        vec![FieldElementExpression::Identifier("a".into()).into(),],
+                        None,
+                    )
                 ],
                 signature: Signature::new().outputs(vec![Type::FieldElement]),
             })
@@ -686,11 +820,12 @@ mod tests {
                                     vec![],
                                     vec![Type::FieldElement],
                                 ),
+                                None,
+                            ),
+                            TypedStatement::Return(
+                                vec![FieldElementExpression::Identifier("a".into()).into()],
+                                None,
                             ),
-                            TypedStatement::Return(vec![FieldElementExpression::Identifier(
-                                "a".into(),
-                            )
-                            .into()]),
                         ],
                         signature: Signature::new().outputs(vec![Type::FieldElement]),
                     }),
@@ -700,9 +835,10 @@ mod tests {
                         .signature(Signature::new().outputs(vec![Type::FieldElement])),
                     TypedFunctionSymbol::Here(TypedFunction {
                         arguments: vec![],
-                        statements: vec![TypedStatement::Return(vec![
-                            FieldElementExpression::Number(FieldPrime::from(42)).into(),
-                        ])],
+                        statements: vec![TypedStatement::Return(
+                            vec![FieldElementExpression::Number(FieldPrime::from(42)).into()],
+                            None,
+                        )],
                         signature: Signature::new().outputs(vec![Type::FieldElement]),
                     }),
                 ),
@@ -737,11 +873,13 @@ mod tests {
                 statements: vec![
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_element("a".into())),
-                        FieldElementExpression::Number(FieldPrime::from(42)).into()
+                        FieldElementExpression::Number(FieldPrime::from(42)).into(),
+                        None,
                     ),
-                    TypedStatement::Return(vec![
-                        FieldElementExpression::Identifier("a".into()).into(),
-                    ])
+                    TypedStatement::Return(
+                        vec![FieldElementExpression::Identifier("a".into()).into(),],
+                        None,
+                    )
                 ],
                 signature: Signature::new().outputs(vec![Type::FieldElement]),
             })
@@ -774,8 +912,8 @@ mod tests {
                     ),
                     TypedFunctionSymbol::Here(TypedFunction {
                         arguments: vec![Parameter::private(Variable::field_element("a".into()))],
-                        statements: vec![TypedStatement::Return(vec![
-                            FieldElementExpression::FunctionCall(
+                        statements: vec![TypedStatement::Return(
+                            vec![FieldElementExpression::FunctionCall(
                                 FunctionKey::with_id("id").signature(
                                     Signature::new()
                                         .inputs(vec![Type::FieldElement])
@@ -791,8 +929,9 @@ mod tests {
                                 )
                                 .into()],
                             )
-                            .into(),
-                        ])],
+                            .into()],
+                            None,
+                        )],
                         signature: Signature::new()
                             .inputs(vec![Type::FieldElement])
                             .outputs(vec![Type::FieldElement]),
@@ -827,9 +966,10 @@ mod tests {
                 ),
                 TypedFunctionSymbol::Here(TypedFunction {
                     arguments: vec![Parameter::private(Variable::field_element("a".into()))],
-                    statements: vec![TypedStatement::Return(vec![
-                        FieldElementExpression::Identifier("a".into()).into(),
-                    ])],
+                    statements: vec![TypedStatement::Return(
+                        vec![FieldElementExpression::Identifier("a".into()).into()],
+                        None,
+                    )],
                     signature: Signature::new()
                         .inputs(vec![Type::FieldElement])
                         .outputs(vec![Type::FieldElement]),
@@ -891,7 +1031,8 @@ mod tests {
                         TypedAssignee::Identifier(Variable::field_element(
                             Identifier::from("a").stack(stack0.clone())
                         )),
-                        FieldElementExpression::Identifier("a".into()).into()
+                        FieldElementExpression::Identifier("a".into()).into(),
+                        None,
                     ),
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_element(
@@ -900,12 +1041,16 @@ mod tests {
                         FieldElementExpression::Identifier(
                             Identifier::from("a").stack(stack0.clone())
                         )
-                        .into()
+                        .into(),
+                        None,
                     ),
-                    TypedStatement::Return(vec![FieldElementExpression::Identifier(
-                        Identifier::from("a").stack(stack1.clone())
+                    TypedStatement::Return(
+                        vec![FieldElementExpression::Identifier(
+                            Identifier::from("a").stack(stack1.clone())
+                        )
+                        .into(),],
+                        None,
                     )
-                    .into(),])
                 ],
                 signature: Signature::new()
                     .inputs(vec![Type::FieldElement])
@@ -913,4 +1058,135 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn keep_call_stack_in_spans() {
+        // // main
+        // from "foo" import foo
+        // def main() -> (field):
+        //    return foo()
+        //
+        // // foo
+        // def foo() -> (field):
+        //    1 == 1
+        //    return 42
+        //
+        //
+        // // inlined
+        // def main() -> (field):
+        //    1 == 1 // attributed to foo, called from main
+        //    return 42
+
+        use parser::Position;
+
+        let span = |module: &str, line| SourceSpan {
+            module: String::from(module),
+            from: Position { line, col: 5 },
+            to: Position { line, col: 15 },
+            stack: vec![],
+        };
+
+        let main = TypedModule {
+            functions: vec![
+                (
+                    FunctionKey::with_id("main")
+                        .signature(Signature::new().outputs(vec![Type::FieldElement])),
+                    TypedFunctionSymbol::Here(TypedFunction {
+                        arguments: vec![],
+                        statements: vec![TypedStatement::Return(
+                            vec![FieldElementExpression::FunctionCall(
+                                FunctionKey::with_id("foo")
+                                    .signature(Signature::new().outputs(vec![Type::FieldElement])),
+                                vec![],
+                            )
+                            .into()],
+                            Some(span("main", 3)),
+                        )],
+                        signature: Signature::new().outputs(vec![Type::FieldElement]),
+                    }),
+                ),
+                (
+                    FunctionKey::with_id("foo")
+                        .signature(Signature::new().outputs(vec![Type::FieldElement])),
+                    TypedFunctionSymbol::There(
+                        FunctionKey::with_id("foo")
+                            .signature(Signature::new().outputs(vec![Type::FieldElement])),
+                        String::from("foo"),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let foo = TypedModule {
+            functions: vec![(
+                FunctionKey::with_id("foo")
+                    .signature(Signature::new().outputs(vec![Type::FieldElement])),
+                TypedFunctionSymbol::Here(TypedFunction {
+                    arguments: vec![],
+                    statements: vec![
+                        TypedStatement::Condition(
+                            FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                            FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                            Some(span("foo", 2)),
+                        ),
+                        TypedStatement::Return(
+                            vec![FieldElementExpression::Number(FieldPrime::from(42)).into()],
+                            Some(span("foo", 3)),
+                        ),
+                    ],
+                    signature: Signature::new().outputs(vec![Type::FieldElement]),
+                }),
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let modules: HashMap<_, _> = vec![(String::from("main"), main), (String::from("foo"), foo)]
+            .into_iter()
+            .collect();
+
+        let program = TypedProgram {
+            main: String::from("main"),
+            modules,
+        };
+
+        let program = Inliner::inline(program);
+
+        assert_eq!(
+            program
+                .modules
+                .get(&String::from("main"))
+                .unwrap()
+                .functions
+                .get(
+                    &FunctionKey::with_id("main")
+                        .signature(Signature::new().outputs(vec![Type::FieldElement]))
+                )
+                .unwrap(),
+            &TypedFunctionSymbol::Here(TypedFunction {
+                arguments: vec![],
+                statements: vec![
+                    TypedStatement::Condition(
+                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                        FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                        Some(SourceSpan {
+                            stack: vec![CallFrame {
+                                module: String::from("foo"),
+                                function: String::from("foo"),
+                                call_site: Some(span("main", 3)),
+                            }],
+                            ..span("foo", 2)
+                        }),
+                    ),
+                    TypedStatement::Return(
+                        vec![FieldElementExpression::Number(FieldPrime::from(42)).into()],
+                        Some(span("main", 3)),
+                    ),
+                ],
+                signature: Signature::new().outputs(vec![Type::FieldElement]),
+            })
+        );
+    }
 }
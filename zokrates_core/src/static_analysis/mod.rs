@@ -8,26 +8,47 @@ mod constrain_inputs;
 mod flat_propagation;
 mod inline;
 mod propagation;
+mod shift_check;
 mod unroll;
 
 use self::constrain_inputs::InputConstrainer;
 use self::inline::Inliner;
-use self::propagation::Propagator;
+pub(crate) use self::propagation::Propagator;
+pub(crate) use self::shift_check::ShiftChecker;
 use self::unroll::Unroller;
 use crate::flat_absy::FlatProg;
-use crate::typed_absy::TypedProgram;
+use crate::typed_absy::{SourceSpan, TypedProgram};
+use std::fmt;
 use zokrates_field::field::Field;
 
+/// An error found once the program is unrolled and constants are propagated
+#[derive(Debug)]
+pub struct Error {
+    span: Option<SourceSpan>,
+    message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = self
+            .span
+            .as_ref()
+            .map(|s| format!("{}", s.from))
+            .unwrap_or("?".to_string());
+        write!(f, "{}\n\t{}", location, self.message)
+    }
+}
+
 pub trait Analyse {
     fn analyse(self) -> Self;
 }
 
 impl<'ast, T: Field> Analyse for TypedProgram<'ast, T> {
     fn analyse(self) -> Self {
+        // inline first, so that functions called from a branch are guarded when unrolling
+        let r = Inliner::inline(self);
         // unroll
-        let r = Unroller::unroll(self);
-        // inline
-        let r = Inliner::inline(r);
+        let r = Unroller::unroll(r);
         // propagate
         let r = Propagator::propagate(r);
         // constrain inputs
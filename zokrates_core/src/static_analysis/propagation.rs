@@ -25,12 +25,26 @@ impl<'ast, T: Field> Propagator<'ast, T> {
     pub fn propagate(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
         Propagator::new().fold_program(p)
     }
+
+    /// Evaluate `e` at compile time, returning `None` if it does not reduce to a constant
+    pub fn evaluate(e: TypedExpression<'ast, T>) -> Option<TypedExpression<'ast, T>> {
+        let e = Propagator::new().fold_expression(e);
+
+        match is_constant(&e) {
+            true => Some(e),
+            false => None,
+        }
+    }
 }
 
 fn is_constant<'ast, T: Field>(e: &TypedExpression<'ast, T>) -> bool {
     match e {
         TypedExpression::FieldElement(FieldElementExpression::Number(..)) => true,
         TypedExpression::Boolean(BooleanExpression::Value(..)) => true,
+        TypedExpression::Uint(u) => match u.as_inner() {
+            UExpressionInner::Value(..) => true,
+            _ => false,
+        },
         TypedExpression::Array(a) => match a.as_inner() {
             ArrayExpressionInner::Value(v) => v.iter().all(|e| is_constant(e)),
             _ => false,
@@ -39,6 +53,10 @@ fn is_constant<'ast, T: Field>(e: &TypedExpression<'ast, T>) -> bool {
             StructExpressionInner::Value(v) => v.iter().all(|e| is_constant(e)),
             _ => false,
         },
+        TypedExpression::Tuple(a) => match a.as_inner() {
+            TupleExpressionInner::Value(v) => v.iter().all(|e| is_constant(e)),
+            _ => false,
+        },
         _ => false,
     }
 }
@@ -52,14 +70,15 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
     fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
         let res = match s {
             TypedStatement::Declaration(v) => Some(TypedStatement::Declaration(v)),
-            TypedStatement::Return(expressions) => Some(TypedStatement::Return(
+            TypedStatement::Return(expressions, span) => Some(TypedStatement::Return(
                 expressions
                     .into_iter()
                     .map(|e| self.fold_expression(e))
                     .collect(),
+                span,
             )),
             // propagation to the defined variable if rhs is a constant
-            TypedStatement::Definition(TypedAssignee::Identifier(var), expr) => {
+            TypedStatement::Definition(TypedAssignee::Identifier(var), expr, span) => {
                 let expr = self.fold_expression(expr);
 
                 if is_constant(&expr) {
@@ -69,33 +88,68 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     Some(TypedStatement::Definition(
                         TypedAssignee::Identifier(var),
                         expr,
+                        span,
                     ))
                 }
             }
-            TypedStatement::Definition(TypedAssignee::Select(..), _) => {
+            TypedStatement::Definition(TypedAssignee::Select(..), ..) => {
                 unreachable!("array updates should have been replaced with full array redef")
             }
-            TypedStatement::Definition(TypedAssignee::Member(..), _) => {
+            TypedStatement::Definition(TypedAssignee::Member(..), ..) => {
                 unreachable!("struct update should have been replaced with full struct redef")
             }
             // propagate lhs and rhs for conditions
-            TypedStatement::Condition(e1, e2) => {
+            TypedStatement::Condition(e1, e2, span) => {
                 // could stop execution here if condition is known to fail
                 Some(TypedStatement::Condition(
                     self.fold_expression(e1),
                     self.fold_expression(e2),
+                    span,
                 ))
             }
-            // we unrolled for loops in the previous step
-            TypedStatement::For(..) => {
+            TypedStatement::Assertion(e, metadata) => Some(TypedStatement::Assertion(
+                self.fold_boolean_expression(e),
+                metadata,
+            )),
+            // we unrolled for loops and lowered if/else blocks in the previous step
+            TypedStatement::For(..) | TypedStatement::BoundedFor(..) => {
                 unreachable!("for loop is unexpected, it should have been unrolled")
             }
-            TypedStatement::MultipleDefinition(variables, expression_list) => {
-                let expression_list = self.fold_expression_list(expression_list);
-                Some(TypedStatement::MultipleDefinition(
-                    variables,
-                    expression_list,
-                ))
+            TypedStatement::IfElse(..) => {
+                unreachable!("if/else block is unexpected, it should have been lowered")
+            }
+            TypedStatement::MultipleDefinition(variables, expression_list, span) => {
+                match self.fold_expression_list(expression_list) {
+                    // destructuring a tuple value defines each variable separately
+                    TypedExpressionList::Tuple(t) => {
+                        let elements = t.ty().clone();
+                        match t.into_inner() {
+                            TupleExpressionInner::Value(values) => {
+                                return variables
+                                    .into_iter()
+                                    .zip(values)
+                                    .flat_map(|(v, e)| {
+                                        self.fold_statement(TypedStatement::Definition(
+                                            TypedAssignee::Identifier(v),
+                                            e,
+                                            span.clone(),
+                                        ))
+                                    })
+                                    .collect();
+                            }
+                            inner => Some(TypedStatement::MultipleDefinition(
+                                variables,
+                                TypedExpressionList::Tuple(inner.annotate(elements)),
+                                span,
+                            )),
+                        }
+                    }
+                    expression_list => Some(TypedStatement::MultipleDefinition(
+                        variables,
+                        expression_list,
+                        span,
+                    )),
+                }
             }
         };
         match res {
@@ -179,6 +233,15 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     )),
                 }
             }
+            FieldElementExpression::FromUint(box e) => {
+                let e = self.fold_uint_expression(e);
+                match e.as_inner() {
+                    UExpressionInner::Value(v) => {
+                        FieldElementExpression::Number(T::from(*v as usize))
+                    }
+                    _ => FieldElementExpression::FromUint(box e),
+                }
+            }
             FieldElementExpression::IfElse(box condition, box consequence, box alternative) => {
                 let consequence = self.fold_field_expression(consequence);
                 let alternative = self.fold_field_expression(alternative);
@@ -249,10 +312,235 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => FieldElementExpression::Member(box inner.annotate(members), m),
                 }
             }
+            FieldElementExpression::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::FieldElement(e) => e,
+                        _ => unreachable!(),
+                    },
+                    inner => FieldElementExpression::Element(box inner.annotate(elements), index),
+                }
+            }
             e => fold_field_expression(self, e),
         }
     }
 
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: usize,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        // all operations wrap around, so constant results are reduced modulo 2**bitwidth
+        let mask = (1 << bitwidth) - 1;
+
+        match e {
+            UExpressionInner::Identifier(id) => {
+                match self
+                    .constants
+                    .get(&TypedAssignee::Identifier(Variable::uint(
+                        id.clone(),
+                        bitwidth,
+                    ))) {
+                    Some(e) => match e {
+                        TypedExpression::Uint(e) => e.as_inner().clone(),
+                        _ => unreachable!("constant stored for a uint should be a uint"),
+                    },
+                    None => UExpressionInner::Identifier(id),
+                }
+            }
+            UExpressionInner::Add(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value((n1 + n2) & mask)
+                }
+                (e1, e2) => {
+                    UExpressionInner::Add(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Sub(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value((n1 + (1 << bitwidth) - n2) & mask)
+                }
+                (e1, e2) => {
+                    UExpressionInner::Sub(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Mult(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value((n1 * n2) & mask)
+                }
+                (e1, e2) => {
+                    UExpressionInner::Mult(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::And(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value(n1 & n2)
+                }
+                (e1, e2) => {
+                    UExpressionInner::And(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Or(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value(n1 | n2)
+                }
+                (e1, e2) => {
+                    UExpressionInner::Or(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Xor(box e1, box e2) => match (
+                self.fold_uint_expression(e1).into_inner(),
+                self.fold_uint_expression(e2).into_inner(),
+            ) {
+                (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                    UExpressionInner::Value(n1 ^ n2)
+                }
+                (e1, e2) => {
+                    UExpressionInner::Xor(box e1.annotate(bitwidth), box e2.annotate(bitwidth))
+                }
+            },
+            UExpressionInner::Not(box e) => match self.fold_uint_expression(e).into_inner() {
+                UExpressionInner::Value(n) => UExpressionInner::Value(!n & mask),
+                e => UExpressionInner::Not(box e.annotate(bitwidth)),
+            },
+            UExpressionInner::LeftShift(box e, box by) => {
+                let e = self.fold_uint_expression(e);
+                match (e.into_inner(), self.fold_field_expression(by)) {
+                    (UExpressionInner::Value(n), FieldElementExpression::Number(by)) => {
+                        // shifting by at least the bitwidth clears all bits
+                        let shifted = by
+                            .to_dec_string()
+                            .parse::<u32>()
+                            .ok()
+                            .and_then(|by| n.checked_shl(by));
+                        UExpressionInner::Value(shifted.unwrap_or(0) & mask)
+                    }
+                    (e, by) => UExpressionInner::LeftShift(box e.annotate(bitwidth), box by),
+                }
+            }
+            UExpressionInner::RightShift(box e, box by) => {
+                let e = self.fold_uint_expression(e);
+                match (e.into_inner(), self.fold_field_expression(by)) {
+                    (UExpressionInner::Value(n), FieldElementExpression::Number(by)) => {
+                        let shifted = by
+                            .to_dec_string()
+                            .parse::<u32>()
+                            .ok()
+                            .and_then(|by| n.checked_shr(by));
+                        UExpressionInner::Value(shifted.unwrap_or(0))
+                    }
+                    (e, by) => UExpressionInner::RightShift(box e.annotate(bitwidth), box by),
+                }
+            }
+            UExpressionInner::FromField(box e) => match self.fold_field_expression(e) {
+                FieldElementExpression::Number(n) => {
+                    let v = n
+                        .to_dec_string()
+                        .parse::<u128>()
+                        .ok()
+                        .filter(|v| *v <= mask);
+                    match v {
+                        Some(v) => UExpressionInner::Value(v),
+                        // out of range casts are left for the flattener to reject at runtime
+                        None => UExpressionInner::FromField(box FieldElementExpression::Number(n)),
+                    }
+                }
+                e => UExpressionInner::FromField(box e),
+            },
+            UExpressionInner::FromUint(box e) => {
+                let e = self.fold_uint_expression(e);
+                match e.as_inner() {
+                    UExpressionInner::Value(n) => UExpressionInner::Value(n & mask),
+                    _ => UExpressionInner::FromUint(box e),
+                }
+            }
+            UExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+                let consequence = self.fold_uint_expression(consequence);
+                let alternative = self.fold_uint_expression(alternative);
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => consequence.into_inner(),
+                    BooleanExpression::Value(false) => alternative.into_inner(),
+                    c => UExpressionInner::IfElse(box c, box consequence, box alternative),
+                }
+            }
+            UExpressionInner::Select(box array, box index) => {
+                let array = self.fold_array_expression(array);
+                let index = self.fold_field_expression(index);
+
+                let inner_type = array.inner_type().clone();
+                let size = array.size();
+
+                match (array.into_inner(), index) {
+                    (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
+                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
+                        if n_as_usize < size {
+                            UExpression::try_from(v[n_as_usize].clone())
+                                .unwrap()
+                                .into_inner()
+                        } else {
+                            unreachable!(
+                                "out of bounds index ({} >= {}) found during static analysis",
+                                n_as_usize, size
+                            );
+                        }
+                    }
+                    (a, i) => UExpressionInner::Select(box a.annotate(inner_type, size), box i),
+                }
+            }
+            UExpressionInner::Member(box s, m) => {
+                let s = self.fold_struct_expression(s);
+
+                let members = match s.get_type() {
+                    Type::Struct(members) => members,
+                    _ => unreachable!(),
+                };
+
+                match s.into_inner() {
+                    StructExpressionInner::Value(v) => {
+                        match members.iter().zip(v).find(|(id, _)| id.0 == m).unwrap().1 {
+                            TypedExpression::Uint(s) => s.into_inner(),
+                            _ => unreachable!(),
+                        }
+                    }
+                    inner => UExpressionInner::Member(box inner.annotate(members), m),
+                }
+            }
+            UExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::Uint(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    inner => UExpressionInner::Element(box inner.annotate(elements), index),
+                }
+            }
+            e => fold_uint_expression_inner(self, bitwidth, e),
+        }
+    }
+
     fn fold_array_expression_inner(
         &mut self,
         ty: &Type,
@@ -345,6 +633,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => ArrayExpressionInner::Member(box inner.annotate(members), m),
                 }
             }
+            ArrayExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::Array(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    inner => ArrayExpressionInner::Element(box inner.annotate(elements), index),
+                }
+            }
             e => fold_array_expression_inner(self, ty, size, e),
         }
     }
@@ -441,10 +742,111 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => StructExpressionInner::Member(box inner.annotate(members), m),
                 }
             }
+            StructExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::Struct(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    inner => StructExpressionInner::Element(box inner.annotate(elements), index),
+                }
+            }
             e => fold_struct_expression_inner(self, ty, e),
         }
     }
 
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &Vec<Type>,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        match e {
+            TupleExpressionInner::Identifier(id) => {
+                match self
+                    .constants
+                    .get(&TypedAssignee::Identifier(Variable::tuple(
+                        id.clone(),
+                        ty.clone(),
+                    ))) {
+                    Some(e) => match e {
+                        TypedExpression::Tuple(e) => e.as_inner().clone(),
+                        _ => panic!("constant stored for a tuple should be a tuple"),
+                    },
+                    None => TupleExpressionInner::Identifier(id),
+                }
+            }
+            TupleExpressionInner::Select(box array, box index) => {
+                let array = self.fold_array_expression(array);
+                let index = self.fold_field_expression(index);
+
+                let inner_type = array.inner_type().clone();
+                let size = array.size();
+
+                match (array.into_inner(), index) {
+                    (ArrayExpressionInner::Value(v), FieldElementExpression::Number(n)) => {
+                        let n_as_usize = n.to_dec_string().parse::<usize>().unwrap();
+                        if n_as_usize < size {
+                            TupleExpression::try_from(v[n_as_usize].clone())
+                                .unwrap()
+                                .into_inner()
+                        } else {
+                            unreachable!(
+                                "out of bounds index ({} >= {}) found during static analysis",
+                                n_as_usize, size
+                            );
+                        }
+                    }
+                    (a, i) => TupleExpressionInner::Select(box a.annotate(inner_type, size), box i),
+                }
+            }
+            TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+                let consequence = self.fold_tuple_expression(consequence);
+                let alternative = self.fold_tuple_expression(alternative);
+                match self.fold_boolean_expression(condition) {
+                    BooleanExpression::Value(true) => consequence.into_inner(),
+                    BooleanExpression::Value(false) => alternative.into_inner(),
+                    c => TupleExpressionInner::IfElse(box c, box consequence, box alternative),
+                }
+            }
+            TupleExpressionInner::Member(box s, m) => {
+                let s = self.fold_struct_expression(s);
+
+                let members = match s.get_type() {
+                    Type::Struct(members) => members,
+                    _ => unreachable!(),
+                };
+
+                match s.into_inner() {
+                    StructExpressionInner::Value(v) => {
+                        match members.iter().zip(v).find(|(id, _)| id.0 == m).unwrap().1 {
+                            TypedExpression::Tuple(t) => t.into_inner(),
+                            _ => unreachable!(),
+                        }
+                    }
+                    inner => TupleExpressionInner::Member(box inner.annotate(members), m),
+                }
+            }
+            TupleExpressionInner::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::Tuple(e) => e.into_inner(),
+                        _ => unreachable!(),
+                    },
+                    inner => TupleExpressionInner::Element(box inner.annotate(elements), index),
+                }
+            }
+            e => fold_tuple_expression_inner(self, ty, e),
+        }
+    }
+
     fn fold_boolean_expression(
         &mut self,
         e: BooleanExpression<'ast, T>,
@@ -587,6 +989,19 @@ impl<'ast, T: Field> Folder<'ast, T> for Propagator<'ast, T> {
                     inner => BooleanExpression::Member(box inner.annotate(members), m),
                 }
             }
+            BooleanExpression::Element(box t, index) => {
+                let t = self.fold_tuple_expression(t);
+
+                let elements = t.ty().clone();
+
+                match t.into_inner() {
+                    TupleExpressionInner::Value(v) => match v.into_iter().nth(index).unwrap() {
+                        TypedExpression::Boolean(e) => e,
+                        _ => unreachable!(),
+                    },
+                    inner => BooleanExpression::Element(box inner.annotate(elements), index),
+                }
+            }
             e => fold_boolean_expression(self, e),
         }
     }
@@ -720,6 +1135,113 @@ mod tests {
             }
         }
 
+        #[cfg(test)]
+        mod uint {
+            use super::*;
+
+            #[test]
+            fn add() {
+                let e = UExpressionInner::Add(
+                    box UExpressionInner::Value(250).annotate(8),
+                    box UExpressionInner::Value(10).annotate(8),
+                )
+                .annotate(8);
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_uint_expression(e),
+                    UExpressionInner::Value(4).annotate(8)
+                );
+            }
+
+            #[test]
+            fn sub() {
+                let e = UExpressionInner::Sub(
+                    box UExpressionInner::Value(2).annotate(16),
+                    box UExpressionInner::Value(3).annotate(16),
+                )
+                .annotate(16);
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_uint_expression(e),
+                    UExpressionInner::Value(0xffff).annotate(16)
+                );
+            }
+
+            #[test]
+            fn not() {
+                let e = UExpressionInner::Not(box UExpressionInner::Value(0x0f).annotate(8))
+                    .annotate(8);
+
+                assert_eq!(
+                    Propagator::<FieldPrime>::new().fold_uint_expression(e),
+                    UExpressionInner::Value(0xf0).annotate(8)
+                );
+            }
+
+            #[test]
+            fn left_shift() {
+                let e = UExpressionInner::LeftShift(
+                    box UExpressionInner::Value(0x81).annotate(8),
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                )
+                .annotate(8);
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UExpressionInner::Value(0x02).annotate(8)
+                );
+            }
+
+            #[test]
+            fn large_shift() {
+                // 2**70 does not fit a machine integer
+                let by = FieldPrime::try_from_dec_str("1180591620717411303424").unwrap();
+
+                let e = UExpressionInner::LeftShift(
+                    box UExpressionInner::Value(0x81).annotate(8),
+                    box FieldElementExpression::Number(by.clone()),
+                )
+                .annotate(8);
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UExpressionInner::Value(0).annotate(8)
+                );
+
+                let e = UExpressionInner::RightShift(
+                    box UExpressionInner::Value(0x81).annotate(8),
+                    box FieldElementExpression::Number(by),
+                )
+                .annotate(8);
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UExpressionInner::Value(0).annotate(8)
+                );
+            }
+
+            #[test]
+            fn from_field() {
+                let e = UExpressionInner::FromField(box FieldElementExpression::Number(
+                    FieldPrime::from(42),
+                ))
+                .annotate(8);
+
+                assert_eq!(
+                    Propagator::new().fold_uint_expression(e),
+                    UExpressionInner::Value(42).annotate(8)
+                );
+
+                // out of range values are left for the runtime check
+                let e = UExpressionInner::FromField(box FieldElementExpression::Number(
+                    FieldPrime::from(256),
+                ))
+                .annotate(8);
+
+                assert_eq!(Propagator::new().fold_uint_expression(e.clone()), e);
+            }
+        }
+
         #[cfg(test)]
         mod boolean {
             use super::*;
@@ -0,0 +1,66 @@
+//! Module checking that shifts are by amounts known at compile time, once loops are unrolled and constants propagated
+//!
+//! @file shift_check.rs
+//! @date 2020
+
+use crate::static_analysis::Error;
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use zokrates_field::field::Field;
+
+pub struct ShiftChecker {
+    /// the location of the statement we're visiting
+    span: Option<SourceSpan>,
+    errors: Vec<Error>,
+}
+
+impl ShiftChecker {
+    fn new() -> Self {
+        ShiftChecker {
+            span: None,
+            errors: vec![],
+        }
+    }
+
+    pub fn check<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, Vec<Error>> {
+        let mut checker = ShiftChecker::new();
+        let p = checker.fold_program(p);
+
+        match checker.errors.len() {
+            0 => Ok(p),
+            _ => Err(checker.errors),
+        }
+    }
+
+    fn check_amount<T: Field>(&mut self, by: &FieldElementExpression<T>) {
+        match by {
+            FieldElementExpression::Number(_) => {}
+            by => self.errors.push(Error {
+                span: self.span.clone(),
+                message: format!("Shift amounts should be constants, found {}", by),
+            }),
+        }
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for ShiftChecker {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        self.span = s.span();
+        fold_statement(self, s)
+    }
+
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: usize,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        match e {
+            UExpressionInner::LeftShift(_, ref by) | UExpressionInner::RightShift(_, ref by) => {
+                self.check_amount(by)
+            }
+            _ => {}
+        }
+
+        fold_uint_expression_inner(self, bitwidth, e)
+    }
+}
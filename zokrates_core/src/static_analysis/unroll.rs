@@ -1,4 +1,4 @@
-//! Module containing SSA reduction, including for-loop unrolling
+//! Module containing SSA reduction, including for-loop unrolling and the lowering of if/else blocks
 //!
 //! @file unroll.rs
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
@@ -11,14 +11,39 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use zokrates_field::field::Field;
 
+/// The name of the variables holding the conditions under which branches of if/else blocks
+/// are taken
+const GUARD: &str = "#GUARD";
+/// The name of the variables holding the upper bounds of loops which are only known at execution
+/// time
+const BOUND: &str = "#BOUND";
+
 pub struct Unroller<'ast> {
     substitution: HashMap<Identifier<'ast>, usize>,
+    /// The branches of if/else blocks we are currently in, innermost last
+    branches: Vec<Branch<'ast>>,
+}
+
+/// A branch of an if/else block being lowered
+struct Branch<'ast> {
+    /// The variable holding the condition under which this branch is taken, including the
+    /// conditions of enclosing branches
+    guard: Variable<'ast>,
+    /// The variables declared in this branch, which are not visible outside of it
+    locals: HashSet<Identifier<'ast>>,
+}
+
+impl<'ast> Branch<'ast> {
+    fn guard<T: Field>(&self) -> BooleanExpression<'ast, T> {
+        BooleanExpression::Identifier(self.guard.id.clone())
+    }
 }
 
 impl<'ast> Unroller<'ast> {
     fn new() -> Self {
         Unroller {
             substitution: HashMap::new(),
+            branches: vec![],
         }
     }
 
@@ -26,9 +51,8 @@ impl<'ast> Unroller<'ast> {
         let res = match self.substitution.get(&v.id) {
             Some(i) => Variable {
                 id: Identifier {
-                    id: v.id.id,
                     version: i + 1,
-                    stack: vec![],
+                    ..v.id.clone()
                 },
                 ..v
             },
@@ -45,6 +69,114 @@ impl<'ast> Unroller<'ast> {
         Unroller::new().fold_program(p)
     }
 
+    /// Whether assigning to `v` in the current branch must be conditional, which is the case for
+    /// variables which were defined before entering the branch
+    fn is_guarded(&self, v: &Variable<'ast>) -> bool {
+        match self.branches.last() {
+            Some(branch) => {
+                !branch.locals.contains(&v.id) && self.substitution.contains_key(&v.id)
+            }
+            None => false,
+        }
+    }
+
+    /// Select `new` if the current branch is taken, `current` otherwise
+    fn guard<T: Field>(
+        &self,
+        new: TypedExpression<'ast, T>,
+        current: TypedExpression<'ast, T>,
+    ) -> TypedExpression<'ast, T> {
+        match self.branches.last() {
+            Some(branch) => if_else(branch.guard(), new, current),
+            None => new,
+        }
+    }
+
+    /// Make a constraint only hold if the current branch is taken
+    fn guard_statement<T: Field>(&self, s: TypedStatement<'ast, T>) -> TypedStatement<'ast, T> {
+        match self.branches.last() {
+            Some(branch) => match s {
+                TypedStatement::Condition(lhs, rhs, span) => {
+                    TypedStatement::Condition(if_else(branch.guard(), lhs, rhs.clone()), rhs, span)
+                }
+                TypedStatement::Assertion(e, metadata) => TypedStatement::Assertion(
+                    BooleanExpression::Or(box BooleanExpression::Not(box branch.guard()), box e),
+                    metadata,
+                ),
+                s => s,
+            },
+            None => s,
+        }
+    }
+
+    /// Unroll the statements of a branch which is taken if `guard` holds, defining `guard` first
+    fn fold_branch<T: Field>(
+        &mut self,
+        guard: BooleanExpression<'ast, T>,
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        // the guard is stored in a variable so that it is only computed once
+        let guard_variable = self.issue_next_ssa_variable(Variable::boolean(GUARD.into()));
+
+        let mut res = vec![TypedStatement::Definition(
+            TypedAssignee::Identifier(guard_variable.clone()),
+            guard.into(),
+            None,
+        )];
+
+        self.branches.push(Branch {
+            guard: guard_variable,
+            locals: HashSet::new(),
+        });
+
+        for s in statements {
+            res.extend(self.fold_statement(s));
+        }
+
+        self.branches.pop();
+
+        res
+    }
+
+    /// Lower an if/else block with an already folded `condition` to conditional assignments
+    fn fold_if_else<T: Field>(
+        &mut self,
+        condition: BooleanExpression<'ast, T>,
+        consequence: Vec<TypedStatement<'ast, T>>,
+        alternative: Vec<TypedStatement<'ast, T>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        let outer_guard = self.branches.last().map(|branch| branch.guard());
+
+        let consequence_guard = match outer_guard.clone() {
+            Some(outer_guard) => BooleanExpression::And(box outer_guard, box condition),
+            None => condition,
+        };
+
+        let mut res = self.fold_branch(consequence_guard, consequence);
+
+        if !alternative.is_empty() {
+            // the alternative is taken if the enclosing branch is and the consequence isn't
+            let consequence_guard = match &res[0] {
+                TypedStatement::Definition(TypedAssignee::Identifier(v), ..) => {
+                    BooleanExpression::Identifier(v.id.clone())
+                }
+                _ => unreachable!("a branch should start with the definition of its guard"),
+            };
+
+            let alternative_guard = match outer_guard {
+                Some(outer_guard) => BooleanExpression::And(
+                    box outer_guard,
+                    box BooleanExpression::Not(box consequence_guard),
+                ),
+                None => BooleanExpression::Not(box consequence_guard),
+            };
+
+            res.extend(self.fold_branch(alternative_guard, alternative));
+        }
+
+        res
+    }
+
     fn choose_many<T: Field>(
         base: TypedExpression<'ast, T>,
         indices: Vec<Access<'ast, T>>,
@@ -72,6 +204,7 @@ impl<'ast> Unroller<'ast> {
                                 )
                                 .into(),
                                 BooleanExpression::Value(true).into(),
+                                None,
                             ));
 
                             ArrayExpressionInner::Value(
@@ -185,6 +318,60 @@ impl<'ast> Unroller<'ast> {
                                             ),
                                         )
                                         .into(),
+                                        Type::Uint(..) => UExpression::if_else(
+                                            BooleanExpression::Eq(
+                                                box FieldElementExpression::Number(T::from(i)),
+                                                box head.clone(),
+                                            ),
+                                            match Self::choose_many(
+                                                UExpression::select(
+                                                    base.clone(),
+                                                    FieldElementExpression::Number(T::from(i)),
+                                                )
+                                                .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            ) {
+                                                TypedExpression::Uint(e) => e,
+                                                e => unreachable!(
+                                            "the interior was expected to be a uint, was {}",
+                                            e.get_type()
+                                        ),
+                                            },
+                                            UExpression::select(
+                                                base.clone(),
+                                                FieldElementExpression::Number(T::from(i)),
+                                            ),
+                                        )
+                                        .into(),
+                                        Type::Tuple(..) => TupleExpression::if_else(
+                                            BooleanExpression::Eq(
+                                                box FieldElementExpression::Number(T::from(i)),
+                                                box head.clone(),
+                                            ),
+                                            match Self::choose_many(
+                                                TupleExpression::select(
+                                                    base.clone(),
+                                                    FieldElementExpression::Number(T::from(i)),
+                                                )
+                                                .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            ) {
+                                                TypedExpression::Tuple(e) => e,
+                                                e => unreachable!(
+                                            "the interior was expected to be a tuple, was {}",
+                                            e.get_type()
+                                        ),
+                                            },
+                                            TupleExpression::select(
+                                                base.clone(),
+                                                FieldElementExpression::Number(T::from(i)),
+                                            ),
+                                        )
+                                        .into(),
                                     })
                                     .collect(),
                             )
@@ -243,6 +430,19 @@ impl<'ast> Unroller<'ast> {
                                                 .into()
                                         }
                                     }
+                                    Type::Uint(..) => {
+                                        if id == head {
+                                            Self::choose_many(
+                                                UExpression::member(base.clone(), head.clone())
+                                                    .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            UExpression::member(base.clone(), id.clone()).into()
+                                        }
+                                    }
                                     Type::Array(..) => {
                                         if id == head {
                                             Self::choose_many(
@@ -273,6 +473,19 @@ impl<'ast> Unroller<'ast> {
                                                 .into()
                                         }
                                     }
+                                    Type::Tuple(..) => {
+                                        if id == head {
+                                            Self::choose_many(
+                                                TupleExpression::member(base.clone(), head.clone())
+                                                    .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            TupleExpression::member(base.clone(), id.clone()).into()
+                                        }
+                                    }
                                 })
                                 .collect(),
                         )
@@ -310,33 +523,72 @@ fn linear<'ast, T: Field>(a: TypedAssignee<'ast, T>) -> (Variable, Vec<Access<'a
     }
 }
 
+/// Build `if condition then consequence else alternative fi` for two expressions of the same type
+fn if_else<'ast, T: Field>(
+    condition: BooleanExpression<'ast, T>,
+    consequence: TypedExpression<'ast, T>,
+    alternative: TypedExpression<'ast, T>,
+) -> TypedExpression<'ast, T> {
+    match (consequence, alternative) {
+        (TypedExpression::FieldElement(c), TypedExpression::FieldElement(a)) => {
+            FieldElementExpression::if_else(condition, c, a).into()
+        }
+        (TypedExpression::Boolean(c), TypedExpression::Boolean(a)) => {
+            BooleanExpression::if_else(condition, c, a).into()
+        }
+        (TypedExpression::Uint(c), TypedExpression::Uint(a)) => {
+            UExpression::if_else(condition, c, a).into()
+        }
+        (TypedExpression::Array(c), TypedExpression::Array(a)) => {
+            ArrayExpression::if_else(condition, c, a).into()
+        }
+        (TypedExpression::Struct(c), TypedExpression::Struct(a)) => {
+            StructExpression::if_else(condition, c, a).into()
+        }
+        (TypedExpression::Tuple(c), TypedExpression::Tuple(a)) => {
+            TupleExpression::if_else(condition, c, a).into()
+        }
+        (c, a) => unreachable!(
+            "both sides of a conditional should have the same type, found {} and {}",
+            c.get_type(),
+            a.get_type()
+        ),
+    }
+}
+
+/// The expression reading variable `v`
+fn identifier_expression<'ast, T: Field>(v: Variable<'ast>) -> TypedExpression<'ast, T> {
+    match v.get_type() {
+        Type::FieldElement => FieldElementExpression::Identifier(v.id).into(),
+        Type::Boolean => BooleanExpression::Identifier(v.id).into(),
+        Type::Uint(bitwidth) => UExpressionInner::Identifier(v.id).annotate(bitwidth).into(),
+        Type::Array(box ty, size) => ArrayExpressionInner::Identifier(v.id)
+            .annotate(ty, size)
+            .into(),
+        Type::Struct(members) => StructExpressionInner::Identifier(v.id)
+            .annotate(members)
+            .into(),
+        Type::Tuple(elements) => TupleExpressionInner::Identifier(v.id)
+            .annotate(elements)
+            .into(),
+    }
+}
+
 impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
     fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
         match s {
-            TypedStatement::Declaration(_) => vec![],
-            TypedStatement::Definition(assignee, expr) => {
+            TypedStatement::Declaration(v) => {
+                if let Some(branch) = self.branches.last_mut() {
+                    branch.locals.insert(v.id);
+                }
+                vec![]
+            }
+            TypedStatement::Definition(assignee, expr, span) => {
                 let expr = self.fold_expression(expr);
 
                 let (variable, indices) = linear(assignee);
 
-                let base = match variable.get_type() {
-                    Type::FieldElement => {
-                        FieldElementExpression::Identifier(variable.id.clone().into()).into()
-                    }
-                    Type::Boolean => {
-                        BooleanExpression::Identifier(variable.id.clone().into()).into()
-                    }
-                    Type::Array(box ty, size) => {
-                        ArrayExpressionInner::Identifier(variable.id.clone().into())
-                            .annotate(ty, size)
-                            .into()
-                    }
-                    Type::Struct(members) => {
-                        StructExpressionInner::Identifier(variable.id.clone().into())
-                            .annotate(members)
-                            .into()
-                    }
-                };
+                let base = identifier_expression(variable.clone());
 
                 let base = self.fold_expression(base);
                 let indices = indices
@@ -348,24 +600,121 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                     .collect();
 
                 let mut range_checks = HashSet::new();
-                let e = Self::choose_many(base, indices, expr, &mut range_checks);
+                let e = Self::choose_many(base.clone(), indices, expr, &mut range_checks);
+
+                // inside a branch, the variable keeps its value if the branch is not taken
+                let e = match self.is_guarded(&variable) {
+                    true => self.guard(e, base),
+                    false => e,
+                };
 
                 range_checks
+                    .into_iter()
+                    // range checks originate from the definition they guard
+                    .map(|s| match s {
+                        TypedStatement::Condition(lhs, rhs, _) => {
+                            TypedStatement::Condition(lhs, rhs, span.clone())
+                        }
+                        s => s,
+                    })
+                    .map(|s| self.guard_statement(s))
+                    .collect::<Vec<_>>()
                     .into_iter()
                     .chain(std::iter::once(TypedStatement::Definition(
                         TypedAssignee::Identifier(self.issue_next_ssa_variable(variable)),
                         e,
+                        span.clone(),
                     )))
                     .collect()
             }
-            TypedStatement::MultipleDefinition(variables, exprs) => {
+            TypedStatement::MultipleDefinition(variables, exprs, span) => {
                 let exprs = self.fold_expression_list(exprs);
-                let variables = variables
-                    .into_iter()
-                    .map(|v| self.issue_next_ssa_variable(v))
+
+                // inside a branch, we need the values of the variables before the definition to
+                // keep them if the branch is not taken
+                let current: Vec<_> = variables
+                    .iter()
+                    .map(|v| match self.is_guarded(v) {
+                        true => Some(self.fold_expression(identifier_expression(v.clone()))),
+                        false => None,
+                    })
                     .collect();
 
-                vec![TypedStatement::MultipleDefinition(variables, exprs)]
+                let defined: Vec<_> = variables
+                    .iter()
+                    .map(|v| self.issue_next_ssa_variable(v.clone()))
+                    .collect();
+
+                let mut res = vec![TypedStatement::MultipleDefinition(
+                    defined.clone(),
+                    exprs,
+                    span.clone(),
+                )];
+
+                for ((v, d), current) in variables.into_iter().zip(defined).zip(current) {
+                    if let Some(current) = current {
+                        let e = self.guard(identifier_expression(d), current);
+                        res.push(TypedStatement::Definition(
+                            TypedAssignee::Identifier(self.issue_next_ssa_variable(v)),
+                            e,
+                            span.clone(),
+                        ));
+                    }
+                }
+
+                res
+            }
+            TypedStatement::IfElse(condition, consequence, alternative) => {
+                let condition = self.fold_boolean_expression(condition);
+                self.fold_if_else(condition, consequence, alternative)
+            }
+            TypedStatement::Condition(..) | TypedStatement::Assertion(..) => {
+                fold_statement(self, s)
+                    .into_iter()
+                    .map(|s| self.guard_statement(s))
+                    .collect()
+            }
+            TypedStatement::BoundedFor(v, from, to, max, stats) => {
+                let to = self.fold_field_expression(to);
+
+                // the bound is stored in a variable so that it is evaluated once, before the loop
+                let bound = self.issue_next_ssa_variable(Variable::field_element(BOUND.into()));
+                let bound_expression = FieldElementExpression::Identifier(bound.id.clone());
+
+                let mut res = vec![
+                    TypedStatement::Definition(TypedAssignee::Identifier(bound), to.into(), None),
+                    // the loop is unrolled up to the maximum, so the bound cannot exceed it
+                    self.guard_statement(TypedStatement::Condition(
+                        BooleanExpression::Le(
+                            box bound_expression.clone(),
+                            box FieldElementExpression::Number(max.clone()),
+                        )
+                        .into(),
+                        BooleanExpression::Value(true).into(),
+                        None,
+                    )),
+                ];
+
+                let mut current = from;
+                while current < max {
+                    res.extend(self.fold_statement(TypedStatement::Declaration(v.clone())));
+                    res.extend(self.fold_statement(TypedStatement::Definition(
+                        TypedAssignee::Identifier(v.clone()),
+                        FieldElementExpression::Number(current.clone()).into(),
+                        None,
+                    )));
+
+                    // the body of an iteration is only executed if the index is below the bound
+                    let condition = BooleanExpression::Lt(
+                        box FieldElementExpression::Number(current.clone()),
+                        box bound_expression.clone(),
+                    );
+                    res.extend(self.fold_if_else(condition, stats.clone(), vec![]));
+
+                    current = T::one() + &current;
+                }
+
+                res
             }
             TypedStatement::For(v, from, to, stats) => {
                 let mut values: Vec<T> = vec![];
@@ -384,6 +733,7 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
                                 TypedStatement::Definition(
                                     TypedAssignee::Identifier(v.clone()),
                                     FieldElementExpression::Number(index).into(),
+                                    None,
                                 ),
                             ],
                             stats.clone(),
@@ -410,6 +760,35 @@ impl<'ast, T: Field> Folder<'ast, T> for Unroller<'ast> {
         fold_function(self, f)
     }
 
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        match e {
+            FieldElementExpression::Div(box left, box right) => {
+                let left = self.fold_field_expression(left);
+                let right = self.fold_field_expression(right);
+
+                // dividing requires the divisor to be invertible, which must only hold if the
+                // current branch is taken: otherwise we divide by one
+                let right = match (self.branches.last(), right) {
+                    (Some(_), FieldElementExpression::Number(n)) if n != T::zero() => {
+                        FieldElementExpression::Number(n)
+                    }
+                    (Some(branch), right) => FieldElementExpression::if_else(
+                        branch.guard(),
+                        right,
+                        FieldElementExpression::Number(T::one()),
+                    ),
+                    (None, right) => right,
+                };
+
+                FieldElementExpression::Div(box left, box right)
+            }
+            e => fold_field_expression(self, e),
+        }
+    }
+
     fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
         Identifier {
             version: self.substitution.get(&n).unwrap_or(&0).clone(),
@@ -690,6 +1069,7 @@ mod tests {
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_element("foo".into())),
                         FieldElementExpression::Identifier("i".into()).into(),
+                        None,
                     ),
                 ],
             );
@@ -700,36 +1080,42 @@ mod tests {
                         Identifier::from("i").version(0),
                     )),
                     FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("foo").version(0),
                     )),
                     FieldElementExpression::Identifier(Identifier::from("i").version(0)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("i").version(1),
                     )),
                     FieldElementExpression::Number(FieldPrime::from(3)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("foo").version(1),
                     )),
                     FieldElementExpression::Identifier(Identifier::from("i").version(1)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("i").version(2),
                     )),
                     FieldElementExpression::Number(FieldPrime::from(4)).into(),
+                    None,
                 ),
                 TypedStatement::Definition(
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("foo").version(2),
                     )),
                     FieldElementExpression::Identifier(Identifier::from("i").version(2)).into(),
+                    None,
                 ),
             ];
 
@@ -759,6 +1145,7 @@ mod tests {
             let s = TypedStatement::Definition(
                 TypedAssignee::Identifier(Variable::field_element("a".into())),
                 FieldElementExpression::Number(FieldPrime::from(5)).into(),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -766,13 +1153,15 @@ mod tests {
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("a").version(0)
                     )),
-                    FieldElementExpression::Number(FieldPrime::from(5)).into()
+                    FieldElementExpression::Number(FieldPrime::from(5)).into(),
+                    None,
                 )]
             );
 
             let s = TypedStatement::Definition(
                 TypedAssignee::Identifier(Variable::field_element("a".into())),
                 FieldElementExpression::Number(FieldPrime::from(6)).into(),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -780,7 +1169,8 @@ mod tests {
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("a").version(1)
                     )),
-                    FieldElementExpression::Number(FieldPrime::from(6)).into()
+                    FieldElementExpression::Number(FieldPrime::from(6)).into(),
+                    None,
                 )]
             );
 
@@ -811,6 +1201,7 @@ mod tests {
             let s = TypedStatement::Definition(
                 TypedAssignee::Identifier(Variable::field_element("a".into())),
                 FieldElementExpression::Number(FieldPrime::from(5)).into(),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -818,7 +1209,8 @@ mod tests {
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("a").version(0)
                     )),
-                    FieldElementExpression::Number(FieldPrime::from(5)).into()
+                    FieldElementExpression::Number(FieldPrime::from(5)).into(),
+                    None,
                 )]
             );
 
@@ -829,6 +1221,7 @@ mod tests {
                     box FieldElementExpression::Number(FieldPrime::from(1)),
                 )
                 .into(),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -840,7 +1233,8 @@ mod tests {
                         box FieldElementExpression::Identifier(Identifier::from("a").version(0)),
                         box FieldElementExpression::Number(FieldPrime::from(1))
                     )
-                    .into()
+                    .into(),
+                    None,
                 )]
             );
         }
@@ -866,6 +1260,7 @@ mod tests {
             let s = TypedStatement::Definition(
                 TypedAssignee::Identifier(Variable::field_element("a".into())),
                 FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -873,7 +1268,8 @@ mod tests {
                     TypedAssignee::Identifier(Variable::field_element(
                         Identifier::from("a").version(0)
                     )),
-                    FieldElementExpression::Number(FieldPrime::from(2)).into()
+                    FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                    None,
                 )]
             );
 
@@ -888,6 +1284,7 @@ mod tests {
                     vec![FieldElementExpression::Identifier("a".into()).into()],
                     vec![Type::FieldElement],
                 ),
+                None,
             );
             assert_eq!(
                 u.fold_statement(s),
@@ -904,7 +1301,8 @@ mod tests {
                                 .into()
                         ],
                         vec![Type::FieldElement],
-                    )
+                    ),
+                    None,
                 )]
             );
         }
@@ -932,6 +1330,7 @@ mod tests {
                 ])
                 .annotate(Type::FieldElement, 2)
                 .into(),
+                None,
             );
 
             assert_eq!(
@@ -946,7 +1345,8 @@ mod tests {
                         FieldElementExpression::Number(FieldPrime::from(1)).into()
                     ])
                     .annotate(Type::FieldElement, 2)
-                    .into()
+                    .into(),
+                    None,
                 )]
             );
 
@@ -956,6 +1356,7 @@ mod tests {
                     box FieldElementExpression::Number(FieldPrime::from(1)),
                 ),
                 FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                None,
             );
 
             assert_eq!(
@@ -967,7 +1368,8 @@ mod tests {
                             box FieldElementExpression::Number(FieldPrime::from(2))
                         )
                         .into(),
-                        BooleanExpression::Value(true).into()
+                        BooleanExpression::Value(true).into(),
+                        None,
                     ),
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::field_array(
@@ -1007,7 +1409,8 @@ mod tests {
                             .into(),
                         ])
                         .annotate(Type::FieldElement, 2)
-                        .into()
+                        .into(),
+                        None,
                     )
                 ]
             );
@@ -1052,6 +1455,7 @@ mod tests {
                 ])
                 .annotate(Type::array(Type::FieldElement, 2), 2)
                 .into(),
+                None,
             );
 
             assert_eq!(
@@ -1077,6 +1481,7 @@ mod tests {
                     ])
                     .annotate(Type::array(Type::FieldElement, 2), 2)
                     .into(),
+                    None,
                 )]
             );
 
@@ -1094,6 +1499,7 @@ mod tests {
                 ])
                 .annotate(Type::FieldElement, 2)
                 .into(),
+                None,
             );
 
             assert_eq!(
@@ -1105,7 +1511,8 @@ mod tests {
                             box FieldElementExpression::Number(FieldPrime::from(2))
                         )
                         .into(),
-                        BooleanExpression::Value(true).into()
+                        BooleanExpression::Value(true).into(),
+                        None,
                     ),
                     TypedStatement::Definition(
                         TypedAssignee::Identifier(Variable::with_id_and_type(
@@ -1159,10 +1566,348 @@ mod tests {
                             .into(),
                         ])
                         .annotate(Type::array(Type::FieldElement, 2), 2)
-                        .into()
+                        .into(),
+                        None,
                     )
                 ]
             );
         }
+
+        #[test]
+        fn if_else() {
+            use crate::parser::Position;
+
+            // field a = 1
+            // if a == 1 then
+            //     a = 2
+            //     field b = 3
+            //     assert(a == b)
+            // else
+            //     a = 4
+            // fi
+
+            // should be turned into
+            // a_0 = 1
+            // #GUARD_0 = a_0 == 1
+            // a_1 = #GUARD_0 ? 2 : a_0
+            // b_0 = 3
+            // assert(!#GUARD_0 || a_1 == b_0)
+            // #GUARD_1 = !#GUARD_0
+            // a_2 = #GUARD_1 ? 4 : a_1
+
+            let metadata = AssertionMetadata {
+                span: SourceSpan {
+                    module: String::from("main"),
+                    from: Position::mock(),
+                    to: Position::mock(),
+                    stack: vec![],
+                },
+                message: None,
+            };
+
+            let mut u = Unroller::new();
+
+            let s: TypedStatement<FieldPrime> =
+                TypedStatement::Declaration(Variable::field_element("a".into()));
+            assert_eq!(u.fold_statement(s), vec![]);
+
+            let s = TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a".into())),
+                FieldElementExpression::Number(FieldPrime::from(1)).into(),
+                None,
+            );
+            u.fold_statement(s);
+
+            let s = TypedStatement::IfElse(
+                BooleanExpression::Eq(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Number(FieldPrime::from(1)),
+                ),
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("a".into())),
+                        FieldElementExpression::Number(FieldPrime::from(2)).into(),
+                        None,
+                    ),
+                    TypedStatement::Declaration(Variable::field_element("b".into())),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("b".into())),
+                        FieldElementExpression::Number(FieldPrime::from(3)).into(),
+                        None,
+                    ),
+                    TypedStatement::Assertion(
+                        BooleanExpression::Eq(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("b".into()),
+                        ),
+                        metadata.clone(),
+                    ),
+                ],
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a".into())),
+                    FieldElementExpression::Number(FieldPrime::from(4)).into(),
+                    None,
+                )],
+            );
+
+            let guard = |version| {
+                BooleanExpression::Identifier(Identifier::from(GUARD).version(version))
+            };
+            let a = |version| {
+                FieldElementExpression::Identifier(Identifier::from("a").version(version))
+            };
+
+            assert_eq!(
+                u.fold_statement(s),
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::boolean(GUARD.into())),
+                        BooleanExpression::Eq(
+                            box a(0),
+                            box FieldElementExpression::Number(FieldPrime::from(1)),
+                        )
+                        .into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("a").version(1)
+                        )),
+                        FieldElementExpression::if_else(
+                            guard(0),
+                            FieldElementExpression::Number(FieldPrime::from(2)),
+                            a(0),
+                        )
+                        .into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element("b".into())),
+                        FieldElementExpression::Number(FieldPrime::from(3)).into(),
+                        None,
+                    ),
+                    TypedStatement::Assertion(
+                        BooleanExpression::Or(
+                            box BooleanExpression::Not(box guard(0)),
+                            box BooleanExpression::Eq(
+                                box a(1),
+                                box FieldElementExpression::Identifier(
+                                    Identifier::from("b").version(0)
+                                ),
+                            ),
+                        ),
+                        metadata,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::boolean(
+                            Identifier::from(GUARD).version(1)
+                        )),
+                        BooleanExpression::Not(box guard(0)).into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("a").version(2)
+                        )),
+                        FieldElementExpression::if_else(
+                            guard(1),
+                            FieldElementExpression::Number(FieldPrime::from(4)),
+                            a(1),
+                        )
+                        .into(),
+                        None,
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn division_in_branch() {
+            // field a = 0
+            // if c then
+            //     a = b / d
+            // fi
+
+            // should be turned into
+            // a_0 = 0
+            // #GUARD_0 = c
+            // a_1 = #GUARD_0 ? b / (#GUARD_0 ? d : 1) : a_0
+
+            let mut u = Unroller::new();
+
+            let s: TypedStatement<FieldPrime> =
+                TypedStatement::Declaration(Variable::field_element("a".into()));
+            assert_eq!(u.fold_statement(s), vec![]);
+
+            let s = TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a".into())),
+                FieldElementExpression::Number(FieldPrime::from(0)).into(),
+                None,
+            );
+            u.fold_statement(s);
+
+            let s = TypedStatement::IfElse(
+                BooleanExpression::Identifier("c".into()),
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a".into())),
+                    FieldElementExpression::Div(
+                        box FieldElementExpression::Identifier("b".into()),
+                        box FieldElementExpression::Identifier("d".into()),
+                    )
+                    .into(),
+                    None,
+                )],
+                vec![],
+            );
+
+            let guard = BooleanExpression::Identifier(Identifier::from(GUARD).version(0));
+
+            assert_eq!(
+                u.fold_statement(s),
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::boolean(GUARD.into())),
+                        BooleanExpression::Identifier("c".into()).into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("a").version(1)
+                        )),
+                        FieldElementExpression::if_else(
+                            guard.clone(),
+                            FieldElementExpression::Div(
+                                box FieldElementExpression::Identifier("b".into()),
+                                box FieldElementExpression::if_else(
+                                    guard,
+                                    FieldElementExpression::Identifier("d".into()),
+                                    FieldElementExpression::Number(FieldPrime::from(1)),
+                                ),
+                            ),
+                            FieldElementExpression::Identifier(Identifier::from("a").version(0)),
+                        )
+                        .into(),
+                        None,
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn bounded_for() {
+            // field a = 0
+            // for field i in 0..n max 2 do
+            //     a = a + i
+            // endfor
+
+            // should be turned into
+            // a_0 = 0
+            // #BOUND_0 = n
+            // #BOUND_0 <= 2 == true
+            // i_0 = 0
+            // #GUARD_0 = 0 < #BOUND_0
+            // a_1 = #GUARD_0 ? a_0 + i_0 : a_0
+            // i_1 = 1
+            // #GUARD_1 = 1 < #BOUND_0
+            // a_2 = #GUARD_1 ? a_1 + i_1 : a_1
+
+            let mut u = Unroller::new();
+
+            let s: TypedStatement<FieldPrime> =
+                TypedStatement::Declaration(Variable::field_element("a".into()));
+            assert_eq!(u.fold_statement(s), vec![]);
+
+            let s = TypedStatement::Definition(
+                TypedAssignee::Identifier(Variable::field_element("a".into())),
+                FieldElementExpression::Number(FieldPrime::from(0)).into(),
+                None,
+            );
+            u.fold_statement(s);
+
+            let s = TypedStatement::BoundedFor(
+                Variable::field_element("i".into()),
+                FieldPrime::from(0),
+                FieldElementExpression::Identifier("n".into()),
+                FieldPrime::from(2),
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element("a".into())),
+                    FieldElementExpression::Add(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Identifier("i".into()),
+                    )
+                    .into(),
+                    None,
+                )],
+            );
+
+            let bound = FieldElementExpression::Identifier(Identifier::from(BOUND));
+            let guard = |version| {
+                BooleanExpression::Identifier(Identifier::from(GUARD).version(version))
+            };
+            let field = |id, version| {
+                FieldElementExpression::Identifier(Identifier::from(id).version(version))
+            };
+
+            let iteration = |index: usize| {
+                vec![
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("i").version(index),
+                        )),
+                        FieldElementExpression::Number(FieldPrime::from(index)).into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::boolean(
+                            Identifier::from(GUARD).version(index),
+                        )),
+                        BooleanExpression::Lt(
+                            box FieldElementExpression::Number(FieldPrime::from(index)),
+                            box bound.clone(),
+                        )
+                        .into(),
+                        None,
+                    ),
+                    TypedStatement::Definition(
+                        TypedAssignee::Identifier(Variable::field_element(
+                            Identifier::from("a").version(index + 1),
+                        )),
+                        FieldElementExpression::if_else(
+                            guard(index),
+                            FieldElementExpression::Add(
+                                box field("a", index),
+                                box field("i", index),
+                            ),
+                            field("a", index),
+                        )
+                        .into(),
+                        None,
+                    ),
+                ]
+            };
+
+            let expected = vec![
+                TypedStatement::Definition(
+                    TypedAssignee::Identifier(Variable::field_element(BOUND.into())),
+                    FieldElementExpression::Identifier("n".into()).into(),
+                    None,
+                ),
+                TypedStatement::Condition(
+                    BooleanExpression::Le(
+                        box bound.clone(),
+                        box FieldElementExpression::Number(FieldPrime::from(2)),
+                    )
+                    .into(),
+                    BooleanExpression::Value(true).into(),
+                    None,
+                ),
+            ]
+            .into_iter()
+            .chain(iteration(0))
+            .chain(iteration(1))
+            .collect::<Vec<_>>();
+
+            assert_eq!(u.fold_statement(s), expected);
+        }
     }
 }
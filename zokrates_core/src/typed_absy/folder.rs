@@ -60,8 +60,10 @@ pub trait Folder<'ast, T: Field>: Sized {
         match e {
             TypedExpression::FieldElement(e) => self.fold_field_expression(e).into(),
             TypedExpression::Boolean(e) => self.fold_boolean_expression(e).into(),
+            TypedExpression::Uint(e) => self.fold_uint_expression(e).into(),
             TypedExpression::Array(e) => self.fold_array_expression(e).into(),
             TypedExpression::Struct(e) => self.fold_struct_expression(e).into(),
+            TypedExpression::Tuple(e) => self.fold_tuple_expression(e).into(),
         }
     }
 
@@ -76,6 +78,10 @@ pub trait Folder<'ast, T: Field>: Sized {
         fold_struct_expression(self, e)
     }
 
+    fn fold_tuple_expression(&mut self, e: TupleExpression<'ast, T>) -> TupleExpression<'ast, T> {
+        fold_tuple_expression(self, e)
+    }
+
     fn fold_expression_list(
         &mut self,
         es: TypedExpressionList<'ast, T>,
@@ -91,6 +97,9 @@ pub trait Folder<'ast, T: Field>: Sized {
                     types,
                 )
             }
+            TypedExpressionList::Tuple(e) => {
+                TypedExpressionList::Tuple(self.fold_tuple_expression(e))
+            }
         }
     }
 
@@ -106,6 +115,16 @@ pub trait Folder<'ast, T: Field>: Sized {
     ) -> BooleanExpression<'ast, T> {
         fold_boolean_expression(self, e)
     }
+    fn fold_uint_expression(&mut self, e: UExpression<'ast, T>) -> UExpression<'ast, T> {
+        fold_uint_expression(self, e)
+    }
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: usize,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        fold_uint_expression_inner(self, bitwidth, e)
+    }
     fn fold_array_expression_inner(
         &mut self,
         ty: &Type,
@@ -121,6 +140,13 @@ pub trait Folder<'ast, T: Field>: Sized {
     ) -> StructExpressionInner<'ast, T> {
         fold_struct_expression_inner(self, ty, e)
     }
+    fn fold_tuple_expression_inner(
+        &mut self,
+        ty: &Vec<Type>,
+        e: TupleExpressionInner<'ast, T>,
+    ) -> TupleExpressionInner<'ast, T> {
+        fold_tuple_expression_inner(self, ty, e)
+    }
 }
 
 pub fn fold_module<'ast, T: Field, F: Folder<'ast, T>>(
@@ -142,18 +168,22 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
     s: TypedStatement<'ast, T>,
 ) -> Vec<TypedStatement<'ast, T>> {
     let res = match s {
-        TypedStatement::Return(expressions) => TypedStatement::Return(
+        TypedStatement::Return(expressions, span) => TypedStatement::Return(
             expressions
                 .into_iter()
                 .map(|e| f.fold_expression(e))
                 .collect(),
+            span,
         ),
-        TypedStatement::Definition(a, e) => {
-            TypedStatement::Definition(f.fold_assignee(a), f.fold_expression(e))
+        TypedStatement::Definition(a, e, span) => {
+            TypedStatement::Definition(f.fold_assignee(a), f.fold_expression(e), span)
         }
         TypedStatement::Declaration(v) => TypedStatement::Declaration(f.fold_variable(v)),
-        TypedStatement::Condition(left, right) => {
-            TypedStatement::Condition(f.fold_expression(left), f.fold_expression(right))
+        TypedStatement::Condition(left, right, span) => {
+            TypedStatement::Condition(f.fold_expression(left), f.fold_expression(right), span)
+        }
+        TypedStatement::Assertion(e, metadata) => {
+            TypedStatement::Assertion(f.fold_boolean_expression(e), metadata)
         }
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v),
@@ -164,10 +194,34 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|s| f.fold_statement(s))
                 .collect(),
         ),
-        TypedStatement::MultipleDefinition(variables, elist) => TypedStatement::MultipleDefinition(
-            variables.into_iter().map(|v| f.fold_variable(v)).collect(),
-            f.fold_expression_list(elist),
+        TypedStatement::BoundedFor(v, from, to, max, statements) => TypedStatement::BoundedFor(
+            f.fold_variable(v),
+            from,
+            f.fold_field_expression(to),
+            max,
+            statements
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+        ),
+        TypedStatement::IfElse(condition, consequence, alternative) => TypedStatement::IfElse(
+            f.fold_boolean_expression(condition),
+            consequence
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+            alternative
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
         ),
+        TypedStatement::MultipleDefinition(variables, elist, span) => {
+            TypedStatement::MultipleDefinition(
+                variables.into_iter().map(|v| f.fold_variable(v)).collect(),
+                f.fold_expression_list(elist),
+                span,
+            )
+        }
     };
     vec![res]
 }
@@ -198,6 +252,10 @@ pub fn fold_array_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let s = f.fold_struct_expression(s);
             ArrayExpressionInner::Member(box s, id)
         }
+        ArrayExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            ArrayExpressionInner::Element(box t, index)
+        }
         ArrayExpressionInner::Select(box array, box index) => {
             let array = f.fold_array_expression(array);
             let index = f.fold_field_expression(index);
@@ -231,6 +289,10 @@ pub fn fold_struct_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let s = f.fold_struct_expression(s);
             StructExpressionInner::Member(box s, id)
         }
+        StructExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            StructExpressionInner::Element(box t, index)
+        }
         StructExpressionInner::Select(box array, box index) => {
             let array = f.fold_array_expression(array);
             let index = f.fold_field_expression(index);
@@ -239,6 +301,43 @@ pub fn fold_struct_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_tuple_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    _: &Vec<Type>,
+    e: TupleExpressionInner<'ast, T>,
+) -> TupleExpressionInner<'ast, T> {
+    match e {
+        TupleExpressionInner::Identifier(id) => TupleExpressionInner::Identifier(f.fold_name(id)),
+        TupleExpressionInner::Value(exprs) => {
+            TupleExpressionInner::Value(exprs.into_iter().map(|e| f.fold_expression(e)).collect())
+        }
+        TupleExpressionInner::FunctionCall(id, exps) => {
+            let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
+            TupleExpressionInner::FunctionCall(id, exps)
+        }
+        TupleExpressionInner::IfElse(box condition, box consequence, box alternative) => {
+            TupleExpressionInner::IfElse(
+                box f.fold_boolean_expression(condition),
+                box f.fold_tuple_expression(consequence),
+                box f.fold_tuple_expression(alternative),
+            )
+        }
+        TupleExpressionInner::Member(box s, id) => {
+            let s = f.fold_struct_expression(s);
+            TupleExpressionInner::Member(box s, id)
+        }
+        TupleExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            TupleExpressionInner::Element(box t, index)
+        }
+        TupleExpressionInner::Select(box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            TupleExpressionInner::Select(box array, box index)
+        }
+    }
+}
+
 pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     e: FieldElementExpression<'ast, T>,
@@ -287,11 +386,105 @@ pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let s = f.fold_struct_expression(s);
             FieldElementExpression::Member(box s, id)
         }
+        FieldElementExpression::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            FieldElementExpression::Element(box t, index)
+        }
         FieldElementExpression::Select(box array, box index) => {
             let array = f.fold_array_expression(array);
             let index = f.fold_field_expression(index);
             FieldElementExpression::Select(box array, box index)
         }
+        FieldElementExpression::FromUint(box e) => {
+            let e = f.fold_uint_expression(e);
+            FieldElementExpression::FromUint(box e)
+        }
+    }
+}
+
+pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    _: usize,
+    e: UExpressionInner<'ast, T>,
+) -> UExpressionInner<'ast, T> {
+    match e {
+        UExpressionInner::Value(v) => UExpressionInner::Value(v),
+        UExpressionInner::Identifier(id) => UExpressionInner::Identifier(f.fold_name(id)),
+        UExpressionInner::Add(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::Add(box e1, box e2)
+        }
+        UExpressionInner::Sub(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::Sub(box e1, box e2)
+        }
+        UExpressionInner::Mult(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::Mult(box e1, box e2)
+        }
+        UExpressionInner::Xor(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::Xor(box e1, box e2)
+        }
+        UExpressionInner::And(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::And(box e1, box e2)
+        }
+        UExpressionInner::Or(box e1, box e2) => {
+            let e1 = f.fold_uint_expression(e1);
+            let e2 = f.fold_uint_expression(e2);
+            UExpressionInner::Or(box e1, box e2)
+        }
+        UExpressionInner::Not(box e) => {
+            let e = f.fold_uint_expression(e);
+            UExpressionInner::Not(box e)
+        }
+        UExpressionInner::LeftShift(box e, box by) => {
+            let e = f.fold_uint_expression(e);
+            let by = f.fold_field_expression(by);
+            UExpressionInner::LeftShift(box e, box by)
+        }
+        UExpressionInner::RightShift(box e, box by) => {
+            let e = f.fold_uint_expression(e);
+            let by = f.fold_field_expression(by);
+            UExpressionInner::RightShift(box e, box by)
+        }
+        UExpressionInner::FromField(box e) => {
+            let e = f.fold_field_expression(e);
+            UExpressionInner::FromField(box e)
+        }
+        UExpressionInner::FromUint(box e) => {
+            let e = f.fold_uint_expression(e);
+            UExpressionInner::FromUint(box e)
+        }
+        UExpressionInner::IfElse(box cond, box cons, box alt) => {
+            let cond = f.fold_boolean_expression(cond);
+            let cons = f.fold_uint_expression(cons);
+            let alt = f.fold_uint_expression(alt);
+            UExpressionInner::IfElse(box cond, box cons, box alt)
+        }
+        UExpressionInner::FunctionCall(key, exps) => {
+            let exps = exps.into_iter().map(|e| f.fold_expression(e)).collect();
+            UExpressionInner::FunctionCall(key, exps)
+        }
+        UExpressionInner::Member(box s, id) => {
+            let s = f.fold_struct_expression(s);
+            UExpressionInner::Member(box s, id)
+        }
+        UExpressionInner::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            UExpressionInner::Element(box t, index)
+        }
+        UExpressionInner::Select(box array, box index) => {
+            let array = f.fold_array_expression(array);
+            let index = f.fold_field_expression(index);
+            UExpressionInner::Select(box array, box index)
+        }
     }
 }
 
@@ -351,6 +544,10 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let s = f.fold_struct_expression(s);
             BooleanExpression::Member(box s, id)
         }
+        BooleanExpression::Element(box t, index) => {
+            let t = f.fold_tuple_expression(t);
+            BooleanExpression::Element(box t, index)
+        }
         BooleanExpression::Select(box array, box index) => {
             let array = f.fold_array_expression(array);
             let index = f.fold_field_expression(index);
@@ -378,6 +575,16 @@ pub fn fold_function<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_uint_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: UExpression<'ast, T>,
+) -> UExpression<'ast, T> {
+    UExpression {
+        inner: f.fold_uint_expression_inner(e.bitwidth, e.inner),
+        ..e
+    }
+}
+
 pub fn fold_array_expression<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     e: ArrayExpression<'ast, T>,
@@ -398,6 +605,16 @@ pub fn fold_struct_expression<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_tuple_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: TupleExpression<'ast, T>,
+) -> TupleExpression<'ast, T> {
+    TupleExpression {
+        inner: f.fold_tuple_expression_inner(&e.ty, e.inner),
+        ..e
+    }
+}
+
 pub fn fold_function_symbol<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     s: TypedFunctionSymbol<'ast, T>,
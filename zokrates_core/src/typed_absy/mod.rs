@@ -14,6 +14,7 @@ pub use crate::typed_absy::parameter::Parameter;
 pub use crate::typed_absy::types::Type;
 pub use crate::typed_absy::variable::Variable;
 
+use crate::parser::Position;
 use crate::typed_absy::types::{FunctionKey, MemberId, Signature};
 use embed::FlatEmbed;
 use std::collections::HashMap;
@@ -24,7 +25,8 @@ use zokrates_field::field::Field;
 pub use self::folder::Folder;
 
 /// A identifier for a variable
-#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[derive(Debug, PartialEq, Clone, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct Identifier<'ast> {
     /// the id of the variable
     pub id: &'ast str,
@@ -125,7 +127,8 @@ impl<'ast> Identifier<'ast> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum TypedFunctionSymbol<'ast, T: Field> {
     Here(TypedFunction<'ast, T>),
     There(FunctionKey<'ast>, TypedModuleId),
@@ -184,7 +187,8 @@ impl<'ast, T: Field> fmt::Debug for TypedModule<'ast, T> {
 }
 
 /// A typed function
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub struct TypedFunction<'ast, T: Field> {
     /// Arguments of the function
     pub arguments: Vec<Parameter<'ast>>,
@@ -235,7 +239,8 @@ impl<'ast, T: Field> fmt::Debug for TypedFunction<'ast, T> {
 }
 
 /// Something we can assign to.
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum TypedAssignee<'ast, T: Field> {
     Identifier(Variable<'ast>),
     Select(
@@ -285,21 +290,173 @@ impl<'ast, T: Field> fmt::Display for TypedAssignee<'ast, T> {
     }
 }
 
+/// A span of source code in a given module, used to map compiled statements back to the source
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    /// the module the span is in
+    pub module: TypedModuleId,
+    /// the start of the span
+    pub from: Position,
+    /// the end of the span
+    pub to: Position,
+    /// the inlined calls which led to this span, outermost first
+    #[serde(default)]
+    pub stack: Vec<CallFrame>,
+}
+
+impl SourceSpan {
+    #[cfg(test)]
+    pub fn mock() -> Self {
+        SourceSpan {
+            module: String::from(""),
+            from: Position::mock(),
+            to: Position::mock(),
+            stack: vec![],
+        }
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.module, self.from)
+    }
+}
+
+impl fmt::Debug for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SourceSpan({}:{}-{})", self.module, self.from, self.to)
+    }
+}
+
+/// A function call which was inlined, kept so that statements can be attributed to the functions they come from
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+pub struct CallFrame {
+    /// the module the called function is defined in
+    pub module: TypedModuleId,
+    /// the id of the called function
+    pub function: String,
+    /// the location of the call, if known
+    pub call_site: Option<SourceSpan>,
+}
+
+impl fmt::Display for CallFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.module.as_str() {
+            "" => write!(f, "{}", self.function),
+            module => write!(f, "{}::{}", module, self.function),
+        }
+    }
+}
+
+impl fmt::Debug for CallFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.call_site {
+            Some(ref call_site) => write!(f, "CallFrame({} at {})", self, call_site),
+            None => write!(f, "CallFrame({})", self),
+        }
+    }
+}
+
+/// The origin of an `assert` statement, kept so that failures can be reported at runtime
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+pub struct AssertionMetadata {
+    /// the location of the assertion
+    pub span: SourceSpan,
+    /// the message provided by the user, if any
+    pub message: Option<String>,
+}
+
+impl fmt::Display for AssertionMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.span)?;
+        match self.message {
+            Some(ref message) => write!(f, ": {}", message),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for AssertionMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AssertionMetadata({})", self)
+    }
+}
+
 /// A statement in a `TypedFunction`
-#[derive(Clone, PartialEq, Hash, Eq)]
+///
+/// Statements which generate constraints optionally keep track of the source they were checked from
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum TypedStatement<'ast, T: Field> {
-    Return(Vec<TypedExpression<'ast, T>>),
-    Definition(TypedAssignee<'ast, T>, TypedExpression<'ast, T>),
+    Return(Vec<TypedExpression<'ast, T>>, Option<SourceSpan>),
+    Definition(
+        TypedAssignee<'ast, T>,
+        TypedExpression<'ast, T>,
+        Option<SourceSpan>,
+    ),
     Declaration(Variable<'ast>),
-    Condition(TypedExpression<'ast, T>, TypedExpression<'ast, T>),
+    Condition(
+        TypedExpression<'ast, T>,
+        TypedExpression<'ast, T>,
+        Option<SourceSpan>,
+    ),
+    Assertion(BooleanExpression<'ast, T>, AssertionMetadata),
     For(Variable<'ast>, T, T, Vec<TypedStatement<'ast, T>>),
-    MultipleDefinition(Vec<Variable<'ast>>, TypedExpressionList<'ast, T>),
+    BoundedFor(
+        Variable<'ast>,
+        T,
+        FieldElementExpression<'ast, T>,
+        T,
+        Vec<TypedStatement<'ast, T>>,
+    ),
+    IfElse(
+        BooleanExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+        Vec<TypedStatement<'ast, T>>,
+    ),
+    MultipleDefinition(
+        Vec<Variable<'ast>>,
+        TypedExpressionList<'ast, T>,
+        Option<SourceSpan>,
+    ),
+}
+
+impl<'ast, T: Field> TypedStatement<'ast, T> {
+    /// The location of the source this statement was checked from, if known
+    pub fn span(&self) -> Option<SourceSpan> {
+        match *self {
+            TypedStatement::Return(_, ref span)
+            | TypedStatement::Definition(_, _, ref span)
+            | TypedStatement::Condition(_, _, ref span)
+            | TypedStatement::MultipleDefinition(_, _, ref span) => span.clone(),
+            TypedStatement::Assertion(_, ref metadata) => Some(metadata.span.clone()),
+            TypedStatement::Declaration(..)
+            | TypedStatement::For(..)
+            | TypedStatement::BoundedFor(..)
+            | TypedStatement::IfElse(..) => None,
+        }
+    }
+
+    /// A mutable reference to the location of the source this statement was checked from, if known
+    pub fn span_mut(&mut self) -> Option<&mut SourceSpan> {
+        match *self {
+            TypedStatement::Return(_, ref mut span)
+            | TypedStatement::Definition(_, _, ref mut span)
+            | TypedStatement::Condition(_, _, ref mut span)
+            | TypedStatement::MultipleDefinition(_, _, ref mut span) => span.as_mut(),
+            TypedStatement::Assertion(_, ref mut metadata) => Some(&mut metadata.span),
+            TypedStatement::Declaration(..)
+            | TypedStatement::For(..)
+            | TypedStatement::BoundedFor(..)
+            | TypedStatement::IfElse(..) => None,
+        }
+    }
 }
 
 impl<'ast, T: Field> fmt::Debug for TypedStatement<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            TypedStatement::Return(ref exprs) => {
+            TypedStatement::Return(ref exprs, _) => {
                 write!(f, "Return(")?;
                 for (i, expr) in exprs.iter().enumerate() {
                     write!(f, "{}", expr)?;
@@ -310,12 +467,15 @@ impl<'ast, T: Field> fmt::Debug for TypedStatement<'ast, T> {
                 write!(f, ")")
             }
             TypedStatement::Declaration(ref var) => write!(f, "Declaration({:?})", var),
-            TypedStatement::Definition(ref lhs, ref rhs) => {
+            TypedStatement::Definition(ref lhs, ref rhs, _) => {
                 write!(f, "Definition({:?}, {:?})", lhs, rhs)
             }
-            TypedStatement::Condition(ref lhs, ref rhs) => {
+            TypedStatement::Condition(ref lhs, ref rhs, _) => {
                 write!(f, "Condition({:?}, {:?})", lhs, rhs)
             }
+            TypedStatement::Assertion(ref e, ref metadata) => {
+                write!(f, "Assertion({:?}, {:?})", e, metadata)
+            }
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {:?} in {:?}..{:?} do\n", var, start, stop)?;
                 for l in list {
@@ -323,7 +483,25 @@ impl<'ast, T: Field> fmt::Debug for TypedStatement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
-            TypedStatement::MultipleDefinition(ref lhs, ref rhs) => {
+            TypedStatement::BoundedFor(ref var, ref start, ref stop, ref max, ref list) => {
+                write!(f, "for {:?} in {:?}..{:?} max {:?} do\n", var, start, stop, max)?;
+                for l in list {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\tendfor")
+            }
+            TypedStatement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {:?} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{:?}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
+            TypedStatement::MultipleDefinition(ref lhs, ref rhs, _) => {
                 write!(f, "MultipleDefinition({:?}, {:?})", lhs, rhs)
             }
         }
@@ -333,7 +511,7 @@ impl<'ast, T: Field> fmt::Debug for TypedStatement<'ast, T> {
 impl<'ast, T: Field> fmt::Display for TypedStatement<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            TypedStatement::Return(ref exprs) => {
+            TypedStatement::Return(ref exprs, _) => {
                 write!(f, "return ")?;
                 for (i, expr) in exprs.iter().enumerate() {
                     write!(f, "{}", expr)?;
@@ -344,8 +522,12 @@ impl<'ast, T: Field> fmt::Display for TypedStatement<'ast, T> {
                 write!(f, "")
             }
             TypedStatement::Declaration(ref var) => write!(f, "{}", var),
-            TypedStatement::Definition(ref lhs, ref rhs) => write!(f, "{} = {}", lhs, rhs),
-            TypedStatement::Condition(ref lhs, ref rhs) => write!(f, "{} == {}", lhs, rhs),
+            TypedStatement::Definition(ref lhs, ref rhs, _) => write!(f, "{} = {}", lhs, rhs),
+            TypedStatement::Condition(ref lhs, ref rhs, _) => write!(f, "{} == {}", lhs, rhs),
+            TypedStatement::Assertion(ref e, ref metadata) => match metadata.message {
+                Some(ref message) => write!(f, "assert({}, \"{}\")", e, message),
+                None => write!(f, "assert({})", e),
+            },
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 write!(f, "for {} in {}..{} do\n", var, start, stop)?;
                 for l in list {
@@ -353,7 +535,25 @@ impl<'ast, T: Field> fmt::Display for TypedStatement<'ast, T> {
                 }
                 write!(f, "\tendfor")
             }
-            TypedStatement::MultipleDefinition(ref ids, ref rhs) => {
+            TypedStatement::BoundedFor(ref var, ref start, ref stop, ref max, ref list) => {
+                write!(f, "for {} in {}..{} max {} do\n", var, start, stop, max)?;
+                for l in list {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tendfor")
+            }
+            TypedStatement::IfElse(ref condition, ref consequence, ref alternative) => {
+                write!(f, "if {} then\n", condition)?;
+                for l in consequence {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\telse\n")?;
+                for l in alternative {
+                    write!(f, "\t\t{}\n", l)?;
+                }
+                write!(f, "\tfi")
+            }
+            TypedStatement::MultipleDefinition(ref ids, ref rhs, _) => {
                 for (i, id) in ids.iter().enumerate() {
                     write!(f, "{}", id)?;
                     if i < ids.len() - 1 {
@@ -371,12 +571,15 @@ pub trait Typed {
 }
 
 /// A typed expression
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum TypedExpression<'ast, T: Field> {
     Boolean(BooleanExpression<'ast, T>),
     FieldElement(FieldElementExpression<'ast, T>),
+    Uint(UExpression<'ast, T>),
     Array(ArrayExpression<'ast, T>),
     Struct(StructExpression<'ast, T>),
+    Tuple(TupleExpression<'ast, T>),
 }
 
 impl<'ast, T: Field> From<BooleanExpression<'ast, T>> for TypedExpression<'ast, T> {
@@ -391,6 +594,12 @@ impl<'ast, T: Field> From<FieldElementExpression<'ast, T>> for TypedExpression<'
     }
 }
 
+impl<'ast, T: Field> From<UExpression<'ast, T>> for TypedExpression<'ast, T> {
+    fn from(e: UExpression<'ast, T>) -> TypedExpression<T> {
+        TypedExpression::Uint(e)
+    }
+}
+
 impl<'ast, T: Field> From<ArrayExpression<'ast, T>> for TypedExpression<'ast, T> {
     fn from(e: ArrayExpression<'ast, T>) -> TypedExpression<T> {
         TypedExpression::Array(e)
@@ -403,13 +612,21 @@ impl<'ast, T: Field> From<StructExpression<'ast, T>> for TypedExpression<'ast, T
     }
 }
 
+impl<'ast, T: Field> From<TupleExpression<'ast, T>> for TypedExpression<'ast, T> {
+    fn from(e: TupleExpression<'ast, T>) -> TypedExpression<T> {
+        TypedExpression::Tuple(e)
+    }
+}
+
 impl<'ast, T: Field> fmt::Display for TypedExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             TypedExpression::Boolean(ref e) => write!(f, "{}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{}", e),
+            TypedExpression::Uint(ref e) => write!(f, "{}", e),
             TypedExpression::Array(ref e) => write!(f, "{}", e),
             TypedExpression::Struct(ref s) => write!(f, "{}", s),
+            TypedExpression::Tuple(ref t) => write!(f, "{}", t),
         }
     }
 }
@@ -419,12 +636,33 @@ impl<'ast, T: Field> fmt::Debug for TypedExpression<'ast, T> {
         match *self {
             TypedExpression::Boolean(ref e) => write!(f, "{:?}", e),
             TypedExpression::FieldElement(ref e) => write!(f, "{:?}", e),
+            TypedExpression::Uint(ref e) => write!(f, "{:?}", e),
             TypedExpression::Array(ref e) => write!(f, "{:?}", e),
             TypedExpression::Struct(ref s) => write!(f, "{}", s),
+            TypedExpression::Tuple(ref t) => write!(f, "{}", t),
         }
     }
 }
 
+impl<'ast, T: Field> fmt::Display for UExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            UExpressionInner::Value(ref v) => {
+                write!(f, "{:#0width$x}", v, width = self.bitwidth / 4 + 2)
+            }
+            UExpressionInner::FromField(ref e) => write!(f, "u{}({})", self.bitwidth, e),
+            UExpressionInner::FromUint(ref e) => write!(f, "u{}({})", self.bitwidth, e),
+            _ => write!(f, "{}", self.inner),
+        }
+    }
+}
+
+impl<'ast, T: Field> fmt::Debug for UExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
 impl<'ast, T: Field> fmt::Display for ArrayExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.inner)
@@ -470,6 +708,9 @@ impl<'ast, T: Field> fmt::Display for StructExpression<'ast, T> {
                 )
             }
             StructExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            StructExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
             StructExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
         }
     }
@@ -481,13 +722,60 @@ impl<'ast, T: Field> fmt::Debug for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> fmt::Display for TupleExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            TupleExpressionInner::Identifier(ref var) => write!(f, "{}", var),
+            TupleExpressionInner::Value(ref values) => write!(
+                f,
+                "({})",
+                values
+                    .iter()
+                    .map(|o| o.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            TupleExpressionInner::FunctionCall(ref key, ref p) => {
+                write!(f, "{}(", key.id,)?;
+                for (i, param) in p.iter().enumerate() {
+                    write!(f, "{}", param)?;
+                    if i < p.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, ")")
+            }
+            TupleExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => {
+                write!(
+                    f,
+                    "if {} then {} else {} fi",
+                    condition, consequent, alternative
+                )
+            }
+            TupleExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            TupleExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
+            TupleExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+        }
+    }
+}
+
+impl<'ast, T: Field> fmt::Debug for TupleExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
 impl<'ast, T: Field> Typed for TypedExpression<'ast, T> {
     fn get_type(&self) -> Type {
         match *self {
             TypedExpression::Boolean(ref e) => e.get_type(),
             TypedExpression::FieldElement(ref e) => e.get_type(),
+            TypedExpression::Uint(ref e) => e.get_type(),
             TypedExpression::Array(ref e) => e.get_type(),
             TypedExpression::Struct(ref s) => s.get_type(),
+            TypedExpression::Tuple(ref t) => t.get_type(),
         }
     }
 }
@@ -504,6 +792,18 @@ impl<'ast, T: Field> Typed for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Typed for TupleExpression<'ast, T> {
+    fn get_type(&self) -> Type {
+        Type::Tuple(self.ty.clone())
+    }
+}
+
+impl<'ast, T: Field> Typed for UExpression<'ast, T> {
+    fn get_type(&self) -> Type {
+        Type::Uint(self.bitwidth)
+    }
+}
+
 impl<'ast, T: Field> Typed for FieldElementExpression<'ast, T> {
     fn get_type(&self) -> Type {
         Type::FieldElement
@@ -520,21 +820,25 @@ pub trait MultiTyped {
     fn get_types(&self) -> &Vec<Type>;
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum TypedExpressionList<'ast, T: Field> {
     FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>, Vec<Type>),
+    Tuple(TupleExpression<'ast, T>),
 }
 
 impl<'ast, T: Field> MultiTyped for TypedExpressionList<'ast, T> {
     fn get_types(&self) -> &Vec<Type> {
         match *self {
             TypedExpressionList::FunctionCall(_, _, ref types) => types,
+            TypedExpressionList::Tuple(ref tuple) => tuple.ty(),
         }
     }
 }
 
 /// An expression of type `field`
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum FieldElementExpression<'ast, T: Field> {
     Number(T),
     Identifier(Identifier<'ast>),
@@ -565,14 +869,17 @@ pub enum FieldElementExpression<'ast, T: Field> {
     ),
     FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>),
     Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
     Select(
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    FromUint(Box<UExpression<'ast, T>>),
 }
 
 /// An expression of type `bool`
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum BooleanExpression<'ast, T: Field> {
     Identifier(Identifier<'ast>),
     Value(bool),
@@ -611,25 +918,98 @@ pub enum BooleanExpression<'ast, T: Field> {
         Box<BooleanExpression<'ast, T>>,
     ),
     Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
+    Select(
+        Box<ArrayExpression<'ast, T>>,
+        Box<FieldElementExpression<'ast, T>>,
+    ),
+}
+
+/// An expression of type `u8`, `u16` or `u32`
+/// # Remarks
+/// * Like arrays, we wrap an enum `UExpressionInner` in a struct in order to keep track of the bitwidth
+/// * Values are represented as a single field element in the range `[0, 2**bitwidth)`
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
+pub struct UExpression<'ast, T: Field> {
+    bitwidth: usize,
+    inner: UExpressionInner<'ast, T>,
+}
+
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
+pub enum UExpressionInner<'ast, T: Field> {
+    Identifier(Identifier<'ast>),
+    Value(u128),
+    Add(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Sub(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Mult(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Xor(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    And(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Or(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Not(Box<UExpression<'ast, T>>),
+    LeftShift(
+        Box<UExpression<'ast, T>>,
+        Box<FieldElementExpression<'ast, T>>,
+    ),
+    RightShift(
+        Box<UExpression<'ast, T>>,
+        Box<FieldElementExpression<'ast, T>>,
+    ),
+    FromField(Box<FieldElementExpression<'ast, T>>),
+    FromUint(Box<UExpression<'ast, T>>),
+    IfElse(
+        Box<BooleanExpression<'ast, T>>,
+        Box<UExpression<'ast, T>>,
+        Box<UExpression<'ast, T>>,
+    ),
+    FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>),
+    Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
     Select(
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
 }
 
+impl<'ast, T: Field> UExpressionInner<'ast, T> {
+    pub fn annotate(self, bitwidth: usize) -> UExpression<'ast, T> {
+        UExpression {
+            bitwidth,
+            inner: self,
+        }
+    }
+}
+
+impl<'ast, T: Field> UExpression<'ast, T> {
+    pub fn bitwidth(&self) -> usize {
+        self.bitwidth
+    }
+
+    pub fn as_inner(&self) -> &UExpressionInner<'ast, T> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> UExpressionInner<'ast, T> {
+        self.inner
+    }
+}
+
 /// An expression of type `array`
 /// # Remarks
 /// * Contrary to basic types which represented as enums, we wrap an enum `ArrayExpressionInner` in a struct in order to keep track of the type (content and size)
 /// of the array. Only using an enum would require generics, which would propagate up to TypedExpression which we want to keep simple, hence this "runtime"
 /// type checking
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub struct ArrayExpression<'ast, T: Field> {
     size: usize,
     ty: Type,
     inner: ArrayExpressionInner<'ast, T>,
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum ArrayExpressionInner<'ast, T: Field> {
     Identifier(Identifier<'ast>),
     Value(Vec<TypedExpression<'ast, T>>),
@@ -640,6 +1020,7 @@ pub enum ArrayExpressionInner<'ast, T: Field> {
         Box<ArrayExpression<'ast, T>>,
     ),
     Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
     Select(
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -674,7 +1055,8 @@ impl<'ast, T: Field> ArrayExpression<'ast, T> {
     }
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub struct StructExpression<'ast, T: Field> {
     ty: Vec<(MemberId, Type)>,
     inner: StructExpressionInner<'ast, T>,
@@ -694,7 +1076,8 @@ impl<'ast, T: Field> StructExpression<'ast, T> {
     }
 }
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
 pub enum StructExpressionInner<'ast, T: Field> {
     Identifier(Identifier<'ast>),
     Value(Vec<TypedExpression<'ast, T>>),
@@ -705,6 +1088,7 @@ pub enum StructExpressionInner<'ast, T: Field> {
         Box<StructExpression<'ast, T>>,
     ),
     Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
     Select(
         Box<ArrayExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
@@ -717,6 +1101,55 @@ impl<'ast, T: Field> StructExpressionInner<'ast, T> {
     }
 }
 
+/// An expression of type tuple
+/// # Remarks
+/// * Like structs, we wrap an enum `TupleExpressionInner` in a struct in order to keep track of the types of the elements
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
+pub struct TupleExpression<'ast, T: Field> {
+    ty: Vec<Type>,
+    inner: TupleExpressionInner<'ast, T>,
+}
+
+impl<'ast, T: Field> TupleExpression<'ast, T> {
+    pub fn ty(&self) -> &Vec<Type> {
+        &self.ty
+    }
+
+    pub fn as_inner(&self) -> &TupleExpressionInner<'ast, T> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> TupleExpressionInner<'ast, T> {
+        self.inner
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast, T: ::serde::Deserialize<'de>"))]
+pub enum TupleExpressionInner<'ast, T: Field> {
+    Identifier(Identifier<'ast>),
+    Value(Vec<TypedExpression<'ast, T>>),
+    FunctionCall(FunctionKey<'ast>, Vec<TypedExpression<'ast, T>>),
+    IfElse(
+        Box<BooleanExpression<'ast, T>>,
+        Box<TupleExpression<'ast, T>>,
+        Box<TupleExpression<'ast, T>>,
+    ),
+    Member(Box<StructExpression<'ast, T>>, MemberId),
+    Element(Box<TupleExpression<'ast, T>>, usize),
+    Select(
+        Box<ArrayExpression<'ast, T>>,
+        Box<FieldElementExpression<'ast, T>>,
+    ),
+}
+
+impl<'ast, T: Field> TupleExpressionInner<'ast, T> {
+    pub fn annotate(self, ty: Vec<Type>) -> TupleExpression<'ast, T> {
+        TupleExpression { ty, inner: self }
+    }
+}
+
 // Downcasts
 // Due to the fact that we keep TypedExpression simple, we end up with ArrayExpressionInner::Value whose elements are any TypedExpression, but we enforce by
 // construction that these elements are of the type declared in the corresponding ArrayExpression. As we know this by construction, we can downcast the TypedExpression to the correct type
@@ -745,6 +1178,17 @@ impl<'ast, T: Field> TryFrom<TypedExpression<'ast, T>> for BooleanExpression<'as
     }
 }
 
+impl<'ast, T: Field> TryFrom<TypedExpression<'ast, T>> for UExpression<'ast, T> {
+    type Error = ();
+
+    fn try_from(te: TypedExpression<'ast, T>) -> Result<UExpression<'ast, T>, Self::Error> {
+        match te {
+            TypedExpression::Uint(e) => Ok(e),
+            _ => Err(()),
+        }
+    }
+}
+
 impl<'ast, T: Field> TryFrom<TypedExpression<'ast, T>> for ArrayExpression<'ast, T> {
     type Error = ();
 
@@ -767,6 +1211,17 @@ impl<'ast, T: Field> TryFrom<TypedExpression<'ast, T>> for StructExpression<'ast
     }
 }
 
+impl<'ast, T: Field> TryFrom<TypedExpression<'ast, T>> for TupleExpression<'ast, T> {
+    type Error = ();
+
+    fn try_from(te: TypedExpression<'ast, T>) -> Result<TupleExpression<'ast, T>, Self::Error> {
+        match te {
+            TypedExpression::Tuple(e) => Ok(e),
+            _ => Err(()),
+        }
+    }
+}
+
 impl<'ast, T: Field> fmt::Display for FieldElementExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -795,7 +1250,49 @@ impl<'ast, T: Field> fmt::Display for FieldElementExpression<'ast, T> {
                 write!(f, ")")
             }
             FieldElementExpression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            FieldElementExpression::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
             FieldElementExpression::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
+            FieldElementExpression::FromUint(ref e) => write!(f, "field({})", e),
+        }
+    }
+}
+
+impl<'ast, T: Field> fmt::Display for UExpressionInner<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UExpressionInner::Identifier(ref var) => write!(f, "{}", var),
+            UExpressionInner::Value(ref v) => write!(f, "{:#x}", v),
+            UExpressionInner::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
+            UExpressionInner::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
+            UExpressionInner::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
+            UExpressionInner::Xor(ref lhs, ref rhs) => write!(f, "({} ^ {})", lhs, rhs),
+            UExpressionInner::And(ref lhs, ref rhs) => write!(f, "({} & {})", lhs, rhs),
+            UExpressionInner::Or(ref lhs, ref rhs) => write!(f, "({} | {})", lhs, rhs),
+            UExpressionInner::Not(ref e) => write!(f, "!{}", e),
+            UExpressionInner::LeftShift(ref e, ref by) => write!(f, "({} << {})", e, by),
+            UExpressionInner::RightShift(ref e, ref by) => write!(f, "({} >> {})", e, by),
+            UExpressionInner::FromField(ref e) => write!(f, "uint({})", e),
+            UExpressionInner::FromUint(ref e) => write!(f, "uint({})", e),
+            UExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "if {} then {} else {} fi",
+                condition, consequent, alternative
+            ),
+            UExpressionInner::FunctionCall(ref k, ref p) => {
+                write!(f, "{}(", k.id,)?;
+                for (i, param) in p.iter().enumerate() {
+                    write!(f, "{}", param)?;
+                    if i < p.len() - 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, ")")
+            }
+            UExpressionInner::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            UExpressionInner::Element(ref tuple, ref index) => write!(f, "{}.{}", tuple, index),
+            UExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
         }
     }
 }
@@ -819,6 +1316,7 @@ impl<'ast, T: Field> fmt::Display for BooleanExpression<'ast, T> {
                 condition, consequent, alternative
             ),
             BooleanExpression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
+            BooleanExpression::Element(ref tuple, ref index) => write!(f, "{}.{}", tuple, index),
             BooleanExpression::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
         }
     }
@@ -853,6 +1351,9 @@ impl<'ast, T: Field> fmt::Display for ArrayExpressionInner<'ast, T> {
                 condition, consequent, alternative
             ),
             ArrayExpressionInner::Member(ref s, ref id) => write!(f, "{}.{}", s, id),
+            ArrayExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "{}.{}", tuple, index)
+            }
             ArrayExpressionInner::Select(ref id, ref index) => write!(f, "{}[{}]", id, index),
         }
     }
@@ -891,9 +1392,56 @@ impl<'ast, T: Field> fmt::Debug for FieldElementExpression<'ast, T> {
             FieldElementExpression::Member(ref struc, ref id) => {
                 write!(f, "Member({:?}, {:?})", struc, id)
             }
+            FieldElementExpression::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             FieldElementExpression::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
+            FieldElementExpression::FromUint(ref e) => write!(f, "FromUint({:?})", e),
+        }
+    }
+}
+
+impl<'ast, T: Field> fmt::Debug for UExpressionInner<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UExpressionInner::Identifier(ref var) => write!(f, "Ide({})", var),
+            UExpressionInner::Value(ref v) => write!(f, "Value({:#x})", v),
+            UExpressionInner::Add(ref lhs, ref rhs) => write!(f, "Add({:?}, {:?})", lhs, rhs),
+            UExpressionInner::Sub(ref lhs, ref rhs) => write!(f, "Sub({:?}, {:?})", lhs, rhs),
+            UExpressionInner::Mult(ref lhs, ref rhs) => write!(f, "Mult({:?}, {:?})", lhs, rhs),
+            UExpressionInner::Xor(ref lhs, ref rhs) => write!(f, "Xor({:?}, {:?})", lhs, rhs),
+            UExpressionInner::And(ref lhs, ref rhs) => write!(f, "And({:?}, {:?})", lhs, rhs),
+            UExpressionInner::Or(ref lhs, ref rhs) => write!(f, "Or({:?}, {:?})", lhs, rhs),
+            UExpressionInner::Not(ref e) => write!(f, "Not({:?})", e),
+            UExpressionInner::LeftShift(ref e, ref by) => {
+                write!(f, "LeftShift({:?}, {:?})", e, by)
+            }
+            UExpressionInner::RightShift(ref e, ref by) => {
+                write!(f, "RightShift({:?}, {:?})", e, by)
+            }
+            UExpressionInner::FromField(ref e) => write!(f, "FromField({:?})", e),
+            UExpressionInner::FromUint(ref e) => write!(f, "FromUint({:?})", e),
+            UExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => write!(
+                f,
+                "IfElse({:?}, {:?}, {:?})",
+                condition, consequent, alternative
+            ),
+            UExpressionInner::FunctionCall(ref i, ref p) => {
+                write!(f, "FunctionCall({:?}, (", i)?;
+                f.debug_list().entries(p.iter()).finish()?;
+                write!(f, ")")
+            }
+            UExpressionInner::Member(ref struc, ref id) => {
+                write!(f, "Member({:?}, {:?})", struc, id)
+            }
+            UExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
+            UExpressionInner::Select(ref id, ref index) => {
+                write!(f, "Select({:?}, {:?})", id, index)
+            }
         }
     }
 }
@@ -916,6 +1464,9 @@ impl<'ast, T: Field> fmt::Debug for ArrayExpressionInner<'ast, T> {
             ArrayExpressionInner::Member(ref struc, ref id) => {
                 write!(f, "Member({:?}, {:?})", struc, id)
             }
+            ArrayExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             ArrayExpressionInner::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
@@ -943,6 +1494,9 @@ impl<'ast, T: Field> fmt::Debug for StructExpressionInner<'ast, T> {
             StructExpressionInner::Member(ref struc, ref id) => {
                 write!(f, "Member({:?}, {:?})", struc, id)
             }
+            StructExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
             StructExpressionInner::Select(ref id, ref index) => {
                 write!(f, "Select({:?}, {:?})", id, index)
             }
@@ -950,6 +1504,36 @@ impl<'ast, T: Field> fmt::Debug for StructExpressionInner<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> fmt::Debug for TupleExpressionInner<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TupleExpressionInner::Identifier(ref var) => write!(f, "{:?}", var),
+            TupleExpressionInner::Value(ref values) => write!(f, "{:?}", values),
+            TupleExpressionInner::FunctionCall(ref i, ref p) => {
+                write!(f, "FunctionCall({:?}, (", i)?;
+                f.debug_list().entries(p.iter()).finish()?;
+                write!(f, ")")
+            }
+            TupleExpressionInner::IfElse(ref condition, ref consequent, ref alternative) => {
+                write!(
+                    f,
+                    "IfElse({:?}, {:?}, {:?})",
+                    condition, consequent, alternative
+                )
+            }
+            TupleExpressionInner::Member(ref struc, ref id) => {
+                write!(f, "Member({:?}, {:?})", struc, id)
+            }
+            TupleExpressionInner::Element(ref tuple, ref index) => {
+                write!(f, "Element({:?}, {:?})", tuple, index)
+            }
+            TupleExpressionInner::Select(ref id, ref index) => {
+                write!(f, "Select({:?}, {:?})", id, index)
+            }
+        }
+    }
+}
+
 impl<'ast, T: Field> fmt::Display for TypedExpressionList<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -963,6 +1547,7 @@ impl<'ast, T: Field> fmt::Display for TypedExpressionList<'ast, T> {
                 }
                 write!(f, ")")
             }
+            TypedExpressionList::Tuple(ref tuple) => write!(f, "{}", tuple),
         }
     }
 }
@@ -975,6 +1560,7 @@ impl<'ast, T: Field> fmt::Debug for TypedExpressionList<'ast, T> {
                 f.debug_list().entries(p.iter()).finish()?;
                 write!(f, ")")
             }
+            TypedExpressionList::Tuple(ref tuple) => write!(f, "Tuple({:?})", tuple),
         }
     }
 }
@@ -1006,6 +1592,17 @@ impl<'ast, T: Field> IfElse<'ast, T> for BooleanExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> IfElse<'ast, T> for UExpression<'ast, T> {
+    fn if_else(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+    ) -> Self {
+        let bitwidth = consequence.bitwidth();
+        UExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(bitwidth)
+    }
+}
+
 impl<'ast, T: Field> IfElse<'ast, T> for ArrayExpression<'ast, T> {
     fn if_else(
         condition: BooleanExpression<'ast, T>,
@@ -1030,6 +1627,17 @@ impl<'ast, T: Field> IfElse<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> IfElse<'ast, T> for TupleExpression<'ast, T> {
+    fn if_else(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+    ) -> Self {
+        let ty = consequence.ty().clone();
+        TupleExpressionInner::IfElse(box condition, box consequence, box alternative).annotate(ty)
+    }
+}
+
 pub trait Select<'ast, T: Field> {
     fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self;
 }
@@ -1046,6 +1654,17 @@ impl<'ast, T: Field> Select<'ast, T> for BooleanExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Select<'ast, T> for UExpression<'ast, T> {
+    fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self {
+        let bitwidth = match array.inner_type() {
+            Type::Uint(bitwidth) => *bitwidth,
+            _ => unreachable!(),
+        };
+
+        UExpressionInner::Select(box array, box index).annotate(bitwidth)
+    }
+}
+
 impl<'ast, T: Field> Select<'ast, T> for ArrayExpression<'ast, T> {
     fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self {
         let (ty, size) = match array.inner_type() {
@@ -1068,6 +1687,17 @@ impl<'ast, T: Field> Select<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Select<'ast, T> for TupleExpression<'ast, T> {
+    fn select(array: ArrayExpression<'ast, T>, index: FieldElementExpression<'ast, T>) -> Self {
+        let elements = match array.inner_type().clone() {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Select(box array, box index).annotate(elements)
+    }
+}
+
 pub trait Member<'ast, T: Field> {
     fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self;
 }
@@ -1084,6 +1714,25 @@ impl<'ast, T: Field> Member<'ast, T> for BooleanExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Member<'ast, T> for UExpression<'ast, T> {
+    fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self {
+        let members = s.ty().clone();
+
+        let ty = members
+            .into_iter()
+            .find(|(id, _)| *id == member_id)
+            .unwrap()
+            .1;
+
+        let bitwidth = match ty {
+            Type::Uint(bitwidth) => bitwidth,
+            _ => unreachable!(),
+        };
+
+        UExpressionInner::Member(box s, member_id).annotate(bitwidth)
+    }
+}
+
 impl<'ast, T: Field> Member<'ast, T> for ArrayExpression<'ast, T> {
     fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self {
         let members = s.ty().clone();
@@ -1121,3 +1770,82 @@ impl<'ast, T: Field> Member<'ast, T> for StructExpression<'ast, T> {
         StructExpressionInner::Member(box s, member_id).annotate(members)
     }
 }
+
+impl<'ast, T: Field> Member<'ast, T> for TupleExpression<'ast, T> {
+    fn member(s: StructExpression<'ast, T>, member_id: MemberId) -> Self {
+        let members = s.ty().clone();
+
+        let ty = members
+            .into_iter()
+            .find(|(id, _)| *id == member_id)
+            .unwrap()
+            .1;
+
+        let elements = match ty {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Member(box s, member_id).annotate(elements)
+    }
+}
+
+pub trait Element<'ast, T: Field> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self;
+}
+
+impl<'ast, T: Field> Element<'ast, T> for FieldElementExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        FieldElementExpression::Element(box t, index)
+    }
+}
+
+impl<'ast, T: Field> Element<'ast, T> for BooleanExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        BooleanExpression::Element(box t, index)
+    }
+}
+
+impl<'ast, T: Field> Element<'ast, T> for UExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let bitwidth = match t.ty()[index] {
+            Type::Uint(bitwidth) => bitwidth,
+            _ => unreachable!(),
+        };
+
+        UExpressionInner::Element(box t, index).annotate(bitwidth)
+    }
+}
+
+impl<'ast, T: Field> Element<'ast, T> for ArrayExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let (ty, size) = match t.ty()[index].clone() {
+            Type::Array(box ty, size) => (ty, size),
+            _ => unreachable!(),
+        };
+
+        ArrayExpressionInner::Element(box t, index).annotate(ty, size)
+    }
+}
+
+impl<'ast, T: Field> Element<'ast, T> for StructExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let members = match t.ty()[index].clone() {
+            Type::Struct(members) => members,
+            _ => unreachable!(),
+        };
+
+        StructExpressionInner::Element(box t, index).annotate(members)
+    }
+}
+
+impl<'ast, T: Field> Element<'ast, T> for TupleExpression<'ast, T> {
+    fn element(t: TupleExpression<'ast, T>, index: usize) -> Self {
+        let elements = match t.ty()[index].clone() {
+            Type::Tuple(elements) => elements,
+            _ => unreachable!(),
+        };
+
+        TupleExpressionInner::Element(box t, index).annotate(elements)
+    }
+}
@@ -1,7 +1,8 @@
 use crate::typed_absy::Variable;
 use std::fmt;
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct Parameter<'ast> {
     pub id: Variable<'ast>,
     pub private: bool,
@@ -8,8 +8,10 @@ pub type MemberId = String;
 pub enum Type {
     FieldElement,
     Boolean,
+    Uint(usize),
     Array(Box<Type>, usize),
     Struct(Vec<(MemberId, Type)>),
+    Tuple(Vec<Type>),
 }
 
 impl fmt::Display for Type {
@@ -17,6 +19,7 @@ impl fmt::Display for Type {
         match self {
             Type::FieldElement => write!(f, "field"),
             Type::Boolean => write!(f, "bool"),
+            Type::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
             Type::Array(ref ty, ref size) => write!(f, "{}[{}]", ty, size),
             Type::Struct(ref members) => write!(
                 f,
@@ -27,6 +30,15 @@ impl fmt::Display for Type {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Type::Tuple(ref elements) => write!(
+                f,
+                "({})",
+                elements
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -36,6 +48,7 @@ impl fmt::Debug for Type {
         match self {
             Type::FieldElement => write!(f, "field"),
             Type::Boolean => write!(f, "bool"),
+            Type::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
             Type::Array(ref ty, ref size) => write!(f, "{}[{}]", ty, size),
             Type::Struct(ref members) => write!(
                 f,
@@ -46,6 +59,15 @@ impl fmt::Debug for Type {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Type::Tuple(ref elements) => write!(
+                f,
+                "({})",
+                elements
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -59,6 +81,7 @@ impl Type {
         match self {
             Type::FieldElement => String::from("f"),
             Type::Boolean => String::from("b"),
+            Type::Uint(bitwidth) => format!("u{}", bitwidth),
             Type::Array(box ty, size) => format!("{}[{}]", ty.to_slug(), size),
             Type::Struct(members) => format!(
                 "{{{}}}",
@@ -68,6 +91,14 @@ impl Type {
                     .collect::<Vec<_>>()
                     .join(",")
             ),
+            Type::Tuple(elements) => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(|ty| ty.to_slug())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
         }
     }
 
@@ -76,15 +107,18 @@ impl Type {
         match self {
             Type::FieldElement => 1,
             Type::Boolean => 1,
+            Type::Uint(_) => 1,
             Type::Array(ty, size) => size * ty.get_primitive_count(),
             Type::Struct(members) => members.iter().map(|(_, t)| t.get_primitive_count()).sum(),
+            Type::Tuple(elements) => elements.iter().map(|t| t.get_primitive_count()).sum(),
         }
     }
 }
 
 pub type FunctionIdentifier<'ast> = &'ast str;
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct FunctionKey<'ast> {
     pub id: FunctionIdentifier<'ast>,
     pub signature: Signature,
@@ -275,6 +309,15 @@ pub mod signature {
 
             assert_eq!(s.to_slug(), String::from("if[42]f[21]o"));
         }
+
+        #[test]
+        fn uint_slug() {
+            let s = Signature::new()
+                .inputs(vec![Type::Uint(32), Type::Uint(32), Type::FieldElement])
+                .outputs(vec![Type::Uint(8)]);
+
+            assert_eq!(s.to_slug(), String::from("i2u32fou8"));
+        }
     }
 }
 
@@ -287,4 +330,15 @@ mod tests {
         let t = Type::Array(box Type::FieldElement, 42);
         assert_eq!(t.get_primitive_count(), 42);
     }
+
+    #[test]
+    fn tuple() {
+        let t = Type::Tuple(vec![
+            Type::FieldElement,
+            Type::array(Type::Boolean, 3),
+            Type::Uint(32),
+        ]);
+        assert_eq!(t.get_primitive_count(), 5);
+        assert_eq!(t.to_string(), "(field, bool[3], u32)");
+    }
 }
@@ -2,7 +2,8 @@ use crate::typed_absy::types::{MemberId, Type};
 use crate::typed_absy::Identifier;
 use std::fmt;
 
-#[derive(Clone, PartialEq, Hash, Eq)]
+#[derive(Clone, PartialEq, Hash, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "'de: 'ast"))]
 pub struct Variable<'ast> {
     pub id: Identifier<'ast>,
     pub _type: Type,
@@ -17,6 +18,10 @@ impl<'ast> Variable<'ast> {
         Self::with_id_and_type(id, Type::Boolean)
     }
 
+    pub fn uint(id: Identifier<'ast>, bitwidth: usize) -> Variable<'ast> {
+        Self::with_id_and_type(id, Type::Uint(bitwidth))
+    }
+
     #[cfg(test)]
     pub fn field_array(id: Identifier<'ast>, size: usize) -> Variable<'ast> {
         Self::array(id, Type::FieldElement, size)
@@ -30,6 +35,10 @@ impl<'ast> Variable<'ast> {
         Self::with_id_and_type(id, Type::Struct(ty))
     }
 
+    pub fn tuple(id: Identifier<'ast>, ty: Vec<Type>) -> Variable<'ast> {
+        Self::with_id_and_type(id, Type::Tuple(ty))
+    }
+
     pub fn with_id_and_type(id: Identifier<'ast>, _type: Type) -> Variable<'ast> {
         Variable { id, _type }
     }
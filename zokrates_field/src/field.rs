@@ -4,28 +4,23 @@
 // @author Jacob Eberhardt <jacob.eberhardt@tu-berlin.de>
 // @date 2017
 
+use ff::{Field as FfField, PrimeField, PrimeFieldRepr};
 use lazy_static::lazy_static;
-use num_bigint::{BigInt, BigUint, Sign, ToBigInt};
+use num_bigint::{BigInt, BigUint, Sign};
 use num_integer::Integer;
 use num_traits::{One, Zero};
+use pairing::bls12_381::Bls12;
 use pairing::bn256::Bn256;
 use pairing::ff::ScalarEngine;
 use pairing::Engine;
-use serde_derive::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::convert::From;
 use std::fmt;
 use std::fmt::{Debug, Display};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Div, Mul, Sub};
 
-lazy_static! {
-    static ref P: BigInt = BigInt::parse_bytes(
-        b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
-        10
-    )
-    .unwrap();
-}
-
 pub trait Pow<RHS> {
     type Output;
     fn pow(self, _: RHS) -> Self::Output;
@@ -59,15 +54,16 @@ pub trait Field:
     /// An associated type to be able to operate with Bellman ff traits
     type BellmanEngine: Engine;
 
+    /// The name of the curve this is the scalar field of, as given to `--curve`
+    fn name() -> &'static str;
+
     fn from_bellman(e: <Self::BellmanEngine as ScalarEngine>::Fr) -> Self {
-        use ff::{PrimeField, PrimeFieldRepr};
         let mut res: Vec<u8> = vec![];
         e.into_repr().write_le(&mut res).unwrap();
         Self::from_byte_vector(res)
     }
 
     fn into_bellman(self) -> <Self::BellmanEngine as ScalarEngine>::Fr {
-        use ff::PrimeField;
         let s = self.to_dec_string();
         <Self::BellmanEngine as ScalarEngine>::Fr::from_str(&s).unwrap()
     }
@@ -93,283 +89,324 @@ pub trait Field:
     fn to_compact_dec_string(&self) -> String;
 }
 
-#[derive(PartialEq, PartialOrd, Clone, Eq, Ord, Hash, Serialize, Deserialize)]
-pub struct FieldPrime {
+/// The serialized form of the elements of all fields, a `BigInt` between 0 and the modulus
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FieldPrime")]
+struct SerializedField {
     value: BigInt,
 }
 
-impl Field for FieldPrime {
-    type BellmanEngine = Bn256;
+/// Implements `Field` for the integers modulo `$modulus`, the order of the scalar field of the curve `$curve` of
+/// `$engine`, stored in the lazily initialized `$p`
+///
+/// Elements are the 4x64 bit Montgomery form `Fr` of `$engine`, so that arithmetic does not allocate.
+/// They are converted to `BigInt` to be printed, parsed and serialized.
+macro_rules! prime_field {
+    ($name:ident, $p:ident, $modulus:expr, $engine:ty, $curve:expr) => {
+        lazy_static! {
+            static ref $p: BigInt = BigInt::parse_bytes($modulus, 10).unwrap();
+        }
 
-    fn into_byte_vector(&self) -> Vec<u8> {
-        match self.value.to_biguint() {
-            Option::Some(val) => val.to_bytes_le(),
-            Option::None => panic!("Should never happen."),
+        #[derive(Clone, Eq)]
+        pub struct $name {
+            value: <$engine as ScalarEngine>::Fr,
         }
-    }
 
-    fn from_byte_vector(bytes: Vec<u8>) -> Self {
-        let uval = BigUint::from_bytes_le(bytes.as_slice());
-        FieldPrime {
-            value: BigInt::from_biguint(Sign::Plus, uval),
+        impl $name {
+            fn to_bigint(&self) -> BigInt {
+                let mut bytes = vec![];
+                self.value.into_repr().write_le(&mut bytes).unwrap();
+                BigInt::from_bytes_le(Sign::Plus, &bytes)
+            }
+
+            fn from_bigint(x: &BigInt) -> Self {
+                let (_, mut bytes) = (x - x.div_floor(&*$p) * &*$p).to_bytes_le();
+                let mut repr = <<$engine as ScalarEngine>::Fr as PrimeField>::Repr::default();
+                bytes.resize(8 * repr.as_ref().len(), 0);
+                repr.read_le(&bytes[..]).unwrap();
+                $name {
+                    value: PrimeField::from_repr(repr).unwrap(),
+                }
+            }
+
+            fn from_u64(num: u64) -> Self {
+                $name {
+                    value: PrimeField::from_repr(From::from(num)).unwrap(),
+                }
+            }
         }
-    }
 
-    fn to_dec_string(&self) -> String {
-        self.value.to_str_radix(10)
-    }
+        impl Field for $name {
+            type BellmanEngine = $engine;
+
+            fn name() -> &'static str {
+                $curve
+            }
+
+            fn from_bellman(e: <$engine as ScalarEngine>::Fr) -> Self {
+                $name { value: e }
+            }
+
+            fn into_bellman(self) -> <$engine as ScalarEngine>::Fr {
+                self.value
+            }
+
+            fn into_byte_vector(&self) -> Vec<u8> {
+                match self.to_bigint().to_biguint() {
+                    Option::Some(val) => val.to_bytes_le(),
+                    Option::None => panic!("Should never happen."),
+                }
+            }
 
-    fn inverse_mul(&self) -> FieldPrime {
-        let (b, s, _) = extended_euclid(&self.value, &*P);
-        assert_eq!(b, BigInt::one());
-        FieldPrime {
-            value: &s - s.div_floor(&*P) * &*P,
+            fn from_byte_vector(bytes: Vec<u8>) -> Self {
+                let uval = BigUint::from_bytes_le(bytes.as_slice());
+                $name::from_bigint(&BigInt::from_biguint(Sign::Plus, uval))
+            }
+
+            fn to_dec_string(&self) -> String {
+                self.to_bigint().to_str_radix(10)
+            }
+
+            fn inverse_mul(&self) -> $name {
+                $name {
+                    value: self.value.inverse().expect("Zero has no inverse"),
+                }
+            }
+            fn min_value() -> $name {
+                $name::zero()
+            }
+            fn max_value() -> $name {
+                $name::zero() - $name::one()
+            }
+            fn get_required_bits() -> usize {
+                (*$p).bits()
+            }
+            fn try_from_dec_str<'a>(s: &'a str) -> Result<Self, ()> {
+                let x = BigInt::parse_bytes(s.as_bytes(), 10).ok_or(())?;
+                Ok($name::from_bigint(&x))
+            }
+            fn to_compact_dec_string(&self) -> String {
+                // values up to (p-1)/2 included are represented as positive, values between (p+1)/2 and p-1 as represented as negative by subtracting p
+                let value = self.to_bigint();
+                if value <= (&*$p - BigInt::one()) / 2 {
+                    format!("{}", value.to_str_radix(10))
+                } else {
+                    format!("({})", (value - &*$p).to_str_radix(10))
+                }
+            }
         }
-    }
-    fn min_value() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&0).unwrap(),
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::zero()
+            }
         }
-    }
-    fn max_value() -> FieldPrime {
-        FieldPrime {
-            value: &*P - ToBigInt::to_bigint(&1).unwrap(),
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &$name) -> bool {
+                self.value == other.value
+            }
         }
-    }
-    fn get_required_bits() -> usize {
-        (*P).bits()
-    }
-    fn try_from_dec_str<'a>(s: &'a str) -> Result<Self, ()> {
-        let x = BigInt::parse_bytes(s.as_bytes(), 10).ok_or(())?;
-        Ok(FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
-        })
-    }
-    fn to_compact_dec_string(&self) -> String {
-        // values up to (p-1)/2 included are represented as positive, values between (p+1)/2 and p-1 as represented as negative by subtracting p
-        if self.value <= FieldPrime::max_value().value / 2 {
-            format!("{}", self.value.to_str_radix(10))
-        } else {
-            format!(
-                "({})",
-                (&self.value - (FieldPrime::max_value().value + BigInt::one())).to_str_radix(10)
-            )
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.value.into_repr().as_ref().hash(state)
+            }
         }
-    }
-}
 
-impl Default for FieldPrime {
-    fn default() -> Self {
-        FieldPrime {
-            value: BigInt::default(),
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
         }
-    }
-}
 
-impl Display for FieldPrime {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value.to_str_radix(10))
-    }
-}
+        impl Ord for $name {
+            fn cmp(&self, other: &$name) -> Ordering {
+                self.value.into_repr().cmp(&other.value.into_repr())
+            }
+        }
 
-impl Debug for FieldPrime {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value.to_str_radix(10))
-    }
-}
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                SerializedField {
+                    value: self.to_bigint(),
+                }
+                .serialize(serializer)
+            }
+        }
 
-impl From<i32> for FieldPrime {
-    fn from(num: i32) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                SerializedField::deserialize(deserializer).map(|f| $name::from_bigint(&f.value))
+            }
         }
-    }
-}
 
-impl From<u32> for FieldPrime {
-    fn from(num: u32) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.to_dec_string())
+            }
         }
-    }
-}
 
-impl From<usize> for FieldPrime {
-    fn from(num: usize) -> Self {
-        let x = ToBigInt::to_bigint(&num).unwrap();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+        impl Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.to_dec_string())
+            }
         }
-    }
-}
 
-impl Zero for FieldPrime {
-    fn zero() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&0).unwrap(),
+        impl From<i32> for $name {
+            fn from(num: i32) -> Self {
+                let x = $name::from_u64(num.unsigned_abs() as u64);
+                if num < 0 {
+                    $name::zero() - x
+                } else {
+                    x
+                }
+            }
         }
-    }
-    fn is_zero(&self) -> bool {
-        self.value == ToBigInt::to_bigint(&0).unwrap()
-    }
-}
 
-impl One for FieldPrime {
-    fn one() -> FieldPrime {
-        FieldPrime {
-            value: ToBigInt::to_bigint(&1).unwrap(),
+        impl From<u32> for $name {
+            fn from(num: u32) -> Self {
+                $name::from_u64(num as u64)
+            }
+        }
+
+        impl From<usize> for $name {
+            fn from(num: usize) -> Self {
+                $name::from_u64(num as u64)
+            }
         }
-    }
-}
 
-impl Add<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Zero for $name {
+            fn zero() -> $name {
+                $name {
+                    value: FfField::zero(),
+                }
+            }
+            fn is_zero(&self) -> bool {
+                FfField::is_zero(&self.value)
+            }
+        }
 
-    fn add(self, other: FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value + other.value) % &*P,
+        impl One for $name {
+            fn one() -> $name {
+                $name {
+                    value: FfField::one(),
+                }
+            }
         }
-    }
-}
 
-impl<'a> Add<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Add<$name> for $name {
+            type Output = $name;
 
-    fn add(self, other: &FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value + other.value.clone()) % &*P,
+            fn add(self, other: $name) -> $name {
+                self + &other
+            }
         }
-    }
-}
 
-impl Sub<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Add<&'a $name> for $name {
+            type Output = $name;
 
-    fn sub(self, other: FieldPrime) -> FieldPrime {
-        let x = self.value - other.value;
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+            fn add(mut self, other: &$name) -> $name {
+                self.value.add_assign(&other.value);
+                self
+            }
         }
-    }
-}
 
-impl<'a> Sub<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Sub<$name> for $name {
+            type Output = $name;
 
-    fn sub(self, other: &FieldPrime) -> FieldPrime {
-        let x = self.value - other.value.clone();
-        FieldPrime {
-            value: &x - x.div_floor(&*P) * &*P,
+            fn sub(self, other: $name) -> $name {
+                self - &other
+            }
         }
-    }
-}
 
-impl Mul<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Sub<&'a $name> for $name {
+            type Output = $name;
 
-    fn mul(self, other: FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value * other.value) % &*P,
+            fn sub(mut self, other: &$name) -> $name {
+                self.value.sub_assign(&other.value);
+                self
+            }
         }
-    }
-}
 
-impl<'a> Mul<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Mul<$name> for $name {
+            type Output = $name;
 
-    fn mul(self, other: &FieldPrime) -> FieldPrime {
-        FieldPrime {
-            value: (self.value * other.value.clone()) % &*P,
+            fn mul(self, other: $name) -> $name {
+                self * &other
+            }
         }
-    }
-}
 
-impl Div<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Mul<&'a $name> for $name {
+            type Output = $name;
 
-    fn div(self, other: FieldPrime) -> FieldPrime {
-        self * other.inverse_mul()
-    }
-}
+            fn mul(mut self, other: &$name) -> $name {
+                self.value.mul_assign(&other.value);
+                self
+            }
+        }
 
-impl<'a> Div<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Div<$name> for $name {
+            type Output = $name;
 
-    fn div(self, other: &FieldPrime) -> FieldPrime {
-        self / other.clone()
-    }
-}
+            fn div(self, other: $name) -> $name {
+                self * other.inverse_mul()
+            }
+        }
+
+        impl<'a> Div<&'a $name> for $name {
+            type Output = $name;
 
-impl Pow<usize> for FieldPrime {
-    type Output = FieldPrime;
+            fn div(self, other: &$name) -> $name {
+                self * other.inverse_mul()
+            }
+        }
+
+        impl Pow<usize> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: usize) -> FieldPrime {
-        let mut res = FieldPrime::from(1);
-        for _ in 0..exp {
-            res = res * &self;
+            fn pow(self, exp: usize) -> $name {
+                $name {
+                    value: self.value.pow(&[exp as u64]),
+                }
+            }
         }
-        res
-    }
-}
 
-impl Pow<FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl Pow<$name> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: FieldPrime) -> FieldPrime {
-        let mut res = FieldPrime::one();
-        let mut current = FieldPrime::zero();
-        loop {
-            if current >= exp {
-                return res;
+            fn pow(self, exp: $name) -> $name {
+                self.pow(&exp)
             }
-            res = res * &self;
-            current = current + FieldPrime::one();
         }
-    }
-}
 
-impl<'a> Pow<&'a FieldPrime> for FieldPrime {
-    type Output = FieldPrime;
+        impl<'a> Pow<&'a $name> for $name {
+            type Output = $name;
 
-    fn pow(self, exp: &'a FieldPrime) -> FieldPrime {
-        let mut res = FieldPrime::one();
-        let mut current = FieldPrime::zero();
-        loop {
-            if &current >= exp {
-                return res;
+            fn pow(self, exp: &'a $name) -> $name {
+                $name {
+                    value: self.value.pow(exp.value.into_repr()),
+                }
             }
-            res = res * &self;
-            current = current + FieldPrime::one();
         }
-    }
+    };
 }
 
-/// Calculates the gcd using an iterative implementation of the extended euclidian algorithm.
-/// Returning `(d, s, t)` so that `d = s * a + t * b`
-///
-/// # Arguments
-/// * `a` - First number as `BigInt`
-/// * `b` - Second number as `BigInt`
-fn extended_euclid(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
-    let (mut s, mut old_s) = (BigInt::zero(), BigInt::one());
-    let (mut t, mut old_t) = (BigInt::one(), BigInt::zero());
-    let (mut r, mut old_r) = (b.clone(), a.clone());
-    while !&r.is_zero() {
-        let quotient = &old_r / &r;
-        let tmp_r = old_r.clone();
-        old_r = r.clone();
-        r = &tmp_r - &quotient * &r;
-        let tmp_s = old_s.clone();
-        old_s = s.clone();
-        s = &tmp_s - &quotient * &s;
-        let tmp_t = old_t.clone();
-        old_t = t.clone();
-        t = &tmp_t - &quotient * &t;
-    }
-    return (old_r, old_s, old_t);
-}
+prime_field!(
+    FieldPrime,
+    P,
+    b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    Bn256,
+    "bn128"
+);
+
+prime_field!(
+    Bls12_381Field,
+    P_BLS12_381,
+    b"52435875175126190479447740508185965837690552500527637822603658699938581184513",
+    Bls12,
+    "bls12_381"
+);
 
 #[cfg(test)]
 mod tests {
@@ -390,7 +427,7 @@ mod tests {
         fn positive_number() {
             assert_eq!(
                 "1234245612".parse::<BigInt>().unwrap(),
-                FieldPrime::from("1234245612").value
+                FieldPrime::from("1234245612").to_bigint()
             );
         }
 
@@ -398,19 +435,25 @@ mod tests {
         fn negative_number() {
             assert_eq!(
                 P.checked_sub(&"12".parse::<BigInt>().unwrap()).unwrap(),
-                FieldPrime::from("-12").value
+                FieldPrime::from("-12").to_bigint()
             );
         }
 
+        #[test]
+        fn from_i32() {
+            assert_eq!(FieldPrime::from(-12), FieldPrime::from("-12"));
+            assert_eq!(FieldPrime::from(i32::MIN), FieldPrime::from("-2147483648"));
+        }
+
         #[test]
         fn addition() {
             assert_eq!(
                 "65484493".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") + FieldPrime::from("68135")).value
+                (FieldPrime::from("65416358") + FieldPrime::from("68135")).to_bigint()
             );
             assert_eq!(
                 "65484493".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") + &FieldPrime::from("68135")).value
+                (FieldPrime::from("65416358") + &FieldPrime::from("68135")).to_bigint()
             );
         }
 
@@ -418,11 +461,11 @@ mod tests {
         fn addition_negative_small() {
             assert_eq!(
                 "3".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("5") + FieldPrime::from("-2")).value
+                (FieldPrime::from("5") + FieldPrime::from("-2")).to_bigint()
             );
             assert_eq!(
                 "3".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("5") + &FieldPrime::from("-2")).value
+                (FieldPrime::from("5") + &FieldPrime::from("-2")).to_bigint()
             );
         }
 
@@ -430,11 +473,11 @@ mod tests {
         fn addition_negative() {
             assert_eq!(
                 "65348223".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") + FieldPrime::from("-68135")).value
+                (FieldPrime::from("65416358") + FieldPrime::from("-68135")).to_bigint()
             );
             assert_eq!(
                 "65348223".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") + &FieldPrime::from("-68135")).value
+                (FieldPrime::from("65416358") + &FieldPrime::from("-68135")).to_bigint()
             );
         }
 
@@ -442,11 +485,11 @@ mod tests {
         fn subtraction() {
             assert_eq!(
                 "65348223".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") - FieldPrime::from("68135")).value
+                (FieldPrime::from("65416358") - FieldPrime::from("68135")).to_bigint()
             );
             assert_eq!(
                 "65348223".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") - &FieldPrime::from("68135")).value
+                (FieldPrime::from("65416358") - &FieldPrime::from("68135")).to_bigint()
             );
         }
 
@@ -454,11 +497,11 @@ mod tests {
         fn subtraction_negative() {
             assert_eq!(
                 "65484493".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") - FieldPrime::from("-68135")).value
+                (FieldPrime::from("65416358") - FieldPrime::from("-68135")).to_bigint()
             );
             assert_eq!(
                 "65484493".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("65416358") - &FieldPrime::from("-68135")).value
+                (FieldPrime::from("65416358") - &FieldPrime::from("-68135")).to_bigint()
             );
         }
 
@@ -468,13 +511,13 @@ mod tests {
                 "21888242871839275222246405745257275088548364400416034343698204186575743147394"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("68135") - FieldPrime::from("65416358")).value
+                (FieldPrime::from("68135") - FieldPrime::from("65416358")).to_bigint()
             );
             assert_eq!(
                 "21888242871839275222246405745257275088548364400416034343698204186575743147394"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("68135") - &FieldPrime::from("65416358")).value
+                (FieldPrime::from("68135") - &FieldPrime::from("65416358")).to_bigint()
             );
         }
 
@@ -482,11 +525,11 @@ mod tests {
         fn multiplication() {
             assert_eq!(
                 "13472".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("32") * FieldPrime::from("421")).value
+                (FieldPrime::from("32") * FieldPrime::from("421")).to_bigint()
             );
             assert_eq!(
                 "13472".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("32") * &FieldPrime::from("421")).value
+                (FieldPrime::from("32") * &FieldPrime::from("421")).to_bigint()
             );
         }
 
@@ -496,13 +539,13 @@ mod tests {
                 "21888242871839275222246405745257275088548364400416034343698204186575808014369"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("54") * FieldPrime::from("-8912")).value
+                (FieldPrime::from("54") * FieldPrime::from("-8912")).to_bigint()
             );
             assert_eq!(
                 "21888242871839275222246405745257275088548364400416034343698204186575808014369"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("54") * &FieldPrime::from("-8912")).value
+                (FieldPrime::from("54") * &FieldPrime::from("-8912")).to_bigint()
             );
         }
 
@@ -510,11 +553,11 @@ mod tests {
         fn multiplication_two_negative() {
             assert_eq!(
                 "648".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("-54") * FieldPrime::from("-12")).value
+                (FieldPrime::from("-54") * FieldPrime::from("-12")).to_bigint()
             );
             assert_eq!(
                 "648".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("-54") * &FieldPrime::from("-12")).value
+                (FieldPrime::from("-54") * &FieldPrime::from("-12")).to_bigint()
             );
         }
 
@@ -527,7 +570,7 @@ mod tests {
                 (FieldPrime::from(
                     "21888242871839225222246405785257275088694311157297823662689037894645225727"
                 ) * FieldPrime::from("218882428715392752222464057432572755886923"))
-                .value
+                .to_bigint()
             );
             assert_eq!(
                 "6042471409729479866150380306128222617399890671095126975526159292198160466142"
@@ -536,7 +579,7 @@ mod tests {
                 (FieldPrime::from(
                     "21888242871839225222246405785257275088694311157297823662689037894645225727"
                 ) * &FieldPrime::from("218882428715392752222464057432572755886923"))
-                    .value
+                    .to_bigint()
             );
         }
 
@@ -568,11 +611,11 @@ mod tests {
         fn pow_small() {
             assert_eq!(
                 "8".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("2").pow(FieldPrime::from("3"))).value
+                (FieldPrime::from("2").pow(FieldPrime::from("3"))).to_bigint()
             );
             assert_eq!(
                 "8".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("2").pow(&FieldPrime::from("3"))).value
+                (FieldPrime::from("2").pow(&FieldPrime::from("3"))).to_bigint()
             );
         }
 
@@ -580,7 +623,7 @@ mod tests {
         fn pow_usize() {
             assert_eq!(
                 "614787626176508399616".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("54").pow(12)).value
+                (FieldPrime::from("54").pow(12)).to_bigint()
             );
         }
 
@@ -588,11 +631,11 @@ mod tests {
         fn pow() {
             assert_eq!(
                 "614787626176508399616".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("54").pow(FieldPrime::from("12"))).value
+                (FieldPrime::from("54").pow(FieldPrime::from("12"))).to_bigint()
             );
             assert_eq!(
                 "614787626176508399616".parse::<BigInt>().unwrap(),
-                (FieldPrime::from("54").pow(&FieldPrime::from("12"))).value
+                (FieldPrime::from("54").pow(&FieldPrime::from("12"))).to_bigint()
             );
         }
 
@@ -602,13 +645,13 @@ mod tests {
                 "21888242871839275222246405745257275088548364400416034343686819230535502784513"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("-54").pow(FieldPrime::from("11"))).value
+                (FieldPrime::from("-54").pow(FieldPrime::from("11"))).to_bigint()
             );
             assert_eq!(
                 "21888242871839275222246405745257275088548364400416034343686819230535502784513"
                     .parse::<BigInt>()
                     .unwrap(),
-                (FieldPrime::from("-54").pow(&FieldPrime::from("11"))).value
+                (FieldPrime::from("-54").pow(&FieldPrime::from("11"))).to_bigint()
             );
         }
 
@@ -626,6 +669,19 @@ mod tests {
             assert_eq!(FieldPrime::from("11"), deserialized);
         }
 
+        #[test]
+        fn serde_format() {
+            // elements are serialized as they were when stored as a `BigInt`
+            assert_eq!(
+                serde_json::to_string(&FieldPrime::from("11")).unwrap(),
+                r#"{"value":[1,[11]]}"#
+            );
+            assert_eq!(
+                serialize(&FieldPrime::from("-1"), Infinite).unwrap(),
+                serialize(&(&*P - BigInt::one()), Infinite).unwrap()
+            );
+        }
+
         #[test]
         fn bytes_ser_deser() {
             let fp = FieldPrime::from("101");
@@ -683,50 +739,16 @@ mod tests {
     }
 
     #[test]
-    fn test_extended_euclid() {
-        assert_eq!(
-            (
-                ToBigInt::to_bigint(&1).unwrap(),
-                ToBigInt::to_bigint(&-9).unwrap(),
-                ToBigInt::to_bigint(&47).unwrap()
-            ),
-            extended_euclid(
-                &ToBigInt::to_bigint(&120).unwrap(),
-                &ToBigInt::to_bigint(&23).unwrap()
-            )
-        );
-        assert_eq!(
-            (
-                ToBigInt::to_bigint(&2).unwrap(),
-                ToBigInt::to_bigint(&2).unwrap(),
-                ToBigInt::to_bigint(&-11).unwrap()
-            ),
-            extended_euclid(
-                &ToBigInt::to_bigint(&122).unwrap(),
-                &ToBigInt::to_bigint(&22).unwrap()
-            )
-        );
-        assert_eq!(
-            (
-                ToBigInt::to_bigint(&2).unwrap(),
-                ToBigInt::to_bigint(&-9).unwrap(),
-                ToBigInt::to_bigint(&47).unwrap()
-            ),
-            extended_euclid(
-                &ToBigInt::to_bigint(&240).unwrap(),
-                &ToBigInt::to_bigint(&46).unwrap()
-            )
-        );
-        let (b, s, _) = extended_euclid(&ToBigInt::to_bigint(&253).unwrap(), &*P);
-        assert_eq!(b, BigInt::one());
-        let s_field = FieldPrime {
-            value: &s - s.div_floor(&*P) * &*P,
-        };
+    fn inverse() {
         assert_eq!(
             FieldPrime::from(
                 "12717674712096337777352654721552646000065650461901806515903699665717959876900"
             ),
-            s_field
+            FieldPrime::from(253).inverse_mul()
+        );
+        assert_eq!(
+            FieldPrime::one(),
+            FieldPrime::from(-54).inverse_mul() * FieldPrime::from(-54)
         );
     }
 
@@ -798,4 +820,45 @@ mod tests {
             assert_eq!(FieldPrime::from_bellman(a), cc);
         }
     }
+
+    mod bls12_381 {
+        use super::*;
+
+        use ff::Field as FField;
+
+        extern crate rand;
+        use pairing::bls12_381::Fr;
+        use rand::{thread_rng, Rng};
+
+        #[test]
+        fn fr_to_field_to_fr() {
+            let rng = &mut thread_rng();
+            for _ in 0..1000 {
+                let a: Fr = rng.gen();
+                assert_eq!(Bls12_381Field::from_bellman(a).into_bellman(), a);
+            }
+        }
+
+        #[test]
+        fn minus_one() {
+            let mut a: Fr = Fr::one();
+            a.negate();
+            assert_eq!(Bls12_381Field::from_bellman(a), Bls12_381Field::from(-1));
+        }
+
+        #[test]
+        fn modulus() {
+            assert_eq!(
+                Bls12_381Field::max_value().to_dec_string(),
+                "52435875175126190479447740508185965837690552500527637822603658699938581184512"
+            );
+            assert_eq!(
+                Bls12_381Field::max_value() + Bls12_381Field::one(),
+                Bls12_381Field::zero()
+            );
+            assert_eq!(Bls12_381Field::get_required_bits(), 255);
+            assert_eq!(Bls12_381Field::name(), "bls12_381");
+            assert_eq!(FieldPrime::name(), "bn128");
+        }
+    }
 }
@@ -1,116 +1,219 @@
+extern crate zokrates_core;
+
 use std::fs::File;
 use std::io;
-use std::io::BufReader;
+use std::io::Read;
 use std::path::Path;
 use std::path::{Component, PathBuf};
+use zokrates_core::resolve::Resolver;
 
 const ZOKRATES_HOME: &str = &"ZOKRATES_HOME";
 
-pub fn resolve<'a>(
-    location: Option<String>,
-    source: &'a str,
-) -> Result<(BufReader<File>, String, &'a str), io::Error> {
-    // the fs resolver has to be provided a location, as it supports relative paths
-    match location {
-        Some(location) => resolve_with_location(location, source),
-        None => Err(io::Error::new(io::ErrorKind::Other, "No location provided")),
-    }
+/// A resolver finding modules on the file system
+///
+/// Paths starting with `./` or `../` are interpreted relative to the importing module, and other paths relative to
+/// each search path in turn.
+#[derive(Debug, Default, Clone)]
+pub struct FsResolver {
+    search_paths: Vec<PathBuf>,
 }
 
-fn resolve_with_location<'a>(
-    location: String,
-    source: &'a str,
-) -> Result<(BufReader<File>, String, &'a str), io::Error> {
-    let source = Path::new(source);
+impl FsResolver {
+    pub fn new() -> Self {
+        FsResolver::default()
+    }
 
-    // paths starting with `./` or `../` are interpreted relative to the current file
-    // other paths `abc/def` are interpreted relative to $ZOKRATES_HOME
-    let base = match source.components().next() {
-        Some(Component::CurDir) | Some(Component::ParentDir) => PathBuf::from(location),
-        _ => PathBuf::from(
-            std::env::var(ZOKRATES_HOME).expect("$ZOKRATES_HOME is not set, please set it"),
-        ),
-    };
+    pub fn with_search_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
 
-    let path_owned = base.join(PathBuf::from(source)).with_extension("zok");
+    /// Add `$ZOKRATES_HOME` to the search paths, if it is set
+    pub fn with_zokrates_home(self) -> Self {
+        match std::env::var_os(ZOKRATES_HOME) {
+            Some(home) => self.with_search_path(home),
+            None => self,
+        }
+    }
 
-    if path_owned.is_dir() {
-        return Err(io::Error::new(io::ErrorKind::Other, "Not a file"));
+    fn find(&self, location: Option<String>, source: &Path) -> Result<PathBuf, io::Error> {
+        match source.components().next() {
+            Some(Component::CurDir) | Some(Component::ParentDir) => {
+                // the fs resolver has to be provided a location to resolve relative paths
+                let location = location
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No location provided"))?;
+                // the location is the importing module, or the directory it is in
+                let location = Path::new(&location);
+                let base = match location.is_dir() {
+                    true => location,
+                    false => location
+                        .parent()
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Invalid location"))?,
+                };
+                Ok(base.join(source).with_extension("zok"))
+            }
+            _ => {
+                if self.search_paths.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("{} not found: no search path provided", source.display()),
+                    ));
+                }
+
+                self.search_paths
+                    .iter()
+                    .map(|search_path| search_path.join(source).with_extension("zok"))
+                    .find(|path| path.is_file())
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!(
+                                "{} not found in {}",
+                                source.display(),
+                                self.search_paths
+                                    .iter()
+                                    .map(|p| p.display().to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
+                        )
+                    })
+            }
+        }
     }
+}
 
-    let alias = generate_alias(source);
-    let next_location = generate_next_location(&path_owned)?;
+impl Resolver<io::Error> for FsResolver {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), io::Error> {
+        let path = self.find(current_location, Path::new(import_location))?;
 
-    File::open(path_owned).and_then(|f| Ok((BufReader::new(f), next_location, alias)))
-}
+        if path.is_dir() {
+            return Err(io::Error::new(io::ErrorKind::Other, "Not a file"));
+        }
+
+        let mut source = String::new();
+        File::open(&path)?.read_to_string(&mut source)?;
 
-fn generate_next_location<'a>(path: &'a PathBuf) -> Result<String, io::Error> {
-    path.parent()
-        .ok_or(io::Error::new(io::ErrorKind::Other, "Invalid path"))
-        .map(|v| v.to_path_buf().into_os_string().into_string().unwrap())
+        Ok((source, generate_next_location(&path)?))
+    }
 }
 
-fn generate_alias<'a>(path: &'a Path) -> &'a str {
-    path.file_stem().unwrap().to_str().unwrap()
+// the location of a module is its canonical path, so that a file imported through different paths is the same module
+fn generate_next_location(path: &PathBuf) -> Result<String, io::Error> {
+    path.canonicalize()
+        .map(|v| v.into_os_string().into_string().unwrap())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
+
+    fn location(folder: &Path) -> Option<String> {
+        Some(folder.to_string_lossy().to_string())
+    }
 
     #[test]
     fn valid_path_with_location() {
-        use std::io::Write;
+        // create a source folder with a zok file
+        let folder = tempfile::tempdir().unwrap();
+        let file_path = folder.path().join("bar.zok");
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, "some code").unwrap();
+        let (code, next_location) = FsResolver::new()
+            .resolve(location(folder.path()), "./bar")
+            .unwrap();
+        assert_eq!(
+            next_location,
+            file_path
+                .canonicalize()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(code, String::from("some code\n"));
+    }
+
+    #[test]
+    fn valid_path_with_module_location() {
+        // the location can also be the importing module itself
+        let folder = tempfile::tempdir().unwrap();
+        let file_path = folder.path().join("bar.zok");
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, "some code").unwrap();
+        let (code, _) = FsResolver::new()
+            .resolve(
+                Some(folder.path().join("main.zok").to_string_lossy().to_string()),
+                "./bar",
+            )
+            .unwrap();
+        assert_eq!(code, String::from("some code\n"));
+    }
 
+    #[test]
+    fn same_location_for_different_paths() {
         // create a source folder with a zok file
         let folder = tempfile::tempdir().unwrap();
+        let subfolder = tempfile::tempdir_in(&folder).unwrap();
         let file_path = folder.path().join("bar.zok");
-        let mut file = File::create(file_path).unwrap();
+        let mut file = File::create(&file_path).unwrap();
         writeln!(file, "some code").unwrap();
-        let (_, next_location, alias) =
-            resolve(Some(folder.path().to_str().unwrap().to_string()), &"./bar").unwrap();
-        assert_eq!(next_location, folder.path().to_str().unwrap().to_string());
-        assert_eq!(alias, String::from("bar"));
+
+        let (_, location_from_folder) = FsResolver::new()
+            .resolve(location(folder.path()), "./bar")
+            .unwrap();
+
+        let subfolder_name = subfolder.path().file_name().unwrap().to_str().unwrap();
+        let (_, location_from_subfolder) = FsResolver::new()
+            .resolve(
+                location(subfolder.path()),
+                &format!("../{}/../bar.zok", subfolder_name),
+            )
+            .unwrap();
+
+        assert_eq!(location_from_folder, location_from_subfolder);
     }
 
     #[test]
     fn valid_path_without_location() {
-        let res = resolve(None, &"./src/lib.rs");
+        let res = FsResolver::new().resolve(None, "./src/lib.rs");
         assert!(res.is_err());
     }
 
     #[test]
     fn non_existing_file() {
-        let res = resolve(Some(String::from("./src")), &"./rubbish");
+        let res = FsResolver::new().resolve(Some(String::from("./src")), "./rubbish");
         assert!(res.is_err());
     }
 
     #[test]
     fn invalid_location() {
-        let res = resolve(Some(String::from(",8!-$2abc")), &"./foo.zok");
+        let res = FsResolver::new().resolve(Some(String::from(",8!-$2abc")), "./foo.zok");
         assert!(res.is_err());
     }
 
     #[test]
     fn not_a_file() {
-        let res = resolve(Some(String::from(".")), &"./src/");
+        let res = FsResolver::new().resolve(Some(String::from(".")), "./src/");
         assert!(res.is_err());
     }
 
     #[test]
     fn no_parent() {
-        let res = resolve(Some(String::from(".")), &".");
+        let res = FsResolver::new().resolve(Some(String::from(".")), ".");
         assert!(res.is_err());
     }
 
     #[test]
     fn treat_relative_as_local() {
-        use std::io::BufRead;
-        use std::io::Write;
-
-        // create a HOME folder with a code file
-        let zokrates_home_folder = tempfile::tempdir().unwrap();
-        let file_path = zokrates_home_folder.path().join("bar.zok");
+        // create a search path folder with a code file
+        let search_path = tempfile::tempdir().unwrap();
+        let file_path = search_path.path().join("bar.zok");
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<stdlib code>").unwrap();
 
@@ -120,34 +223,19 @@ mod tests {
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<user code>").unwrap();
 
-        // assign HOME folder to ZOKRATES_HOME
-        std::env::set_var(ZOKRATES_HOME, zokrates_home_folder.path());
-
-        let result = resolve(
-            Some(
-                source_folder
-                    .path()
-                    .to_path_buf()
-                    .to_string_lossy()
-                    .to_string(),
-            ),
-            &"./bar.zok",
-        );
+        let result = FsResolver::new()
+            .with_search_path(search_path.path())
+            .resolve(location(source_folder.path()), "./bar.zok");
         assert!(result.is_ok());
-        let mut code = String::new();
-        result.unwrap().0.read_line(&mut code).unwrap();
         // the imported file should be the user's
-        assert_eq!(code, "<user code>\n".to_string());
+        assert_eq!(result.unwrap().0, "<user code>\n".to_string());
     }
 
     #[test]
     fn treat_absolute_as_std() {
-        use std::io::BufRead;
-        use std::io::Write;
-
-        // create a HOME folder with a code file
-        let zokrates_home_folder = tempfile::tempdir().unwrap();
-        let file_path = zokrates_home_folder.path().join("bar.zok");
+        // create a search path folder with a code file
+        let search_path = tempfile::tempdir().unwrap();
+        let file_path = search_path.path().join("bar.zok");
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<stdlib code>").unwrap();
 
@@ -157,31 +245,43 @@ mod tests {
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<user code>").unwrap();
 
-        // assign HOME folder to ZOKRATES_HOME
-        std::env::set_var(ZOKRATES_HOME, zokrates_home_folder.path());
-
-        let result = resolve(
-            Some(
-                source_folder
-                    .path()
-                    .to_path_buf()
-                    .to_string_lossy()
-                    .to_string(),
-            ),
-            &"bar.zok",
-        );
+        let result = FsResolver::new()
+            .with_search_path(search_path.path())
+            .resolve(location(source_folder.path()), "bar.zok");
         assert!(result.is_ok());
-        let mut code = String::new();
-        result.unwrap().0.read_line(&mut code).unwrap();
-        // the imported file should be the user's
-        assert_eq!(code, "<stdlib code>\n".to_string());
+        // the imported file should be the one in the search path
+        assert_eq!(result.unwrap().0, "<stdlib code>\n".to_string());
     }
 
     #[test]
-    fn navigate_up() {
-        use std::io::BufRead;
-        use std::io::Write;
+    fn search_paths_in_order() {
+        // create two search path folders, only the second one having `foo`
+        let first = tempfile::tempdir().unwrap();
+        let mut file = File::create(first.path().join("bar.zok")).unwrap();
+        writeln!(file, "<first bar>").unwrap();
+
+        let second = tempfile::tempdir().unwrap();
+        let mut file = File::create(second.path().join("bar.zok")).unwrap();
+        writeln!(file, "<second bar>").unwrap();
+        let mut file = File::create(second.path().join("foo.zok")).unwrap();
+        writeln!(file, "<second foo>").unwrap();
+
+        let resolver = FsResolver::new()
+            .with_search_path(first.path())
+            .with_search_path(second.path());
+
+        assert_eq!(
+            resolver.resolve(None, "bar").unwrap().0,
+            "<first bar>\n".to_string()
+        );
+        assert_eq!(
+            resolver.resolve(None, "foo").unwrap().0,
+            "<second foo>\n".to_string()
+        );
+    }
 
+    #[test]
+    fn navigate_up() {
         // create a user folder with a code file
         let source_folder = tempfile::tempdir().unwrap();
         let source_subfolder = tempfile::tempdir_in(&source_folder).unwrap();
@@ -189,51 +289,129 @@ mod tests {
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<user code>").unwrap();
 
-        let result = resolve(
-            Some(
-                source_subfolder
-                    .path()
-                    .to_path_buf()
-                    .to_string_lossy()
-                    .to_string(),
-            ),
-            &"../bar.zok",
-        );
+        let result = FsResolver::new().resolve(location(source_subfolder.path()), "../bar.zok");
         assert!(result.is_ok());
-        let mut code = String::new();
-        result.unwrap().0.read_line(&mut code).unwrap();
         // the imported file should be the user's
-        assert_eq!(code, "<user code>\n".to_string());
+        assert_eq!(result.unwrap().0, "<user code>\n".to_string());
     }
 
     #[test]
     fn dont_fallback_to_std() {
-        use std::io::Write;
-
-        // create a HOME folder
-        let zokrates_home_folder = tempfile::tempdir().unwrap();
-        let file_path = zokrates_home_folder.path().join("bar.zok");
+        // create a search path folder
+        let search_path = tempfile::tempdir().unwrap();
+        let file_path = search_path.path().join("bar.zok");
         let mut file = File::create(file_path).unwrap();
         writeln!(file, "<stdlib code>").unwrap();
 
-        // assign HOME folder to ZOKRATES_HOME
-        std::env::set_var(ZOKRATES_HOME, zokrates_home_folder.path());
-
-        let result = resolve(Some("/path/to/user/folder".to_string()), &"./bar.zok");
+        let result = FsResolver::new()
+            .with_search_path(search_path.path())
+            .resolve(Some("/path/to/user/folder".to_string()), "./bar.zok");
         assert!(result.is_err());
     }
 
     #[test]
     fn fail_if_not_found_in_std() {
-        std::env::set_var(ZOKRATES_HOME, "");
-        let result = resolve(Some("/path/to/source".to_string()), &"bar.zok");
+        let result = FsResolver::new()
+            .with_search_path("")
+            .resolve(Some("/path/to/source".to_string()), "bar.zok");
         assert!(result.is_err());
     }
 
     #[test]
-    #[should_panic]
-    fn panic_if_home_not_set() {
-        std::env::remove_var(ZOKRATES_HOME);
-        let _ = resolve(Some("/path/to/source".to_string()), &"bar.zok");
+    fn fail_without_search_path() {
+        let result = FsResolver::new().resolve(Some("/path/to/source".to_string()), "bar.zok");
+        assert!(result.is_err());
+    }
+
+    mod cycles {
+        extern crate zokrates_field;
+
+        use self::zokrates_field::field::FieldPrime;
+        use super::*;
+        use std::io::{BufReader, Write};
+        use zokrates_core::compile::compile;
+        use zokrates_core::ir;
+
+        fn write_module(folder: &Path, name: &str, code: &str) -> String {
+            let path = folder.join(name);
+            let mut file = File::create(&path).unwrap();
+            write!(file, "{}", code).unwrap();
+            path.canonicalize().unwrap().to_str().unwrap().to_string()
+        }
+
+        fn compile_module(location: &str) -> Result<ir::Prog<FieldPrime>, String> {
+            let mut reader = BufReader::new(File::open(location).unwrap());
+            compile(
+                &mut reader,
+                Some(location.to_string()),
+                Some(&FsResolver::new()),
+            )
+            .map_err(|e| e.to_string())
+        }
+
+        #[test]
+        fn detect_cycle() {
+            let folder = tempfile::tempdir().unwrap();
+            let a = write_module(
+                folder.path(),
+                "a.zok",
+                "import \"./b\"\n\ndef main() -> (field):\n\treturn b()\n",
+            );
+            let b = write_module(
+                folder.path(),
+                "b.zok",
+                "// b imports a\nimport \"./a\"\n\ndef main() -> (field):\n\treturn a()\n",
+            );
+
+            let error = compile_module(&a).unwrap_err();
+
+            assert!(error.contains(&format!("Import cycle detected: {} -> {} -> {}", a, b, a)));
+            // the position of each import of the cycle is reported
+            assert!(error.contains(&format!("{}:1:1: import of {}", a, b)));
+            assert!(error.contains(&format!("{}:2:1: import of {}", b, a)));
+        }
+
+        #[test]
+        fn detect_self_import() {
+            let folder = tempfile::tempdir().unwrap();
+            let a = write_module(
+                folder.path(),
+                "a.zok",
+                "import \"./a\" as foo\n\ndef main() -> (field):\n\treturn foo()\n",
+            );
+
+            let error = compile_module(&a).unwrap_err();
+
+            assert!(error.contains(&format!("Import cycle detected: {} -> {}", a, a)));
+            assert!(error.contains(&format!("{}:1:1: import of {}", a, a)));
+        }
+
+        #[test]
+        fn accept_diamond() {
+            // a module imported along different paths is not a cycle
+            let folder = tempfile::tempdir().unwrap();
+            write_module(
+                folder.path(),
+                "d.zok",
+                "def main() -> (field):\n\treturn 1\n",
+            );
+            write_module(
+                folder.path(),
+                "b.zok",
+                "import \"./d\"\n\ndef main() -> (field):\n\treturn d()\n",
+            );
+            write_module(
+                folder.path(),
+                "c.zok",
+                "import \"./d\"\n\ndef main() -> (field):\n\treturn d()\n",
+            );
+            let a = write_module(
+                folder.path(),
+                "a.zok",
+                "import \"./b\"\nimport \"./c\"\n\ndef main() -> (field):\n\treturn b() + c()\n",
+            );
+
+            assert!(compile_module(&a).is_ok());
+        }
     }
 }
@@ -16,48 +16,239 @@
 //! repository's `master` branch by downloading from URL:
 //! https://raw.githubusercontent.com/Zokrates/ZoKrates/master/zokrates_cli/examples/merkleTree/sha256PathProof3.zok
 //!
+//! ## Lock file and cache
+//!
+//! Branches are mutable, so the first time an import is resolved, its branch is resolved to a commit, and the
+//! commit and the sha256 hash of the imported file are recorded in a lock file (`zokrates.lock`). Later
+//! builds download the file at the locked commit and check its hash, so that they import the exact same code.
+//!
+//! Downloaded files are stored in a cache directory under their hash, and read from there whenever the hash
+//! matches the lock file. In offline mode, nothing is downloaded and imports which are not locked and cached fail.
+//!
 
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
 use reqwest;
-use std::fs::File;
-use std::io::{self, copy, BufReader};
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io;
 use std::path::{Path, PathBuf};
-use tempfile::NamedTempFile;
+use zokrates_core::resolve::{relative_path, Resolver};
 
 #[cfg(test)]
 use mockito::{self, Mock};
 
 /// Prefix for github import source to be distinguished.
-const GITHUB_IMPORT_PREFIX: &str = "github.com/";
+pub const GITHUB_IMPORT_PREFIX: &str = "github.com/";
+
+/// Default name of the lock file
+pub const LOCK_FILE_NAME: &str = "zokrates.lock";
 
 /// GitHub download URL base
 #[cfg(not(test))]
 const GITHUB_URL_BASE: &str = "https://raw.githubusercontent.com";
 
-/// Resolves import from the Github.
-/// This importer needs to be provided with location since relative paths could be used inside the
-/// files that are imported from github.
-pub fn resolve<'a>(
-    location: Option<String>,
-    path: &'a str,
-) -> Result<(BufReader<File>, String, &'a str), io::Error> {
-    if let Some(location) = location {
-        let path = Path::new(path);
+/// GitHub API URL base, used to resolve branches to commits
+#[cfg(not(test))]
+const GITHUB_API_URL_BASE: &str = "https://api.github.com";
+
+/// The commit and content hash an import is locked to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockEntry {
+    /// The commit the branch of the import was resolved to
+    pub commit: String,
+    /// The hex encoded sha256 hash of the imported file
+    pub hash: String,
+}
+
+/// The content of a lock file: the entry of each github import, by import location
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lock {
+    pub imports: BTreeMap<String, LockEntry>,
+}
+
+impl Lock {
+    /// Reads the lock file at `path`, which is empty if the file does not exist
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            return Ok(Lock::default());
+        }
+
+        serde_json::from_reader(File::open(path)?).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid lock file {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Writes the lock file to `path`
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        fs::write(path, json + "\n")
+    }
+}
+
+/// Resolves imports from GitHub.
+/// Relative imports in files imported from github are resolved in the same repository and branch, so that
+/// this resolver should also be used for imports in modules located on github.
+///
+/// Without a lock file, imports are locked in memory only. Without a cache directory, files are always downloaded.
+#[derive(Debug, Default, Clone)]
+pub struct GithubResolver {
+    lock: RefCell<Lock>,
+    lock_file: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+}
+
+impl GithubResolver {
+    pub fn new() -> Self {
+        GithubResolver::default()
+    }
+
+    /// Locks imports using the lock file at `path`, which is created when the first import is resolved
+    pub fn with_lock_file<P: Into<PathBuf>>(mut self, path: P) -> Result<Self, io::Error> {
+        let path = path.into();
+        self.lock = RefCell::new(Lock::read(&path)?);
+        self.lock_file = Some(path);
+        Ok(self)
+    }
+
+    /// Stores downloaded files in the directory at `path`
+    pub fn with_cache_dir<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.cache_dir = Some(path.into());
+        self
+    }
+
+    /// Only resolves imports which are locked and cached, without accessing the network
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// The current content of the lock
+    pub fn lock(&self) -> Lock {
+        self.lock.borrow().clone()
+    }
+
+    /// The default cache directory, `$HOME/.zokrates/cache/github`
+    pub fn default_cache_dir() -> Option<PathBuf> {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".zokrates/cache/github"))
+    }
+
+    fn cached(&self, hash: &str) -> Option<String> {
+        let path = self.cache_dir.as_ref()?.join(hash);
+
+        // ignore cached files which were modified
+        fs::read_to_string(path)
+            .ok()
+            .filter(|source| sha256(source) == hash)
+    }
+
+    fn store(&self, hash: &str, source: &str) -> Result<(), io::Error> {
+        match self.cache_dir {
+            Some(ref dir) => {
+                fs::create_dir_all(dir)?;
+                fs::write(dir.join(hash), source)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn insert(&self, location: String, entry: LockEntry) -> Result<(), io::Error> {
+        let mut lock = self.lock.borrow_mut();
+        lock.imports.insert(location, entry);
+
+        match self.lock_file {
+            Some(ref path) => lock.write(path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Resolver<io::Error> for GithubResolver {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), io::Error> {
+        let path = current_location
+            .and_then(|location| relative_path(&location, import_location))
+            .unwrap_or_else(|| PathBuf::from(import_location));
+
         let (root, repo, branch, file_path) = parse_input_path(&path)?;
 
+        // the location of a github module is its import path, which identifies it uniquely
+        let location = path.to_str().unwrap().to_owned();
+
         #[cfg(not(test))]
-        let url = GITHUB_URL_BASE;
+        let (url, api_url) = (GITHUB_URL_BASE, GITHUB_API_URL_BASE);
         #[cfg(test)]
-        let url = mockito::server_url();
+        let server_url = mockito::server_url();
+        #[cfg(test)]
+        let (url, api_url) = (server_url.as_str(), server_url.as_str());
+
+        let entry = self.lock.borrow().imports.get(&location).cloned();
+
+        let source = match entry {
+            Some(entry) => match self.cached(&entry.hash) {
+                Some(source) => source,
+                None => {
+                    if self.offline {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!(
+                                "{} is locked at commit {} but is not in the cache, and cannot be downloaded in offline mode",
+                                location, entry.commit
+                            ),
+                        ));
+                    }
+
+                    let source = download_from_github(url, root, repo, &entry.commit, file_path)?;
+
+                    if sha256(&source) != entry.hash {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "Content of {} at commit {} does not match its hash in the lock file",
+                                location, entry.commit
+                            ),
+                        ));
+                    }
 
-        let pb = download_from_github(&url, &root, &repo, &branch, &file_path)?;
-        let file = File::open(&pb)?;
-        let br = BufReader::new(file);
+                    self.store(&entry.hash, &source)?;
+                    source
+                }
+            },
+            None => {
+                if self.offline {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "{} is not in the lock file, and cannot be resolved in offline mode",
+                            location
+                        ),
+                    ));
+                }
 
-        let alias = path.file_stem().unwrap().to_str().unwrap();
+                let commit = resolve_commit(api_url, root, repo, branch)?;
+                let source = download_from_github(url, root, repo, &commit, file_path)?;
+                let hash = sha256(&source);
 
-        Ok((br, location.to_owned(), &alias))
-    } else {
-        Err(io::Error::new(io::ErrorKind::Other, "No location provided"))
+                self.store(&hash, &source)?;
+                self.insert(location.clone(), LockEntry { commit, hash })?;
+                source
+            }
+        };
+
+        Ok((source, location))
     }
 }
 
@@ -103,34 +294,68 @@ fn parse_input_path<'a>(path: &'a Path) -> Result<(&'a str, &'a str, &'a str, &'
     Ok((root, repo, branch, path))
 }
 
-/// Downloads the file from github by specific root (user/org), repository, branch and path.
+/// Resolves a branch of a repository to the commit it currently points to, using the GitHub API.
+fn resolve_commit(
+    github_api: &str,
+    root: &str,
+    repo: &str,
+    branch: &str,
+) -> Result<String, io::Error> {
+    let url = format!(
+        "{github_api}/repos/{root}/{repo}/commits/{branch}",
+        github_api = github_api,
+        root = root,
+        repo = repo,
+        branch = branch
+    );
+
+    // this media type makes the API return the sha of the commit only
+    let commit = download_url(&url, "application/vnd.github.VERSION.sha")?;
+    let commit = commit.trim();
+
+    if commit.is_empty() || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("Unable to resolve branch {} of {}/{}", branch, root, repo),
+        ));
+    }
+
+    Ok(commit.to_string())
+}
+
+/// Downloads the file from github by specific root (user/org), repository, reference (branch or commit) and path.
 fn download_from_github(
     github: &str,
     root: &str,
     repo: &str,
-    branch: &str,
+    reference: &str,
     path: &str,
-) -> Result<PathBuf, io::Error> {
+) -> Result<String, io::Error> {
     let url = format!(
-        "{github}/{root}/{repo}/{branch}/{path}{extension}",
+        "{github}/{root}/{repo}/{reference}/{path}{extension}",
         github = github,
         root = root,
         repo = repo,
-        branch = branch,
+        reference = reference,
         path = path,
         extension = ".zok"
     );
 
-    download_url(&url)
+    download_url(&url, "text/plain")
 }
 
-fn download_url(url: &str) -> Result<PathBuf, io::Error> {
-    let mut response = reqwest::get(url).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::Other,
-            format!("Unable to access github: {}", e.to_string()),
-        )
-    })?;
+fn download_url(url: &str, accept: &str) -> Result<String, io::Error> {
+    let mut response = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::ACCEPT, accept)
+        .header(reqwest::header::USER_AGENT, "zokrates")
+        .send()
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("Unable to access github: {}", e.to_string()),
+            )
+        })?;
 
     if !response.status().is_success() {
         return Err(io::Error::new(
@@ -139,22 +364,43 @@ fn download_url(url: &str) -> Result<PathBuf, io::Error> {
         ));
     }
 
-    let (mut dest, pb) = NamedTempFile::new()?.keep()?;
-    copy(&mut response, &mut dest)?;
+    response.text().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("Unable to read github response: {}", e.to_string()),
+        )
+    })
+}
 
-    Ok(pb)
+/// The hex encoded sha256 hash of `source`
+fn sha256(source: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.input_str(source);
+    hasher.result_str()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::tempdir;
+
+    const COMMIT: &str = "4b4dd4f2a2d9e2c5b4d2a0e4f6a1a3d7f2a6b2c1";
 
     /// Initializes github.com mocks for `import_github` example to run from `examples` tests.
     /// Note that returned mock objects should be alive prior to github requests.
-    fn init_github_mock() -> (Mock, Mock) {
+    fn init_github_mock() -> (Mock, Mock, Mock) {
+        let m0 = mockito::mock("GET", "/repos/Zokrates/ZoKrates/commits/master")
+            .with_status(200)
+            .with_body(COMMIT)
+            .create();
+
         let m1 = mockito::mock(
             "GET",
-            "/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/foo.zok",
+            format!(
+                "/Zokrates/ZoKrates/{}/zokrates_cli/examples/imports/foo.zok",
+                COMMIT
+            )
+            .as_str(),
         )
         .with_status(200)
         .with_body_from_file("./static/foo.zok")
@@ -162,14 +408,20 @@ mod tests {
 
         let m2 = mockito::mock(
             "GET",
-            "/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/notfound.zok",
+            format!(
+                "/Zokrates/ZoKrates/{}/zokrates_cli/examples/imports/notfound.zok",
+                COMMIT
+            )
+            .as_str(),
         )
         .with_status(404)
         .create();
 
-        (m1, m2)
+        (m0, m1, m2)
     }
 
+    const FOO: &str = "github.com/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/foo";
+
     #[test]
     pub fn import_simple() {
         let res = parse_input_path(Path::new(
@@ -204,21 +456,126 @@ mod tests {
 
     #[test]
     pub fn resolve_ok() {
-        let (_m0, _m1) = init_github_mock();
-        let res = resolve(
-            Some("".to_string()),
-            &"github.com/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/foo",
-        );
-        assert!(res.is_ok());
+        let _m = init_github_mock();
+        let (source, _) = GithubResolver::new()
+            .resolve(Some("".to_string()), FOO)
+            .unwrap();
+        assert_eq!(source, fs::read_to_string("./static/foo.zok").unwrap());
+    }
+
+    #[test]
+    pub fn resolve_relative() {
+        let _m = init_github_mock();
+        let (_, location) = GithubResolver::new()
+            .resolve(
+                Some(
+                    "github.com/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/bar"
+                        .to_string(),
+                ),
+                &"./foo",
+            )
+            .unwrap();
+        assert_eq!(location, FOO);
     }
 
     #[test]
     pub fn resolve_err() {
-        let (_m0, _m1) = init_github_mock();
-        assert!(resolve(
-            Some("".to_string()),
-            &"github.com/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/notfound"
-        )
-        .is_err());
+        let _m = init_github_mock();
+        assert!(GithubResolver::new()
+            .resolve(
+                Some("".to_string()),
+                &"github.com/Zokrates/ZoKrates/master/zokrates_cli/examples/imports/notfound"
+            )
+            .is_err());
+    }
+
+    #[test]
+    pub fn write_lock_file() {
+        let _m = init_github_mock();
+        let dir = tempdir().unwrap();
+        let lock_file = dir.path().join(LOCK_FILE_NAME);
+        let cache_dir = dir.path().join("cache");
+
+        let (source, _) = GithubResolver::new()
+            .with_lock_file(&lock_file)
+            .unwrap()
+            .with_cache_dir(&cache_dir)
+            .resolve(None, FOO)
+            .unwrap();
+
+        let entry = Lock::read(&lock_file).unwrap().imports[FOO].clone();
+        assert_eq!(entry.commit, COMMIT);
+        assert_eq!(entry.hash, sha256(&source));
+        assert_eq!(
+            fs::read_to_string(cache_dir.join(&entry.hash)).unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    pub fn resolve_offline_from_cache() {
+        let _m = init_github_mock();
+        let dir = tempdir().unwrap();
+        let lock_file = dir.path().join(LOCK_FILE_NAME);
+        let cache_dir = dir.path().join("cache");
+
+        let (source, _) = GithubResolver::new()
+            .with_lock_file(&lock_file)
+            .unwrap()
+            .with_cache_dir(&cache_dir)
+            .resolve(None, FOO)
+            .unwrap();
+
+        let offline = GithubResolver::new()
+            .with_lock_file(&lock_file)
+            .unwrap()
+            .with_cache_dir(&cache_dir)
+            .offline(true);
+
+        assert_eq!(offline.resolve(None, FOO).unwrap().0, source);
+
+        // once removed from the cache, the module cannot be resolved offline
+        fs::remove_dir_all(&cache_dir).unwrap();
+        let e = offline.resolve(None, FOO).unwrap_err();
+        assert!(e.to_string().contains("offline mode"));
+    }
+
+    #[test]
+    pub fn resolve_offline_not_locked() {
+        let e = GithubResolver::new()
+            .offline(true)
+            .resolve(None, FOO)
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!(
+                "{} is not in the lock file, and cannot be resolved in offline mode",
+                FOO
+            )
+        );
+    }
+
+    #[test]
+    pub fn reject_hash_mismatch() {
+        let _m = init_github_mock();
+        let dir = tempdir().unwrap();
+        let lock_file = dir.path().join(LOCK_FILE_NAME);
+
+        let mut lock = Lock::default();
+        lock.imports.insert(
+            FOO.to_string(),
+            LockEntry {
+                commit: COMMIT.to_string(),
+                hash: sha256("something else"),
+            },
+        );
+        lock.write(&lock_file).unwrap();
+
+        let e = GithubResolver::new()
+            .with_lock_file(&lock_file)
+            .unwrap()
+            .resolve(None, FOO)
+            .unwrap_err();
+        assert!(e.to_string().contains("does not match its hash"));
     }
 }
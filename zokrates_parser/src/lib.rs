@@ -123,8 +123,10 @@ mod tests {
                 tokens: [
                     statement(0, 22, [
                         multi_assignment_statement(0, 9, [
-                            optionally_typed_identifier(0, 1, [
-                                identifier(0, 1)
+                            optionally_typed_assignee(0, 1, [
+                                optionally_typed_identifier(0, 1, [
+                                    identifier(0, 1)
+                                ])
                             ]),
                             identifier(4, 7),
                         ])
@@ -208,5 +210,198 @@ mod tests {
             let parse = ZoKratesParser::parse(Rule::iteration_statement, input);
             assert!(parse.is_ok());
         }
+
+        #[test]
+        fn parse_generic_function() {
+            let input = "def sum<N>(field[N] a) -> (field):\n return a[0]\n";
+
+            let parse = ZoKratesParser::parse(Rule::function_definition, input);
+            assert_eq!(parse.unwrap().as_str(), input);
+
+            let input = "def zip<N, M>(field[N] a, field[M] b) -> (field[N][M]):\n return a\n";
+            assert!(ZoKratesParser::parse(Rule::function_definition, input).is_ok());
+        }
+
+        #[test]
+        fn parse_constant() {
+            let input = "const field[2] A = [1, 2 * B]\n";
+
+            let parse = ZoKratesParser::parse(Rule::const_definition, input);
+            assert_eq!(parse.unwrap().as_str(), input);
+
+            assert!(ZoKratesParser::parse(Rule::identifier, "const").is_err());
+        }
+
+        #[test]
+        fn parse_hex_number() {
+            assert!(ZoKratesParser::parse(Rule::hex_number, "0x1f").is_ok());
+            assert!(ZoKratesParser::parse(Rule::hex_number, "0x001f").is_ok());
+            assert!(ZoKratesParser::parse(Rule::hex_number, "0x0000001f").is_ok());
+            assert!(ZoKratesParser::parse(Rule::hex_number, "0x01f").is_err());
+        }
+
+        #[test]
+        fn parse_uint_expression() {
+            let input = "u32(a) << 2 & 0x0000ffff | 0x00ff0000 ^ !b";
+
+            let parse = ZoKratesParser::parse(Rule::expression, input);
+            assert_eq!(parse.unwrap().as_str(), input);
+        }
+
+        #[test]
+        fn parse_tuple() {
+            let input = "(field, bool[2]) t = (a, [true, false])\n";
+            assert_eq!(
+                ZoKratesParser::parse(Rule::statement, input)
+                    .unwrap()
+                    .as_str(),
+                input
+            );
+
+            let input = "field a, _ = foo().1\n";
+            let parse = ZoKratesParser::parse(Rule::statement, input).unwrap();
+            assert_eq!(
+                parse
+                    .clone()
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .next()
+                    .unwrap()
+                    .as_rule(),
+                Rule::destructuring_statement
+            );
+
+            assert!(ZoKratesParser::parse(Rule::ty_tuple, "(field)").is_err());
+            assert!(ZoKratesParser::parse(Rule::inline_tuple_expression, "(a)").is_err());
+        }
+
+        #[test]
+        fn parse_call_in_expression() {
+            // a function call followed by an operator is not a multi-assignment
+            let input = "field a = foo() + 1\n";
+            let parse = ZoKratesParser::parse(Rule::statement, input).unwrap();
+            assert_eq!(
+                parse
+                    .clone()
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .next()
+                    .unwrap()
+                    .as_rule(),
+                Rule::definition_statement
+            );
+        }
+
+        #[test]
+        fn parse_assertion() {
+            parses_to! {
+                parser: ZoKratesParser,
+                input: r#"assert(a, "a is false")"#,
+                rule: Rule::assertion_statement,
+                tokens: [
+                    assertion_statement(0, 23, [
+                        expression(7, 8, [
+                            term(7, 8, [
+                                primary_expression(7, 8, [
+                                    identifier(7, 8)
+                                ])
+                            ])
+                        ]),
+                        assertion_message(10, 22)
+                    ])
+                ]
+            };
+
+            assert!(ZoKratesParser::parse(Rule::assertion_statement, "assert(a == b)").is_ok());
+
+            // quotes and backslashes can be escaped
+            let input = r#"assert(a, "a is \"false\" \\")"#;
+            assert_eq!(
+                ZoKratesParser::parse(Rule::assertion_statement, input)
+                    .unwrap()
+                    .as_str(),
+                input
+            );
+            assert!(ZoKratesParser::parse(Rule::identifier, "assert").is_err());
+        }
+
+        #[test]
+        fn parse_if_else_statement() {
+            let input = "if a == 1 then\n    b = 2\n    c = 3\nelse\n    b = 4\nfi\n";
+            let parse = ZoKratesParser::parse(Rule::statement, input).unwrap();
+            assert_eq!(parse.as_str(), input);
+            assert_eq!(
+                parse
+                    .clone()
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .next()
+                    .unwrap()
+                    .as_rule(),
+                Rule::if_else_statement
+            );
+
+            // the else block is optional
+            let input = "if a then\n    b = 2\nfi\n";
+            assert_eq!(
+                ZoKratesParser::parse(Rule::statement, input)
+                    .unwrap()
+                    .as_str(),
+                input
+            );
+
+            // the expression form is not a statement block
+            let input = "field a = if b then 1 else 2 fi\n";
+            let parse = ZoKratesParser::parse(Rule::statement, input).unwrap();
+            assert_eq!(
+                parse
+                    .clone()
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .next()
+                    .unwrap()
+                    .as_rule(),
+                Rule::definition_statement
+            );
+
+            assert!(ZoKratesParser::parse(Rule::identifier, "fi").is_err());
+            assert!(ZoKratesParser::parse(Rule::identifier, "final").is_ok());
+        }
+
+        #[test]
+        fn parse_bounded_iteration_statement() {
+            let input = "for field i in 0..n max 64 do
+    a = a + i
+endfor
+";
+            let parse = ZoKratesParser::parse(Rule::statement, input).unwrap();
+            assert_eq!(parse.as_str(), input);
+
+            let iteration = parse.clone().next().unwrap().into_inner().next().unwrap();
+            assert_eq!(iteration.as_rule(), Rule::iteration_statement);
+            assert_eq!(
+                iteration
+                    .into_inner()
+                    .find(|p| p.as_rule() == Rule::iteration_max)
+                    .unwrap()
+                    .as_str(),
+                "max 64 "
+            );
+
+            // `max` is not reserved
+            let input = "for field i in 0..max max 64 do
+endfor
+";
+            assert_eq!(
+                ZoKratesParser::parse(Rule::statement, input)
+                    .unwrap()
+                    .as_str(),
+                input
+            );
+        }
     }
 }
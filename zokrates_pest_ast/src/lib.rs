@@ -8,14 +8,17 @@ use zokrates_parser::Rule;
 extern crate lazy_static;
 
 pub use ast::{
-    Access, ArrayAccess, ArrayInitializerExpression, ArrayType, AssertionStatement, Assignee,
-    AssigneeAccess, AssignmentStatement, BasicOrStructType, BasicType, BinaryExpression,
-    BinaryOperator, CallAccess, ConstantExpression, DefinitionStatement, Expression, File,
-    FromExpression, Function, IdentifierExpression, ImportDirective, ImportSource,
-    InlineArrayExpression, InlineStructExpression, InlineStructMember, IterationStatement,
-    MultiAssignmentStatement, Parameter, PostfixExpression, Range, RangeOrExpression,
-    ReturnStatement, Span, Spread, SpreadOrExpression, Statement, StructDefinition, StructField,
-    TernaryExpression, ToExpression, Type, UnaryExpression, UnaryOperator, Visibility,
+    Access, ArrayAccess, ArrayInitializerExpression, ArrayType, AssertionMessage,
+    AssertionStatement, Assignee, AssigneeAccess, AssignmentStatement, BasicOrStructType,
+    BasicType, BinaryExpression, BinaryOperator, CallAccess, CastExpression, ConstantDefinition,
+    ConstantExpression, DefinitionStatement, DestructuringStatement, ElementAccess, ElseBlock,
+    Expression, ExpressionStatement, File, FromExpression, Function, IdentifierExpression, IfBlock,
+    IfElseStatement, ImportDirective, ImportSource, InlineArrayExpression, InlineStructExpression,
+    InlineStructMember, InlineTupleExpression, IterationMax, IterationStatement,
+    MultiAssignmentStatement, OptionallyTypedAssignee, OptionallyTypedIdentifier, Parameter,
+    PostfixExpression, Range, RangeOrExpression, ReturnStatement, Span, Spread, SpreadOrExpression,
+    Statement, StructDefinition, StructField, TernaryExpression, ToExpression, TupleType, Type,
+    UnaryExpression, UnaryOperator, Visibility, Wildcard,
 };
 
 mod ast {
@@ -35,14 +38,18 @@ mod ast {
     fn build_precedence_climber() -> PrecClimber<Rule> {
         PrecClimber::new(vec![
             Operator::new(Rule::op_inclusive_or, Assoc::Left),
-            Operator::new(Rule::op_exclusive_or, Assoc::Left),
             Operator::new(Rule::op_and, Assoc::Left),
+            Operator::new(Rule::op_bit_or, Assoc::Left),
+            Operator::new(Rule::op_exclusive_or, Assoc::Left),
+            Operator::new(Rule::op_bit_and, Assoc::Left),
             Operator::new(Rule::op_equal, Assoc::Left)
                 | Operator::new(Rule::op_not_equal, Assoc::Left),
             Operator::new(Rule::op_lte, Assoc::Left)
                 | Operator::new(Rule::op_gte, Assoc::Left)
                 | Operator::new(Rule::op_lt, Assoc::Left)
                 | Operator::new(Rule::op_gt, Assoc::Left),
+            Operator::new(Rule::op_left_shift, Assoc::Left)
+                | Operator::new(Rule::op_right_shift, Assoc::Left),
             Operator::new(Rule::op_add, Assoc::Left) | Operator::new(Rule::op_sub, Assoc::Left),
             Operator::new(Rule::op_mul, Assoc::Left) | Operator::new(Rule::op_div, Assoc::Left),
             Operator::new(Rule::op_pow, Assoc::Left),
@@ -76,6 +83,10 @@ mod ast {
             Rule::op_inclusive_or => Expression::binary(BinaryOperator::Or, lhs, rhs, span),
             Rule::op_exclusive_or => Expression::binary(BinaryOperator::Xor, lhs, rhs, span),
             Rule::op_and => Expression::binary(BinaryOperator::And, lhs, rhs, span),
+            Rule::op_bit_or => Expression::binary(BinaryOperator::BitOr, lhs, rhs, span),
+            Rule::op_bit_and => Expression::binary(BinaryOperator::BitAnd, lhs, rhs, span),
+            Rule::op_left_shift => Expression::binary(BinaryOperator::LeftShift, lhs, rhs, span),
+            Rule::op_right_shift => Expression::binary(BinaryOperator::RightShift, lhs, rhs, span),
             _ => unreachable!(),
         })
     }
@@ -100,6 +111,9 @@ mod ast {
                     Rule::conditional_expression => Expression::Ternary(
                         TernaryExpression::from_pest(&mut pair.into_inner()).unwrap(),
                     ),
+                    Rule::cast_expression => Expression::Cast(
+                        CastExpression::from_pest(&mut pair.into_inner()).unwrap(),
+                    ),
                     Rule::primary_expression => {
                         // maybe this could be simplified
                         let next = next.into_inner().next().unwrap();
@@ -122,6 +136,9 @@ mod ast {
                     Rule::postfix_expression => Expression::Postfix(
                         PostfixExpression::from_pest(&mut pair.into_inner()).unwrap(),
                     ),
+                    Rule::inline_tuple_expression => Expression::InlineTuple(
+                        InlineTupleExpression::from_pest(&mut pair.into_inner()).unwrap(),
+                    ),
                     Rule::inline_struct_expression => Expression::InlineStruct(
                         InlineStructExpression::from_pest(&mut pair.into_inner()).unwrap(),
                     ),
@@ -145,7 +162,7 @@ mod ast {
                             span
                         })
                     },
-                    r => unreachable!("`term` should contain one of [`expression`, `conditional_expression`, `primary_expression`, `postfix_expression`, `inline_array_expression`, `unary_expression`, `array_initializer_expression`], found {:#?}", r)
+                    r => unreachable!("`term` should contain one of [`expression`, `conditional_expression`, `cast_expression`, `primary_expression`, `postfix_expression`, `inline_tuple_expression`, `inline_array_expression`, `unary_expression`, `array_initializer_expression`], found {:#?}", r)
                 }
             }
             r => unreachable!(
@@ -159,6 +176,7 @@ mod ast {
     #[pest_ast(rule(Rule::file))]
     pub struct File<'ast> {
         pub imports: Vec<ImportDirective<'ast>>,
+        pub constants: Vec<ConstantDefinition<'ast>>,
         pub structs: Vec<StructDefinition<'ast>>,
         pub functions: Vec<Function<'ast>>,
         pub eoi: EOI,
@@ -166,6 +184,16 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::const_definition))]
+    pub struct ConstantDefinition<'ast> {
+        pub ty: Type<'ast>,
+        pub id: IdentifierExpression<'ast>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct_definition))]
     pub struct StructDefinition<'ast> {
@@ -188,6 +216,7 @@ mod ast {
     #[pest_ast(rule(Rule::function_definition))]
     pub struct Function<'ast> {
         pub id: IdentifierExpression<'ast>,
+        pub generics: Vec<IdentifierExpression<'ast>>,
         pub parameters: Vec<Parameter<'ast>>,
         pub returns: Vec<Type<'ast>>,
         pub statements: Vec<Statement<'ast>>,
@@ -236,6 +265,7 @@ mod ast {
         Basic(BasicType<'ast>),
         Array(ArrayType<'ast>),
         Struct(StructType<'ast>),
+        Tuple(TupleType<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -243,6 +273,9 @@ mod ast {
     pub enum BasicType<'ast> {
         Field(FieldType<'ast>),
         Boolean(BooleanType<'ast>),
+        U8(U8Type<'ast>),
+        U16(U16Type<'ast>),
+        U32(U32Type<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -266,6 +299,7 @@ mod ast {
     pub enum BasicOrStructType<'ast> {
         Struct(StructType<'ast>),
         Basic(BasicType<'ast>),
+        Tuple(TupleType<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -275,6 +309,27 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_u8))]
+    pub struct U8Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_u16))]
+    pub struct U16Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_u32))]
+    pub struct U32Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct))]
     pub struct StructType<'ast> {
@@ -283,6 +338,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_tuple))]
+    pub struct TupleType<'ast> {
+        pub elements: Vec<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::parameter))]
     pub struct Parameter<'ast> {
@@ -313,10 +376,13 @@ mod ast {
     pub enum Statement<'ast> {
         Return(ReturnStatement<'ast>),
         Definition(DefinitionStatement<'ast>),
+        Expression(ExpressionStatement<'ast>),
         Assertion(AssertionStatement<'ast>),
         Iteration(IterationStatement<'ast>),
+        IfElse(IfElseStatement<'ast>),
         Assignment(AssignmentStatement<'ast>),
         MultiAssignment(MultiAssignmentStatement<'ast>),
+        Destructuring(DestructuringStatement<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -340,8 +406,26 @@ mod ast {
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::expression_statement))]
+    pub struct ExpressionStatement<'ast> {
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::assertion_statement))]
     pub struct AssertionStatement<'ast> {
         pub expression: Expression<'ast>,
+        pub message: Option<AssertionMessage<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::assertion_message))]
+    pub struct AssertionMessage<'ast> {
+        #[pest_ast(outer(with(span_into_quoted_str)))]
+        pub value: String,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
     }
@@ -353,6 +437,41 @@ mod ast {
         pub index: IdentifierExpression<'ast>,
         pub from: Expression<'ast>,
         pub to: Expression<'ast>,
+        pub max: Option<IterationMax<'ast>>,
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::iteration_max))]
+    pub struct IterationMax<'ast> {
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::if_else_statement))]
+    pub struct IfElseStatement<'ast> {
+        pub condition: Expression<'ast>,
+        pub consequence: IfBlock<'ast>,
+        pub alternative: Option<ElseBlock<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::if_block))]
+    pub struct IfBlock<'ast> {
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::else_block))]
+    pub struct ElseBlock<'ast> {
         pub statements: Vec<Statement<'ast>>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
@@ -361,13 +480,22 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::multi_assignment_statement))]
     pub struct MultiAssignmentStatement<'ast> {
-        pub lhs: Vec<OptionallyTypedIdentifier<'ast>>,
+        pub lhs: Vec<OptionallyTypedAssignee<'ast>>,
         pub function_id: IdentifierExpression<'ast>,
         pub arguments: Vec<Expression<'ast>>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::destructuring_statement))]
+    pub struct DestructuringStatement<'ast> {
+        pub lhs: Vec<OptionallyTypedAssignee<'ast>>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::return_statement))]
     pub struct ReturnStatement<'ast> {
@@ -392,6 +520,10 @@ mod ast {
         Lte,
         Gte,
         Pow,
+        BitAnd,
+        BitOr,
+        LeftShift,
+        RightShift,
     }
 
     #[derive(Debug, PartialEq, FromPest, Clone)]
@@ -410,12 +542,14 @@ mod ast {
     #[derive(Debug, PartialEq, Clone)]
     pub enum Expression<'ast> {
         Ternary(TernaryExpression<'ast>),
+        Cast(CastExpression<'ast>),
         Binary(BinaryExpression<'ast>),
         Postfix(PostfixExpression<'ast>),
         Identifier(IdentifierExpression<'ast>),
         Constant(ConstantExpression<'ast>),
         InlineArray(InlineArrayExpression<'ast>),
         InlineStruct(InlineStructExpression<'ast>),
+        InlineTuple(InlineTupleExpression<'ast>),
         ArrayInitializer(ArrayInitializerExpression<'ast>),
         Unary(UnaryExpression<'ast>),
     }
@@ -485,6 +619,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::inline_tuple_expression))]
+    pub struct InlineTupleExpression<'ast> {
+        pub elements: Vec<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::inline_struct_expression))]
     pub struct InlineStructExpression<'ast> {
@@ -512,6 +654,20 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::optionally_typed_assignee))]
+    pub enum OptionallyTypedAssignee<'ast> {
+        Wildcard(Wildcard<'ast>),
+        Identifier(OptionallyTypedIdentifier<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::wildcard))]
+    pub struct Wildcard<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::optionally_typed_identifier))]
     pub struct OptionallyTypedIdentifier<'ast> {
@@ -526,6 +682,7 @@ mod ast {
     pub enum Access<'ast> {
         Call(CallAccess<'ast>),
         Select(ArrayAccess<'ast>),
+        Element(ElementAccess<'ast>),
         Member(MemberAccess<'ast>),
     }
 
@@ -560,6 +717,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::element_access))]
+    pub struct ElementAccess<'ast> {
+        pub index: DecimalNumberExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, PartialEq, Clone)]
     pub struct BinaryExpression<'ast> {
         pub op: BinaryOperator,
@@ -578,6 +743,15 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::cast_expression))]
+    pub struct CastExpression<'ast> {
+        pub ty: BasicType<'ast>,
+        pub expression: Box<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     impl<'ast> Expression<'ast> {
         pub fn ternary(
             first: Box<Expression<'ast>>,
@@ -613,9 +787,11 @@ mod ast {
                 Expression::Identifier(i) => &i.span,
                 Expression::Constant(c) => &c.span(),
                 Expression::Ternary(t) => &t.span,
+                Expression::Cast(c) => &c.span,
                 Expression::Postfix(p) => &p.span,
                 Expression::InlineArray(a) => &a.span,
                 Expression::InlineStruct(s) => &s.span,
+                Expression::InlineTuple(t) => &t.span,
                 Expression::ArrayInitializer(a) => &a.span,
                 Expression::Unary(u) => &u.span,
             }
@@ -648,6 +824,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::constant))]
     pub enum ConstantExpression<'ast> {
+        HexNumber(HexNumberExpression<'ast>),
         DecimalNumber(DecimalNumberExpression<'ast>),
         BooleanLiteral(BooleanLiteralExpression<'ast>),
     }
@@ -655,6 +832,7 @@ mod ast {
     impl<'ast> ConstantExpression<'ast> {
         pub fn span(&self) -> &Span<'ast> {
             match self {
+                ConstantExpression::HexNumber(n) => &n.span,
                 ConstantExpression::DecimalNumber(n) => &n.span,
                 ConstantExpression::BooleanLiteral(c) => &c.span,
             }
@@ -670,6 +848,15 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::hex_number))]
+    pub struct HexNumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::boolean_literal))]
     pub struct BooleanLiteralExpression<'ast> {
@@ -701,6 +888,26 @@ mod ast {
         span.as_str().to_string()
     }
 
+    /// The content of a quoted string, where `\"` and `\\` stand for `"` and `\`
+    fn span_into_quoted_str(span: Span) -> String {
+        let s = span.as_str();
+        let mut res = String::new();
+        let mut chars = s[1..s.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.clone().next() {
+                    Some(escaped @ '"') | Some(escaped @ '\\') => {
+                        res.push(escaped);
+                        chars.next();
+                    }
+                    _ => res.push(c),
+                },
+                c => res.push(c),
+            }
+        }
+        res
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::EOI))]
     pub struct EOI;
@@ -794,12 +1001,14 @@ mod tests {
         assert_eq!(
             generate_ast(&source),
             Ok(File {
+                constants: vec![],
                 structs: vec![],
                 functions: vec![Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -846,12 +1055,14 @@ mod tests {
         assert_eq!(
             generate_ast(&source),
             Ok(File {
+                constants: vec![],
                 structs: vec![],
                 functions: vec![Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -916,12 +1127,14 @@ mod tests {
         assert_eq!(
             generate_ast(&source),
             Ok(File {
+                constants: vec![],
                 structs: vec![],
                 functions: vec![Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 33, 37).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 44, 49).unwrap()
@@ -973,12 +1186,14 @@ mod tests {
         assert_eq!(
             generate_ast(&source),
             Ok(File {
+                constants: vec![],
                 structs: vec![],
                 functions: vec![Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 4, 8).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 15, 20).unwrap()
@@ -1008,12 +1223,14 @@ mod tests {
         assert_eq!(
             generate_ast(&source),
             Ok(File {
+                constants: vec![],
                 structs: vec![],
                 functions: vec![Function {
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(&source, 4, 8).unwrap()
                     },
+                    generics: vec![],
                     parameters: vec![],
                     returns: vec![Type::Basic(BasicType::Field(FieldType {
                         span: Span::new(&source, 15, 20).unwrap()
@@ -1024,7 +1241,7 @@ mod tests {
                             span: Span::new(&source, 36, 39).unwrap()
                         },
                         lhs: vec![
-                            OptionallyTypedIdentifier {
+                            OptionallyTypedAssignee::Identifier(OptionallyTypedIdentifier {
                                 ty: Some(Type::Basic(BasicType::Field(FieldType {
                                     span: Span::new(&source, 23, 28).unwrap()
                                 }))),
@@ -1033,15 +1250,15 @@ mod tests {
                                     span: Span::new(&source, 29, 30).unwrap(),
                                 },
                                 span: Span::new(&source, 23, 30).unwrap()
-                            },
-                            OptionallyTypedIdentifier {
+                            }),
+                            OptionallyTypedAssignee::Identifier(OptionallyTypedIdentifier {
                                 ty: None,
                                 id: IdentifierExpression {
                                     value: String::from("b"),
                                     span: Span::new(&source, 32, 33).unwrap(),
                                 },
                                 span: Span::new(&source, 32, 33).unwrap()
-                            },
+                            }),
                         ],
                         arguments: vec![
                             Expression::Constant(ConstantExpression::DecimalNumber(
@@ -1077,6 +1294,215 @@ mod tests {
         );
     }
 
+    #[test]
+    fn destructuring() {
+        let source = r#"def main() -> (field): _, b = t.1
+"#;
+        assert_eq!(
+            generate_ast(&source),
+            Ok(File {
+                constants: vec![],
+                structs: vec![],
+                functions: vec![Function {
+                    id: IdentifierExpression {
+                        value: String::from("main"),
+                        span: Span::new(&source, 4, 8).unwrap()
+                    },
+                    generics: vec![],
+                    parameters: vec![],
+                    returns: vec![Type::Basic(BasicType::Field(FieldType {
+                        span: Span::new(&source, 15, 20).unwrap()
+                    }))],
+                    statements: vec![Statement::Destructuring(DestructuringStatement {
+                        lhs: vec![
+                            OptionallyTypedAssignee::Wildcard(Wildcard {
+                                span: Span::new(&source, 23, 24).unwrap()
+                            }),
+                            OptionallyTypedAssignee::Identifier(OptionallyTypedIdentifier {
+                                ty: None,
+                                id: IdentifierExpression {
+                                    value: String::from("b"),
+                                    span: Span::new(&source, 26, 27).unwrap(),
+                                },
+                                span: Span::new(&source, 26, 27).unwrap()
+                            }),
+                        ],
+                        expression: Expression::Postfix(PostfixExpression {
+                            id: IdentifierExpression {
+                                value: String::from("t"),
+                                span: Span::new(&source, 30, 31).unwrap()
+                            },
+                            accesses: vec![Access::Element(ElementAccess {
+                                index: DecimalNumberExpression {
+                                    value: String::from("1"),
+                                    span: Span::new(&source, 32, 33).unwrap()
+                                },
+                                span: Span::new(&source, 31, 33).unwrap()
+                            })],
+                            span: Span::new(&source, 30, 33).unwrap()
+                        }),
+                        span: Span::new(&source, 23, 33).unwrap()
+                    })],
+                    span: Span::new(&source, 0, 34).unwrap(),
+                }],
+                imports: vec![],
+                eoi: EOI {},
+                span: Span::new(&source, 0, 34).unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        let source = r#"def main() -> (field): if a then
+b = 1
+else
+b = 2
+fi
+"#;
+        assert_eq!(
+            generate_ast(&source),
+            Ok(File {
+                constants: vec![],
+                structs: vec![],
+                functions: vec![Function {
+                    id: IdentifierExpression {
+                        value: String::from("main"),
+                        span: Span::new(&source, 4, 8).unwrap()
+                    },
+                    generics: vec![],
+                    parameters: vec![],
+                    returns: vec![Type::Basic(BasicType::Field(FieldType {
+                        span: Span::new(&source, 15, 20).unwrap()
+                    }))],
+                    statements: vec![Statement::IfElse(IfElseStatement {
+                        condition: Expression::Identifier(IdentifierExpression {
+                            value: String::from("a"),
+                            span: Span::new(&source, 26, 27).unwrap()
+                        }),
+                        consequence: IfBlock {
+                            statements: vec![Statement::Assignment(AssignmentStatement {
+                                assignee: Assignee {
+                                    id: IdentifierExpression {
+                                        value: String::from("b"),
+                                        span: Span::new(&source, 33, 34).unwrap()
+                                    },
+                                    accesses: vec![],
+                                    span: Span::new(&source, 33, 35).unwrap()
+                                },
+                                expression: Expression::Constant(
+                                    ConstantExpression::DecimalNumber(DecimalNumberExpression {
+                                        value: String::from("1"),
+                                        span: Span::new(&source, 37, 38).unwrap()
+                                    })
+                                ),
+                                span: Span::new(&source, 33, 38).unwrap()
+                            })],
+                            span: Span::new(&source, 33, 39).unwrap()
+                        },
+                        alternative: Some(ElseBlock {
+                            statements: vec![Statement::Assignment(AssignmentStatement {
+                                assignee: Assignee {
+                                    id: IdentifierExpression {
+                                        value: String::from("b"),
+                                        span: Span::new(&source, 44, 45).unwrap()
+                                    },
+                                    accesses: vec![],
+                                    span: Span::new(&source, 44, 46).unwrap()
+                                },
+                                expression: Expression::Constant(
+                                    ConstantExpression::DecimalNumber(DecimalNumberExpression {
+                                        value: String::from("2"),
+                                        span: Span::new(&source, 48, 49).unwrap()
+                                    })
+                                ),
+                                span: Span::new(&source, 44, 49).unwrap()
+                            })],
+                            span: Span::new(&source, 39, 50).unwrap()
+                        }),
+                        span: Span::new(&source, 23, 52).unwrap()
+                    })],
+                    span: Span::new(&source, 0, 53).unwrap(),
+                }],
+                imports: vec![],
+                eoi: EOI {},
+                span: Span::new(&source, 0, 53).unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn bounded_for() {
+        let source = r#"def main() -> (field): for field i in 0..n max 4 do
+endfor
+"#;
+        assert_eq!(
+            generate_ast(&source),
+            Ok(File {
+                constants: vec![],
+                structs: vec![],
+                functions: vec![Function {
+                    id: IdentifierExpression {
+                        value: String::from("main"),
+                        span: Span::new(&source, 4, 8).unwrap()
+                    },
+                    generics: vec![],
+                    parameters: vec![],
+                    returns: vec![Type::Basic(BasicType::Field(FieldType {
+                        span: Span::new(&source, 15, 20).unwrap()
+                    }))],
+                    statements: vec![Statement::Iteration(IterationStatement {
+                        ty: Type::Basic(BasicType::Field(FieldType {
+                            span: Span::new(&source, 27, 32).unwrap()
+                        })),
+                        index: IdentifierExpression {
+                            value: String::from("i"),
+                            span: Span::new(&source, 33, 34).unwrap()
+                        },
+                        from: Expression::Constant(ConstantExpression::DecimalNumber(
+                            DecimalNumberExpression {
+                                value: String::from("0"),
+                                span: Span::new(&source, 38, 39).unwrap()
+                            }
+                        )),
+                        to: Expression::Identifier(IdentifierExpression {
+                            value: String::from("n"),
+                            span: Span::new(&source, 41, 42).unwrap()
+                        }),
+                        max: Some(IterationMax {
+                            expression: Expression::Constant(ConstantExpression::DecimalNumber(
+                                DecimalNumberExpression {
+                                    value: String::from("4"),
+                                    span: Span::new(&source, 47, 48).unwrap()
+                                }
+                            )),
+                            span: Span::new(&source, 43, 49).unwrap()
+                        }),
+                        statements: vec![],
+                        span: Span::new(&source, 23, 58).unwrap()
+                    })],
+                    span: Span::new(&source, 0, 59).unwrap(),
+                }],
+                imports: vec![],
+                eoi: EOI {},
+                span: Span::new(&source, 0, 59).unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn assertion_message() {
+        let source = r#"def main() -> (field): assert(a, "a \"b\" \\ \c")
+"#;
+        let file = generate_ast(&source).unwrap();
+        match &file.functions[0].statements[0] {
+            Statement::Assertion(statement) => {
+                assert_eq!(statement.message.as_ref().unwrap().value, r#"a "b" \ \c"#)
+            }
+            s => panic!("Expected an assertion, found {:?}", s),
+        }
+    }
+
     #[test]
     fn playground() {
         let source = r#"import "heyman" as yo
@@ -1,19 +1,58 @@
-use fs_extra::copy_items;
-use fs_extra::dir::CopyOptions;
 use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use zokrates_test::write_tests;
 
 fn main() {
-    // export stdlib folder to OUT_DIR
-    export_stdlib();
+    // embed stdlib sources
+    embed_stdlib().unwrap();
 
     // generate tests
     write_tests("./tests/tests/");
 }
 
-fn export_stdlib() {
+// as no `rerun-if-changed` is emitted, this runs again whenever a file of the package changes
+fn embed_stdlib() -> io::Result<()> {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("stdlib");
+
+    let mut modules = vec![];
+    collect_modules(&root, &mut modules)?;
+    modules.sort();
+
     let out_dir = env::var("OUT_DIR").unwrap();
-    let mut options = CopyOptions::new();
-    options.overwrite = true;
-    copy_items(&vec!["stdlib"], out_dir, &options).unwrap();
+    let mut file = File::create(Path::new(&out_dir).join("stdlib.rs"))?;
+
+    writeln!(file, "/// The modules of the standard library, by path")?;
+    writeln!(file, "pub static STDLIB: &[(&str, &str)] = &[")?;
+    for path in modules {
+        let name = path
+            .strip_prefix(&root)
+            .unwrap()
+            .components()
+            .map(|c| c.as_os_str().to_str().unwrap())
+            .collect::<Vec<_>>()
+            .join("/");
+        writeln!(
+            file,
+            "    ({:?}, include_str!({:?})),",
+            name,
+            path.to_str().unwrap()
+        )?;
+    }
+    writeln!(file, "];")?;
+
+    Ok(())
+}
+
+fn collect_modules(dir: &Path, modules: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_modules(&path, modules)?;
+        } else if path.extension().map(|e| e == "zok").unwrap_or(false) {
+            modules.push(path);
+        }
+    }
+    Ok(())
 }
@@ -1 +1,138 @@
+//! # ZoKrates standard library
+//!
+//! The modules of the standard library are embedded in this crate, so that they can be imported without
+//! `$ZOKRATES_HOME` being set:
+//! ```zokrates
+//! import "hashes/sha256/512bit" as sha256
+//! ```
 
+use std::io;
+use std::path::PathBuf;
+use zokrates_core::resolve::{relative_path, Resolver};
+
+include!(concat!(env!("OUT_DIR"), "/stdlib.rs"));
+
+/// Prefix of the location of the modules of the standard library
+pub const STDLIB_PREFIX: &str = "stdlib:";
+
+/// Get the source code of the module of the standard library at `path`, for example `hashes/sha256/512bit.zok`
+pub fn get(path: &str) -> Option<&'static str> {
+    STDLIB
+        .iter()
+        .find(|(module, _)| *module == path)
+        .map(|(_, source)| *source)
+}
+
+/// Resolves imports of the standard library, such as `hashes/sha256/512bit`, as well as relative imports in the modules
+/// of the standard library.
+/// The locations of these modules start with `STDLIB_PREFIX`, so that relative imports in them can be routed to this
+/// resolver.
+#[derive(Debug, Default, Clone)]
+pub struct StdlibResolver;
+
+impl StdlibResolver {
+    pub fn new() -> Self {
+        StdlibResolver
+    }
+}
+
+impl Resolver<io::Error> for StdlibResolver {
+    fn resolve(
+        &self,
+        current_location: Option<String>,
+        import_location: &str,
+    ) -> Result<(String, String), io::Error> {
+        // relative imports are resolved in the module they appear in, so that they are only found in the standard
+        // library if that module is part of it
+        let path = match current_location {
+            Some(ref location) if location.starts_with(STDLIB_PREFIX) => {
+                relative_path(&location[STDLIB_PREFIX.len()..], import_location)
+            }
+            _ => None,
+        }
+        .unwrap_or_else(|| PathBuf::from(import_location))
+        .with_extension("zok");
+
+        let path = path.to_string_lossy();
+
+        let source = get(&path);
+        let location = format!("{}{}", STDLIB_PREFIX, path);
+
+        source
+            .map(|source| (source.to_string(), location.clone()))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} not found in the standard library", import_location),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_core::compile::compile;
+    use zokrates_core::ir;
+    use zokrates_field::field::FieldPrime;
+
+    #[test]
+    fn resolve_absolute() {
+        let (source, location) = StdlibResolver::new()
+            .resolve(None, "hashes/sha256/512bit")
+            .unwrap();
+        assert_eq!(location, "stdlib:hashes/sha256/512bit.zok");
+        assert_eq!(source, get("hashes/sha256/512bit.zok").unwrap());
+    }
+
+    #[test]
+    fn resolve_relative() {
+        let (_, location) = StdlibResolver::new()
+            .resolve(
+                Some(String::from("stdlib:hashes/sha256/512bit.zok")),
+                "./IVconstants",
+            )
+            .unwrap();
+        assert_eq!(location, "stdlib:hashes/sha256/IVconstants.zok");
+
+        let (_, location) = StdlibResolver::new()
+            .resolve(
+                Some(String::from("stdlib:hashes/sha256/512bitPacked.zok")),
+                "../../utils/pack/pack128",
+            )
+            .unwrap();
+        assert_eq!(location, "stdlib:utils/pack/pack128.zok");
+    }
+
+    #[test]
+    fn resolve_not_found() {
+        assert!(StdlibResolver::new().resolve(None, "foo/bar").is_err());
+        // relative imports in user modules are not resolved in the standard library
+        assert!(StdlibResolver::new()
+            .resolve(
+                Some(String::from("/path/to/main.zok")),
+                "./hashes/sha256/512bit"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn compile_with_stdlib() {
+        let source = r#"
+import "ecc/babyjubjubParams" as context
+import "ecc/edwardsAdd" as add
+
+def main(field[2] pt) -> (field[2]):
+    field[10] ctx = context()
+    return add(pt, pt, ctx)
+"#;
+
+        let res: Result<ir::Prog<FieldPrime>, _> = compile(
+            &mut source.as_bytes(),
+            Some(String::from("main.zok")),
+            Some(&StdlibResolver::new()),
+        );
+
+        assert!(res.is_ok());
+    }
+}
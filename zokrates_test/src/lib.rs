@@ -69,7 +69,7 @@ fn compare(result: ir::ExecutionResult<FieldPrime>, expected: TestResult) -> Res
 
 use std::io::{BufReader, Read};
 use zokrates_core::compile::compile;
-use zokrates_fs_resolver::resolve;
+use zokrates_fs_resolver::FsResolver;
 
 pub fn test_inner(test_path: &str) {
     let t: Tests =
@@ -79,15 +79,8 @@ pub fn test_inner(test_path: &str) {
 
     let bin = compile(
         &mut code_reader,
-        Some(
-            t.entry_point
-                .parent()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_string(),
-        ),
-        Some(resolve),
+        Some(t.entry_point.to_str().unwrap().to_string()),
+        Some(&FsResolver::new().with_zokrates_home()),
     )
     .unwrap();
 